@@ -0,0 +1,153 @@
+//! Checks shared by the tests of every [`Database`] implementation, so that all backends
+//! agree on ordering, range bounds and the keys returned by prefix iteration.
+//!
+//! [`crate::PrefixDB`] strips the prefix from the keys returned by prefix iteration, so it only
+//! runs [`run_without_prefix_iteration`].
+
+use crate::{Column, Database};
+
+type Pairs = Vec<(Vec<u8>, Vec<u8>)>;
+
+fn collect(iter: crate::DBIterator<'_>) -> Pairs {
+    iter.map(Result::unwrap)
+        .map(|(k, v)| (k.into_vec(), v.into_vec()))
+        .collect()
+}
+
+fn pairs(keys: &[&[u8]]) -> Pairs {
+    keys.iter().map(|k| (k.to_vec(), k.to_vec())).collect()
+}
+
+fn reversed(mut pairs: Pairs) -> Pairs {
+    pairs.reverse();
+    pairs
+}
+
+/// Runs every check against an empty database
+pub(crate) fn run<DB: Database>(db: DB) {
+    run_without_prefix_iteration(&db);
+    prefix_iteration(&db);
+}
+
+/// Runs every check except for the keys returned by prefix iteration against an empty database
+pub(crate) fn run_without_prefix_iteration<DB: Database>(db: &DB) {
+    get_put_has_delete(db);
+    iteration(db);
+    columns(db);
+}
+
+fn get_put_has_delete<DB: Database>(db: &DB) {
+    assert_eq!(db.get(&[9, 9]).unwrap(), None);
+    assert!(!db.has(&[9, 9]).unwrap());
+
+    db.put(vec![9, 9], vec![1]).unwrap();
+    db.put(vec![9, 9], vec![2]).unwrap();
+    assert_eq!(db.get(&[9, 9]).unwrap(), Some(vec![2]));
+    assert!(db.has(&[9, 9]).unwrap());
+
+    db.delete(&[9, 9]).unwrap();
+    assert_eq!(db.get(&[9, 9]).unwrap(), None);
+    assert!(!db.has(&[9, 9]).unwrap());
+
+    // deleting a missing key is not an error
+    db.delete(&[9, 9]).unwrap();
+}
+
+fn iteration<DB: Database>(db: &DB) {
+    // inserted out of order
+    for key in [
+        vec![2, 1],
+        vec![1],
+        vec![2],
+        vec![255, 255],
+        vec![2, 255],
+        vec![3],
+        vec![2, 0],
+    ] {
+        db.put(key.clone(), key).unwrap();
+    }
+    db.put(vec![4], vec![4]).unwrap();
+    db.delete(&[4]).unwrap();
+
+    let all = pairs(&[&[1], &[2], &[2, 0], &[2, 1], &[2, 255], &[3], &[255, 255]]);
+
+    assert_eq!(collect(db.iterator()), all);
+    assert_eq!(collect(db.reverse_iterator()), reversed(all.clone()));
+    assert_eq!(collect(db.range(None, None)), all);
+
+    let bounded = pairs(&[&[2], &[2, 0], &[2, 1], &[2, 255]]);
+    assert_eq!(collect(db.range(Some(&[2]), Some(&[3]))), bounded);
+    assert_eq!(
+        collect(db.reverse_range(Some(&[2]), Some(&[3]))),
+        reversed(bounded)
+    );
+
+    // start is inclusive, end is exclusive, bounds need not be keys
+    let bounded = pairs(&[&[2, 0], &[2, 1]]);
+    assert_eq!(collect(db.range(Some(&[2, 0]), Some(&[2, 2]))), bounded);
+    assert_eq!(
+        collect(db.reverse_range(Some(&[2, 0]), Some(&[2, 2]))),
+        reversed(bounded)
+    );
+    assert_eq!(
+        collect(db.reverse_range(Some(&[2, 0]), Some(&[2, 255]))),
+        pairs(&[&[2, 1], &[2, 0]])
+    );
+
+    assert_eq!(collect(db.range(None, Some(&[2]))), pairs(&[&[1]]));
+    assert_eq!(collect(db.reverse_range(None, Some(&[2]))), pairs(&[&[1]]));
+    assert_eq!(
+        collect(db.range(Some(&[3]), None)),
+        pairs(&[&[3], &[255, 255]])
+    );
+    assert_eq!(
+        collect(db.reverse_range(Some(&[3]), None)),
+        pairs(&[&[255, 255], &[3]])
+    );
+
+    // empty and inverted ranges
+    assert!(collect(db.range(Some(&[2]), Some(&[2]))).is_empty());
+    assert!(collect(db.range(Some(&[3]), Some(&[2]))).is_empty());
+    assert!(collect(db.reverse_range(Some(&[3]), Some(&[2]))).is_empty());
+}
+
+/// Expects the default column to be populated by [`iteration`]
+fn prefix_iteration<DB: Database>(db: &DB) {
+    // prefix iteration returns the full keys
+    let prefixed = pairs(&[&[2], &[2, 0], &[2, 1], &[2, 255]]);
+    assert_eq!(collect(db.prefix_iterator(vec![2])), prefixed);
+    assert_eq!(
+        collect(db.reverse_prefix_iterator(vec![2])),
+        reversed(prefixed)
+    );
+    assert_eq!(
+        collect(db.prefix_iterator(vec![255])),
+        pairs(&[&[255, 255]])
+    );
+    assert!(collect(db.prefix_iterator(vec![4])).is_empty());
+}
+
+/// Expects the default column to be populated by [`iteration`]
+fn columns<DB: Database>(db: &DB) {
+    let nodes = db.column(Column::Nodes);
+    assert!(collect(nodes.iterator()).is_empty());
+
+    nodes.put(vec![1], vec![7]).unwrap();
+    assert_eq!(nodes.get(&[1]).unwrap(), Some(vec![7]));
+    assert_eq!(db.get(&[1]).unwrap(), Some(vec![1]));
+    assert!(!db.column(Column::Roots).has(&[1]).unwrap());
+
+    // handles to the same column share their keys
+    assert_eq!(
+        collect(db.column(Column::Nodes).iterator()),
+        vec![(vec![1], vec![7])]
+    );
+    assert_eq!(
+        collect(nodes.column(Column::Default).range(Some(&[1]), Some(&[2]))),
+        pairs(&[&[1]])
+    );
+
+    nodes.delete(&[1]).unwrap();
+    assert!(collect(db.column(Column::Nodes).iterator()).is_empty());
+    assert_eq!(db.get(&[1]).unwrap(), Some(vec![1]));
+}
@@ -2,6 +2,18 @@ use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq, Eq)]
 pub enum Error {
+    #[cfg(feature = "rocksdb")]
     #[error(transparent)]
     Decode(#[from] rocksdb::Error),
+    #[cfg(feature = "sled")]
+    #[error("{0}")]
+    Sled(String),
+}
+
+// sled errors can't be compared so only the message is kept
+#[cfg(feature = "sled")]
+impl From<sled::Error> for Error {
+    fn from(e: sled::Error) -> Self {
+        Error::Sled(e.to_string())
+    }
 }
@@ -1,24 +1,124 @@
 #![warn(rust_2018_idioms)]
 
+#[cfg(test)]
+mod conformance;
 pub mod error;
 pub mod ext;
 mod memory;
 mod prefix;
+#[cfg(feature = "rocksdb")]
 mod rocks;
+#[cfg(feature = "sled")]
+mod sled_db;
 
 pub use memory::*;
 pub use prefix::*;
+#[cfg(feature = "rocksdb")]
 pub use rocks::*;
+#[cfg(feature = "sled")]
+pub use sled_db::*;
 
-pub trait Database {
-    fn get(&self, key: &[u8]) -> Option<Vec<u8>>;
+use crate::error::Error;
 
-    fn put(&self, key: Vec<u8>, value: Vec<u8>);
+/// An iterator over key value pairs, an item is an error if the pair couldn't be read
+pub type DBIterator<'a> = Box<dyn Iterator<Item = Result<(Box<[u8]>, Box<[u8]>), Error>> + 'a>;
 
-    fn iterator<'a>(&'a self) -> Box<dyn Iterator<Item = (Box<[u8]>, Box<[u8]>)> + 'a>;
+/// A group of keys which a backend may store separately, e.g. as a RocksDB column family. Every
+/// column has its own key space.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Column {
+    Default,
+    /// IAVL tree nodes
+    Nodes,
+    /// IAVL tree root hashes
+    Roots,
+    /// IAVL fast nodes, reserved so the column family exists once fast nodes are implemented
+    FastNodes,
+}
+
+impl Column {
+    pub const ALL: [Column; 4] = [
+        Column::Default,
+        Column::Nodes,
+        Column::Roots,
+        Column::FastNodes,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Column::Default => "default",
+            Column::Nodes => "nodes",
+            Column::Roots => "roots",
+            Column::FastNodes => "fast_nodes",
+        }
+    }
+}
+
+pub trait Database: Send + Sync + 'static {
+    fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>, Error>;
+
+    fn put(&self, key: Vec<u8>, value: Vec<u8>) -> Result<(), Error>;
+
+    /// Removes the key, it isn't an error if the key doesn't exist
+    fn delete(&self, key: &[u8]) -> Result<(), Error>;
+
+    /// Returns a handle to the given column of the same underlying database
+    fn column(&self, column: Column) -> Self
+    where
+        Self: Sized;
+
+    fn has(&self, key: &[u8]) -> Result<bool, Error> {
+        Ok(self.get(key)?.is_some())
+    }
+
+    /// Makes every write so far durable, a no-op for backends which don't buffer writes
+    fn flush(&self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Iterates in ascending key order over the keys in `[start, end)`, a `None` bound is unbounded
+    fn range(&self, start: Option<&[u8]>, end: Option<&[u8]>) -> DBIterator<'_>;
+
+    /// Same as [`Database::range`] but iterates in descending key order
+    fn reverse_range(&self, start: Option<&[u8]>, end: Option<&[u8]>) -> DBIterator<'_>;
+
+    fn iterator(&self) -> DBIterator<'_> {
+        self.range(None, None)
+    }
+
+    fn reverse_iterator(&self) -> DBIterator<'_> {
+        self.reverse_range(None, None)
+    }
+
+    /// Iterates in ascending key order over the keys which start with the prefix. The returned
+    /// keys include the prefix, except for a [`PrefixDB`] which strips it.
+    fn prefix_iterator(&self, prefix: Vec<u8>) -> DBIterator<'_> {
+        self.range(Some(&prefix), prefix_end(&prefix).as_deref())
+    }
+
+    /// Same as [`Database::prefix_iterator`] but iterates in descending key order
+    fn reverse_prefix_iterator(&self, prefix: Vec<u8>) -> DBIterator<'_> {
+        self.reverse_range(Some(&prefix), prefix_end(&prefix).as_deref())
+    }
+}
+
+/// Returns the exclusive end of a range over all keys with the given prefix
+///
+/// That is the smallest x such that, prefix + y < x for all y. If
+/// no such x exists (i.e. prefix = vec![255; N]; for some N) it returns None
+pub(crate) fn prefix_end(prefix: &[u8]) -> Option<Vec<u8>> {
+    let mut end = prefix.to_vec();
 
-    fn prefix_iterator<'a>(
-        &'a self,
-        prefix: Vec<u8>,
-    ) -> Box<dyn Iterator<Item = (Box<[u8]>, Box<[u8]>)> + 'a>;
+    loop {
+        match end.last_mut() {
+            None => return None,
+            Some(255) => {
+                end.pop();
+            }
+            Some(last) => {
+                *last += 1;
+                return Some(end);
+            }
+        }
+    }
 }
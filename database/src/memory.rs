@@ -1,10 +1,17 @@
-use std::{cell::RefCell, collections::BTreeMap, ops::Bound};
+use std::{
+    collections::BTreeMap,
+    ops::Bound,
+    sync::{Arc, RwLock},
+};
 
-use crate::Database;
+use crate::{error::Error, Column, DBIterator, Database};
+
+type Store = RwLock<BTreeMap<Vec<u8>, Vec<u8>>>;
 
 #[derive(Debug)]
 pub struct MemDB {
-    store: RefCell<BTreeMap<Vec<u8>, Vec<u8>>>, // we use a refcell because the set method on the DB trait doesn't take a mutable ref
+    columns: Arc<[Store; Column::ALL.len()]>, // we use a lock because the set method on the DB trait doesn't take a mutable ref, and the DB may be shared between threads
+    column: Column,
 }
 
 impl Default for MemDB {
@@ -16,67 +23,90 @@ impl Default for MemDB {
 impl MemDB {
     pub fn new() -> MemDB {
         MemDB {
-            store: RefCell::new(BTreeMap::new()),
+            columns: Arc::default(),
+            column: Column::Default,
+        }
+    }
+
+    fn store(&self) -> &Store {
+        &self.columns[self.column as usize]
+    }
+
+    /// Iterates over a copy of the pairs in `[start, end)`, the lock can't be held by the
+    /// iterator since writes may happen while iterating
+    fn copied_range(
+        &self,
+        start: Option<&[u8]>,
+        end: Option<&[u8]>,
+        reverse: bool,
+    ) -> DBIterator<'_> {
+        let start = start.map_or(Bound::Unbounded, |start| Bound::Included(start.to_vec()));
+        let end = end.map_or(Bound::Unbounded, |end| Bound::Excluded(end.to_vec()));
+
+        if let (Bound::Included(start), Bound::Excluded(end)) = (&start, &end) {
+            // BTreeMap::range panics if start > end
+            if start >= end {
+                return Box::new(std::iter::empty());
+            }
+        }
+
+        let mut pairs: Vec<_> = self
+            .store()
+            .read()
+            .expect("RwLock will not be poisoned")
+            .range::<Vec<u8>, _>((start, end))
+            .map(|(k, v)| Ok((k.clone().into_boxed_slice(), v.clone().into_boxed_slice())))
+            .collect();
+
+        if reverse {
+            pairs.reverse();
         }
+
+        Box::new(pairs.into_iter())
     }
 }
 
 impl Database for MemDB {
-    fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
-        self.store.borrow().get(key).cloned()
+    fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>, Error> {
+        Ok(self
+            .store()
+            .read()
+            .expect("RwLock will not be poisoned")
+            .get(key)
+            .cloned())
     }
 
-    fn put(&self, key: Vec<u8>, value: Vec<u8>) {
-        self.store.borrow_mut().insert(key, value);
-    }
+    fn put(&self, key: Vec<u8>, value: Vec<u8>) -> Result<(), Error> {
+        self.store()
+            .write()
+            .expect("RwLock will not be poisoned")
+            .insert(key, value);
 
-    fn iterator<'a>(&'a self) -> Box<dyn Iterator<Item = (Box<[u8]>, Box<[u8]>)> + 'a> {
-        Box::new(
-            self.store
-                .borrow()
-                .clone()
-                .into_iter()
-                .map(|(key, value)| (key.into_boxed_slice(), value.into_boxed_slice())),
-        )
+        Ok(())
     }
 
-    fn prefix_iterator<'a>(
-        &'a self,
-        prefix: Vec<u8>,
-    ) -> Box<dyn Iterator<Item = (Box<[u8]>, Box<[u8]>)> + 'a> {
-        let start = Bound::Included(prefix.clone());
-        let end = prefix_end_bound(prefix);
+    fn delete(&self, key: &[u8]) -> Result<(), Error> {
+        self.store()
+            .write()
+            .expect("RwLock will not be poisoned")
+            .remove(key);
 
-        let mut pairs = Vec::new();
+        Ok(())
+    }
 
-        for (k, v) in self.store.borrow().range((start, end)) {
-            //println!("Found: {}: {}", k, v);
-            let pair = (k.clone().into_boxed_slice(), v.clone().into_boxed_slice());
-            pairs.push(pair)
+    fn column(&self, column: Column) -> Self {
+        MemDB {
+            columns: self.columns.clone(),
+            column,
         }
+    }
 
-        Box::new(pairs.into_iter())
+    fn range(&self, start: Option<&[u8]>, end: Option<&[u8]>) -> DBIterator<'_> {
+        self.copied_range(start, end, false)
     }
-}
 
-/// Returns the Bound on a range query for a given prefix
-///
-/// That is the smallest x such that, prefix + y < x for all y. If
-/// no such x exists (i.e. prefix = vec![255; N]; for some N) it returns Bound::Unbounded
-fn prefix_end_bound(mut prefix: Vec<u8>) -> Bound<Vec<u8>> {
-    loop {
-        let last = prefix.last_mut();
-
-        match last {
-            None => return Bound::Unbounded,
-            Some(last) => {
-                if *last != 255 {
-                    *last += 1;
-                    return Bound::Excluded(prefix);
-                }
-                prefix.pop();
-            }
-        }
+    fn reverse_range(&self, start: Option<&[u8]>, end: Option<&[u8]>) -> DBIterator<'_> {
+        self.copied_range(start, end, true)
     }
 }
 
@@ -85,14 +115,16 @@ mod tests {
 
     use super::*;
 
+    type Pairs = Vec<(Box<[u8]>, Box<[u8]>)>;
+
     #[test]
     fn iterator_works() {
         let db = MemDB::new();
-        db.put(vec![1], vec![1]);
-        db.put(vec![2], vec![2]);
-        let got_pairs: Vec<(Box<[u8]>, Box<[u8]>)> = db.iterator().collect();
+        db.put(vec![1], vec![1]).unwrap();
+        db.put(vec![2], vec![2]).unwrap();
+        let got_pairs: Pairs = db.iterator().map(Result::unwrap).collect();
 
-        let expected_pairs: Vec<(Box<[u8]>, Box<[u8]>)> = vec![
+        let expected_pairs: Pairs = vec![
             (vec![1].into_boxed_slice(), vec![1].into_boxed_slice()),
             (vec![2].into_boxed_slice(), vec![2].into_boxed_slice()),
         ];
@@ -104,19 +136,24 @@ mod tests {
     #[test]
     fn prefix_iterator_works() {
         let db = MemDB::new();
-        db.put(vec![1, 1], vec![1]);
-        db.put(vec![2, 1], vec![2]);
-        db.put(vec![3, 1], vec![3]);
-        db.put(vec![4, 1], vec![4]);
+        db.put(vec![1, 1], vec![1]).unwrap();
+        db.put(vec![2, 1], vec![2]).unwrap();
+        db.put(vec![3, 1], vec![3]).unwrap();
+        db.put(vec![4, 1], vec![4]).unwrap();
 
-        let got_pairs: Vec<(Box<[u8]>, Box<[u8]>)> = db.prefix_iterator(vec![2]).collect();
+        let got_pairs: Pairs = db.prefix_iterator(vec![2]).map(Result::unwrap).collect();
 
         println!("got pairs: {:?}", got_pairs);
 
-        let expected_pairs: Vec<(Box<[u8]>, Box<[u8]>)> =
+        let expected_pairs: Pairs =
             vec![(vec![2, 1].into_boxed_slice(), vec![2].into_boxed_slice())];
 
         assert_eq!(expected_pairs.len(), got_pairs.len());
         assert!(got_pairs.iter().all(|e| { expected_pairs.contains(e) }));
     }
+
+    #[test]
+    fn conformance() {
+        crate::conformance::run(MemDB::new());
+    }
 }
@@ -1,6 +1,6 @@
 use std::sync::Arc;
 
-use crate::Database;
+use crate::{error::Error, Column, DBIterator, Database};
 
 #[derive(Debug)]
 pub struct PrefixDB<T> {
@@ -12,41 +12,81 @@ impl<T: Database> PrefixDB<T> {
     pub fn new(db: Arc<T>, prefix: Vec<u8>) -> Self {
         PrefixDB { db, prefix }
     }
+
+    /// Maps a range over this DB to a range over the underlying DB
+    fn bounds(&self, start: Option<&[u8]>, end: Option<&[u8]>) -> (Vec<u8>, Option<Vec<u8>>) {
+        let start = [&self.prefix, start.unwrap_or_default()].concat();
+        let end = match end {
+            Some(end) => Some([&self.prefix, end].concat()),
+            None => crate::prefix_end(&self.prefix),
+        };
+
+        (start, end)
+    }
+
+    /// Strips this DB's prefix and a further `extra` bytes from the keys
+    fn strip_prefix<'a>(&self, iter: DBIterator<'a>, extra: usize) -> DBIterator<'a> {
+        let prefix_length = self.prefix.len() + extra;
+        Box::new(iter.map(move |res| {
+            res.map(|(k, v)| {
+                let key = k[prefix_length..].to_vec();
+                (key.into_boxed_slice(), v)
+            })
+        }))
+    }
 }
 impl<T: Database> Database for PrefixDB<T> {
-    fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+    fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>, Error> {
         let key = [&self.prefix, key].concat();
         self.db.get(&key)
     }
 
-    fn put(&self, key: Vec<u8>, value: Vec<u8>) {
+    fn put(&self, key: Vec<u8>, value: Vec<u8>) -> Result<(), Error> {
         let key = [self.prefix.clone(), key].concat();
         self.db.put(key, value)
     }
 
-    fn iterator<'a>(&'a self) -> Box<dyn Iterator<Item = (Box<[u8]>, Box<[u8]>)> + 'a> {
-        let prefix_length = self.prefix.len();
-        Box::new(
+    fn delete(&self, key: &[u8]) -> Result<(), Error> {
+        let key = [&self.prefix, key].concat();
+        self.db.delete(&key)
+    }
+
+    fn flush(&self) -> Result<(), Error> {
+        self.db.flush()
+    }
+
+    fn column(&self, column: Column) -> Self {
+        PrefixDB::new(Arc::new(self.db.column(column)), self.prefix.clone())
+    }
+
+    fn range(&self, start: Option<&[u8]>, end: Option<&[u8]>) -> DBIterator<'_> {
+        let (start, end) = self.bounds(start, end);
+        self.strip_prefix(self.db.range(Some(&start), end.as_deref()), 0)
+    }
+
+    fn reverse_range(&self, start: Option<&[u8]>, end: Option<&[u8]>) -> DBIterator<'_> {
+        let (start, end) = self.bounds(start, end);
+        self.strip_prefix(self.db.reverse_range(Some(&start), end.as_deref()), 0)
+    }
+
+    /// Unlike the other backends the returned keys don't include the given prefix
+    fn prefix_iterator(&self, prefix: Vec<u8>) -> DBIterator<'_> {
+        let prefix_length = prefix.len();
+        self.strip_prefix(
             self.db
-                .prefix_iterator(self.prefix.clone())
-                .map(move |(k, v)| {
-                    let key = k[prefix_length..].to_vec();
-                    (key.into_boxed_slice(), v)
-                }),
+                .prefix_iterator([&self.prefix, &prefix[..]].concat()),
+            prefix_length,
         )
     }
 
-    fn prefix_iterator<'a>(
-        &'a self,
-        prefix: Vec<u8>,
-    ) -> Box<dyn Iterator<Item = (Box<[u8]>, Box<[u8]>)> + 'a> {
-        let prefix = [self.prefix.clone(), prefix].concat();
+    /// Unlike the other backends the returned keys don't include the given prefix
+    fn reverse_prefix_iterator(&self, prefix: Vec<u8>) -> DBIterator<'_> {
         let prefix_length = prefix.len();
-
-        Box::new(self.db.prefix_iterator(prefix).map(move |(k, v)| {
-            let key = k[prefix_length..].to_vec();
-            (key.into_boxed_slice(), v)
-        }))
+        self.strip_prefix(
+            self.db
+                .reverse_prefix_iterator([&self.prefix, &prefix[..]].concat()),
+            prefix_length,
+        )
     }
 }
 
@@ -57,38 +97,39 @@ mod tests {
 
     use super::*;
 
+    type Pairs = Vec<(Box<[u8]>, Box<[u8]>)>;
+
     #[test]
     fn get_works() {
         let db = MemDB::new();
-        db.put(vec![1, 1], vec![1]);
-        db.put(vec![2, 1], vec![2]);
+        db.put(vec![1, 1], vec![1]).unwrap();
+        db.put(vec![2, 1], vec![2]).unwrap();
         let prefix_db = PrefixDB::new(Arc::new(db), vec![2]);
 
-        assert!(prefix_db.get(&[1, 1]).is_none());
-        assert_eq!(prefix_db.get(&[1]), Some(vec![2]));
+        assert!(prefix_db.get(&[1, 1]).unwrap().is_none());
+        assert_eq!(prefix_db.get(&[1]).unwrap(), Some(vec![2]));
     }
 
     #[test]
     fn put_works() {
         let db = MemDB::new();
         let prefix_db = PrefixDB::new(Arc::new(db), vec![2]);
-        prefix_db.put(vec![2], vec![1, 2, 3]);
+        prefix_db.put(vec![2], vec![1, 2, 3]).unwrap();
 
-        assert_eq!(prefix_db.get(&[2]), Some(vec![1, 2, 3]));
+        assert_eq!(prefix_db.get(&[2]).unwrap(), Some(vec![1, 2, 3]));
     }
 
     #[test]
     fn iterator_works() {
         let db = MemDB::new();
-        db.put(vec![1, 1], vec![1]);
-        db.put(vec![2, 1], vec![2]);
-        db.put(vec![3, 1], vec![3]);
+        db.put(vec![1, 1], vec![1]).unwrap();
+        db.put(vec![2, 1], vec![2]).unwrap();
+        db.put(vec![3, 1], vec![3]).unwrap();
         let prefix_db = PrefixDB::new(Arc::new(db), vec![2]);
 
-        let got_pairs: Vec<(Box<[u8]>, Box<[u8]>)> = prefix_db.iterator().collect();
+        let got_pairs: Pairs = prefix_db.iterator().map(Result::unwrap).collect();
 
-        let expected_pairs: Vec<(Box<[u8]>, Box<[u8]>)> =
-            vec![(vec![1].into_boxed_slice(), vec![2].into_boxed_slice())];
+        let expected_pairs: Pairs = vec![(vec![1].into_boxed_slice(), vec![2].into_boxed_slice())];
 
         assert_eq!(expected_pairs.len(), got_pairs.len());
         assert!(got_pairs.iter().all(|e| { expected_pairs.contains(e) }));
@@ -97,19 +138,22 @@ mod tests {
     #[test]
     fn prefix_iterator_works() {
         let db = MemDB::new();
-        db.put(vec![1, 1], vec![1]);
-        db.put(vec![2, 1], vec![2]);
-        db.put(vec![2, 2, 3], vec![2]);
-        db.put(vec![2, 2, 4], vec![6]);
-        db.put(vec![2, 1], vec![2]);
-        db.put(vec![3, 1], vec![3]);
-        db.put(vec![4, 1], vec![4]);
+        db.put(vec![1, 1], vec![1]).unwrap();
+        db.put(vec![2, 1], vec![2]).unwrap();
+        db.put(vec![2, 2, 3], vec![2]).unwrap();
+        db.put(vec![2, 2, 4], vec![6]).unwrap();
+        db.put(vec![2, 1], vec![2]).unwrap();
+        db.put(vec![3, 1], vec![3]).unwrap();
+        db.put(vec![4, 1], vec![4]).unwrap();
 
         let prefix_db = PrefixDB::new(Arc::new(db), vec![2]);
 
-        let got_pairs: Vec<(Box<[u8]>, Box<[u8]>)> = prefix_db.prefix_iterator(vec![2]).collect();
+        let got_pairs: Pairs = prefix_db
+            .prefix_iterator(vec![2])
+            .map(Result::unwrap)
+            .collect();
 
-        let expected_pairs: Vec<(Box<[u8]>, Box<[u8]>)> = vec![
+        let expected_pairs: Pairs = vec![
             (vec![3].into_boxed_slice(), vec![2].into_boxed_slice()),
             (vec![4].into_boxed_slice(), vec![6].into_boxed_slice()),
         ];
@@ -117,4 +161,16 @@ mod tests {
         assert_eq!(expected_pairs.len(), got_pairs.len());
         assert!(got_pairs.iter().all(|e| { expected_pairs.contains(e) }));
     }
+
+    #[test]
+    fn conformance() {
+        let db = Arc::new(MemDB::new());
+        for key in [vec![6], vec![6, 255], vec![8], vec![8, 0]] {
+            db.put(key, vec![0]).unwrap();
+        }
+        crate::conformance::run_without_prefix_iteration(&PrefixDB::new(db.clone(), vec![7]));
+
+        // a prefix with no upper bound
+        crate::conformance::run_without_prefix_iteration(&PrefixDB::new(db, vec![255]));
+    }
 }
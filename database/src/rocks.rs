@@ -1,57 +1,278 @@
-use crate::{error::Error, Database};
-use std::path::Path;
+use crate::{error::Error, Column, DBIterator, Database};
+use std::{path::Path, sync::Arc};
 
-use rocksdb::{DBWithThreadMode, SingleThreaded};
+use rocksdb::{
+    checkpoint::Checkpoint, BlockBasedOptions, Cache, ColumnFamily, ColumnFamilyDescriptor,
+    DBCompressionType, DBRawIteratorWithThreadMode, DBWithThreadMode, SingleThreaded,
+};
 
+/// Compression applied to the files of every column
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RocksDBCompression {
+    None,
+    Snappy,
+    #[default]
+    Lz4,
+    Zstd,
+}
+
+impl From<RocksDBCompression> for DBCompressionType {
+    fn from(compression: RocksDBCompression) -> Self {
+        match compression {
+            RocksDBCompression::None => DBCompressionType::None,
+            RocksDBCompression::Snappy => DBCompressionType::Snappy,
+            RocksDBCompression::Lz4 => DBCompressionType::Lz4,
+            RocksDBCompression::Zstd => DBCompressionType::Zstd,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RocksDBOptions {
+    /// Size in bytes of the block cache shared by all columns
+    pub cache_size: usize,
+    pub compression: RocksDBCompression,
+    /// -1 means there's no limit
+    pub max_open_files: i32,
+    /// Size in bytes of a column's memtable
+    pub write_buffer_size: usize,
+}
+
+impl Default for RocksDBOptions {
+    fn default() -> Self {
+        Self {
+            cache_size: 32 << 20,
+            compression: RocksDBCompression::default(),
+            max_open_files: -1,
+            write_buffer_size: 64 << 20,
+        }
+    }
+}
+
+/// Each [`Column`] is stored in a column family of the same name
 #[derive(Debug)]
 pub struct RocksDB {
-    db: DBWithThreadMode<SingleThreaded>,
+    db: Arc<DBWithThreadMode<SingleThreaded>>,
+    column: Column,
 }
 
-// TODO: remove panics
-
 impl RocksDB {
     pub fn new<P>(path: P) -> Result<RocksDB, Error>
     where
         P: AsRef<Path>,
     {
-        Ok(RocksDB {
-            db: rocksdb::DB::open_default(path)?,
-        })
+        Self::new_with_options(path, RocksDBOptions::default())
+    }
+
+    pub fn new_with_options<P>(path: P, options: RocksDBOptions) -> Result<RocksDB, Error>
+    where
+        P: AsRef<Path>,
+    {
+        let opts = Self::db_options(&options);
+        let columns =
+            Column::ALL.map(|column| ColumnFamilyDescriptor::new(column.name(), opts.clone()));
+
+        Ok(Self::from_db(DBWithThreadMode::open_cf_descriptors(
+            &opts, path, columns,
+        )?))
+    }
+
+    /// Opens the database without taking the lock so it can be read while another process has
+    /// it open. Writes return an error and writes made by other processes after opening aren't
+    /// visible.
+    pub fn new_read_only<P>(path: P, options: RocksDBOptions) -> Result<RocksDB, Error>
+    where
+        P: AsRef<Path>,
+    {
+        let opts = Self::db_options(&options);
+        let columns = Column::ALL.map(|column| column.name());
+
+        Ok(Self::from_db(DBWithThreadMode::open_cf_for_read_only(
+            &opts, path, columns, false,
+        )?))
+    }
+
+    /// Opens the database as a secondary instance which follows the primary instance opened by
+    /// another process. The secondary keeps its own info logs in `secondary_path`. Writes return
+    /// an error, call [`RocksDB::try_catch_up_with_primary`] to see newer writes of the primary.
+    pub fn new_secondary<P, S>(
+        primary_path: P,
+        secondary_path: S,
+        options: RocksDBOptions,
+    ) -> Result<RocksDB, Error>
+    where
+        P: AsRef<Path>,
+        S: AsRef<Path>,
+    {
+        let mut opts = Self::db_options(&options);
+        // required by secondary instances
+        opts.set_max_open_files(-1);
+        let columns = Column::ALL.map(|column| column.name());
+
+        Ok(Self::from_db(DBWithThreadMode::open_cf_as_secondary(
+            &opts,
+            primary_path.as_ref(),
+            secondary_path.as_ref(),
+            columns,
+        )?))
+    }
+
+    /// Updates a secondary instance with the writes made by the primary since it was opened or
+    /// last caught up
+    pub fn try_catch_up_with_primary(&self) -> Result<(), Error> {
+        Ok(self.db.try_catch_up_with_primary()?)
+    }
+
+    fn db_options(options: &RocksDBOptions) -> rocksdb::Options {
+        let mut opts = rocksdb::Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        opts.set_max_open_files(options.max_open_files);
+        opts.set_write_buffer_size(options.write_buffer_size);
+        opts.set_compression_type(options.compression.into());
+
+        let mut table_opts = BlockBasedOptions::default();
+        table_opts.set_block_cache(&Cache::new_lru_cache(options.cache_size));
+        opts.set_block_based_table_factory(&table_opts);
+
+        opts
+    }
+
+    fn from_db(db: DBWithThreadMode<SingleThreaded>) -> RocksDB {
+        RocksDB {
+            db: Arc::new(db),
+            column: Column::Default,
+        }
+    }
+
+    /// Creates a consistent copy of the whole database, including all columns, in a directory
+    /// which mustn't already exist. Files are hard linked where possible, so it's cheap and is
+    /// safe to call while the database is being written to.
+    pub fn checkpoint<P>(&self, path: P) -> Result<(), Error>
+    where
+        P: AsRef<Path>,
+    {
+        Checkpoint::new(&*self.db)?.create_checkpoint(path)?;
+        Ok(())
+    }
+
+    fn cf(&self) -> &ColumnFamily {
+        self.db
+            .cf_handle(self.column.name())
+            .expect("all column families are created when the database is opened")
     }
 }
 
 impl Database for RocksDB {
-    fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
-        self.db
-            .get(key)
-            .unwrap_or_else(|e| panic!("unrecoverable database error {}", e))
+    fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>, Error> {
+        Ok(self.db.get_cf(self.cf(), key)?)
     }
 
-    fn put(&self, key: Vec<u8>, value: Vec<u8>) {
-        self.db
-            .put(key, value)
-            .unwrap_or_else(|e| panic!("unrecoverable database error {}", e))
+    fn put(&self, key: Vec<u8>, value: Vec<u8>) -> Result<(), Error> {
+        Ok(self.db.put_cf(self.cf(), key, value)?)
     }
 
-    fn iterator<'a>(&'a self) -> Box<dyn Iterator<Item = (Box<[u8]>, Box<[u8]>)> + 'a> {
-        Box::new(
-            self.db
-                .iterator(rocksdb::IteratorMode::Start)
-                .map(|res| res.unwrap_or_else(|e| panic!("unrecoverable database error {}", e))),
-        )
+    fn delete(&self, key: &[u8]) -> Result<(), Error> {
+        Ok(self.db.delete_cf(self.cf(), key)?)
     }
 
-    fn prefix_iterator<'a>(
-        &'a self,
-        prefix: Vec<u8>,
-    ) -> Box<dyn Iterator<Item = (Box<[u8]>, Box<[u8]>)> + 'a> {
-        Box::new(
-            self.db
-                .prefix_iterator(&prefix)
-                .map(|res| res.unwrap_or_else(|e| panic!("unrecoverable database error {}", e)))
-                .take_while(move |(k, _)| k.starts_with(&prefix)), //rocks db returns keys beyond the prefix see https://github.com/rust-rocksdb/rust-rocksdb/issues/577
-        )
+    fn flush(&self) -> Result<(), Error> {
+        // every column family shares the write ahead log
+        Ok(self.db.flush_wal(true)?)
+    }
+
+    fn column(&self, column: Column) -> Self {
+        RocksDB {
+            db: self.db.clone(),
+            column,
+        }
+    }
+
+    fn range(&self, start: Option<&[u8]>, end: Option<&[u8]>) -> DBIterator<'_> {
+        let mut iter = self.db.raw_iterator_cf(self.cf());
+        match start {
+            Some(start) => iter.seek(start),
+            None => iter.seek_to_first(),
+        }
+
+        Box::new(RawRange {
+            iter,
+            start: None,
+            end: end.map(<[u8]>::to_vec),
+            reverse: false,
+            done: false,
+        })
+    }
+
+    fn reverse_range(&self, start: Option<&[u8]>, end: Option<&[u8]>) -> DBIterator<'_> {
+        let mut iter = self.db.raw_iterator_cf(self.cf());
+        match end {
+            Some(end) => {
+                iter.seek_for_prev(end);
+                // the end is exclusive
+                if iter.key() == Some(end) {
+                    iter.prev();
+                }
+            }
+            None => iter.seek_to_last(),
+        }
+
+        Box::new(RawRange {
+            iter,
+            start: start.map(<[u8]>::to_vec),
+            end: None,
+            reverse: true,
+            done: false,
+        })
+    }
+}
+
+/// Walks a raw iterator which has already been positioned at the first key of the range,
+/// stopping once a key falls outside of `[start, end)`
+struct RawRange<'a> {
+    iter: DBRawIteratorWithThreadMode<'a, DBWithThreadMode<SingleThreaded>>,
+    start: Option<Vec<u8>>,
+    end: Option<Vec<u8>>,
+    reverse: bool,
+    done: bool,
+}
+
+impl Iterator for RawRange<'_> {
+    type Item = Result<(Box<[u8]>, Box<[u8]>), Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        if !self.iter.valid() {
+            self.done = true;
+            // an invalid iterator is either exhausted or has hit an error
+            return self.iter.status().err().map(|e| Err(e.into()));
+        }
+
+        let (key, value) = self.iter.item()?;
+
+        let in_range = match (&self.start, &self.end) {
+            (Some(start), _) if key < start.as_slice() => false,
+            (_, Some(end)) if key >= end.as_slice() => false,
+            _ => true,
+        };
+
+        if !in_range {
+            self.done = true;
+            return None;
+        }
+
+        let item = (key.into(), value.into());
+
+        if self.reverse {
+            self.iter.prev();
+        } else {
+            self.iter.next();
+        }
+
+        Some(Ok(item))
     }
 }
 
@@ -63,9 +284,9 @@ mod tests {
     #[test]
     fn iterator_works() {
         let db = RocksDB::new("tmp/1").unwrap();
-        db.put(vec![1], vec![1]);
-        db.put(vec![2], vec![2]);
-        let got_pairs: Vec<(Box<[u8]>, Box<[u8]>)> = db.iterator().collect();
+        db.put(vec![1], vec![1]).unwrap();
+        db.put(vec![2], vec![2]).unwrap();
+        let got_pairs: Vec<(Box<[u8]>, Box<[u8]>)> = db.iterator().map(Result::unwrap).collect();
 
         let expected_pairs: Vec<(Box<[u8]>, Box<[u8]>)> = vec![
             (vec![1].into_boxed_slice(), vec![1].into_boxed_slice()),
@@ -79,16 +300,94 @@ mod tests {
     #[test]
     fn prefix_iterator_works() {
         let db = RocksDB::new("tmp/2").unwrap();
-        db.put(vec![1, 1], vec![1]);
-        db.put(vec![2, 1], vec![2]);
-        db.put(vec![3, 1], vec![3]);
-        db.put(vec![4, 1], vec![4]);
+        db.put(vec![1, 1], vec![1]).unwrap();
+        db.put(vec![2, 1], vec![2]).unwrap();
+        db.put(vec![3, 1], vec![3]).unwrap();
+        db.put(vec![4, 1], vec![4]).unwrap();
 
-        let got_pairs: Vec<(Box<[u8]>, Box<[u8]>)> = db.prefix_iterator(vec![2]).collect();
+        let got_pairs: Vec<(Box<[u8]>, Box<[u8]>)> =
+            db.prefix_iterator(vec![2]).map(Result::unwrap).collect();
         let expected_pairs: Vec<(Box<[u8]>, Box<[u8]>)> =
             vec![(vec![2, 1].into_boxed_slice(), vec![2].into_boxed_slice())];
 
         assert_eq!(expected_pairs.len(), got_pairs.len());
         assert!(got_pairs.iter().all(|e| { expected_pairs.contains(e) }));
     }
+
+    #[test]
+    fn conformance() {
+        let _ = std::fs::remove_dir_all("tmp/conformance");
+        crate::conformance::run(RocksDB::new("tmp/conformance").unwrap());
+    }
+
+    #[test]
+    fn checkpoint_works() {
+        let _ = std::fs::remove_dir_all("tmp/checkpoint");
+        let _ = std::fs::remove_dir_all("tmp/checkpoint-copy");
+
+        let db = RocksDB::new("tmp/checkpoint").unwrap();
+        db.put(vec![1], vec![1]).unwrap();
+        db.column(Column::Nodes).put(vec![2], vec![2]).unwrap();
+        db.checkpoint("tmp/checkpoint-copy").unwrap();
+        db.put(vec![3], vec![3]).unwrap();
+
+        let copy = RocksDB::new("tmp/checkpoint-copy").unwrap();
+        assert_eq!(copy.get(&[1]).unwrap(), Some(vec![1]));
+        assert_eq!(copy.column(Column::Nodes).get(&[2]).unwrap(), Some(vec![2]));
+        assert_eq!(copy.get(&[3]).unwrap(), None);
+    }
+
+    #[test]
+    fn read_only_works() {
+        let _ = std::fs::remove_dir_all("tmp/read-only");
+
+        let db = RocksDB::new("tmp/read-only").unwrap();
+        db.column(Column::Roots).put(vec![1], vec![1]).unwrap();
+
+        let read_only = RocksDB::new_read_only("tmp/read-only", RocksDBOptions::default()).unwrap();
+        assert_eq!(
+            read_only.column(Column::Roots).get(&[1]).unwrap(),
+            Some(vec![1])
+        );
+        assert!(read_only.put(vec![2], vec![2]).is_err());
+    }
+
+    #[test]
+    fn read_only_checkpoint_works() {
+        let _ = std::fs::remove_dir_all("tmp/read-only-checkpoint");
+        let _ = std::fs::remove_dir_all("tmp/read-only-checkpoint-copy");
+
+        let db = RocksDB::new("tmp/read-only-checkpoint").unwrap();
+        db.column(Column::Nodes).put(vec![1], vec![1]).unwrap();
+
+        let read_only =
+            RocksDB::new_read_only("tmp/read-only-checkpoint", RocksDBOptions::default()).unwrap();
+        read_only
+            .checkpoint("tmp/read-only-checkpoint-copy")
+            .unwrap();
+        drop(read_only);
+        drop(db);
+
+        let copy = RocksDB::new("tmp/read-only-checkpoint-copy").unwrap();
+        assert_eq!(copy.column(Column::Nodes).get(&[1]).unwrap(), Some(vec![1]));
+    }
+
+    #[test]
+    fn secondary_works() {
+        let _ = std::fs::remove_dir_all("tmp/primary");
+        let _ = std::fs::remove_dir_all("tmp/secondary");
+
+        let db = RocksDB::new("tmp/primary").unwrap();
+        db.put(vec![1], vec![1]).unwrap();
+
+        let secondary =
+            RocksDB::new_secondary("tmp/primary", "tmp/secondary", RocksDBOptions::default())
+                .unwrap();
+        assert_eq!(secondary.get(&[1]).unwrap(), Some(vec![1]));
+        assert!(secondary.put(vec![2], vec![2]).is_err());
+
+        db.put(vec![2], vec![2]).unwrap();
+        secondary.try_catch_up_with_primary().unwrap();
+        assert_eq!(secondary.get(&[2]).unwrap(), Some(vec![2]));
+    }
 }
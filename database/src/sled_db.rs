@@ -0,0 +1,124 @@
+use crate::{error::Error, Column, DBIterator, Database};
+use std::{ops::Bound, path::Path, sync::Arc};
+
+/// A pure Rust alternative to [`crate::RocksDB`], columns are stored as sled trees
+#[derive(Debug)]
+pub struct SledDB {
+    columns: Arc<[sled::Tree]>,
+    column: Column,
+}
+
+impl SledDB {
+    pub fn new<P>(path: P) -> Result<SledDB, Error>
+    where
+        P: AsRef<Path>,
+    {
+        let db = sled::open(path)?;
+
+        let mut columns = Vec::with_capacity(Column::ALL.len());
+        for column in Column::ALL {
+            columns.push(match column {
+                Column::Default => (*db).clone(),
+                column => db.open_tree(column.name())?,
+            });
+        }
+
+        Ok(SledDB {
+            columns: columns.into(),
+            column: Column::Default,
+        })
+    }
+
+    fn tree(&self) -> &sled::Tree {
+        &self.columns[self.column as usize]
+    }
+
+    /// Returns `None` if the range is empty
+    fn iter(&self, start: Option<&[u8]>, end: Option<&[u8]>) -> Option<sled::Iter> {
+        let start = start.map_or(Bound::Unbounded, |start| Bound::Included(start.to_vec()));
+        let end = end.map_or(Bound::Unbounded, |end| Bound::Excluded(end.to_vec()));
+
+        if let (Bound::Included(start), Bound::Excluded(end)) = (&start, &end) {
+            // sled panics if start > end
+            if start >= end {
+                return None;
+            }
+        }
+
+        Some(self.tree().range::<Vec<u8>, _>((start, end)))
+    }
+}
+
+impl Database for SledDB {
+    fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>, Error> {
+        Ok(self.tree().get(key)?.map(|value| value.to_vec()))
+    }
+
+    fn put(&self, key: Vec<u8>, value: Vec<u8>) -> Result<(), Error> {
+        self.tree().insert(key, value)?;
+        Ok(())
+    }
+
+    fn delete(&self, key: &[u8]) -> Result<(), Error> {
+        self.tree().remove(key)?;
+        Ok(())
+    }
+
+    fn flush(&self) -> Result<(), Error> {
+        // flushing any tree flushes the whole sled database
+        self.tree().flush()?;
+        Ok(())
+    }
+
+    fn column(&self, column: Column) -> Self {
+        SledDB {
+            columns: self.columns.clone(),
+            column,
+        }
+    }
+
+    fn range(&self, start: Option<&[u8]>, end: Option<&[u8]>) -> DBIterator<'_> {
+        Box::new(self.iter(start, end).into_iter().flatten().map(|res| {
+            res.map(|(k, v)| (k.to_vec().into_boxed_slice(), v.to_vec().into_boxed_slice()))
+                .map_err(Error::from)
+        }))
+    }
+
+    fn reverse_range(&self, start: Option<&[u8]>, end: Option<&[u8]>) -> DBIterator<'_> {
+        Box::new(
+            self.iter(start, end)
+                .into_iter()
+                .flatten()
+                .rev()
+                .map(|res| {
+                    res.map(|(k, v)| (k.to_vec().into_boxed_slice(), v.to_vec().into_boxed_slice()))
+                        .map_err(Error::from)
+                }),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn conformance() {
+        let dir = tempfile::tempdir().unwrap();
+        crate::conformance::run(SledDB::new(dir.path()).unwrap());
+    }
+
+    #[test]
+    fn flushed_writes_are_persisted() {
+        let dir = tempfile::tempdir().unwrap();
+        {
+            let db = SledDB::new(dir.path()).unwrap();
+            db.put(vec![1], vec![2]).unwrap();
+            db.flush().unwrap();
+        }
+
+        let db = SledDB::new(dir.path()).unwrap();
+        assert_eq!(db.get(&[1]).unwrap(), Some(vec![2]));
+    }
+}
@@ -85,14 +85,14 @@ impl gears::baseapp::ABCIHandler<Message, GaiaStoreKey, GenesisState> for ABCIHa
         &self,
         ctx: &mut InitContext<'_, DB, GaiaStoreKey>,
         genesis: GenesisState,
-    ) {
+    ) -> Result<(), AppError> {
         self.bank_abci_handler.genesis(ctx, genesis.bank);
-        self.auth_abci_handler.genesis(ctx, genesis.auth);
+        self.auth_abci_handler.genesis(ctx, genesis.auth)
     }
 
     fn query<DB: Database + Send + Sync>(
         &self,
-        ctx: &QueryContext<'_, DB, GaiaStoreKey>,
+        ctx: &QueryContext<DB, GaiaStoreKey>,
         query: RequestQuery,
     ) -> Result<bytes::Bytes, AppError> {
         if query.path.starts_with("/cosmos.auth") {
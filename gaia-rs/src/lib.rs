@@ -5,6 +5,7 @@ use auth::cli::query::AuthQueryHandler;
 use bank::cli::query::BankQueryHandler;
 use client::tx_command_handler;
 use client::GaiaQueryCommands;
+use database::Database;
 use gears::application::client::Client;
 use gears::application::command::NilAux;
 use gears::application::command::NilAuxCommand;
@@ -123,8 +124,9 @@ impl Node for GaiaCore {
     type ABCIHandler = ABCIHandler;
     type ApplicationConfig = config::AppConfig;
 
-    fn router<AI: ApplicationInfo>() -> axum::Router<
+    fn router<DB: Database, AI: ApplicationInfo>() -> axum::Router<
         gears::client::rest::RestState<
+            DB,
             Self::StoreKey,
             Self::ParamsSubspaceKey,
             Self::Message,
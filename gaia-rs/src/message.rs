@@ -2,9 +2,9 @@ use auth::signing::renderer::value_renderer::{Error, ValueRenderer};
 use gears_derive::RoutingMessage;
 use proto_messages::cosmos::tx::v1beta1::{screen::Screen, tx_metadata::Metadata};
 use proto_types::{AccAddress, Denom};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, RoutingMessage, Serialize)]
+#[derive(Debug, Clone, RoutingMessage, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum Message {
     #[gears(url = "/cosmos.bank.v1beta1")]
@@ -20,7 +20,7 @@ impl ValueRenderer for Message {
     ) -> Result<Vec<Screen>, Error> {
         match self {
             Message::Bank(msg) => msg.format(get_metadata),
-            Message::Ibc(_) => Err(Error::NotImplemented),
+            Message::Ibc(msg) => msg.format(get_metadata),
         }
     }
 }
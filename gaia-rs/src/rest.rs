@@ -1,4 +1,5 @@
 use axum::Router;
+use database::Database;
 use gears::{
     application::ApplicationInfo,
     baseapp::{ABCIHandler, Genesis},
@@ -9,12 +10,13 @@ use proto_messages::cosmos::tx::v1beta1::message::Message;
 use store::StoreKey;
 
 pub fn get_router<
+    DB: Database,
     SK: StoreKey,
     PSK: ParamsSubspaceKey,
     M: Message,
     H: ABCIHandler<M, SK, G>,
     G: Genesis,
     AI: ApplicationInfo,
->() -> Router<RestState<SK, PSK, M, H, G, AI>> {
+>() -> Router<RestState<DB, SK, PSK, M, H, G, AI>> {
     Router::new().nest("/cosmos/bank", bank::rest::get_router())
 }
@@ -9,7 +9,7 @@ use gears::{
     config::DEFAULT_TENDERMINT_RPC_ADDRESS,
 };
 use proto_messages::cosmos::{
-    bank::v1beta1::{QueryAllBalancesResponse, QueryDenomsMetadataResponse},
+    bank::v1beta1::{PageResponse, QueryAllBalancesResponse, QueryDenomsMetadataResponse},
     base::v1beta1::Coin,
 };
 use proto_types::{AccAddress, Denom};
@@ -44,7 +44,10 @@ fn balances_query() -> anyhow::Result<()> {
                 denom: Denom::from_str("uatom")?,
                 amount: 34_u32.into(),
             }],
-            pagination: None,
+            pagination: Some(PageResponse {
+                next_key: vec![],
+                total: 1,
+            }),
         },
     ));
 
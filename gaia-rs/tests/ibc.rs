@@ -1,9 +1,12 @@
 use std::{str::FromStr, time::Duration};
 
 use gaia_rs::{client::GaiaTxCommands, GaiaCoreClient};
-use gears::client::{
-    keys::KeyringBackend,
-    tx::{run_tx, TxCommand},
+use gears::{
+    client::{
+        keys::KeyringBackend,
+        tx::{run_tx, TxCommand},
+    },
+    crypto::TxSignMode,
 };
 use ibc::{
     client::cli::tx::{
@@ -76,7 +79,20 @@ fn client_create_tx() -> anyhow::Result<()> {
         from_key: KEY_NAME.to_owned(),
         chain_id: "test-chain".try_into()?,
         fee: None,
+        gas_prices: None,
+        gas_limit: 100_000_000,
+        memo: String::new(),
+        timeout_height: 0,
         keyring_backend: KeyringBackend::Test,
+        sign_mode: TxSignMode::Direct,
+        account_number: None,
+        sequence: None,
+        tip: None,
+        fee_payer: None,
+        fee_granter: None,
+        generate_only: false,
+        dry_run: false,
+        multisig_signers: vec![],
 
         inner: GaiaTxCommands::IBC(args_cmd),
     };
@@ -108,7 +124,20 @@ fn client_update_tx() -> anyhow::Result<()> {
         from_key: KEY_NAME.to_owned(),
         chain_id: "test-chain".try_into()?,
         fee: None,
+        gas_prices: None,
+        gas_limit: 100_000_000,
+        memo: String::new(),
+        timeout_height: 0,
         keyring_backend: KeyringBackend::Test,
+        sign_mode: TxSignMode::Direct,
+        account_number: None,
+        sequence: None,
+        tip: None,
+        fee_payer: None,
+        fee_granter: None,
+        generate_only: false,
+        dry_run: false,
+        multisig_signers: vec![],
 
         inner: GaiaTxCommands::IBC(args_cmd),
     };
@@ -143,7 +172,20 @@ fn client_upgrade_tx() -> anyhow::Result<()> {
         from_key: KEY_NAME.to_owned(),
         chain_id: "test-chain".try_into()?,
         fee: None,
+        gas_prices: None,
+        gas_limit: 100_000_000,
+        memo: String::new(),
+        timeout_height: 0,
         keyring_backend: KeyringBackend::Test,
+        sign_mode: TxSignMode::Direct,
+        account_number: None,
+        sequence: None,
+        tip: None,
+        fee_payer: None,
+        fee_granter: None,
+        generate_only: false,
+        dry_run: false,
+        multisig_signers: vec![],
 
         inner: GaiaTxCommands::IBC(args_cmd),
     };
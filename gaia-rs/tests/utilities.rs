@@ -83,6 +83,15 @@ pub fn key_add(home: impl Into<PathBuf>) -> anyhow::Result<()> {
         recover: Default::default(),
         home: home.into(),
         keyring_backend: gears::client::keys::KeyringBackend::Test,
+        interactive: false,
+        hd_path: None,
+        coin_type: 118,
+        account: 0,
+        index: 0,
+        algo: gears::client::keys::KeyAlgorithm::Secp256k1,
+        multisig: vec![],
+        multisig_threshold: 1,
+        nosort: false,
     };
 
     keys(KeyCommand::Add(cmd))?;
@@ -3,6 +3,8 @@ use quote::quote;
 use syn::{self};
 use syner::Syner;
 
+mod value_renderer;
+
 #[derive(Syner)]
 struct Gears {
     pub url: String,
@@ -43,6 +45,18 @@ fn impl_message(ast: &syn::DeriveInput) -> TokenStream {
                 }
             });
 
+            let amino_url = enum_data.variants.iter().map(|v| v.clone().ident).map(|i| {
+                quote! {
+                    Self::#i(msg) => proto_messages::cosmos::tx::v1beta1::message::Message::amino_url(msg)
+                }
+            });
+
+            let to_amino_json = enum_data.variants.iter().map(|v| v.clone().ident).map(|i| {
+                quote! {
+                    Self::#i(msg) => proto_messages::cosmos::tx::v1beta1::message::Message::to_amino_json(msg)
+                }
+            });
+
             let into_any = enum_data.variants.iter().map(|v| v.clone().ident).map(|i| {
                 quote! {
                     #name ::#i(msg) => msg.into()
@@ -86,6 +100,18 @@ fn impl_message(ast: &syn::DeriveInput) -> TokenStream {
                         }
                     }
 
+                    fn amino_url(&self) -> Option<&'static str> {
+                        match self {
+                            #(#amino_url),*
+                        }
+                    }
+
+                    fn to_amino_json(&self) -> std::result::Result<::proto_messages::serde_json::Value, ::proto_messages::Error> {
+                        match self {
+                            #(#to_amino_json),*
+                        }
+                    }
+
 
                 }
 
@@ -118,3 +144,38 @@ fn impl_message(ast: &syn::DeriveInput) -> TokenStream {
         }
     }
 }
+
+/// Derive `ValueRenderer` of the textual sign mode for a message struct. Every field is rendered
+/// into a `Screen` titled with the field name in sentence case and indented under the tx's
+/// "Message (i/n)" screen, which matches the hand-written renderers e.g. of `MsgSend`.
+///
+/// Fields holding their default value, e.g. an empty string or zero, aren't rendered, which is
+/// checked with the `IsDefault` trait of the renderer.
+///
+/// Message attributes:
+/// - `#[renderer(type_url = "/cosmos.bank.v1beta1.MsgSend")]` adds a header screen with the type URL
+/// - `#[renderer(indent = 1)]` sets the indent of the fields, 2 by default
+/// - `#[renderer(remote = "MsgSend")]` implements `ValueRenderer` for a type of another crate whose
+/// fields are mirrored by the struct
+/// - `#[renderer(crate = "crate")]` sets the path of the crate of the renderer traits, `::auth` by
+/// default
+///
+/// Field attributes:
+/// - `#[renderer(title = "Title")]` overrides the title
+/// - `#[renderer(indent = 3)]` overrides the indent
+/// - `#[renderer(expert)]` marks the screens as expert-only
+/// - `#[renderer(skip)]` doesn't render the field
+/// - `#[renderer(try_format)]`, `#[renderer(with_metadata)]` or `#[renderer(message)]` format the
+/// value with `TryPrimitiveValueRenderer`, `TryPrimitiveValueRendererWithMetadata` or
+/// `ValueRenderer` instead of `PrimitiveValueRenderer`
+/// - `#[renderer(by_ref)]` passes a reference to the value instead of a clone
+///
+/// The generated code refers to the `proto_messages` and `proto_types` crates.
+#[proc_macro_derive(ValueRenderer, attributes(renderer))]
+pub fn value_renderer_derive(input: TokenStream) -> TokenStream {
+    let ast = syn::parse(input).unwrap();
+
+    value_renderer::impl_value_renderer(&ast)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
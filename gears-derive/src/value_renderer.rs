@@ -0,0 +1,377 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Attribute, Data, DataStruct, DeriveInput, Fields, Ident, Lit, Meta, NestedMeta, Path};
+
+const ATTRIBUTE: &str = "renderer";
+
+/// Fields of a message of a tx are rendered under the tx's "Message (i/n)" screen
+const DEFAULT_INDENT: u8 = 2;
+/// Max indent of a `Screen`
+const MAX_INDENT: u8 = 16;
+
+/// How the value of a field is formatted
+enum Renderer {
+    /// `PrimitiveValueRenderer::format`
+    Format,
+    /// `TryPrimitiveValueRenderer::try_format`
+    TryFormat,
+    /// `TryPrimitiveValueRendererWithMetadata::try_format_with_metadata`
+    WithMetadata,
+    /// `ValueRenderer::format` of a nested message
+    Message,
+}
+
+/// Attributes of the struct: `#[renderer(type_url = "...", indent = N, remote = "...",
+/// crate = "...")]`
+#[derive(Default)]
+struct MessageAttributes {
+    type_url: Option<String>,
+    indent: Option<u8>,
+    remote: Option<Path>,
+    krate: Option<Path>,
+}
+
+impl MessageAttributes {
+    fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut message = Self::default();
+
+        parse_attributes(attrs, |ident, lit| {
+            match ident.to_string().as_str() {
+                "type_url" => message.type_url = Some(lit_str(ident, lit)?),
+                "indent" => message.indent = Some(lit_indent(ident, lit)?),
+                "remote" => message.remote = Some(lit_path(ident, lit)?),
+                "crate" => message.krate = Some(lit_path(ident, lit)?),
+                _ => return Err(syn::Error::new_spanned(ident, "unknown message attribute")),
+            }
+
+            Ok(())
+        })?;
+
+        Ok(message)
+    }
+}
+
+/// Attributes of a field: `#[renderer(title = "...", indent = N, expert, skip, by_ref)]` and
+/// one of `try_format`, `with_metadata` or `message` to select the renderer of the value
+#[derive(Default)]
+struct FieldAttributes {
+    title: Option<String>,
+    indent: Option<u8>,
+    expert: bool,
+    skip: bool,
+    by_ref: bool,
+    renderer: Option<Renderer>,
+}
+
+impl FieldAttributes {
+    fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut field = Self::default();
+
+        parse_attributes(attrs, |ident, lit| {
+            let renderer = match ident.to_string().as_str() {
+                "title" => {
+                    field.title = Some(lit_str(ident, lit)?);
+                    return Ok(());
+                }
+                "indent" => {
+                    field.indent = Some(lit_indent(ident, lit)?);
+                    return Ok(());
+                }
+                "expert" => {
+                    field.expert = lit_flag(ident, lit)?;
+                    return Ok(());
+                }
+                "skip" => {
+                    field.skip = lit_flag(ident, lit)?;
+                    return Ok(());
+                }
+                "by_ref" => {
+                    field.by_ref = lit_flag(ident, lit)?;
+                    return Ok(());
+                }
+                "try_format" => Renderer::TryFormat,
+                "with_metadata" => Renderer::WithMetadata,
+                "message" => Renderer::Message,
+                _ => return Err(syn::Error::new_spanned(ident, "unknown field attribute")),
+            };
+
+            lit_flag(ident, lit)?;
+            if field.renderer.replace(renderer).is_some() {
+                return Err(syn::Error::new_spanned(
+                    ident,
+                    "only one of `try_format`, `with_metadata` and `message` can be set",
+                ));
+            }
+
+            Ok(())
+        })?;
+
+        Ok(field)
+    }
+}
+
+pub fn impl_value_renderer(ast: &DeriveInput) -> syn::Result<TokenStream> {
+    let name = &ast.ident;
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+
+    let fields = match &ast.data {
+        Data::Struct(DataStruct {
+            fields: Fields::Named(fields),
+            ..
+        }) => &fields.named,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                ast,
+                "ValueRenderer can only be derived for structs with named fields",
+            ))
+        }
+    };
+
+    let message = MessageAttributes::parse(&ast.attrs)?;
+    let fields_indent = message.indent.unwrap_or(DEFAULT_INDENT);
+    let krate = message
+        .krate
+        .unwrap_or_else(|| syn::parse_quote! { ::auth });
+    let renderer = quote! { #krate::signing::renderer };
+    // the impl is for the remote type whose fields mirror the fields of the struct
+    let target = match message.remote {
+        Some(remote) => quote! { #remote },
+        None => quote! { #name },
+    };
+
+    let header = match message.type_url {
+        Some(type_url) => {
+            let indent =
+                match fields_indent.checked_sub(1) {
+                    Some(indent) => indent_tokens(indent),
+                    None => return Err(syn::Error::new_spanned(
+                        ast,
+                        "the indent of the fields must be at least 1 to show the type URL header",
+                    )),
+                };
+
+            quote! {
+                screens.push(::proto_messages::cosmos::tx::v1beta1::screen::Screen {
+                    title: ::std::string::String::new(),
+                    content: <#renderer::value_renderer::DefaultPrimitiveRenderer
+                        as #renderer::value_renderer::TryPrimitiveValueRenderer<_>>::try_format(#type_url)?,
+                    indent: #indent,
+                    expert: false,
+                });
+            }
+        }
+        None => quote! {},
+    };
+
+    let mut fields_rendering = Vec::with_capacity(fields.len());
+    for field in fields {
+        let attributes = FieldAttributes::parse(&field.attrs)?;
+        if attributes.skip {
+            continue;
+        }
+
+        let ident = field
+            .ident
+            .as_ref()
+            .expect("fields of the struct are named");
+        let title = attributes
+            .title
+            .unwrap_or_else(|| default_title(&ident.to_string()));
+        let indent = attributes.indent.unwrap_or(fields_indent);
+        let expert = attributes.expert;
+
+        let value = if attributes.by_ref {
+            quote! { &self.#ident }
+        } else {
+            quote! { ::core::clone::Clone::clone(&self.#ident) }
+        };
+
+        let content = match attributes.renderer.unwrap_or(Renderer::Format) {
+            Renderer::Format => quote! {
+                <#renderer::value_renderer::DefaultPrimitiveRenderer
+                    as #renderer::value_renderer::PrimitiveValueRenderer<_>>::format(#value)
+            },
+            Renderer::TryFormat => quote! {
+                <#renderer::value_renderer::DefaultPrimitiveRenderer
+                    as #renderer::value_renderer::TryPrimitiveValueRenderer<_>>::try_format(#value)?
+            },
+            Renderer::WithMetadata => quote! {
+                <#renderer::value_renderer::DefaultPrimitiveRenderer
+                    as #renderer::value_renderer::TryPrimitiveValueRendererWithMetadata<_>>::try_format_with_metadata(#value, get_metadata)?
+            },
+            Renderer::Message => {
+                let expert = if expert {
+                    quote! {
+                        for screen in field_screens.iter_mut() {
+                            screen.expert = true;
+                        }
+                    }
+                } else {
+                    quote! {}
+                };
+
+                // the screens of a nested message are indented relative to its header which
+                // is titled with the field name
+                fields_rendering.push(quote! {{
+                    let mut field_screens = #renderer::value_renderer::ValueRenderer::format(&self.#ident, get_metadata)?;
+                    let first = field_screens.first_mut().ok_or_else(|| {
+                        #renderer::value_renderer::Error::Rendering(
+                            ::std::format!("empty rendering for {}", #title),
+                        )
+                    })?;
+                    first.title = ::std::string::ToString::to_string(#title);
+                    #expert
+                    #renderer::composite::indent_screens(&mut field_screens, #indent)?;
+                    screens.append(&mut field_screens);
+                }});
+                continue;
+            }
+        };
+
+        // fields with the default value, e.g. an empty string or zero, aren't rendered
+        let indent = indent_tokens(indent);
+        fields_rendering.push(quote! {
+            if !#renderer::value_renderer::IsDefault::is_default(&self.#ident) {
+                screens.push(::proto_messages::cosmos::tx::v1beta1::screen::Screen {
+                    title: ::std::string::ToString::to_string(#title),
+                    content: #content,
+                    indent: #indent,
+                    expert: #expert,
+                });
+            }
+        });
+    }
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics #renderer::value_renderer::ValueRenderer for #target #ty_generics #where_clause {
+            #[allow(unused_mut, unused_variables)]
+            fn format<F: Fn(&::proto_types::Denom) -> Option<::proto_messages::cosmos::tx::v1beta1::tx_metadata::Metadata>>(
+                &self,
+                get_metadata: &F,
+            ) -> Result<
+                Vec<::proto_messages::cosmos::tx::v1beta1::screen::Screen>,
+                #renderer::value_renderer::Error,
+            > {
+                let mut screens = Vec::new();
+
+                #header
+                #(#fields_rendering)*
+
+                Ok(screens)
+            }
+        }
+    })
+}
+
+/// Calls `parse` with the name and the value, if any, of every item of the `#[renderer(...)]`
+/// attributes
+fn parse_attributes(
+    attrs: &[Attribute],
+    mut parse: impl FnMut(&Ident, Option<&Lit>) -> syn::Result<()>,
+) -> syn::Result<()> {
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident(ATTRIBUTE)) {
+        let list = match attr.parse_meta()? {
+            Meta::List(list) => list,
+            meta => {
+                return Err(syn::Error::new_spanned(
+                    meta,
+                    format!("expected `#[{ATTRIBUTE}(...)]`"),
+                ))
+            }
+        };
+
+        for nested in list.nested {
+            match nested {
+                NestedMeta::Meta(Meta::Path(path)) => parse(path_ident(&path)?, None)?,
+                NestedMeta::Meta(Meta::NameValue(name_value)) => {
+                    parse(path_ident(&name_value.path)?, Some(&name_value.lit))?
+                }
+                nested => return Err(syn::Error::new_spanned(nested, "unknown attribute")),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn path_ident(path: &Path) -> syn::Result<&Ident> {
+    path.get_ident()
+        .ok_or_else(|| syn::Error::new_spanned(path, "expected an identifier"))
+}
+
+fn lit_str(ident: &Ident, lit: Option<&Lit>) -> syn::Result<String> {
+    match lit {
+        Some(Lit::Str(lit)) => Ok(lit.value()),
+        _ => Err(syn::Error::new_spanned(
+            ident,
+            format!("expected `{ident} = \"...\"`"),
+        )),
+    }
+}
+
+fn lit_path(ident: &Ident, lit: Option<&Lit>) -> syn::Result<Path> {
+    match lit {
+        Some(Lit::Str(lit)) => lit.parse(),
+        _ => Err(syn::Error::new_spanned(
+            ident,
+            format!("expected `{ident} = \"...\"`"),
+        )),
+    }
+}
+
+fn lit_indent(ident: &Ident, lit: Option<&Lit>) -> syn::Result<u8> {
+    match lit {
+        Some(Lit::Int(lit)) => {
+            let indent = lit.base10_parse::<u8>()?;
+            if indent > MAX_INDENT {
+                return Err(syn::Error::new_spanned(
+                    lit,
+                    format!("indent can't be greater than {MAX_INDENT}"),
+                ));
+            }
+
+            Ok(indent)
+        }
+        _ => Err(syn::Error::new_spanned(
+            ident,
+            format!("expected `{ident} = <number>`"),
+        )),
+    }
+}
+
+fn lit_flag(ident: &Ident, lit: Option<&Lit>) -> syn::Result<bool> {
+    match lit {
+        None => Ok(true),
+        Some(lit) => Err(syn::Error::new_spanned(
+            lit,
+            format!("`{ident}` doesn't take a value"),
+        )),
+    }
+}
+
+/// Screens without indent are at the top level
+fn indent_tokens(indent: u8) -> TokenStream {
+    if indent == 0 {
+        quote! { None }
+    } else {
+        quote! {
+            Some(
+                ::proto_messages::cosmos::tx::v1beta1::screen::Indent::new(#indent)
+                    .expect("indent is checked by the derive macro"),
+            )
+        }
+    }
+}
+
+/// Title of a field as the SDK shows it: the field name in sentence case e.g. "from_address"
+/// becomes "From address"
+fn default_title(field_name: &str) -> String {
+    let name = field_name.trim_start_matches("r#").replace('_', " ");
+
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => name,
+    }
+}
@@ -1,4 +1,8 @@
-use crate::client::{keys, query::run_query, tx::run_tx};
+use crate::client::{
+    keys,
+    query::run_query,
+    tx::{run_aux_to_fee, run_broadcast, run_multi_sign, run_sign, run_tx},
+};
 
 use super::{
     command::client::ClientCommands,
@@ -32,6 +36,26 @@ impl<'a, Core: Client> ClientApplication<Core> {
 
                 println!("{}", serde_json::to_string_pretty(&tx)?);
             }
+            ClientCommands::MultiSign(cmd) => {
+                let tx = run_multi_sign::<Core::Message>(cmd)?;
+
+                println!("{}", serde_json::to_string_pretty(&tx)?);
+            }
+            ClientCommands::AuxToFee(cmd) => {
+                let tx = run_aux_to_fee(cmd, &self.core)?;
+
+                println!("{}", serde_json::to_string_pretty(&tx)?);
+            }
+            ClientCommands::Sign(cmd) => {
+                let tx = run_sign(cmd, &self.core)?;
+
+                println!("{}", serde_json::to_string_pretty(&tx)?);
+            }
+            ClientCommands::Broadcast(cmd) => {
+                let res = run_broadcast::<Core::Message>(cmd)?;
+
+                println!("{}", serde_json::to_string_pretty(&res)?);
+            }
             ClientCommands::Query(cmd) => {
                 let query = run_query(cmd, &self.core)?;
 
@@ -14,12 +14,19 @@ pub enum ApplicationCommands<ClientAUX, AppAUX, TX, QUE> {
 }
 
 pub mod client {
-    use crate::client::{query::QueryCommand, tx::TxCommand};
+    use crate::client::{
+        query::QueryCommand,
+        tx::{AuxToFeeCommand, BroadcastCommand, MultiSignCommand, SignCommand, TxCommand},
+    };
 
     #[derive(Debug, Clone)]
     pub enum ClientCommands<AUX, TX, QUE> {
         Aux(AUX),
         Tx(TxCommand<TX>),
+        MultiSign(MultiSignCommand),
+        AuxToFee(AuxToFeeCommand),
+        Sign(SignCommand),
+        Broadcast(BroadcastCommand),
         Query(QueryCommand<QUE>),
         Keys(crate::client::keys::KeyCommand),
     }
@@ -31,6 +38,8 @@ pub mod app {
         Init(crate::client::init::InitCommand),
         Run(crate::baseapp::run::RunCommand),
         GenesisAdd(crate::client::genesis_account::GenesisCommand),
+        Debug(crate::client::debug::DebugCommand),
+        Backup(crate::client::backup::BackupCommand),
         Aux(AUX),
     }
 }
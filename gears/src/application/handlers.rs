@@ -1,7 +1,7 @@
 use keyring::key_pair::KeyPair;
 use proto_messages::cosmos::{query::Query, tx::v1beta1::message::Message};
 use proto_types::AccAddress;
-use serde::Serialize;
+use serde::{de::DeserializeOwned, Serialize};
 use tendermint::{
     informal::block::Height,
     rpc::{endpoint::broadcast::tx_commit::Response, Client, HttpClient},
@@ -9,18 +9,21 @@ use tendermint::{
 
 use crate::{
     client::{query::execute_query, tx::broadcast_tx_commit},
-    crypto::{create_signed_transaction, SigningInfo},
+    crypto::{create_aux_signer_data, create_signed_transaction, SigningInfo, TxSignMode},
     runtime::runtime,
 };
 use proto_messages::cosmos::{
     auth::v1beta1::{QueryAccountRequest, QueryAccountResponse},
     base::v1beta1::SendCoins,
     ibc::{auth::RawQueryAccountResponse, protobuf::Protobuf},
-    tx::v1beta1::{fee::Fee, tx_body::TxBody},
+    tx::v1beta1::{
+        auth_info::AuthInfo, aux_signer_data::AuxSignerData, fee::Fee, tip::Tip, tx::tx::Tx,
+        tx_body::TxBody,
+    },
 };
 
 pub trait TxHandler {
-    type Message: Message;
+    type Message: Message + DeserializeOwned;
     type TxCommands;
 
     fn prepare_tx(
@@ -29,23 +32,185 @@ pub trait TxHandler {
         from_address: AccAddress,
     ) -> anyhow::Result<Self::Message>;
 
+    /// Signs the Tx and broadcasts it. The account number and sequence of the key are queried
+    /// from the node unless they're set.
+    #[allow(clippy::too_many_arguments)]
     fn handle_tx(
         &self,
-        msg: Self::Message,
+        tx_body: TxBody<Self::Message>,
+        key: KeyPair,
+        node: url::Url,
+        chain_id: tendermint::informal::chain::Id,
+        fee: Fee,
+        sign_mode: TxSignMode,
+        account_number: Option<u64>,
+        sequence: Option<u64>,
+    ) -> anyhow::Result<Response> {
+        let address = key.get_address();
+
+        // the tx is only signed by the key so an explicit fee payer must be the key
+        if let Some(payer) = &fee.payer {
+            if payer != &address {
+                return Err(anyhow::anyhow!(
+                    "fee payer {} must be a signer of the tx, it's only signed by {}",
+                    payer,
+                    address
+                ));
+            }
+        }
+
+        let signing_info = get_signing_info(key, node.as_str(), account_number, sequence)?;
+
+        let tip = None; // tips are only paid by aux signers, see handle_aux_tx
+
+        let raw_tx = create_signed_transaction(
+            vec![signing_info],
+            vec![],
+            tx_body,
+            fee,
+            tip,
+            chain_id,
+            sign_mode,
+        )?;
+
+        let client = HttpClient::new(tendermint::rpc::Url::try_from(node)?)?;
+
+        broadcast_tx_commit(client, raw_tx)
+    }
+
+    /// Builds the unsigned Tx of the message, it's signed with `handle_sign` e.g. by an offline key
+    fn handle_generate_only(&self, tx_body: TxBody<Self::Message>, fee: Fee) -> Tx<Self::Message> {
+        Tx {
+            body: tx_body,
+            auth_info: AuthInfo {
+                signer_infos: vec![],
+                fee,
+                tip: None,
+            },
+            signatures: vec![],
+            signatures_data: vec![],
+        }
+    }
+
+    /// Signs an unsigned Tx built by `handle_generate_only`. The account number and sequence of
+    /// the key are queried from the node unless they're set, so a key can sign offline.
+    #[allow(clippy::too_many_arguments)]
+    fn handle_sign(
+        &self,
+        tx: Tx<Self::Message>,
+        key: KeyPair,
+        node: url::Url,
+        chain_id: tendermint::informal::chain::Id,
+        sign_mode: TxSignMode,
+        account_number: Option<u64>,
+        sequence: Option<u64>,
+    ) -> anyhow::Result<Tx<Self::Message>> {
+        if !tx.signatures.is_empty() {
+            return Err(anyhow::anyhow!("tx is already signed"));
+        }
+
+        let address = key.get_address();
+
+        // the tx is only signed by the key so it must be the only signer, including the fee payer
+        let signers = tx.get_signers();
+        if signers != [&address] {
+            return Err(anyhow::anyhow!(
+                "tx must be signed by {}, it can only be signed by {}",
+                signers
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                address
+            ));
+        }
+
+        let signing_info = get_signing_info(key, node.as_str(), account_number, sequence)?;
+
+        let Tx {
+            body, auth_info, ..
+        } = tx;
+
+        let raw_tx = create_signed_transaction(
+            vec![signing_info],
+            vec![],
+            body,
+            auth_info.fee,
+            auth_info.tip,
+            chain_id,
+            sign_mode,
+        )?;
+
+        Ok(Tx::decode_vec(&prost::Message::encode_to_vec(&raw_tx))?)
+    }
+
+    /// Signs the Tx body with `SIGN_MODE_DIRECT_AUX` instead of broadcasting it, the returned
+    /// data is passed to a fee payer who completes the Tx with `handle_aux_to_fee`
+    #[allow(clippy::too_many_arguments)]
+    fn handle_aux_tx(
+        &self,
+        tx_body: TxBody<Self::Message>,
+        key: KeyPair,
+        node: url::Url,
+        chain_id: tendermint::informal::chain::Id,
+        tip: Option<SendCoins>,
+        account_number: Option<u64>,
+        sequence: Option<u64>,
+    ) -> anyhow::Result<AuxSignerData> {
+        let address = key.get_address();
+
+        let signing_info = get_signing_info(key, node.as_str(), account_number, sequence)?;
+
+        let tip = tip.map(|amount| Tip {
+            amount: Some(amount),
+            tipper: address,
+        });
+
+        Ok(create_aux_signer_data(signing_info, tx_body, tip, chain_id))
+    }
+
+    /// Completes a Tx signed by an aux signer: the key pays the fee (and receives any tip), signs
+    /// with `SIGN_MODE_DIRECT` and broadcasts the Tx
+    fn handle_aux_to_fee(
+        &self,
+        aux_signer_data: AuxSignerData,
         key: KeyPair,
         node: url::Url,
         chain_id: tendermint::informal::chain::Id,
         fee: Option<SendCoins>,
+        gas_limit: u64,
+        fee_granter: Option<AccAddress>,
     ) -> anyhow::Result<Response> {
+        aux_signer_data.validate_basic()?;
+
+        if aux_signer_data.sign_doc.chain_id != chain_id.as_str() {
+            return Err(anyhow::anyhow!(
+                "aux signer data is for chain {}, expected {}",
+                aux_signer_data.sign_doc.chain_id,
+                chain_id
+            ));
+        }
+
+        let tx_body = TxBody::<Self::Message>::decode_vec(&aux_signer_data.sign_doc.body_bytes)?;
+
+        // the aux signature is over the original bytes so they must survive re-encoding
+        if tx_body.clone().encode_vec() != aux_signer_data.sign_doc.body_bytes {
+            return Err(anyhow::anyhow!(
+                "aux signer data body isn't canonically encoded"
+            ));
+        }
+
+        let address = key.get_address();
+
         let fee = Fee {
             amount: fee,
-            gas_limit: 100000000, //TODO: remove hard coded gas limit
-            payer: None,          //TODO: remove hard coded payer
-            granter: "".into(),   //TODO: remove hard coded granter
+            gas_limit,
+            payer: Some(address.clone()),
+            granter: fee_granter
+                .map(|granter| granter.to_string())
+                .unwrap_or_default(),
         };
 
-        let address = key.get_address();
-
         let account = get_account_latest(address, node.as_str())?;
 
         let signing_info = SigningInfo {
@@ -54,17 +219,17 @@ pub trait TxHandler {
             account_number: account.account.get_account_number(),
         };
 
-        let tx_body = TxBody {
-            messages: vec![msg],
-            memo: String::new(),                    // TODO: remove hard coded
-            timeout_height: 0,                      // TODO: remove hard coded
-            extension_options: vec![],              // TODO: remove hard coded
-            non_critical_extension_options: vec![], // TODO: remove hard coded
-        };
-
-        let tip = None; //TODO: remove hard coded
+        let tip = aux_signer_data.sign_doc.tip.clone();
 
-        let raw_tx = create_signed_transaction(vec![signing_info], tx_body, fee, tip, chain_id);
+        let raw_tx = create_signed_transaction(
+            vec![signing_info],
+            vec![aux_signer_data],
+            tx_body,
+            fee,
+            tip,
+            chain_id,
+            TxSignMode::Direct,
+        )?;
 
         let client = HttpClient::new(tendermint::rpc::Url::try_from(node)?)?;
 
@@ -141,7 +306,10 @@ pub trait AuxHandler {
 }
 
 // TODO: we're assuming here that the app has an auth module which handles this query
-fn get_account_latest(address: AccAddress, node: &str) -> anyhow::Result<QueryAccountResponse> {
+pub(crate) fn get_account_latest(
+    address: AccAddress,
+    node: &str,
+) -> anyhow::Result<QueryAccountResponse> {
     let query = QueryAccountRequest { address };
 
     execute_query::<QueryAccountResponse, RawQueryAccountResponse>(
@@ -151,3 +319,123 @@ fn get_account_latest(address: AccAddress, node: &str) -> anyhow::Result<QueryAc
         None,
     )
 }
+
+/// The account number and sequence of the key are only queried if they aren't both set
+fn get_signing_info(
+    key: KeyPair,
+    node: &str,
+    account_number: Option<u64>,
+    sequence: Option<u64>,
+) -> anyhow::Result<SigningInfo> {
+    let (account_number, sequence) = match (account_number, sequence) {
+        (Some(account_number), Some(sequence)) => (account_number, sequence),
+        (account_number, sequence) => {
+            let account = get_account_latest(key.get_address(), node)?;
+
+            (
+                account_number.unwrap_or_else(|| account.account.get_account_number()),
+                sequence.unwrap_or_else(|| account.account.get_sequence()),
+            )
+        }
+    };
+
+    Ok(SigningInfo {
+        key,
+        sequence,
+        account_number,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use bip32::Mnemonic;
+    use prost::Message as _;
+    use proto_messages::cosmos::{
+        bank::v1beta1::MsgSend,
+        ibc::tx::{SignDoc, TxRaw},
+    };
+
+    use super::*;
+
+    struct BankTxHandler;
+
+    impl TxHandler for BankTxHandler {
+        type Message = MsgSend;
+        type TxCommands = ();
+
+        fn prepare_tx(&self, _command: (), _from_address: AccAddress) -> anyhow::Result<MsgSend> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn handle_sign_signs_generated_tx_offline() {
+        let home = std::env::temp_dir().join(format!("gears-handle-sign-{}", std::process::id()));
+        let mnemonic = "race draft rival universe maid cheese steel logic crowd fork comic easy truth drift tomorrow eye buddy head time cash swing swift midnight borrow";
+        let mnemonic = Mnemonic::new(mnemonic, bip32::Language::English).unwrap();
+        let hd_path = keyring::bip44_path(keyring::DEFAULT_COIN_TYPE, 0, 0).unwrap();
+        keyring::add_key(
+            "alice",
+            &mnemonic,
+            "",
+            &hd_path,
+            keyring::KeyType::Secp256k1,
+            keyring::Backend::Test(&home),
+        )
+        .unwrap();
+        let key = keyring::get_key_by_name("alice", keyring::Backend::Test(&home));
+        std::fs::remove_dir_all(&home).unwrap();
+        let key = key.unwrap();
+
+        let handler = BankTxHandler;
+        let tx_body = TxBody {
+            messages: vec![MsgSend {
+                from_address: key.get_address(),
+                to_address: key.get_address(),
+                amount: "10uatom".parse().unwrap(),
+            }],
+            memo: String::new(),
+            timeout_height: 0,
+            extension_options: vec![],
+            non_critical_extension_options: vec![],
+        };
+        let fee = Fee {
+            amount: None,
+            gas_limit: 200000,
+            payer: None,
+            granter: "".into(),
+        };
+        let chain_id = tendermint::informal::chain::Id::try_from("test-chain").unwrap();
+
+        // the sign command reads the generated tx from its JSON file
+        let generated = serde_json::to_string(&handler.handle_generate_only(tx_body, fee)).unwrap();
+        let unsigned: Tx<MsgSend> = serde_json::from_str(&generated).unwrap();
+
+        // the node is never queried as the account number and sequence are set
+        let signed = handler
+            .handle_sign(
+                unsigned,
+                key.clone(),
+                "http://localhost:26657".parse().unwrap(),
+                chain_id.clone(),
+                TxSignMode::Direct,
+                Some(9),
+                Some(3),
+            )
+            .unwrap();
+
+        let raw_tx = TxRaw::decode(signed.encode_vec().as_slice()).unwrap();
+        let auth_info = AuthInfo::decode_vec(&raw_tx.auth_info_bytes).unwrap();
+        assert_eq!(auth_info.signer_infos[0].sequence, 3);
+
+        let sign_doc = SignDoc {
+            body_bytes: raw_tx.body_bytes,
+            auth_info_bytes: raw_tx.auth_info_bytes,
+            chain_id: chain_id.into(),
+            account_number: 9,
+        };
+        key.get_gears_public_key()
+            .verify_signature(&sign_doc.encode_to_vec(), &raw_tx.signatures[0])
+            .unwrap();
+    }
+}
@@ -1,11 +1,22 @@
+use std::{
+    marker::PhantomData,
+    path::{Path, PathBuf},
+};
+
+use anyhow::anyhow;
 use axum::Router;
+use database::Database;
 use proto_messages::cosmos::tx::v1beta1::message::Message;
 use store_crate::StoreKey;
 
 use crate::{
-    baseapp::{run, ABCIHandler, Genesis},
-    client::{genesis_account, init, rest::RestState},
-    config::{ApplicationConfig, Config},
+    baseapp::{
+        run::{self, RunCommand, RunError},
+        ABCIHandler, Genesis,
+    },
+    client::{backup, debug, genesis_account, init, rest::RestState},
+    config::{ApplicationConfig, Config, DBBackend},
+    utils::get_db_dir_from_home_dir,
     x::params::ParamsSubspaceKey,
 };
 
@@ -22,8 +33,9 @@ pub trait Node: AuxHandler {
     type ApplicationConfig: ApplicationConfig;
 
     /// Builder method for defining routes of rest server
-    fn router<AI: ApplicationInfo>() -> Router<
+    fn router<DB: Database, AI: ApplicationInfo>() -> Router<
         RestState<
+            DB,
             Self::StoreKey,
             Self::ParamsSubspaceKey,
             Self::Message,
@@ -34,22 +46,15 @@ pub trait Node: AuxHandler {
     >;
 }
 
+/// The database backend of a node is chosen at runtime by the `db_backend` config option, so
+/// the application is run with whichever backend is configured.
 pub struct NodeApplication<'a, Core: Node, AI: ApplicationInfo> {
     core: Core,
-    router: Router<
-        RestState<
-            Core::StoreKey,
-            Core::ParamsSubspaceKey,
-            Core::Message,
-            Core::ABCIHandler,
-            Core::Genesis,
-            AI,
-        >,
-    >,
     abci_handler_builder: &'a dyn Fn(Config<Core::ApplicationConfig>) -> Core::ABCIHandler,
 
     params_store_key: Core::StoreKey,
     params_subspace_key: Core::ParamsSubspaceKey,
+    _info_marker: PhantomData<AI>,
 }
 
 impl<'a, Core: Node, AI: ApplicationInfo> NodeApplication<'a, Core, AI> {
@@ -61,10 +66,10 @@ impl<'a, Core: Node, AI: ApplicationInfo> NodeApplication<'a, Core, AI> {
     ) -> Self {
         Self {
             core,
-            router: Core::router(),
             abci_handler_builder,
             params_store_key,
             params_subspace_key,
+            _info_marker: PhantomData,
         }
     }
 
@@ -74,16 +79,85 @@ impl<'a, Core: Node, AI: ApplicationInfo> NodeApplication<'a, Core, AI> {
             AppCommands::Init(cmd) => {
                 init::init::<_, Core::ApplicationConfig>(cmd, &Core::Genesis::default())?
             }
-            AppCommands::Run(cmd) => run::run(
-                cmd,
-                ParamsKeeper::new(self.params_store_key),
-                self.params_subspace_key,
-                self.abci_handler_builder,
-                self.router,
-            )?,
+            AppCommands::Run(cmd) => {
+                let config = Self::config(&cmd.home)?;
+                let db_dir = Self::db_dir(&cmd.home);
+
+                match config.db_backend {
+                    #[cfg(feature = "rocksdb")]
+                    DBBackend::RocksDB => {
+                        let db = database::RocksDB::new_with_options(
+                            db_dir,
+                            config.rocksdb.clone().into(),
+                        )?;
+                        self.run(cmd, config, db)?
+                    }
+                    #[cfg(feature = "sled")]
+                    DBBackend::Sled => self.run(cmd, config, database::SledDB::new(db_dir)?)?,
+                    #[allow(unreachable_patterns)]
+                    backend => return Err(disabled_backend(backend)),
+                }
+            }
             AppCommands::GenesisAdd(cmd) => {
                 genesis_account::genesis_account_add::<Core::Genesis>(cmd)?
             }
+            AppCommands::Debug(cmd) => {
+                let config = Self::config(cmd.home())?;
+                let db_dir = Self::db_dir(cmd.home());
+
+                match config.db_backend {
+                    #[cfg(feature = "rocksdb")]
+                    DBBackend::RocksDB => {
+                        // a secondary instance doesn't need the lock held by a running node
+                        let secondary_dir = std::env::temp_dir().join(format!(
+                            "{}-debug-{}",
+                            AI::APP_NAME,
+                            std::process::id()
+                        ));
+                        let db = database::RocksDB::new_secondary(
+                            db_dir,
+                            &secondary_dir,
+                            config.rocksdb.into(),
+                        )?;
+                        let res = debug::debug::<_, Core::StoreKey>(cmd, db);
+                        let _ = std::fs::remove_dir_all(secondary_dir);
+                        res?
+                    }
+                    #[cfg(feature = "sled")]
+                    DBBackend::Sled => {
+                        // sled has no read only or secondary mode, the DB can't be opened while
+                        // a node holds its lock
+                        let db = database::SledDB::new(db_dir).map_err(|e| {
+                            anyhow!("failed to open the sled database, the node must be stopped before running debug commands: {e}")
+                        })?;
+                        debug::debug::<_, Core::StoreKey>(cmd, db)?
+                    }
+                    #[allow(unreachable_patterns)]
+                    backend => return Err(disabled_backend(backend)),
+                }
+            }
+            AppCommands::Backup(cmd) => {
+                let config = Self::config(&cmd.home)?;
+                let db_dir = Self::db_dir(&cmd.home);
+
+                match config.db_backend {
+                    #[cfg(feature = "rocksdb")]
+                    DBBackend::RocksDB => {
+                        // a read only instance doesn't need the lock held by a running node
+                        let db = database::RocksDB::new_read_only(db_dir, config.rocksdb.into())?;
+                        backup::backup(cmd, &db)?
+                    }
+                    #[allow(unreachable_patterns)]
+                    backend => {
+                        return Err(match backend {
+                            DBBackend::RocksDB => disabled_backend(backend),
+                            backend => {
+                                anyhow!("the {backend} database backend doesn't support backups")
+                            }
+                        })
+                    }
+                }
+            }
             AppCommands::Aux(cmd) => {
                 let cmd = self.core.prepare_aux(cmd)?;
                 self.core.handle_aux(cmd)?;
@@ -92,4 +166,35 @@ impl<'a, Core: Node, AI: ApplicationInfo> NodeApplication<'a, Core, AI> {
 
         Ok(())
     }
+
+    fn run<DB: Database>(
+        self,
+        cmd: RunCommand,
+        config: Config<Core::ApplicationConfig>,
+        db: DB,
+    ) -> Result<(), RunError> {
+        run::run(
+            cmd,
+            config,
+            db,
+            ParamsKeeper::new(self.params_store_key),
+            self.params_subspace_key,
+            self.abci_handler_builder,
+            Core::router::<DB, AI>(),
+        )
+    }
+
+    fn config(home: &Path) -> anyhow::Result<Config<Core::ApplicationConfig>> {
+        Config::from_home_dir(home).map_err(|e| anyhow!("Error reading config file: {:?}", e))
+    }
+
+    fn db_dir(home: &Path) -> PathBuf {
+        let mut db_dir = home.to_path_buf();
+        get_db_dir_from_home_dir(&mut db_dir);
+        db_dir
+    }
+}
+
+fn disabled_backend(backend: DBBackend) -> anyhow::Error {
+    anyhow!("the {backend} database backend isn't enabled, rebuild with the `{backend}` feature")
 }
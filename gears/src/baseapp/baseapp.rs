@@ -1,5 +1,5 @@
 use bytes::Bytes;
-use database::{Database, RocksDB};
+use database::Database;
 use proto_messages::cosmos::{
     base::v1beta1::SendCoins,
     tx::v1beta1::{message::Message, tx_raw::TxWithRaw},
@@ -8,7 +8,7 @@ use proto_types::AccAddress;
 use serde::{de::DeserializeOwned, Serialize};
 use std::{
     marker::PhantomData,
-    sync::{Arc, RwLock},
+    sync::{mpsc::Sender, Arc, RwLock},
 };
 use store_crate::{MultiStore, StoreKey};
 use tendermint::abci::Application;
@@ -25,13 +25,16 @@ use tracing::{error, info};
 
 use crate::types::context::query_context::QueryContext;
 use crate::types::context::{context::Context, init_context::InitContext};
+use crate::types::gas::{
+    basic_meter::BasicGasMeter, gas_meter::Gas, infinite_meter::InfiniteGasMeter,
+};
 use crate::{application::ApplicationInfo, types::context::tx_context::TxContext};
 use crate::{
     error::AppError,
     x::params::{Keeper, ParamsSubspaceKey},
 };
 
-use super::params::BaseAppParamsKeeper;
+use super::{params::BaseAppParamsKeeper, run::RunError, BaseAppOptions};
 
 pub trait ABCIHandler<M: Message, SK: StoreKey, G: DeserializeOwned + Clone + Send + Sync + 'static>:
     Clone + Send + Sync + 'static
@@ -65,15 +68,36 @@ pub trait ABCIHandler<M: Message, SK: StoreKey, G: DeserializeOwned + Clone + Se
         vec![]
     }
 
-    fn init_genesis<DB: Database>(&self, ctx: &mut InitContext<'_, DB, SK>, genesis: G);
+    fn init_genesis<DB: Database>(
+        &self,
+        ctx: &mut InitContext<'_, DB, SK>,
+        genesis: G,
+    ) -> Result<(), AppError>;
 
     fn query<DB: Database + Send + Sync>(
         &self,
-        ctx: &QueryContext<'_, DB, SK>,
+        ctx: &QueryContext<DB, SK>,
         query: RequestQuery,
     ) -> Result<Bytes, AppError>;
 }
 
+/// The gas limit of a tx and the gas it consumed, reported to tendermint
+#[derive(Debug, Clone, Copy, Default)]
+struct GasInfo {
+    gas_wanted: u64,
+    gas_used: u64,
+}
+
+impl GasInfo {
+    fn gas_wanted(&self) -> i64 {
+        self.gas_wanted.try_into().unwrap_or(i64::MAX)
+    }
+
+    fn gas_used(&self) -> i64 {
+        self.gas_used.try_into().unwrap_or(i64::MAX)
+    }
+}
+
 pub trait Genesis: Default + DeserializeOwned + Serialize + Clone + Send + Sync + 'static {
     fn add_genesis_account(
         &mut self,
@@ -82,8 +106,9 @@ pub trait Genesis: Default + DeserializeOwned + Serialize + Clone + Send + Sync
     ) -> Result<(), AppError>;
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 pub struct BaseApp<
+    DB: Database,
     SK: StoreKey,
     PSK: ParamsSubspaceKey,
     M: Message,
@@ -91,39 +116,81 @@ pub struct BaseApp<
     G: Genesis,
     AI: ApplicationInfo,
 > {
-    multi_store: Arc<RwLock<MultiStore<RocksDB, SK>>>,
+    multi_store: Arc<RwLock<MultiStore<DB, SK>>>,
     height: Arc<RwLock<u64>>,
     abci_handler: H,
     block_header: Arc<RwLock<Option<Header>>>, // passed by Tendermint in call to begin_block
     baseapp_params_keeper: BaseAppParamsKeeper<SK, PSK>,
+    options: BaseAppOptions,
+    halt: Sender<RunError>,
     pub m: PhantomData<M>,
     pub g: PhantomData<G>,
     _info_marker: PhantomData<AI>,
 }
 
+// deriving Clone would needlessly require the DB to be Clone
 impl<
+        DB: Database,
+        SK: StoreKey,
+        PSK: ParamsSubspaceKey,
+        M: Message,
+        H: ABCIHandler<M, SK, G>,
+        G: Genesis,
+        AI: ApplicationInfo,
+    > Clone for BaseApp<DB, SK, PSK, M, H, G, AI>
+{
+    fn clone(&self) -> Self {
+        Self {
+            multi_store: self.multi_store.clone(),
+            height: self.height.clone(),
+            abci_handler: self.abci_handler.clone(),
+            block_header: self.block_header.clone(),
+            baseapp_params_keeper: self.baseapp_params_keeper.clone(),
+            options: self.options.clone(),
+            halt: self.halt.clone(),
+            m: PhantomData,
+            g: PhantomData,
+            _info_marker: PhantomData,
+        }
+    }
+}
+
+impl<
+        DB: Database,
         M: Message,
         SK: StoreKey,
         PSK: ParamsSubspaceKey,
         H: ABCIHandler<M, SK, G>,
         G: Genesis,
         AI: ApplicationInfo,
-    > Application for BaseApp<SK, PSK, M, H, G, AI>
+    > Application for BaseApp<DB, SK, PSK, M, H, G, AI>
 {
     fn init_chain(&self, request: RequestInitChain) -> ResponseInitChain {
         info!("Got init chain request");
-        let mut multi_store = self
-            .multi_store
-            .write()
-            .expect("RwLock will not be poisoned");
 
         //TODO: handle request height > 1 as is done in SDK
 
         let chain_id = request.chain_id.try_into().unwrap_or_else(|_| {
-            error!("Invalid chain id provided by Tendermint.\nTerminating process\n");
-            std::process::exit(1)
+            self.halt(AppError::Genesis(
+                "Invalid chain id provided by Tendermint".into(),
+            ))
         });
 
+        let genesis: G = String::from_utf8(request.app_state_bytes.into())
+            .map_err(|e| AppError::Genesis(e.to_string()))
+            .and_then(|s| serde_json::from_str(&s).map_err(|e| AppError::Genesis(e.to_string())))
+            .unwrap_or_else(|e| {
+                self.halt(AppError::Genesis(format!(
+                    "Invalid genesis provided by Tendermint: {}",
+                    e
+                )))
+            });
+
+        let mut multi_store = self
+            .multi_store
+            .write()
+            .expect("RwLock will not be poisoned");
+
         let mut ctx = InitContext::new(&mut multi_store, self.get_block_height(), chain_id);
 
         if let Some(params) = request.consensus_params.clone() {
@@ -131,18 +198,10 @@ impl<
                 .set_consensus_params(&mut ctx.as_any(), params);
         }
 
-        let genesis: G = String::from_utf8(request.app_state_bytes.into())
-            .map_err(|e| AppError::Genesis(e.to_string()))
-            .and_then(|s| serde_json::from_str(&s).map_err(|e| AppError::Genesis(e.to_string())))
-            .unwrap_or_else(|e| {
-                error!(
-                    "Invalid genesis provided by Tendermint.\n{}\nTerminating process",
-                    e.to_string()
-                );
-                std::process::exit(1)
-            });
-
-        self.abci_handler.init_genesis(&mut ctx, genesis);
+        if let Err(e) = self.abci_handler.init_genesis(&mut ctx, genesis) {
+            drop(multi_store);
+            self.halt(e)
+        }
 
         multi_store.write_then_clear_tx_caches();
 
@@ -203,36 +262,59 @@ impl<
         }
     }
 
-    fn check_tx(&self, _request: RequestCheckTx) -> ResponseCheckTx {
+    fn check_tx(&self, request: RequestCheckTx) -> ResponseCheckTx {
         info!("Got check tx request");
-        ResponseCheckTx {
-            code: 0,
-            data: Default::default(),
-            log: "".to_string(),
-            info: "".to_string(),
-            gas_wanted: 1,
-            gas_used: 0,
-            events: vec![],
-            codespace: "".to_string(),
-            mempool_error: "".to_string(),
-            priority: 0,
-            sender: "".to_string(),
+        let mut gas_info = GasInfo::default();
+        match self.run_check_tx(request.tx, &mut gas_info) {
+            Ok(_) => ResponseCheckTx {
+                code: 0,
+                data: Default::default(),
+                log: "".to_string(),
+                info: "".to_string(),
+                gas_wanted: gas_info.gas_wanted(),
+                gas_used: gas_info.gas_used(),
+                events: vec![],
+                codespace: "".to_string(),
+                mempool_error: "".to_string(),
+                priority: 0,
+                sender: "".to_string(),
+            },
+            Err(e) => {
+                info!("Rejected tx from the mempool: {}", e);
+                ResponseCheckTx {
+                    code: e.code(),
+                    data: Bytes::new(),
+                    log: e.to_string(),
+                    info: "".to_string(),
+                    gas_wanted: gas_info.gas_wanted(),
+                    gas_used: gas_info.gas_used(),
+                    events: vec![],
+                    codespace: "".to_string(),
+                    mempool_error: "".to_string(),
+                    priority: 0,
+                    sender: "".to_string(),
+                }
+            }
         }
     }
 
     fn deliver_tx(&self, request: RequestDeliverTx) -> ResponseDeliverTx {
         info!("Got deliver tx request");
-        match self.run_tx(request.tx) {
+        let mut gas_info = GasInfo::default();
+        match self.run_tx(request.tx, &mut gas_info) {
             Ok(events) => ResponseDeliverTx {
                 code: 0,
                 data: Default::default(),
                 log: "".to_string(),
                 info: "".to_string(),
-                gas_wanted: 0,
-                gas_used: 0,
+                gas_wanted: gas_info.gas_wanted(),
+                gas_used: gas_info.gas_used(),
                 events: events.into_iter().map(|e| e.into()).collect(),
                 codespace: "".to_string(),
             },
+            // a store error isn't a result of the tx, the node can't go on with a state that
+            // differs from the other nodes
+            Err(AppError::Tree(e)) => self.halt(AppError::Tree(e)),
             Err(e) => {
                 info!("Failed to process tx: {}", e);
                 ResponseDeliverTx {
@@ -240,8 +322,8 @@ impl<
                     data: Bytes::new(),
                     log: e.to_string(),
                     info: "".to_string(),
-                    gas_wanted: 0,
-                    gas_used: 0,
+                    gas_wanted: gas_info.gas_wanted(),
+                    gas_used: gas_info.gas_used(),
                     events: vec![],
                     codespace: "".to_string(),
                 }
@@ -257,7 +339,15 @@ impl<
             .write()
             .expect("RwLock will not be poisoned");
 
-        let hash = multi_store.commit();
+        // a failed commit can't be retried since tendermint expects the app hash, the state
+        // on disk is recovered when the node is restarted
+        let hash = match multi_store.commit() {
+            Ok(hash) => hash,
+            Err(e) => {
+                drop(multi_store);
+                self.halt(e.into())
+            }
+        };
         info!(
             "Committed state, block height: {} app hash: {}",
             new_height,
@@ -302,6 +392,7 @@ impl<
             self.get_block_header()
                 .expect("block header is set in begin block"),
             vec![],
+            Box::new(InfiniteGasMeter::new()),
         );
 
         self.abci_handler.begin_block(&mut ctx, request);
@@ -328,6 +419,7 @@ impl<
             self.get_block_header()
                 .expect("block header is set in begin block"),
             vec![],
+            Box::new(InfiniteGasMeter::new()),
         );
 
         let validator_updates = self.abci_handler.end_block(&mut ctx, request);
@@ -380,36 +472,41 @@ impl<
 }
 
 impl<
+        DB: Database,
         M: Message,
         SK: StoreKey,
         PSK: ParamsSubspaceKey,
         H: ABCIHandler<M, SK, G>,
         G: Genesis,
         AI: ApplicationInfo,
-    > BaseApp<SK, PSK, M, H, G, AI>
+    > BaseApp<DB, SK, PSK, M, H, G, AI>
 {
     pub fn new(
-        db: RocksDB,
+        db: DB,
         params_keeper: Keeper<SK, PSK>,
         params_subspace_key: PSK,
         abci_handler: H,
-    ) -> Self {
-        let multi_store = MultiStore::new(db);
+        options: BaseAppOptions,
+        halt: Sender<RunError>,
+    ) -> Result<Self, store_crate::error::Error> {
+        let multi_store = MultiStore::new(db)?;
         let baseapp_params_keeper = BaseAppParamsKeeper {
             params_keeper,
             params_subspace_key,
         };
         let height = multi_store.get_head_version().into();
-        Self {
+        Ok(Self {
             multi_store: Arc::new(RwLock::new(multi_store)),
             abci_handler,
             block_header: Arc::new(RwLock::new(None)),
             baseapp_params_keeper,
+            options,
+            halt,
             height: Arc::new(RwLock::new(height)),
             m: PhantomData,
             g: PhantomData,
             _info_marker: PhantomData,
-        }
+        })
     }
 
     pub fn get_block_height(&self) -> u64 {
@@ -438,6 +535,19 @@ impl<
             .get_head_commit_hash()
     }
 
+    /// Stops the node after an error it can't recover from. The error is passed to `run`
+    /// which returns it, the calling thread is parked since Tendermint mustn't get a response.
+    fn halt(&self, err: AppError) -> ! {
+        error!("{}\nHalting the node", err);
+
+        // the receiver is only dropped once `run` has returned
+        let _ = self.halt.send(RunError::Halted(err));
+
+        loop {
+            std::thread::park();
+        }
+    }
+
     fn increment_block_height(&self) -> u64 {
         let mut height = self.height.write().expect("RwLock will not be poisoned");
         *height += 1;
@@ -449,18 +559,39 @@ impl<
             AppError::InvalidRequest("Block height must be greater than or equal to zero".into())
         })?;
 
-        let multi_store = self
-            .multi_store
-            .read()
-            .expect("RwLock will not be poisoned");
-        let ctx = QueryContext::new(&multi_store, version)?;
+        // the lock is only held while the query trees are checked out, the query itself
+        // runs concurrently with block execution
+        let ctx = QueryContext::new(
+            &self
+                .multi_store
+                .read()
+                .expect("RwLock will not be poisoned"),
+            version,
+        )?;
 
         self.abci_handler.query(&ctx, request.clone())
     }
 
-    fn run_tx(&self, raw: Bytes) -> Result<Vec<tendermint::informal::abci::Event>, AppError> {
+    /// Checks a tx before it enters the mempool, the checks here are local to the node so
+    /// mustn't be repeated in DeliverTx
+    fn run_check_tx(&self, raw: Bytes, gas_info: &mut GasInfo) -> Result<(), AppError> {
+        let tx_with_raw: TxWithRaw<M> =
+            TxWithRaw::from_bytes(raw).map_err(|e| AppError::TxParseError(e.to_string()))?;
+        gas_info.gas_wanted = tx_with_raw.tx.auth_info.fee.gas_limit;
+
+        self.options.check_min_gas_prices(&tx_with_raw.tx)
+    }
+
+    /// Runs the ante checks then the messages of a tx, the gas info is filled in as far as the
+    /// tx got
+    fn run_tx(
+        &self,
+        raw: Bytes,
+        gas_info: &mut GasInfo,
+    ) -> Result<Vec<tendermint::informal::abci::Event>, AppError> {
         let tx_with_raw: TxWithRaw<M> = TxWithRaw::from_bytes(raw.clone())
             .map_err(|e| AppError::TxParseError(e.to_string()))?;
+        gas_info.gas_wanted = tx_with_raw.tx.auth_info.fee.gas_limit;
 
         Self::validate_basic_tx_msgs(tx_with_raw.tx.get_msgs())?;
 
@@ -469,18 +600,24 @@ impl<
             .write()
             .expect("RwLock will not be poisoned");
 
+        // the gas limit is set up before the ante checks, see the SDK's SetUpContextDecorator
+        let gas_limit = tx_with_raw.tx.auth_info.fee.gas_limit;
         let mut ctx = TxContext::new(
             &mut multi_store,
             self.get_block_height(),
             self.get_block_header()
                 .expect("block header is set in begin block"),
             raw.clone().into(),
+            Box::new(BasicGasMeter::new(Gas(gas_limit))),
         );
 
-        match self
+        let ante_result = self
             .abci_handler
-            .run_ante_checks(&mut ctx.as_any(), &tx_with_raw)
-        {
+            .run_ante_checks(&mut ctx.as_any(), &tx_with_raw);
+        let gas_meter = ctx.gas_meter;
+        gas_info.gas_used = gas_meter.gas_consumed().0;
+
+        match ante_result {
             Ok(_) => multi_store.write_then_clear_tx_caches(),
             Err(e) => {
                 multi_store.clear_tx_caches();
@@ -494,9 +631,13 @@ impl<
             self.get_block_header()
                 .expect("block header is set in begin block"),
             raw.into(),
+            gas_meter,
         );
 
-        match self.run_msgs(&mut ctx, tx_with_raw.tx.get_msgs()) {
+        let msgs_result = self.run_msgs(&mut ctx, tx_with_raw.tx.get_msgs());
+        gas_info.gas_used = ctx.gas_meter.gas_consumed().0;
+
+        match msgs_result {
             Ok(_) => {
                 let events = ctx.events;
                 multi_store.write_then_clear_tx_caches();
@@ -1,5 +1,7 @@
 mod baseapp;
+mod options;
 mod params;
 pub mod run;
 
 pub use baseapp::*;
+pub use options::*;
@@ -0,0 +1,182 @@
+use proto_messages::cosmos::{
+    base::v1beta1::{Coin, DecCoins, SendCoins},
+    tx::v1beta1::{message::Message, tx::tx::Tx},
+};
+use proto_types::Decimal256;
+
+use crate::{
+    config::{ApplicationConfig, Config},
+    error::AppError,
+};
+
+/// Node local settings of the BaseApp, these aren't part of consensus
+#[derive(Debug, Clone, Default)]
+pub struct BaseAppOptions {
+    /// Minimum gas prices a tx must pay, in at least one denomination, to enter the mempool
+    pub minimum_gas_prices: DecCoins,
+    /// Type URLs of messages which are exempt from the minimum gas prices
+    pub bypass_min_fee_msg_types: Vec<String>,
+}
+
+impl<AC: ApplicationConfig> From<&Config<AC>> for BaseAppOptions {
+    fn from(config: &Config<AC>) -> Self {
+        Self {
+            minimum_gas_prices: config.minimum_gas_prices.clone(),
+            bypass_min_fee_msg_types: config.bypass_min_fee_msg_types.clone(),
+        }
+    }
+}
+
+impl BaseAppOptions {
+    /// Checks the tx fee is at least gas limit * minimum gas price for one of the minimum gas
+    /// price denominations, see the SDK's MempoolFeeDecorator. Since the prices are local to the
+    /// node this must only be checked in CheckTx.
+    pub fn check_min_gas_prices<M: Message>(&self, tx: &Tx<M>) -> Result<(), AppError> {
+        if self.minimum_gas_prices.is_empty() {
+            return Ok(());
+        }
+
+        let bypass = tx.get_msgs().iter().all(|msg| {
+            self.bypass_min_fee_msg_types
+                .iter()
+                .any(|type_url| type_url == msg.type_url())
+        });
+        if bypass {
+            return Ok(());
+        }
+
+        let gas_limit = Decimal256::from_ratio(tx.auth_info.fee.gas_limit, 1u64);
+        let required_fees = self
+            .minimum_gas_prices
+            .iter()
+            .map(|price| {
+                let amount = price
+                    .amount
+                    .checked_mul(gas_limit)
+                    .map_err(|e| AppError::Coins(e.to_string()))?
+                    .to_uint_ceil();
+
+                Ok(Coin {
+                    denom: price.denom.clone(),
+                    amount,
+                })
+            })
+            .collect::<Result<Vec<_>, AppError>>()?;
+
+        let fee = tx
+            .get_fee()
+            .clone()
+            .map(SendCoins::into_inner)
+            .unwrap_or_default();
+
+        let sufficient = required_fees.iter().any(|required| {
+            fee.iter()
+                .any(|coin| coin.denom == required.denom && coin.amount >= required.amount)
+        });
+
+        if !sufficient {
+            let required_fees: Vec<String> = required_fees.iter().map(Coin::to_string).collect();
+            let fee: Vec<String> = fee.iter().map(Coin::to_string).collect();
+
+            return Err(AppError::InsufficientFee(format!(
+                "got: {} required: {}",
+                fee.join(","),
+                required_fees.join(",")
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use proto_messages::cosmos::{
+        bank::v1beta1::MsgSend,
+        tx::v1beta1::tx::{auth_info::AuthInfo, fee::Fee, tx_body::TxBody},
+    };
+    use proto_types::AccAddress;
+
+    use super::*;
+
+    fn tx(fee: Option<&str>, gas_limit: u64) -> Tx<MsgSend> {
+        Tx {
+            body: TxBody {
+                messages: vec![MsgSend {
+                    from_address: AccAddress::from_bech32(
+                        "cosmos1ulav3hsenupswqfkw2y3sup5kgtqwnvqa8eyhs",
+                    )
+                    .unwrap(),
+                    to_address: AccAddress::from_bech32(
+                        "cosmos1ejrf4cur2wy6kfurg9f2jppp2h3afe5h6pkh5t",
+                    )
+                    .unwrap(),
+                    amount: SendCoins::from_str("10uatom").unwrap(),
+                }],
+                memo: String::new(),
+                timeout_height: 0,
+                extension_options: vec![],
+                non_critical_extension_options: vec![],
+            },
+            auth_info: AuthInfo {
+                signer_infos: vec![],
+                fee: Fee {
+                    amount: fee.map(|fee| SendCoins::from_str(fee).unwrap()),
+                    gas_limit,
+                    payer: None,
+                    granter: String::new(),
+                },
+                tip: None,
+            },
+            signatures: vec![],
+            signatures_data: vec![],
+        }
+    }
+
+    fn options(minimum_gas_prices: &str) -> BaseAppOptions {
+        BaseAppOptions {
+            minimum_gas_prices: DecCoins::from_str(minimum_gas_prices).unwrap(),
+            bypass_min_fee_msg_types: vec![],
+        }
+    }
+
+    #[test]
+    fn check_min_gas_prices_requires_fee_in_any_denom() {
+        let options = options("0.025uatom,0.1stake");
+
+        // 0.025 * 100_001 = 2500.025 is rounded up
+        options
+            .check_min_gas_prices(&tx(Some("2501uatom"), 100_001))
+            .unwrap();
+        options
+            .check_min_gas_prices(&tx(Some("10001stake,1uatom"), 100_001))
+            .unwrap();
+
+        let err = options
+            .check_min_gas_prices(&tx(Some("2500uatom"), 100_001))
+            .unwrap_err();
+        assert_eq!(
+            err,
+            AppError::InsufficientFee("got: 2500uatom required: 10001stake,2501uatom".into())
+        );
+
+        options
+            .check_min_gas_prices(&tx(None, 100_001))
+            .unwrap_err();
+    }
+
+    #[test]
+    fn check_min_gas_prices_skips_unset_prices_and_bypassed_msgs() {
+        options("")
+            .check_min_gas_prices(&tx(None, 100_000))
+            .unwrap();
+
+        let mut options = options("0.025uatom");
+        options
+            .bypass_min_fee_msg_types
+            .push("/cosmos.bank.v1beta1.MsgSend".into());
+        options.check_min_gas_prices(&tx(None, 100_000)).unwrap();
+    }
+}
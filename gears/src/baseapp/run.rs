@@ -1,17 +1,19 @@
 use axum::Router;
-use database::RocksDB;
+use database::Database;
 use proto_messages::cosmos::tx::v1beta1::message::Message;
 use std::net::SocketAddr;
 use std::path::PathBuf;
-use store_crate::StoreKey;
+use std::sync::mpsc;
+use std::thread;
+use store_crate::{MultiStore, StoreKey};
 use tendermint::abci::ServerBuilder;
 use tracing::{error, info};
 
 use crate::application::ApplicationInfo;
-use crate::baseapp::BaseApp;
+use crate::baseapp::{BaseApp, BaseAppOptions};
 use crate::client::rest::{run_rest_server, RestState};
 use crate::config::{ApplicationConfig, Config};
-use crate::utils::get_config_file_from_home_dir;
+use crate::error::AppError;
 use crate::x::params::{Keeper, ParamsSubspaceKey};
 use tracing::metadata::LevelFilter;
 
@@ -33,7 +35,11 @@ pub enum RunError {
     #[error("{0}")]
     Database(#[from] database::error::Error),
     #[error("{0}")]
+    Store(#[from] store_crate::error::Error),
+    #[error("{0}")]
     TendermintServer(#[from] tendermint::abci::Error),
+    #[error("node halted: {0}")]
+    Halted(AppError),
     #[error("{0}")]
     Custom(String),
 }
@@ -67,6 +73,7 @@ impl From<LogLevel> for LevelFilter {
 }
 
 pub fn run<
+    DB: Database,
     SK: StoreKey,
     PSK: ParamsSubspaceKey,
     M: Message,
@@ -76,10 +83,12 @@ pub fn run<
     AI: ApplicationInfo,
 >(
     cmd: RunCommand,
+    config: Config<AC>,
+    db: DB,
     params_keeper: Keeper<SK, PSK>,
     params_subspace_key: PSK,
     abci_handler_builder: &dyn Fn(Config<AC>) -> H, // TODO: why trait object here. Why not FnOnce?
-    router: Router<RestState<SK, PSK, M, H, G, AI>>,
+    router: Router<RestState<DB, SK, PSK, M, H, G, AI>>,
 ) -> Result<(), RunError> {
     let RunCommand {
         home,
@@ -96,21 +105,22 @@ pub fn run<
 
     info!("Using directory {} for config and data", home.display());
 
-    let mut db_dir = home.clone();
-    db_dir.push("data");
-    db_dir.push("application.db");
-    let db = RocksDB::new(db_dir)?;
-
-    let mut cfg_file_path = home.clone();
-    get_config_file_from_home_dir(&mut cfg_file_path);
+    info!("Using {} database backend", config.db_backend);
 
-    let config: Config<AC> = Config::from_file(cfg_file_path)
-        .map_err(|e| RunError::Custom(format!("Error reading config file: {:?}", e)))?;
+    MultiStore::<DB, SK>::migrate_db(&db)?;
 
     let abci_handler = abci_handler_builder(config.clone());
 
-    let app: BaseApp<SK, PSK, M, H, G, AI> =
-        BaseApp::new(db, params_keeper, params_subspace_key, abci_handler);
+    let (halt_sender, halt_receiver) = mpsc::channel();
+
+    let app: BaseApp<DB, SK, PSK, M, H, G, AI> = BaseApp::new(
+        db,
+        params_keeper,
+        params_subspace_key,
+        abci_handler,
+        BaseAppOptions::from(&config),
+        halt_sender.clone(),
+    )?;
 
     run_rest_server(
         app.clone(),
@@ -121,5 +131,17 @@ pub fn run<
 
     let server = ServerBuilder::new(read_buf_size).bind(address, app)?;
 
-    server.listen().map_err(|e| e.into())
+    thread::spawn(move || {
+        let err = match server.listen() {
+            Ok(()) => RunError::Custom("ABCI server stopped".into()),
+            Err(e) => e.into(),
+        };
+
+        let _ = halt_sender.send(err);
+    });
+
+    // blocks until either the server stops or the app halts
+    Err(halt_receiver
+        .recv()
+        .expect("the server thread holds a sender until it sends"))
 }
@@ -0,0 +1,32 @@
+use std::{marker::PhantomData, path::PathBuf};
+
+use clap::{ArgAction, ValueHint};
+
+use crate::{application::ApplicationInfo, client::backup::BackupCommand};
+
+#[derive(Debug, Clone, ::clap::Args)]
+#[command(
+    about = "Back up the application database using hard links. Only supported by the rocksdb backend",
+    long_about = "Back up the application database using hard links. Only supported by the rocksdb backend. The database is opened read only so a running node doesn't need to be stopped, the backup contains the blocks committed before the command started"
+)]
+pub struct CliBackupCommand<T: ApplicationInfo> {
+    #[arg(long, action = ArgAction::Set, value_hint = ValueHint::DirPath, default_value_os_t = T::home_dir(), help = "directory for config and data")]
+    home: PathBuf,
+    #[arg(action = ArgAction::Set, value_hint = ValueHint::DirPath, help = "Directory to write the backup to, it must not exist. Use a directory on the same filesystem as the database so files can be hard linked")]
+    path: PathBuf,
+
+    #[arg(skip)]
+    _marker: PhantomData<T>,
+}
+
+impl<T: ApplicationInfo> From<CliBackupCommand<T>> for BackupCommand {
+    fn from(value: CliBackupCommand<T>) -> Self {
+        let CliBackupCommand {
+            home,
+            path,
+            _marker,
+        } = value;
+
+        Self { home, path }
+    }
+}
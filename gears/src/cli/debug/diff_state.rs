@@ -0,0 +1,42 @@
+use std::{marker::PhantomData, path::PathBuf};
+
+use clap::{ArgAction, ValueHint};
+
+use crate::{application::ApplicationInfo, client::debug::DiffStateCommand};
+
+#[derive(Debug, Clone, ::clap::Args)]
+#[command(
+    about = "Print the keys which were inserted, updated or removed between two committed versions"
+)]
+pub struct CliDiffStateCommand<T: ApplicationInfo> {
+    #[arg(long, action = ArgAction::Set, value_hint = ValueHint::DirPath, default_value_os_t = T::home_dir(), help = "directory for config and data")]
+    home: PathBuf,
+    #[arg(long, action = ArgAction::Set, help = "Only diff the store with this name. Every store is diffed if not provided")]
+    store: Option<String>,
+    #[arg(long, action = ArgAction::Set, help = "The version to diff from")]
+    from: u32,
+    #[arg(long, action = ArgAction::Set, help = "The version to diff to")]
+    to: u32,
+
+    #[arg(skip)]
+    _marker: PhantomData<T>,
+}
+
+impl<T: ApplicationInfo> From<CliDiffStateCommand<T>> for DiffStateCommand {
+    fn from(value: CliDiffStateCommand<T>) -> Self {
+        let CliDiffStateCommand {
+            home,
+            store,
+            from,
+            to,
+            _marker,
+        } = value;
+
+        Self {
+            home,
+            store,
+            from,
+            to,
+        }
+    }
+}
@@ -0,0 +1,29 @@
+use crate::{application::ApplicationInfo, client::debug::DebugCommand};
+
+use self::{diff_state::CliDiffStateCommand, verify_store::CliVerifyStoreCommand};
+
+pub mod diff_state;
+pub mod verify_store;
+
+#[derive(Debug, Clone, ::clap::Subcommand)]
+#[command(
+    about = "Tools for debugging the application's state",
+    long_about = "Tools for debugging the application's state. With the RocksDB backend they can \
+    be run alongside a running node. The sled backend doesn't support this, the node must be \
+    stopped first."
+)]
+pub enum CliDebugCommand<T: ApplicationInfo> {
+    #[command(name = "diff-state")]
+    DiffState(CliDiffStateCommand<T>),
+    #[command(name = "verify-store")]
+    VerifyStore(CliVerifyStoreCommand<T>),
+}
+
+impl<T: ApplicationInfo> From<CliDebugCommand<T>> for DebugCommand {
+    fn from(value: CliDebugCommand<T>) -> Self {
+        match value {
+            CliDebugCommand::DiffState(cmd) => DebugCommand::DiffState(cmd.into()),
+            CliDebugCommand::VerifyStore(cmd) => DebugCommand::VerifyStore(cmd.into()),
+        }
+    }
+}
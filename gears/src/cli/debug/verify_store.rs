@@ -0,0 +1,38 @@
+use std::{marker::PhantomData, path::PathBuf};
+
+use clap::{ArgAction, ValueHint};
+
+use crate::{application::ApplicationInfo, client::debug::VerifyStoreCommand};
+
+#[derive(Debug, Clone, ::clap::Args)]
+#[command(
+    about = "Check the hashes, heights, sizes, balance and key order of every node in a committed version"
+)]
+pub struct CliVerifyStoreCommand<T: ApplicationInfo> {
+    #[arg(long, action = ArgAction::Set, value_hint = ValueHint::DirPath, default_value_os_t = T::home_dir(), help = "directory for config and data")]
+    home: PathBuf,
+    #[arg(long, action = ArgAction::Set, help = "Only verify the store with this name. Every store is verified if not provided")]
+    store: Option<String>,
+    #[arg(long, action = ArgAction::Set, help = "The version to verify. The latest version is verified if not provided")]
+    version: Option<u32>,
+
+    #[arg(skip)]
+    _marker: PhantomData<T>,
+}
+
+impl<T: ApplicationInfo> From<CliVerifyStoreCommand<T>> for VerifyStoreCommand {
+    fn from(value: CliVerifyStoreCommand<T>) -> Self {
+        let CliVerifyStoreCommand {
+            home,
+            store,
+            version,
+            _marker,
+        } = value;
+
+        Self {
+            home,
+            store,
+            version,
+        }
+    }
+}
@@ -4,7 +4,7 @@ use clap::{ArgAction, ValueHint};
 
 use crate::{
     application::ApplicationInfo,
-    client::keys::{AddKeyCommand, KeyringBackend},
+    client::keys::{AddKeyCommand, KeyAlgorithm, KeyringBackend},
 };
 
 #[derive(Debug, Clone, ::clap::Args)]
@@ -21,6 +21,32 @@ pub struct CliAddKeyCommand<T: ApplicationInfo> {
     /// select keyring's backend
     #[arg(long = "keyring-backend",  action = ArgAction::Set, default_value_t = KeyringBackend::File )]
     keyring_backend: KeyringBackend,
+    #[arg(short, long, action = ArgAction::SetTrue, help = "Interactively prompt user for BIP39 passphrase")]
+    interactive: bool,
+    /// manual HD path derivation, overrides the coin type, account and index
+    #[arg(long = "hd-path", action = ArgAction::Set)]
+    hd_path: Option<String>,
+    /// coin type number for HD derivation
+    #[arg(long = "coin-type", action = ArgAction::Set, default_value_t = keyring::DEFAULT_COIN_TYPE)]
+    coin_type: u32,
+    /// account number for HD derivation (less than 2^31)
+    #[arg(long, action = ArgAction::Set, default_value_t = 0)]
+    account: u32,
+    /// address index number for HD derivation (less than 2^31)
+    #[arg(long, action = ArgAction::Set, default_value_t = 0)]
+    index: u32,
+    /// key signing algorithm to generate keys for
+    #[arg(long, action = ArgAction::Set, default_value_t = KeyAlgorithm::Secp256k1)]
+    algo: KeyAlgorithm,
+    /// list of key names stored in keyring to construct a public legacy multisig key
+    #[arg(long, value_delimiter = ',', conflicts_with_all = ["recover", "interactive"])]
+    multisig: Vec<String>,
+    /// K out of N required signatures. For use in conjunction with --multisig
+    #[arg(long = "multisig-threshold", action = ArgAction::Set, default_value_t = 1)]
+    multisig_threshold: u32,
+    /// keys passed to --multisig are taken in the order they're supplied
+    #[arg(long, action = ArgAction::SetTrue)]
+    nosort: bool,
 
     #[arg(skip)]
     _marker: PhantomData<T>,
@@ -33,6 +59,15 @@ impl<T: ApplicationInfo> From<CliAddKeyCommand<T>> for AddKeyCommand {
             recover,
             home,
             keyring_backend,
+            interactive,
+            hd_path,
+            coin_type,
+            account,
+            index,
+            algo,
+            multisig,
+            multisig_threshold,
+            nosort,
             _marker,
         } = value;
 
@@ -41,6 +76,15 @@ impl<T: ApplicationInfo> From<CliAddKeyCommand<T>> for AddKeyCommand {
             recover,
             home,
             keyring_backend,
+            interactive,
+            hd_path,
+            coin_type,
+            account,
+            index,
+            algo,
+            multisig,
+            multisig_threshold,
+            nosort,
         }
     }
 }
@@ -0,0 +1,43 @@
+use std::{marker::PhantomData, path::PathBuf};
+
+use clap::{ArgAction, ValueHint};
+
+use crate::{
+    application::ApplicationInfo,
+    client::keys::{DeleteKeyCommand, KeyringBackend},
+};
+
+#[derive(Debug, Clone, ::clap::Args)]
+#[command(about = "Delete the key with the given name")]
+pub struct CliDeleteKeyCommand<T: ApplicationInfo> {
+    #[arg(required = true)]
+    name: String,
+    #[arg(long, action = ArgAction::Set, value_hint = ValueHint::DirPath, default_value_os_t = T::home_dir(), help = "directory for config and data")]
+    home: PathBuf,
+    /// select keyring's backend
+    #[arg(long = "keyring-backend",  action = ArgAction::Set, default_value_t = KeyringBackend::File )]
+    keyring_backend: KeyringBackend,
+    #[arg(short, long, action = ArgAction::SetTrue, help = "Skip confirmation prompt")]
+    yes: bool,
+    #[arg(skip)]
+    _marker: PhantomData<T>,
+}
+
+impl<T: ApplicationInfo> From<CliDeleteKeyCommand<T>> for DeleteKeyCommand {
+    fn from(value: CliDeleteKeyCommand<T>) -> Self {
+        let CliDeleteKeyCommand {
+            name,
+            home,
+            keyring_backend,
+            yes,
+            _marker,
+        } = value;
+
+        Self {
+            name,
+            home,
+            keyring_backend,
+            yes,
+        }
+    }
+}
@@ -0,0 +1,39 @@
+use std::{marker::PhantomData, path::PathBuf};
+
+use clap::{ArgAction, ValueHint};
+
+use crate::{
+    application::ApplicationInfo,
+    client::keys::{ExportKeyCommand, KeyringBackend},
+};
+
+#[derive(Debug, Clone, ::clap::Args)]
+#[command(about = "Export a private key as an armored, passphrase encrypted PKCS8 PEM")]
+pub struct CliExportKeyCommand<T: ApplicationInfo> {
+    #[arg(required = true)]
+    name: String,
+    #[arg(long, action = ArgAction::Set, value_hint = ValueHint::DirPath, default_value_os_t = T::home_dir(), help = "directory for config and data")]
+    home: PathBuf,
+    /// select keyring's backend
+    #[arg(long = "keyring-backend",  action = ArgAction::Set, default_value_t = KeyringBackend::File )]
+    keyring_backend: KeyringBackend,
+    #[arg(skip)]
+    _marker: PhantomData<T>,
+}
+
+impl<T: ApplicationInfo> From<CliExportKeyCommand<T>> for ExportKeyCommand {
+    fn from(value: CliExportKeyCommand<T>) -> Self {
+        let CliExportKeyCommand {
+            name,
+            home,
+            keyring_backend,
+            _marker,
+        } = value;
+
+        Self {
+            name,
+            home,
+            keyring_backend,
+        }
+    }
+}
@@ -0,0 +1,44 @@
+use std::{marker::PhantomData, path::PathBuf};
+
+use clap::{ArgAction, ValueHint};
+
+use crate::{
+    application::ApplicationInfo,
+    client::keys::{ImportKeyCommand, KeyringBackend},
+};
+
+#[derive(Debug, Clone, ::clap::Args)]
+#[command(about = "Import a private key exported by the export command, saving it to <NAME>")]
+pub struct CliImportKeyCommand<T: ApplicationInfo> {
+    #[arg(required = true)]
+    name: String,
+    /// armored key file
+    #[arg(required = true, value_hint = ValueHint::FilePath)]
+    key_file: PathBuf,
+    #[arg(long, action = ArgAction::Set, value_hint = ValueHint::DirPath, default_value_os_t = T::home_dir(), help = "directory for config and data")]
+    home: PathBuf,
+    /// select keyring's backend
+    #[arg(long = "keyring-backend",  action = ArgAction::Set, default_value_t = KeyringBackend::File )]
+    keyring_backend: KeyringBackend,
+    #[arg(skip)]
+    _marker: PhantomData<T>,
+}
+
+impl<T: ApplicationInfo> From<CliImportKeyCommand<T>> for ImportKeyCommand {
+    fn from(value: CliImportKeyCommand<T>) -> Self {
+        let CliImportKeyCommand {
+            name,
+            key_file,
+            home,
+            keyring_backend,
+            _marker,
+        } = value;
+
+        Self {
+            name,
+            key_file,
+            home,
+            keyring_backend,
+        }
+    }
+}
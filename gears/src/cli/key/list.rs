@@ -0,0 +1,40 @@
+use std::{marker::PhantomData, path::PathBuf};
+
+use clap::{ArgAction, ValueHint};
+
+use crate::{
+    application::ApplicationInfo,
+    client::keys::{KeyringBackend, KeysOutput, ListKeyCommand},
+};
+
+#[derive(Debug, Clone, ::clap::Args)]
+#[command(about = "List all keys")]
+pub struct CliListKeyCommand<T: ApplicationInfo> {
+    #[arg(long, action = ArgAction::Set, value_hint = ValueHint::DirPath, default_value_os_t = T::home_dir(), help = "directory for config and data")]
+    home: PathBuf,
+    /// select keyring's backend
+    #[arg(long = "keyring-backend",  action = ArgAction::Set, default_value_t = KeyringBackend::File )]
+    keyring_backend: KeyringBackend,
+    /// output format
+    #[arg(long, short, action = ArgAction::Set, default_value_t = KeysOutput::Text)]
+    output: KeysOutput,
+    #[arg(skip)]
+    _marker: PhantomData<T>,
+}
+
+impl<T: ApplicationInfo> From<CliListKeyCommand<T>> for ListKeyCommand {
+    fn from(value: CliListKeyCommand<T>) -> Self {
+        let CliListKeyCommand {
+            home,
+            keyring_backend,
+            output,
+            _marker,
+        } = value;
+
+        Self {
+            home,
+            keyring_backend,
+            output,
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use std::{marker::PhantomData, path::PathBuf};
+
+use clap::{ArgAction, ValueHint};
+
+use crate::{
+    application::ApplicationInfo,
+    client::keys::{Bech, KeyringBackend, KeysOutput, ShowKeyCommand},
+};
+
+#[derive(Debug, Clone, ::clap::Args)]
+#[command(about = "Retrieve key information by name")]
+pub struct CliShowKeyCommand<T: ApplicationInfo> {
+    #[arg(required = true)]
+    name: String,
+    #[arg(long, action = ArgAction::Set, value_hint = ValueHint::DirPath, default_value_os_t = T::home_dir(), help = "directory for config and data")]
+    home: PathBuf,
+    /// select keyring's backend
+    #[arg(long = "keyring-backend",  action = ArgAction::Set, default_value_t = KeyringBackend::File )]
+    keyring_backend: KeyringBackend,
+    #[arg(short, long, action = ArgAction::SetTrue, conflicts_with = "pubkey", help = "Output the address only")]
+    address: bool,
+    #[arg(short, long, action = ArgAction::SetTrue, help = "Output the public key only")]
+    pubkey: bool,
+    /// the Bech32 prefix encoding for the address
+    #[arg(long, action = ArgAction::Set, default_value_t = Bech::Acc)]
+    bech: Bech,
+    /// output format
+    #[arg(long, short, action = ArgAction::Set, default_value_t = KeysOutput::Text)]
+    output: KeysOutput,
+    #[arg(skip)]
+    _marker: PhantomData<T>,
+}
+
+impl<T: ApplicationInfo> From<CliShowKeyCommand<T>> for ShowKeyCommand {
+    fn from(value: CliShowKeyCommand<T>) -> Self {
+        let CliShowKeyCommand {
+            name,
+            home,
+            keyring_backend,
+            address,
+            pubkey,
+            bech,
+            output,
+            _marker,
+        } = value;
+
+        Self {
+            name,
+            home,
+            keyring_backend,
+            address,
+            pubkey,
+            bech,
+            output,
+        }
+    }
+}
@@ -11,11 +11,14 @@ use crate::application::{
 };
 
 use self::{
-    genesis::CliGenesisCommand, init::CliInitCommand, key::CliKeyCommand, query::CliQueryCommand,
-    run::CliRunCommand, tx::CliTxCommand,
+    backup::CliBackupCommand, debug::CliDebugCommand, genesis::CliGenesisCommand,
+    init::CliInitCommand, key::CliKeyCommand, query::CliQueryCommand, run::CliRunCommand,
+    tx::CliTxCommand,
 };
 
 pub mod aux;
+pub mod backup;
+pub mod debug;
 pub mod genesis;
 pub mod init;
 pub mod key;
@@ -101,6 +104,7 @@ where
     TX: TryFrom<CliTX, Error = ERR>,
     CliQue: Subcommand,
     QUE: TryFrom<CliQue, Error = ERR>,
+    ERR: From<clap::Error>,
 {
     type Error = ERR;
 
@@ -177,13 +181,14 @@ where
     TX: TryFrom<CliTX, Error = ERR>,
     CliQue: Subcommand,
     QUE: TryFrom<CliQue, Error = ERR>,
+    ERR: From<clap::Error>,
 {
     type Error = ERR;
 
     fn try_from(value: CliClientCommands<T, CliAUX, CliTX, CliQue>) -> Result<Self, Self::Error> {
         let res = match value {
             CliClientCommands::Aux(cmd) => Self::Aux(cmd.try_into()?),
-            CliClientCommands::Tx(cmd) => Self::Tx(cmd.try_into()?),
+            CliClientCommands::Tx(cmd) => cmd.try_into()?,
             CliClientCommands::Query(cmd) => Self::Query(cmd.try_into()?),
             CliClientCommands::Keys(cmd) => Self::Keys(cmd.into()),
         };
@@ -198,6 +203,9 @@ pub enum CliAppCommands<T: ApplicationInfo, CliAUX: Subcommand> {
     Run(CliRunCommand<T>),
     #[command(name = "add-genesis-account")]
     GenesisAdd(CliGenesisCommand<T>),
+    #[command(subcommand)]
+    Debug(CliDebugCommand<T>),
+    Backup(CliBackupCommand<T>),
     #[command(flatten)]
     Aux(CliAUX),
 }
@@ -215,6 +223,8 @@ where
             CliAppCommands::Init(cmd) => Self::Init(cmd.into()),
             CliAppCommands::Run(cmd) => Self::Run(cmd.into()),
             CliAppCommands::GenesisAdd(cmd) => Self::GenesisAdd(cmd.into()),
+            CliAppCommands::Debug(cmd) => Self::Debug(cmd.into()),
+            CliAppCommands::Backup(cmd) => Self::Backup(cmd.into()),
             CliAppCommands::Aux(cmd) => Self::Aux(cmd.try_into()?),
         };
 
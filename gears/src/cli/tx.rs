@@ -1,14 +1,22 @@
 use std::{marker::PhantomData, path::PathBuf};
 
-use clap::{ArgAction, Subcommand, ValueHint};
-use proto_messages::cosmos::base::v1beta1::SendCoins;
+use clap::{error::ErrorKind, ArgAction, Subcommand, ValueHint};
+use proto_messages::cosmos::base::v1beta1::{DecCoins, SendCoins};
+use proto_types::AccAddress;
 
 use tendermint::informal::chain::Id;
 
 use crate::{
-    application::ApplicationInfo,
-    client::{keys::KeyringBackend, tx::TxCommand},
+    application::{command::client::ClientCommands, ApplicationInfo},
+    client::{
+        keys::KeyringBackend,
+        tx::{
+            AuxToFeeCommand, BroadcastCommand, BroadcastMode, MultiSignCommand, SignCommand,
+            TxCommand, DEFAULT_GAS_LIMIT,
+        },
+    },
     config::DEFAULT_TENDERMINT_RPC_ADDRESS,
+    crypto::TxSignMode,
 };
 
 /// Transaction subcommands
@@ -19,31 +27,82 @@ pub struct CliTxCommand<T: ApplicationInfo, C: Subcommand> {
     /// <host>:<port> to Tendermint RPC interface for this chain
     #[arg(long, global = true, action = ArgAction::Set, value_hint = ValueHint::Url, default_value_t = DEFAULT_TENDERMINT_RPC_ADDRESS.parse().expect( "const should be valid"))]
     pub node: url::Url,
-    /// From key
-    #[arg(required = true)]
-    pub from_key: String,
+    /// From key, or its address with `--generate-only`. Required by the message subcommands
+    pub from_key: Option<String>,
     /// file chain-id
     #[arg(long =  "chain-id", global = true, action = ArgAction::Set, default_value_t = Id::try_from( "test-chain" ).expect("unrechable: default should be valid"))]
     pub chain_id: Id,
-    /// TODO
-    #[arg(long, global = true, action = ArgAction::Set)]
+    /// fees to pay along with the transaction
+    #[arg(long = "fees", alias = "fee", global = true, action = ArgAction::Set, conflicts_with = "gas_prices")]
     pub fee: Option<SendCoins>,
+    /// gas prices to determine the fees from the gas limit, e.g. 0.025uatom
+    #[arg(long = "gas-prices", global = true, action = ArgAction::Set)]
+    pub gas_prices: Option<DecCoins>,
+    /// gas limit of the transaction
+    #[arg(long = "gas", global = true, action = ArgAction::Set, default_value_t = DEFAULT_GAS_LIMIT)]
+    pub gas_limit: u64,
+    /// note to add to the transaction
+    #[arg(long, global = true, action = ArgAction::Set, default_value_t = String::new())]
+    pub memo: String,
+    /// block height after which the transaction isn't valid, 0 means it never times out
+    #[arg(long = "timeout-height", global = true, action = ArgAction::Set, default_value_t = 0)]
+    pub timeout_height: u64,
     /// select keyring's backend
     #[arg(long = "keyring-backend",  global = true, action = ArgAction::Set, default_value_t = KeyringBackend::File )]
     pub keyring_backend: KeyringBackend,
+    /// sign mode used to sign the transaction
+    #[arg(long = "sign-mode", global = true, action = ArgAction::Set, default_value_t = TxSignMode::Direct)]
+    pub sign_mode: TxSignMode,
+    /// account number of the signer, queried from the node if not set
+    #[arg(long = "account-number", global = true, action = ArgAction::Set)]
+    pub account_number: Option<u64>,
+    /// sequence of the signer, queried from the node if not set
+    #[arg(long, global = true, action = ArgAction::Set)]
+    pub sequence: Option<u64>,
+    /// tip paid to the fee payer, only used with `--sign-mode direct-aux`
+    #[arg(long, global = true, action = ArgAction::Set)]
+    pub tip: Option<SendCoins>,
+    /// account which pays the fee instead of the first signer, it must sign the tx
+    #[arg(long = "fee-payer", global = true, action = ArgAction::Set)]
+    pub fee_payer: Option<AccAddress>,
+    /// account whose fee allowance pays the fee
+    #[arg(long = "fee-granter", global = true, action = ArgAction::Set)]
+    pub fee_granter: Option<AccAddress>,
+    /// print the unsigned transaction instead of signing and broadcasting it
+    #[arg(long = "generate-only", global = true, action = ArgAction::SetTrue, conflicts_with = "dry_run")]
+    pub generate_only: bool,
+    /// sign the transaction and print it instead of broadcasting it
+    #[arg(long = "dry-run", global = true, action = ArgAction::SetTrue)]
+    pub dry_run: bool,
+    /// addresses of the members of the multisig from key which will sign, only used with `--generate-only`
+    #[arg(long = "multisig-signers", global = true, action = ArgAction::Set, value_delimiter = ',', requires = "generate_only")]
+    pub multisig_signers: Vec<AccAddress>,
 
     #[command(subcommand)]
-    pub command: C,
+    pub command: CliTxCommands<C>,
 
     #[arg(skip)]
     _marker: PhantomData<T>,
 }
 
-impl<T, C, AC, ERR> TryFrom<CliTxCommand<T, C>> for TxCommand<AC>
+#[derive(Debug, Clone, ::clap::Subcommand)]
+pub enum CliTxCommands<C: Subcommand> {
+    #[command(name = "multi-sign")]
+    MultiSign(CliMultiSignCommand),
+    #[command(name = "aux-to-fee")]
+    AuxToFee(CliAuxToFeeCommand),
+    Sign(CliSignCommand),
+    Broadcast(CliBroadcastCommand),
+    #[command(flatten)]
+    Msg(C),
+}
+
+impl<T, C, AUX, AC, QUE, ERR> TryFrom<CliTxCommand<T, C>> for ClientCommands<AUX, AC, QUE>
 where
     T: ApplicationInfo,
     C: Subcommand,
     AC: TryFrom<C, Error = ERR>,
+    ERR: From<clap::Error>,
 {
     type Error = ERR;
 
@@ -54,19 +113,162 @@ where
             from_key,
             chain_id,
             fee,
+            gas_prices,
+            gas_limit,
+            memo,
+            timeout_height,
             keyring_backend,
+            sign_mode,
+            account_number,
+            sequence,
+            tip,
+            fee_payer,
+            fee_granter,
+            generate_only,
+            dry_run,
+            multisig_signers,
             _marker,
             command,
         } = value;
 
-        Ok(Self {
-            home,
-            node,
-            from_key,
-            chain_id,
-            fee,
-            keyring_backend,
-            inner: command.try_into()?,
-        })
+        let res = match command {
+            CliTxCommands::MultiSign(CliMultiSignCommand {
+                tx_file,
+                multisig,
+                signature_files,
+            }) => Self::MultiSign(MultiSignCommand {
+                home,
+                keyring_backend,
+                tx_file,
+                multisig,
+                signature_files,
+            }),
+            CliTxCommands::AuxToFee(CliAuxToFeeCommand {
+                aux_signer_data_file,
+                from_key,
+            }) => Self::AuxToFee(AuxToFeeCommand {
+                home,
+                node,
+                from_key,
+                chain_id,
+                fee,
+                gas_prices,
+                gas_limit,
+                fee_granter,
+                keyring_backend,
+                aux_signer_data_file,
+            }),
+            CliTxCommands::Sign(CliSignCommand {
+                tx_file,
+                from_key,
+                offline,
+                multisig,
+            }) => Self::Sign(SignCommand {
+                home,
+                node,
+                from_key,
+                chain_id,
+                keyring_backend,
+                sign_mode,
+                tx_file,
+                offline,
+                account_number,
+                sequence,
+                multisig,
+            }),
+            CliTxCommands::Broadcast(CliBroadcastCommand { tx_file, mode }) => {
+                Self::Broadcast(BroadcastCommand {
+                    node,
+                    tx_file,
+                    mode,
+                })
+            }
+            CliTxCommands::Msg(command) => {
+                let from_key = from_key.ok_or_else(|| {
+                    clap::Error::raw(
+                        ErrorKind::MissingRequiredArgument,
+                        "the from key is required to create a transaction\n",
+                    )
+                })?;
+
+                Self::Tx(TxCommand {
+                    home,
+                    node,
+                    from_key,
+                    chain_id,
+                    fee,
+                    gas_prices,
+                    gas_limit,
+                    memo,
+                    timeout_height,
+                    keyring_backend,
+                    sign_mode,
+                    account_number,
+                    sequence,
+                    tip,
+                    fee_payer,
+                    fee_granter,
+                    generate_only,
+                    dry_run,
+                    multisig_signers,
+                    inner: command.try_into()?,
+                })
+            }
+        };
+
+        Ok(res)
     }
 }
+
+/// Combine the signatures of multisig members into a signed tx
+#[derive(Debug, Clone, ::clap::Args)]
+pub struct CliMultiSignCommand {
+    /// JSON encoded tx which declares the multisig signer, generated with `--multisig-signers`
+    #[arg(required = true, value_hint = ValueHint::FilePath)]
+    pub tx_file: PathBuf,
+    /// name of the multisig key in the keyring
+    #[arg(required = true)]
+    pub multisig: String,
+    /// JSON encoded signatures of the multisig members
+    #[arg(required = true, value_hint = ValueHint::FilePath)]
+    pub signature_files: Vec<PathBuf>,
+}
+
+/// Pay the fee of a tx signed with `--sign-mode direct-aux`, then sign and broadcast it
+#[derive(Debug, Clone, ::clap::Args)]
+pub struct CliAuxToFeeCommand {
+    /// JSON encoded data signed by the aux signer
+    #[arg(required = true, value_hint = ValueHint::FilePath)]
+    pub aux_signer_data_file: PathBuf,
+    /// fee payer key
+    #[arg(required = true)]
+    pub from_key: String,
+}
+
+/// Sign a transaction generated with `tx --generate-only`
+#[derive(Debug, Clone, ::clap::Args)]
+pub struct CliSignCommand {
+    /// JSON encoded unsigned tx
+    #[arg(required = true, value_hint = ValueHint::FilePath)]
+    pub tx_file: PathBuf,
+    /// signer key
+    #[arg(required = true)]
+    pub from_key: String,
+    /// sign without querying the node, `--account-number` and `--sequence` must be set
+    #[arg(long, action = ArgAction::SetTrue)]
+    pub offline: bool,
+    /// name of a multisig key the signer is a member of, prints the member's signature for `tx multi-sign`
+    #[arg(long, action = ArgAction::Set)]
+    pub multisig: Option<String>,
+}
+
+/// Broadcast a signed transaction
+#[derive(Debug, Clone, ::clap::Args)]
+pub struct CliBroadcastCommand {
+    /// JSON encoded signed tx
+    #[arg(required = true, value_hint = ValueHint::FilePath)]
+    pub tx_file: PathBuf,
+    /// wait for the tx to pass CheckTx (sync), don't wait (async) or wait for it to be committed (commit)
+    #[arg(long = "broadcast-mode", action = ArgAction::Set, default_value_t = BroadcastMode::Sync)]
+    pub mode: BroadcastMode,
+}
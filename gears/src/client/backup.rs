@@ -0,0 +1,35 @@
+use std::path::PathBuf;
+
+#[cfg(feature = "rocksdb")]
+use database::RocksDB;
+
+#[derive(Debug, Clone, derive_builder::Builder)]
+pub struct BackupCommand {
+    pub home: PathBuf,
+    pub path: PathBuf,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum BackupError {
+    #[error("{0}")]
+    Database(#[from] database::error::Error),
+    #[error("backup directory {0} already exists")]
+    Exists(PathBuf),
+}
+
+/// Writes a checkpoint of the application database to the backup directory. The backup is a
+/// complete database which can be used as the data directory of a node.
+#[cfg(feature = "rocksdb")]
+pub fn backup(cmd: BackupCommand, db: &RocksDB) -> Result<(), BackupError> {
+    let BackupCommand { home: _, path } = cmd;
+
+    if path.exists() {
+        return Err(BackupError::Exists(path));
+    }
+
+    db.checkpoint(&path)?;
+
+    println!("Backup written to {}", path.display());
+
+    Ok(())
+}
@@ -0,0 +1,178 @@
+use std::path::{Path, PathBuf};
+
+use database::Database;
+use store_crate::{MultiStore, StoreKey};
+use strum::IntoEnumIterator;
+use trees::iavl::KeyChange;
+
+#[derive(Debug, Clone)]
+pub enum DebugCommand {
+    DiffState(DiffStateCommand),
+    VerifyStore(VerifyStoreCommand),
+}
+
+impl DebugCommand {
+    pub fn home(&self) -> &Path {
+        match self {
+            DebugCommand::DiffState(cmd) => &cmd.home,
+            DebugCommand::VerifyStore(cmd) => &cmd.home,
+        }
+    }
+}
+
+#[derive(Debug, Clone, derive_builder::Builder)]
+pub struct DiffStateCommand {
+    pub home: PathBuf,
+    pub store: Option<String>,
+    pub from: u32,
+    pub to: u32,
+}
+
+#[derive(Debug, Clone, derive_builder::Builder)]
+pub struct VerifyStoreCommand {
+    pub home: PathBuf,
+    pub store: Option<String>,
+    pub version: Option<u32>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum DebugError {
+    #[error("{0}")]
+    Database(#[from] database::error::Error),
+    #[error("{0}")]
+    MultiStore(#[from] store_crate::error::Error),
+    #[error("unknown store: {0}")]
+    UnknownStore(String),
+    #[error("store {store}: {message}")]
+    Store { store: String, message: String },
+    #[error("found problems in {0} store(s)")]
+    Corrupt(usize),
+    #[error("the database hasn't been migrated to the current layout, start the node once to migrate it")]
+    Unmigrated,
+}
+
+/// Runs the command against the application database
+pub fn debug<DB: Database, SK: StoreKey>(command: DebugCommand, db: DB) -> Result<(), DebugError> {
+    // the DB may be opened read only so it's never migrated here, on the legacy layout the
+    // commands would find no versions at all
+    if MultiStore::<DB, SK>::needs_migration(&db)? {
+        return Err(DebugError::Unmigrated);
+    }
+
+    match command {
+        DebugCommand::DiffState(cmd) => diff_state::<DB, SK>(cmd, db),
+        DebugCommand::VerifyStore(cmd) => verify_store::<DB, SK>(cmd, db),
+    }
+}
+
+fn store_keys<SK: StoreKey>(store: Option<String>) -> Result<Vec<SK>, DebugError> {
+    match store {
+        Some(name) => Ok(vec![SK::iter()
+            .find(|key| key.name() == name)
+            .ok_or(DebugError::UnknownStore(name))?]),
+        None => Ok(SK::iter().collect()),
+    }
+}
+
+fn diff_state<DB: Database, SK: StoreKey>(cmd: DiffStateCommand, db: DB) -> Result<(), DebugError> {
+    let DiffStateCommand {
+        home: _,
+        store,
+        from,
+        to,
+    } = cmd;
+
+    let store_keys = store_keys::<SK>(store)?;
+
+    let multi_store: MultiStore<_, SK> = MultiStore::new(db)?;
+
+    for store_key in store_keys {
+        let changes = multi_store
+            .get_kv_store(&store_key)
+            .diff(from, to)
+            .map_err(|e| DebugError::Store {
+                store: store_key.name().to_owned(),
+                message: e.to_string(),
+            })?;
+
+        println!("{}: {} changed keys", store_key.name(), changes.len());
+
+        for change in changes {
+            match change {
+                KeyChange::Inserted { key, value } => {
+                    println!("  + {} {}", hex::encode(key), hex::encode(value))
+                }
+                KeyChange::Updated {
+                    key,
+                    old_value,
+                    new_value,
+                } => println!(
+                    "  ~ {} {} -> {}",
+                    hex::encode(key),
+                    hex::encode(old_value),
+                    hex::encode(new_value)
+                ),
+                KeyChange::Removed { key, old_value } => {
+                    println!("  - {} {}", hex::encode(key), hex::encode(old_value))
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn verify_store<DB: Database, SK: StoreKey>(
+    cmd: VerifyStoreCommand,
+    db: DB,
+) -> Result<(), DebugError> {
+    let VerifyStoreCommand {
+        home: _,
+        store,
+        version,
+    } = cmd;
+
+    let store_keys = store_keys::<SK>(store)?;
+
+    // the stores aren't loaded, loading a corrupt store would panic
+    let reports = MultiStore::<_, SK>::verify_db(db, version);
+
+    let mut corrupt = 0;
+    for (store_key, report) in reports {
+        if !store_keys.contains(&store_key) {
+            continue;
+        }
+
+        let report = report.map_err(|e| DebugError::Store {
+            store: store_key.name().to_owned(),
+            message: e.to_string(),
+        })?;
+
+        if report.is_ok() {
+            println!(
+                "{}: version {} ok, {} nodes checked",
+                store_key.name(),
+                report.version,
+                report.nodes
+            );
+        } else {
+            corrupt += 1;
+            println!(
+                "{}: version {} has {} problem(s), {} nodes checked",
+                store_key.name(),
+                report.version,
+                report.problems.len(),
+                report.nodes
+            );
+
+            for problem in report.problems {
+                println!("  {problem}");
+            }
+        }
+    }
+
+    match corrupt {
+        0 => Ok(()),
+        corrupt => Err(DebugError::Corrupt(corrupt)),
+    }
+}
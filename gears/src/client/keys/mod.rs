@@ -1,5 +1,10 @@
 use anyhow::Result;
 use bip32::Mnemonic;
+use proto_messages::cosmos::{
+    crypto::multisig::LegacyAminoPubKey, tx::v1beta1::public_key::PublicKey,
+};
+use proto_types::{AccAddress, ValAddress};
+use serde::Serialize;
 use std::path::PathBuf;
 use strum::Display;
 use text_io::read;
@@ -33,9 +38,67 @@ impl KeyringBackend {
     }
 }
 
+/// Signature algorithm of a new key
+#[derive(Clone, Copy, Default, Debug, Display)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum KeyAlgorithm {
+    #[default]
+    #[strum(to_string = "secp256k1")]
+    Secp256k1,
+    #[strum(to_string = "secp256r1")]
+    Secp256r1,
+    #[strum(to_string = "ed25519")]
+    Ed25519,
+    /// Ethermint key, use with coin type 60
+    #[cfg(feature = "ethsecp256k1")]
+    #[strum(to_string = "eth_secp256k1")]
+    #[cfg_attr(feature = "cli", value(name = "eth_secp256k1"))]
+    EthSecp256k1,
+}
+
+impl From<KeyAlgorithm> for keyring::KeyType {
+    fn from(algo: KeyAlgorithm) -> Self {
+        match algo {
+            KeyAlgorithm::Secp256k1 => keyring::KeyType::Secp256k1,
+            KeyAlgorithm::Secp256r1 => keyring::KeyType::Secp256r1,
+            KeyAlgorithm::Ed25519 => keyring::KeyType::Ed25519,
+            #[cfg(feature = "ethsecp256k1")]
+            KeyAlgorithm::EthSecp256k1 => keyring::KeyType::EthSecp256k1,
+        }
+    }
+}
+
+/// Format used to print keys
+#[derive(Clone, Copy, Default, Debug, Display)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum KeysOutput {
+    #[default]
+    #[strum(to_string = "text")]
+    Text,
+    #[strum(to_string = "json")]
+    Json,
+}
+
+/// Bech32 prefix used to display a key's address
+#[derive(Clone, Copy, Default, Debug, Display)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum Bech {
+    #[default]
+    #[strum(to_string = "acc")]
+    Acc,
+    #[strum(to_string = "val")]
+    Val,
+}
+
 #[derive(Debug, Clone)]
 pub enum KeyCommand {
     Add(AddKeyCommand),
+    List(ListKeyCommand),
+    Show(ShowKeyCommand),
+    Delete(DeleteKeyCommand),
+    Rename(RenameKeyCommand),
+    Export(ExportKeyCommand),
+    Import(ImportKeyCommand),
 }
 
 #[derive(Debug, Clone, derive_builder::Builder)]
@@ -44,6 +107,169 @@ pub struct AddKeyCommand {
     pub recover: bool,
     pub home: PathBuf,
     pub keyring_backend: KeyringBackend,
+    /// Prompt for a BIP39 passphrase to combine with the mnemonic
+    pub interactive: bool,
+    /// Full HD path, overrides the coin type, account and index
+    pub hd_path: Option<String>,
+    pub coin_type: u32,
+    pub account: u32,
+    pub index: u32,
+    pub algo: KeyAlgorithm,
+    /// Names of the keys which make up a multisig key, when set no private key is created
+    pub multisig: Vec<String>,
+    /// Number of signatures required by the multisig key
+    pub multisig_threshold: u32,
+    /// Keep the multisig keys in the given order rather than sorting them by address
+    pub nosort: bool,
+}
+
+#[derive(Debug, Clone, derive_builder::Builder)]
+pub struct ListKeyCommand {
+    pub home: PathBuf,
+    pub keyring_backend: KeyringBackend,
+    pub output: KeysOutput,
+}
+
+#[derive(Debug, Clone, derive_builder::Builder)]
+pub struct ShowKeyCommand {
+    pub name: String,
+    pub home: PathBuf,
+    pub keyring_backend: KeyringBackend,
+    /// Print only the address
+    pub address: bool,
+    /// Print only the public key
+    pub pubkey: bool,
+    pub bech: Bech,
+    pub output: KeysOutput,
+}
+
+#[derive(Debug, Clone, derive_builder::Builder)]
+pub struct DeleteKeyCommand {
+    pub name: String,
+    pub home: PathBuf,
+    pub keyring_backend: KeyringBackend,
+    /// Skip the confirmation prompt
+    pub yes: bool,
+}
+
+#[derive(Debug, Clone, derive_builder::Builder)]
+pub struct RenameKeyCommand {
+    pub old_name: String,
+    pub new_name: String,
+    pub home: PathBuf,
+    pub keyring_backend: KeyringBackend,
+    /// Skip the confirmation prompt
+    pub yes: bool,
+}
+
+#[derive(Debug, Clone, derive_builder::Builder)]
+pub struct ExportKeyCommand {
+    pub name: String,
+    pub home: PathBuf,
+    pub keyring_backend: KeyringBackend,
+}
+
+#[derive(Debug, Clone, derive_builder::Builder)]
+pub struct ImportKeyCommand {
+    pub name: String,
+    /// Armored key file produced by the export command
+    pub key_file: PathBuf,
+    pub home: PathBuf,
+    pub keyring_backend: KeyringBackend,
+}
+
+/// A key as printed by the list and show commands
+#[derive(Debug, Clone, Serialize)]
+pub struct KeyOutput {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub key_type: String,
+    pub address: String,
+    pub pubkey: PublicKey,
+}
+
+impl KeyOutput {
+    pub fn new(name: String, pubkey: PublicKey, bech: Bech) -> Self {
+        Self::from_public_key(name, "local", pubkey, bech)
+    }
+
+    pub fn multisig(name: String, key: &LegacyAminoPubKey, bech: Bech) -> Self {
+        Self::from_public_key(name, "multi", PublicKey::Multisig(key.to_owned()), bech)
+    }
+
+    fn from_public_key(name: String, key_type: &str, pubkey: PublicKey, bech: Bech) -> Self {
+        let address = pubkey.get_address();
+        let address = match bech {
+            Bech::Acc => address.to_string(),
+            Bech::Val => ValAddress::try_from(Vec::<u8>::from(address))
+                .expect("account and validator addresses have the same length limits")
+                .to_string(),
+        };
+
+        Self {
+            name,
+            key_type: key_type.into(),
+            address,
+            pubkey,
+        }
+    }
+
+    fn to_text(&self) -> Result<String> {
+        Ok(format!(
+            "name: {}\ntype: {}\naddress: {}\npubkey: '{}'",
+            self.name,
+            self.key_type,
+            self.address,
+            serde_json::to_string(&self.pubkey)?
+        ))
+    }
+}
+
+/// Builds a multisig key from the public keys of keyring entries, nested multisig entries are
+/// allowed
+fn multisig_key(
+    names: &[String],
+    threshold: u32,
+    nosort: bool,
+    keyring_backend: &KeyringBackend,
+    keyring_home: &PathBuf,
+) -> Result<LegacyAminoPubKey> {
+    let mut entries: Vec<(String, PublicKey)> =
+        keyring::list_multisig_keys(keyring_backend.to_keyring_backend(keyring_home))?
+            .into_iter()
+            .map(|(name, key)| (name, PublicKey::Multisig(key)))
+            .collect();
+    entries.extend(keyring::list_keys(
+        keyring_backend.to_keyring_backend(keyring_home),
+    )?);
+
+    let mut public_keys = Vec::with_capacity(names.len());
+    for name in names {
+        let (_, key) = entries
+            .iter()
+            .find(|(entry_name, _)| entry_name == name)
+            .ok_or_else(|| anyhow::anyhow!("key {name} not found in the keyring"))?;
+
+        if public_keys.contains(key) {
+            anyhow::bail!("duplicate key {name} in the multisig");
+        }
+
+        public_keys.push(key.to_owned());
+    }
+
+    if !nosort {
+        public_keys.sort_by_key(|key| Vec::<u8>::from(key.get_address()));
+    }
+
+    Ok(LegacyAminoPubKey::new(threshold, public_keys)?)
+}
+
+/// Asks the user to confirm an action, anything other than "y" or "yes" is a refusal
+fn confirm(prompt: &str) -> bool {
+    println!("{prompt} [y/N]");
+    let answer: String = read!("{}\n");
+
+    matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
 }
 
 // TODO: remove this cli code
@@ -55,22 +281,78 @@ pub fn keys(command: KeyCommand) -> Result<()> {
                 recover,
                 home,
                 keyring_backend,
+                interactive,
+                hd_path,
+                coin_type,
+                account,
+                index,
+                algo,
+                multisig,
+                multisig_threshold,
+                nosort,
             } = cmd;
 
             let keyring_home = home.join(keyring_backend.get_sub_dir());
 
+            if !multisig.is_empty() {
+                let key = multisig_key(
+                    &multisig,
+                    multisig_threshold,
+                    nosort,
+                    &keyring_backend,
+                    &keyring_home,
+                )?;
+                keyring::add_multisig_key(
+                    &name,
+                    &key,
+                    keyring_backend.to_keyring_backend(&keyring_home),
+                )?;
+
+                println!(
+                    "Key {} saved to disk.\nAddress: {}",
+                    name,
+                    key.get_address()
+                );
+
+                return Ok(());
+            }
+
+            let hd_path = match hd_path {
+                Some(hd_path) => keyring::parse_hd_path(&hd_path)?,
+                None => keyring::bip44_path(coin_type, account, index)?,
+            };
+
             let backend = keyring_backend.to_keyring_backend(&keyring_home);
 
-            if recover {
+            let mnemonic = if recover {
                 println!("> Enter your bip39 mnemonic");
                 let phrase: String = read!("{}\n");
 
-                let mnemonic = Mnemonic::new(phrase, bip32::Language::English)?;
+                Some(Mnemonic::new(phrase, bip32::Language::English)?)
+            } else {
+                None
+            };
+
+            let bip39_passphrase = if interactive {
+                rpassword::prompt_password(
+                    "Enter your bip39 passphrase. This is combined with the mnemonic to derive the seed. Most users should just hit enter to use the default, \"\": ",
+                )?
+            } else {
+                String::new()
+            };
 
-                keyring::add_key(&name, &mnemonic, keyring::KeyType::Secp256k1, backend)?;
+            if let Some(mnemonic) = mnemonic {
+                keyring::add_key(
+                    &name,
+                    &mnemonic,
+                    &bip39_passphrase,
+                    &hd_path,
+                    algo.into(),
+                    backend,
+                )?;
             } else {
                 let (mnemonic, key_pair) =
-                    keyring::create_key(&name, keyring::KeyType::Secp256k1, backend)?;
+                    keyring::create_key(&name, &bip39_passphrase, &hd_path, algo.into(), backend)?;
 
                 println!("Created key {}\nAddress: {}", name, key_pair.get_address());
 
@@ -78,7 +360,212 @@ pub fn keys(command: KeyCommand) -> Result<()> {
                 println!("{}", mnemonic.phrase());
             }
         }
+        KeyCommand::List(cmd) => {
+            let ListKeyCommand {
+                home,
+                keyring_backend,
+                output,
+            } = cmd;
+
+            let keyring_home = home.join(keyring_backend.get_sub_dir());
+            let backend = keyring_backend.to_keyring_backend(&keyring_home);
+
+            let mut keys: Vec<KeyOutput> = keyring::list_keys(backend)?
+                .into_iter()
+                .map(|(name, pubkey)| KeyOutput::new(name, pubkey, Bech::Acc))
+                .collect();
+            keys.extend(
+                keyring::list_multisig_keys(keyring_backend.to_keyring_backend(&keyring_home))?
+                    .into_iter()
+                    .map(|(name, key)| KeyOutput::multisig(name, &key, Bech::Acc)),
+            );
+            keys.sort_by(|a, b| a.name.cmp(&b.name));
+
+            match output {
+                KeysOutput::Json => println!("{}", serde_json::to_string_pretty(&keys)?),
+                KeysOutput::Text if keys.is_empty() => println!("No keys found"),
+                KeysOutput::Text => {
+                    for key in keys {
+                        println!("- {}", key.to_text()?.replace('\n', "\n  "));
+                    }
+                }
+            }
+        }
+        KeyCommand::Show(cmd) => {
+            let ShowKeyCommand {
+                name,
+                home,
+                keyring_backend,
+                address,
+                pubkey,
+                bech,
+                output,
+            } = cmd;
+
+            if address && pubkey {
+                anyhow::bail!("--address and --pubkey can not be both used");
+            }
+
+            let keyring_home = home.join(keyring_backend.get_sub_dir());
+
+            // multisig entries are read without the keyring passphrase so look for them first
+            let multisig =
+                keyring::list_multisig_keys(keyring_backend.to_keyring_backend(&keyring_home))?
+                    .into_iter()
+                    .find(|(multisig_name, _)| multisig_name == &name);
+
+            let key = match multisig {
+                Some((name, multisig)) => KeyOutput::multisig(name, &multisig, bech),
+                None => {
+                    let backend = keyring_backend.to_keyring_backend(&keyring_home);
+                    let pubkey = keyring::get_public_key_by_name(&name, backend)?;
+                    KeyOutput::new(name, pubkey, bech)
+                }
+            };
+
+            if address {
+                println!("{}", key.address);
+            } else if pubkey {
+                println!("{}", serde_json::to_string(&key.pubkey)?);
+            } else {
+                match output {
+                    KeysOutput::Json => println!("{}", serde_json::to_string_pretty(&key)?),
+                    KeysOutput::Text => println!("{}", key.to_text()?),
+                }
+            }
+        }
+        KeyCommand::Delete(cmd) => {
+            let DeleteKeyCommand {
+                name,
+                home,
+                keyring_backend,
+                yes,
+            } = cmd;
+
+            let keyring_home = home.join(keyring_backend.get_sub_dir());
+            let backend = keyring_backend.to_keyring_backend(&keyring_home);
+
+            if !yes && !confirm("Key reference will be deleted. Continue?") {
+                println!("Aborted");
+                return Ok(());
+            }
+
+            keyring::delete_key_by_name(&name, backend)?;
+
+            println!("Key deleted forever (uh oh!)");
+        }
+        KeyCommand::Rename(cmd) => {
+            let RenameKeyCommand {
+                old_name,
+                new_name,
+                home,
+                keyring_backend,
+                yes,
+            } = cmd;
+
+            let keyring_home = home.join(keyring_backend.get_sub_dir());
+            let backend = keyring_backend.to_keyring_backend(&keyring_home);
+
+            if !yes
+                && !confirm(&format!(
+                    "Key reference will be renamed from {old_name} to {new_name}. Continue?"
+                ))
+            {
+                println!("Aborted");
+                return Ok(());
+            }
+
+            keyring::rename_key(&old_name, &new_name, backend)?;
+
+            println!("Key was successfully renamed from {old_name} to {new_name}");
+        }
+        KeyCommand::Export(cmd) => {
+            let ExportKeyCommand {
+                name,
+                home,
+                keyring_backend,
+            } = cmd;
+
+            let keyring_home = home.join(keyring_backend.get_sub_dir());
+            let backend = keyring_backend.to_keyring_backend(&keyring_home);
+
+            let passphrase =
+                rpassword::prompt_password("Enter passphrase to encrypt the exported key: ")?;
+            let armor = keyring::export_key(&name, passphrase, backend)?;
+
+            print!("{}", *armor);
+        }
+        KeyCommand::Import(cmd) => {
+            let ImportKeyCommand {
+                name,
+                key_file,
+                home,
+                keyring_backend,
+            } = cmd;
+
+            let keyring_home = home.join(keyring_backend.get_sub_dir());
+            let backend = keyring_backend.to_keyring_backend(&keyring_home);
+
+            let armor = std::fs::read_to_string(&key_file)?;
+            let passphrase = rpassword::prompt_password("Enter passphrase to decrypt your key: ")?;
+            let key_pair = keyring::import_key(&name, &armor, passphrase, backend)?;
+
+            println!("Imported key {}\nAddress: {}", name, key_pair.get_address());
+        }
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use keyring::key_pair::KeyPair;
+
+    use super::*;
+
+    fn key_pair() -> KeyPair {
+        let mnemonic = "race draft rival universe maid cheese steel logic crowd fork comic easy truth drift tomorrow eye buddy head time cash swing swift midnight borrow";
+        let mnemonic = Mnemonic::new(mnemonic, bip32::Language::English).unwrap();
+        KeyPair::Secp256k1(
+            keyring::key_pair::secp256k1_key_pair::Secp256k1KeyPair::from_mnemonic(&mnemonic),
+        )
+    }
+
+    #[test]
+    fn key_output_uses_bech_prefix() {
+        let acc = KeyOutput::new("bob".into(), key_pair().get_gears_public_key(), Bech::Acc);
+        assert_eq!(acc.address, "cosmos1syavy2npfyt9tcncdtsdzf7kny9lh777pahuux");
+
+        let val = KeyOutput::new("bob".into(), key_pair().get_gears_public_key(), Bech::Val);
+        let val_address = ValAddress::from_bech32(&val.address).expect("valid validator address");
+        assert_eq!(
+            Vec::<u8>::from(val_address),
+            Vec::<u8>::from(AccAddress::from_bech32(&acc.address).unwrap())
+        );
+    }
+
+    #[test]
+    fn key_output_serializes_like_the_sdk() {
+        let key = KeyOutput::new("bob".into(), key_pair().get_gears_public_key(), Bech::Acc);
+        let json = serde_json::to_value(&key).unwrap();
+
+        assert_eq!(json["name"], "bob");
+        assert_eq!(json["type"], "local");
+        assert_eq!(json["address"], key.address.as_str());
+        assert_eq!(json["pubkey"]["@type"], "/cosmos.crypto.secp256k1.PubKey");
+    }
+
+    #[test]
+    fn key_output_for_multisig() {
+        let multisig = LegacyAminoPubKey::new(1, vec![key_pair().get_gears_public_key()]).unwrap();
+        let key = KeyOutput::multisig("treasury".into(), &multisig, Bech::Acc);
+        let json = serde_json::to_value(&key).unwrap();
+
+        assert_eq!(json["type"], "multi");
+        assert_eq!(
+            json["pubkey"]["@type"],
+            "/cosmos.crypto.multisig.LegacyAminoPubKey"
+        );
+        assert_eq!(key.address, multisig.get_address().to_string());
+    }
+}
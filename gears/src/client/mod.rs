@@ -1,3 +1,5 @@
+pub mod backup;
+pub mod debug;
 pub mod genesis_account;
 pub mod init;
 pub mod keys;
@@ -1,4 +1,5 @@
 use axum::{extract::FromRef, http::Method, routing::get, Router};
+use database::Database;
 use proto_messages::cosmos::tx::v1beta1::message::Message;
 use tendermint::rpc::Url;
 
@@ -15,6 +16,7 @@ use crate::{
 };
 
 pub fn run_rest_server<
+    DB: Database,
     SK: StoreKey,
     PSK: ParamsSubspaceKey,
     M: Message,
@@ -22,9 +24,9 @@ pub fn run_rest_server<
     G: Genesis,
     AI: ApplicationInfo,
 >(
-    app: BaseApp<SK, PSK, M, H, G, AI>,
+    app: BaseApp<DB, SK, PSK, M, H, G, AI>,
     listen_addr: SocketAddr,
-    router: Router<RestState<SK, PSK, M, H, G, AI>>,
+    router: Router<RestState<DB, SK, PSK, M, H, G, AI>>,
     tendermint_rpc_address: Url,
 ) {
     std::thread::spawn(move || {
@@ -35,8 +37,8 @@ pub fn run_rest_server<
     });
 }
 
-#[derive(Clone)]
 pub struct RestState<
+    DB: Database,
     SK: StoreKey,
     PSK: ParamsSubspaceKey,
     M: Message,
@@ -44,34 +46,57 @@ pub struct RestState<
     G: Genesis,
     AI: ApplicationInfo,
 > {
-    app: BaseApp<SK, PSK, M, H, G, AI>,
+    app: BaseApp<DB, SK, PSK, M, H, G, AI>,
     tendermint_rpc_address: Url,
 }
 
+// deriving Clone would needlessly require the DB to be Clone
 impl<
+        DB: Database,
         SK: StoreKey,
         PSK: ParamsSubspaceKey,
         M: Message,
         H: ABCIHandler<M, SK, G>,
         G: Genesis,
         AI: ApplicationInfo,
-    > FromRef<RestState<SK, PSK, M, H, G, AI>> for BaseApp<SK, PSK, M, H, G, AI>
+    > Clone for RestState<DB, SK, PSK, M, H, G, AI>
 {
-    fn from_ref(rest_state: &RestState<SK, PSK, M, H, G, AI>) -> BaseApp<SK, PSK, M, H, G, AI> {
+    fn clone(&self) -> Self {
+        Self {
+            app: self.app.clone(),
+            tendermint_rpc_address: self.tendermint_rpc_address.clone(),
+        }
+    }
+}
+
+impl<
+        DB: Database,
+        SK: StoreKey,
+        PSK: ParamsSubspaceKey,
+        M: Message,
+        H: ABCIHandler<M, SK, G>,
+        G: Genesis,
+        AI: ApplicationInfo,
+    > FromRef<RestState<DB, SK, PSK, M, H, G, AI>> for BaseApp<DB, SK, PSK, M, H, G, AI>
+{
+    fn from_ref(
+        rest_state: &RestState<DB, SK, PSK, M, H, G, AI>,
+    ) -> BaseApp<DB, SK, PSK, M, H, G, AI> {
         rest_state.app.clone()
     }
 }
 
 impl<
+        DB: Database,
         SK: StoreKey,
         PSK: ParamsSubspaceKey,
         M: Message,
         H: ABCIHandler<M, SK, G>,
         G: Genesis,
         AI: ApplicationInfo,
-    > FromRef<RestState<SK, PSK, M, H, G, AI>> for Url
+    > FromRef<RestState<DB, SK, PSK, M, H, G, AI>> for Url
 {
-    fn from_ref(rest_state: &RestState<SK, PSK, M, H, G, AI>) -> Url {
+    fn from_ref(rest_state: &RestState<DB, SK, PSK, M, H, G, AI>) -> Url {
         rest_state.tendermint_rpc_address.clone()
     }
 }
@@ -81,6 +106,7 @@ impl<
 // 2. what happens if a route panics?
 // 3. No error message unrecognized route - does return a 404 - can use a "fallback" route if necessary
 async fn launch<
+    DB: Database,
     SK: StoreKey,
     PSK: ParamsSubspaceKey,
     M: Message,
@@ -88,9 +114,9 @@ async fn launch<
     G: Genesis,
     AI: ApplicationInfo,
 >(
-    app: BaseApp<SK, PSK, M, H, G, AI>,
+    app: BaseApp<DB, SK, PSK, M, H, G, AI>,
     listen_addr: SocketAddr,
-    router: Router<RestState<SK, PSK, M, H, G, AI>>,
+    router: Router<RestState<DB, SK, PSK, M, H, G, AI>>,
     tendermint_rpc_address: Url,
 ) -> anyhow::Result<()> {
     let cors = CorsLayer::new()
@@ -2,15 +2,34 @@ use std::path::PathBuf;
 
 use anyhow::Result;
 use prost::Message;
-use proto_messages::cosmos::{base::v1beta1::SendCoins, ibc::tx::TxRaw};
+use proto_messages::cosmos::{
+    auth::v1beta1::{Params, QueryParamsRequest, QueryParamsResponse},
+    base::v1beta1::{Coin, DecCoins, SendCoins},
+    ibc::{auth::RawQueryParamsResponse, protobuf::Protobuf, tx::TxRaw},
+    tx::v1beta1::{
+        aux_signer_data::AuxSignerData, fee::Fee, message::Message as SDKMessage,
+        public_key::PublicKey, tx::tx::Tx, tx_body::TxBody,
+    },
+};
+use proto_types::{AccAddress, Decimal256};
+use serde::{de::DeserializeOwned, Serialize};
+use strum::Display;
 use tendermint::informal::chain::Id;
-use tendermint::rpc::endpoint::broadcast::tx_commit::Response;
+use tendermint::rpc::endpoint::broadcast::{tx_async, tx_commit::Response, tx_sync};
 use tendermint::rpc::{Client, HttpClient};
 
-use crate::application::handlers::TxHandler;
+use crate::application::handlers::{get_account_latest, TxHandler};
 use crate::client::keys::KeyringBackend;
+use crate::client::query::execute_query;
+use crate::crypto::{
+    combine_multisig_signatures, multisig_signer_info, sign_as_multisig_member,
+    MultisigMemberSignature, TxSignMode,
+};
 use crate::runtime::runtime;
 
+/// Gas limit of a tx when it isn't set, the same as the Cosmos SDK's
+pub const DEFAULT_GAS_LIMIT: u64 = 200_000;
+
 #[derive(Debug, Clone, derive_builder::Builder)]
 pub struct TxCommand<C> {
     pub home: PathBuf,
@@ -18,11 +37,49 @@ pub struct TxCommand<C> {
     pub from_key: String,
     pub chain_id: Id,
     pub fee: Option<SendCoins>,
+    /// Gas prices to compute the fee from, they can't be set together with `fee`
+    pub gas_prices: Option<DecCoins>,
+    pub gas_limit: u64,
+    /// Its length is checked against the `max_memo_characters` auth param of the chain
+    pub memo: String,
+    /// Block height after which the tx isn't valid, 0 means it never times out
+    pub timeout_height: u64,
     pub keyring_backend: KeyringBackend,
+    pub sign_mode: TxSignMode,
+    /// Account number of the signer, queried from the node when it isn't set
+    pub account_number: Option<u64>,
+    /// Sequence of the signer, queried from the node when it isn't set
+    pub sequence: Option<u64>,
+    /// Tip paid by an aux signer to the fee payer, only used with `TxSignMode::DirectAux`
+    pub tip: Option<SendCoins>,
+    /// Account which pays the fee instead of the first signer, it must sign the tx
+    pub fee_payer: Option<AccAddress>,
+    /// Account whose fee allowance pays the fee
+    pub fee_granter: Option<AccAddress>,
+    /// Build the unsigned tx instead of signing and broadcasting it, `from_key` can then also be
+    /// the address of a key which isn't in the keyring
+    pub generate_only: bool,
+    /// Sign the tx without broadcasting it
+    pub dry_run: bool,
+    /// Members of the multisig `from_key` which will sign a generated tx, they're declared in the
+    /// tx's signer info so must be agreed before anyone signs. Only used with `generate_only`
+    pub multisig_signers: Vec<AccAddress>,
 
     pub inner: C,
 }
 
+/// Result of running a tx command: either the response from broadcasting the tx, when signing
+/// with `SIGN_MODE_DIRECT_AUX`, the signed data to pass to a fee payer, the unsigned tx when
+/// it's only generated or the signed tx of a dry run
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum RunTxResponse<M: SDKMessage> {
+    Broadcast(Response),
+    Aux(AuxSignerData),
+    GenerateOnly(Tx<M>),
+    DryRun(Tx<M>),
+}
+
 pub fn run_tx<C, H: TxHandler<TxCommands = C>>(
     TxCommand {
         home,
@@ -30,19 +87,522 @@ pub fn run_tx<C, H: TxHandler<TxCommands = C>>(
         from_key,
         chain_id,
         fee,
+        gas_prices,
+        gas_limit,
+        memo,
+        timeout_height,
         keyring_backend,
+        sign_mode,
+        account_number,
+        sequence,
+        tip,
+        fee_payer,
+        fee_granter,
+        generate_only,
+        dry_run,
+        multisig_signers,
         inner,
     }: TxCommand<C>,
     handler: &H,
-) -> anyhow::Result<Response> {
+) -> anyhow::Result<RunTxResponse<H::Message>> {
+    if tip.is_some() && (generate_only || sign_mode != TxSignMode::DirectAux) {
+        return Err(anyhow::anyhow!(
+            "a tip can only be set when signing with {}",
+            TxSignMode::DirectAux
+        ));
+    }
+
+    if generate_only && dry_run {
+        return Err(anyhow::anyhow!(
+            "a tx can't be both generated only and dry run"
+        ));
+    }
+
+    if !generate_only && !multisig_signers.is_empty() {
+        return Err(anyhow::anyhow!(
+            "multisig signers can only be declared when the tx is only generated"
+        ));
+    }
+
+    let fee = fee_amount(fee, gas_prices, gas_limit)?;
+
+    // the key may be kept offline when the tx is only generated so the node isn't queried
+    if !generate_only && !memo.is_empty() {
+        validate_memo(&memo, &query_auth_params(node.as_str())?)?;
+    }
+
     let keyring_home = home.join(keyring_backend.get_sub_dir());
 
+    let fee = Fee {
+        amount: fee,
+        gas_limit,
+        payer: fee_payer,
+        granter: fee_granter
+            .map(|granter| granter.to_string())
+            .unwrap_or_default(),
+    };
+
+    if generate_only && !multisig_signers.is_empty() {
+        let multisig = keyring::get_multisig_key_by_name(
+            &from_key,
+            keyring_backend.to_keyring_backend(&keyring_home),
+        )?;
+        let address = multisig.get_address();
+
+        let sequence = match sequence {
+            Some(sequence) => sequence,
+            None => get_account_latest(address.clone(), node.as_str())?
+                .account
+                .get_sequence(),
+        };
+
+        let signers = multisig_signers
+            .iter()
+            .map(|signer| {
+                multisig
+                    .public_keys()
+                    .iter()
+                    .find(|key| &key.get_address() == signer)
+                    .cloned()
+                    .ok_or_else(|| anyhow::anyhow!("{} is not a member of the multisig", signer))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let message = handler.prepare_tx(inner, address)?;
+        let tx_body = new_tx_body(message, memo, timeout_height);
+
+        let mut tx = handler.handle_generate_only(tx_body, fee);
+        tx.auth_info.signer_infos = vec![multisig_signer_info(&multisig, &signers, sequence)?];
+
+        return Ok(RunTxResponse::GenerateOnly(tx));
+    }
+
+    if generate_only {
+        // the key may be kept offline, in which case only its address is known
+        let address = match AccAddress::from_bech32(&from_key) {
+            Ok(address) => address,
+            Err(_) => keyring::get_key_by_name(
+                &from_key,
+                keyring_backend.to_keyring_backend(&keyring_home),
+            )?
+            .get_address(),
+        };
+
+        let message = handler.prepare_tx(inner, address)?;
+        let tx_body = new_tx_body(message, memo, timeout_height);
+
+        return Ok(RunTxResponse::GenerateOnly(
+            handler.handle_generate_only(tx_body, fee),
+        ));
+    }
+
     let key =
         keyring::get_key_by_name(&from_key, keyring_backend.to_keyring_backend(&keyring_home))?;
 
     let message = handler.prepare_tx(inner, key.get_address())?;
+    let tx_body = new_tx_body(message, memo, timeout_height);
+
+    match sign_mode {
+        // an aux signer never pays the fee so the tx is completed by a fee payer
+        TxSignMode::DirectAux => Ok(RunTxResponse::Aux(handler.handle_aux_tx(
+            tx_body,
+            key,
+            node,
+            chain_id,
+            tip,
+            account_number,
+            sequence,
+        )?)),
+        // there's no simulation endpoint yet so the gas isn't estimated, the tx is only signed
+        _ if dry_run => {
+            let tx = handler.handle_generate_only(tx_body, fee);
+
+            Ok(RunTxResponse::DryRun(handler.handle_sign(
+                tx,
+                key,
+                node,
+                chain_id,
+                sign_mode,
+                account_number,
+                sequence,
+            )?))
+        }
+        _ => Ok(RunTxResponse::Broadcast(handler.handle_tx(
+            tx_body,
+            key,
+            node,
+            chain_id,
+            fee,
+            sign_mode,
+            account_number,
+            sequence,
+        )?)),
+    }
+}
+
+fn new_tx_body<M: SDKMessage>(message: M, memo: String, timeout_height: u64) -> TxBody<M> {
+    TxBody {
+        messages: vec![message],
+        memo,
+        timeout_height,
+        extension_options: vec![],              // TODO: remove hard coded
+        non_critical_extension_options: vec![], // TODO: remove hard coded
+    }
+}
+
+/// Returns the fee, or computes it from the gas prices when they're set instead
+fn fee_amount(
+    fee: Option<SendCoins>,
+    gas_prices: Option<DecCoins>,
+    gas_limit: u64,
+) -> Result<Option<SendCoins>> {
+    match (fee, gas_prices) {
+        (Some(_), Some(_)) => Err(anyhow::anyhow!("cannot provide both fees and gas prices")),
+        (fee, None) => Ok(fee),
+        (None, Some(gas_prices)) => fee_from_gas_prices(&gas_prices, gas_limit),
+    }
+}
+
+/// The fee is the gas limit times the gas price in each denomination, rounded up
+fn fee_from_gas_prices(gas_prices: &DecCoins, gas_limit: u64) -> Result<Option<SendCoins>> {
+    let gas_limit = Decimal256::from_ratio(gas_limit, 1u64);
+
+    let mut fee = Vec::new();
+    for price in gas_prices.iter() {
+        let amount = price
+            .amount
+            .checked_mul(gas_limit)
+            .map_err(|e| anyhow::anyhow!(e.to_string()))?
+            .to_uint_ceil();
+
+        // the amount is only zero when the gas limit is
+        if !amount.is_zero() {
+            fee.push(Coin {
+                denom: price.denom.clone(),
+                amount,
+            });
+        }
+    }
+
+    if fee.is_empty() {
+        return Ok(None);
+    }
+
+    Ok(Some(SendCoins::new(fee)?))
+}
+
+// TODO: we're assuming here that the app has an auth module which handles this query
+fn query_auth_params(node: &str) -> Result<Params> {
+    Ok(
+        execute_query::<QueryParamsResponse, RawQueryParamsResponse>(
+            "/cosmos.auth.v1beta1.Query/Params".into(),
+            QueryParamsRequest {}.encode_vec(),
+            node,
+            None,
+        )?
+        .params,
+    )
+}
+
+/// Rejects the memo before signing if the chain would reject it for being too long
+fn validate_memo(memo: &str, params: &Params) -> Result<()> {
+    let memo_length = u64::try_from(memo.len())?;
+    if memo_length > params.max_memo_characters {
+        return Err(anyhow::anyhow!(
+            "maximum number of characters is {} but received {} characters",
+            params.max_memo_characters,
+            memo_length
+        ));
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Clone, derive_builder::Builder)]
+pub struct SignCommand {
+    pub home: PathBuf,
+    pub node: url::Url,
+    /// Name of the signer key in the keyring
+    pub from_key: String,
+    pub chain_id: Id,
+    pub keyring_backend: KeyringBackend,
+    pub sign_mode: TxSignMode,
+    /// JSON encoded unsigned tx, e.g. generated by a tx command with `generate_only`
+    pub tx_file: PathBuf,
+    /// Sign without querying the node, the account number and sequence must be set
+    pub offline: bool,
+    /// Account number of the signer, queried from the node when it isn't set
+    pub account_number: Option<u64>,
+    /// Sequence of the signer, queried from the node when it isn't set
+    pub sequence: Option<u64>,
+    /// Name of a multisig key in the keyring which the signer is a member of. The tx must declare
+    /// the multisig signer, its signature is then combined with `run_multi_sign`
+    pub multisig: Option<String>,
+}
+
+/// Result of signing a tx: the signed tx or, when signing as a member of a multisig key, the
+/// member's signature
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum SignResponse<M: SDKMessage> {
+    Signed(Tx<M>),
+    MultisigMember(MultisigMemberSignature),
+}
+
+/// Signs a tx which was generated without being signed, the signed tx is broadcast with
+/// `run_broadcast`
+pub fn run_sign<H: TxHandler>(
+    SignCommand {
+        home,
+        node,
+        from_key,
+        chain_id,
+        keyring_backend,
+        sign_mode,
+        tx_file,
+        offline,
+        account_number,
+        sequence,
+        multisig,
+    }: SignCommand,
+    handler: &H,
+) -> anyhow::Result<SignResponse<H::Message>> {
+    // a multisig member signs with the sequence declared in the tx
+    if offline && multisig.is_some() && account_number.is_none() {
+        return Err(anyhow::anyhow!(
+            "the account number of the multisig must be set to sign offline"
+        ));
+    }
+
+    if offline && multisig.is_none() && (account_number.is_none() || sequence.is_none()) {
+        return Err(anyhow::anyhow!(
+            "the account number and sequence must be set to sign offline"
+        ));
+    }
+
+    // an aux signer signs the tx body before the fee is set, see `TxSignMode::DirectAux`
+    if sign_mode == TxSignMode::DirectAux {
+        return Err(anyhow::anyhow!(
+            "a generated tx can't be signed with {}",
+            TxSignMode::DirectAux
+        ));
+    }
+
+    let keyring_home = home.join(keyring_backend.get_sub_dir());
+
+    let key =
+        keyring::get_key_by_name(&from_key, keyring_backend.to_keyring_backend(&keyring_home))?;
+
+    let tx: Tx<H::Message> = serde_json::from_slice(&std::fs::read(&tx_file)?)?;
+
+    // the node can't be queried for the memo limit when signing offline
+    if !offline && !tx.get_memo().is_empty() {
+        validate_memo(tx.get_memo(), &query_auth_params(node.as_str())?)?;
+    }
+
+    if let Some(multisig) = multisig {
+        // the signer info declared for the multisig has direct mode infos
+        if sign_mode != TxSignMode::Direct {
+            return Err(anyhow::anyhow!(
+                "multisig members can only sign with {}",
+                TxSignMode::Direct
+            ));
+        }
+
+        let multisig = keyring::get_multisig_key_by_name(
+            &multisig,
+            keyring_backend.to_keyring_backend(&keyring_home),
+        )?;
+
+        let multisig_key = Some(PublicKey::Multisig(multisig.clone()));
+        if !tx
+            .auth_info
+            .signer_infos
+            .iter()
+            .any(|info| info.public_key == multisig_key)
+        {
+            return Err(anyhow::anyhow!(
+                "the multisig key is not a signer of the tx, its signers are declared when the tx is generated"
+            ));
+        }
+
+        let account_number = match account_number {
+            Some(account_number) => account_number,
+            None => get_account_latest(multisig.get_address(), node.as_str())?
+                .account
+                .get_account_number(),
+        };
+
+        let tx = TxRaw::decode(tx.encode_vec().as_slice())?;
+
+        return Ok(SignResponse::MultisigMember(sign_as_multisig_member(
+            &key,
+            &tx,
+            chain_id,
+            account_number,
+        )));
+    }
+
+    Ok(SignResponse::Signed(handler.handle_sign(
+        tx,
+        key,
+        node,
+        chain_id,
+        sign_mode,
+        account_number,
+        sequence,
+    )?))
+}
+
+/// How long broadcasting a tx waits for the node
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq, Display)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum BroadcastMode {
+    /// Wait for the tx to pass `CheckTx`
+    #[default]
+    #[strum(to_string = "sync")]
+    Sync,
+    /// Return right away
+    #[strum(to_string = "async")]
+    Async,
+    /// Wait for the tx to be committed in a block
+    #[strum(to_string = "commit")]
+    Commit,
+}
+
+/// Response of the node to broadcasting a tx, which depends on the `BroadcastMode`
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum BroadcastTxResponse {
+    Sync(tx_sync::Response),
+    Async(tx_async::Response),
+    Commit(Response),
+}
+
+#[derive(Debug, Clone, derive_builder::Builder)]
+pub struct BroadcastCommand {
+    pub node: url::Url,
+    /// JSON encoded signed tx
+    pub tx_file: PathBuf,
+    pub mode: BroadcastMode,
+}
+
+/// Broadcasts a tx which was signed with `run_sign`
+pub fn run_broadcast<M: SDKMessage + DeserializeOwned>(
+    BroadcastCommand {
+        node,
+        tx_file,
+        mode,
+    }: BroadcastCommand,
+) -> anyhow::Result<BroadcastTxResponse> {
+    let tx: Tx<M> = serde_json::from_slice(&std::fs::read(&tx_file)?)?;
+
+    if tx.signatures.is_empty() {
+        return Err(anyhow::anyhow!("tx isn't signed"));
+    }
 
-    handler.handle_tx(message, key, node, chain_id, fee)
+    let client = HttpClient::new(tendermint::rpc::Url::try_from(node)?)?;
+
+    broadcast_tx(client, tx.encode_vec(), mode)
+}
+
+#[derive(Debug, Clone, derive_builder::Builder)]
+pub struct AuxToFeeCommand {
+    pub home: PathBuf,
+    pub node: url::Url,
+    /// Name of the fee payer key in the keyring
+    pub from_key: String,
+    pub chain_id: Id,
+    pub fee: Option<SendCoins>,
+    /// Gas prices to compute the fee from, they can't be set together with `fee`
+    pub gas_prices: Option<DecCoins>,
+    pub gas_limit: u64,
+    /// Account whose fee allowance pays the fee
+    pub fee_granter: Option<AccAddress>,
+    pub keyring_backend: KeyringBackend,
+    /// JSON encoded data signed by the aux signer
+    pub aux_signer_data_file: PathBuf,
+}
+
+/// Pays the fee of a tx signed by an aux signer, then signs and broadcasts it
+pub fn run_aux_to_fee<H: TxHandler>(
+    AuxToFeeCommand {
+        home,
+        node,
+        from_key,
+        chain_id,
+        fee,
+        gas_prices,
+        gas_limit,
+        fee_granter,
+        keyring_backend,
+        aux_signer_data_file,
+    }: AuxToFeeCommand,
+    handler: &H,
+) -> anyhow::Result<Response> {
+    let fee = fee_amount(fee, gas_prices, gas_limit)?;
+
+    let keyring_home = home.join(keyring_backend.get_sub_dir());
+
+    let key =
+        keyring::get_key_by_name(&from_key, keyring_backend.to_keyring_backend(&keyring_home))?;
+
+    let aux_signer_data: AuxSignerData =
+        serde_json::from_slice(&std::fs::read(&aux_signer_data_file)?)?;
+
+    handler.handle_aux_to_fee(
+        aux_signer_data,
+        key,
+        node,
+        chain_id,
+        fee,
+        gas_limit,
+        fee_granter,
+    )
+}
+
+#[derive(Debug, Clone, derive_builder::Builder)]
+pub struct MultiSignCommand {
+    pub home: PathBuf,
+    pub keyring_backend: KeyringBackend,
+    /// JSON encoded tx which declares the multisig signer, e.g. generated with `multisig_signers`
+    pub tx_file: PathBuf,
+    /// Name of the multisig key in the keyring
+    pub multisig: String,
+    /// JSON encoded signatures of the multisig members
+    pub signature_files: Vec<PathBuf>,
+}
+
+/// Combines the member signatures into the multisig signature of the tx, the signed tx is
+/// broadcast with `run_broadcast`
+pub fn run_multi_sign<M: SDKMessage + DeserializeOwned>(
+    MultiSignCommand {
+        home,
+        keyring_backend,
+        tx_file,
+        multisig,
+        signature_files,
+    }: MultiSignCommand,
+) -> anyhow::Result<Tx<M>> {
+    let keyring_home = home.join(keyring_backend.get_sub_dir());
+    let multisig = keyring::get_multisig_key_by_name(
+        &multisig,
+        keyring_backend.to_keyring_backend(&keyring_home),
+    )?;
+
+    let tx: Tx<M> = serde_json::from_slice(&std::fs::read(&tx_file)?)?;
+    let tx = TxRaw::decode(tx.encode_vec().as_slice())?;
+
+    let signatures = signature_files
+        .iter()
+        .map(|file| -> anyhow::Result<MultisigMemberSignature> {
+            Ok(serde_json::from_slice(&std::fs::read(file)?)?)
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    let tx = combine_multisig_signatures(tx, &multisig, signatures)?;
+
+    Ok(Tx::decode_vec(&tx.encode_to_vec())?)
 }
 
 pub fn broadcast_tx_commit(client: HttpClient, raw_tx: TxRaw) -> Result<Response> {
@@ -50,3 +610,339 @@ pub fn broadcast_tx_commit(client: HttpClient, raw_tx: TxRaw) -> Result<Response
 
     Ok(res)
 }
+
+pub fn broadcast_tx(
+    client: HttpClient,
+    tx_bytes: Vec<u8>,
+    mode: BroadcastMode,
+) -> Result<BroadcastTxResponse> {
+    let res = match mode {
+        BroadcastMode::Sync => {
+            BroadcastTxResponse::Sync(runtime().block_on(client.broadcast_tx_sync(tx_bytes))?)
+        }
+        BroadcastMode::Async => {
+            BroadcastTxResponse::Async(runtime().block_on(client.broadcast_tx_async(tx_bytes))?)
+        }
+        BroadcastMode::Commit => {
+            BroadcastTxResponse::Commit(runtime().block_on(client.broadcast_tx_commit(tx_bytes))?)
+        }
+    };
+
+    Ok(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use proto_messages::cosmos::{
+        bank::v1beta1::MsgSend,
+        crypto::multisig::LegacyAminoPubKey,
+        ibc::tx::SignDoc,
+        tx::v1beta1::{
+            mode_info::{ModeInfo, SignMode},
+            public_key::SigningError,
+        },
+    };
+
+    use super::*;
+
+    struct BankTxHandler;
+
+    impl TxHandler for BankTxHandler {
+        type Message = MsgSend;
+        type TxCommands = ();
+
+        fn prepare_tx(&self, _command: (), from_address: AccAddress) -> Result<MsgSend> {
+            Ok(MsgSend {
+                from_address: from_address.clone(),
+                to_address: from_address,
+                amount: "10uatom".parse()?,
+            })
+        }
+    }
+
+    fn auth_params(max_memo_characters: u64) -> Params {
+        Params {
+            max_memo_characters,
+            tx_sig_limit: 7,
+            tx_size_cost_per_byte: 10,
+            sig_verify_cost_ed25519: 590,
+            sig_verify_cost_secp256k1: 1000,
+        }
+    }
+
+    #[test]
+    fn generated_tx_has_memo_and_timeout_height() {
+        let command = TxCommand {
+            home: std::env::temp_dir(),
+            node: "http://localhost:26657".parse().unwrap(),
+            // an address so the keyring isn't read
+            from_key: "cosmos1ulav3hsenupswqfkw2y3sup5kgtqwnvqa8eyhs".to_string(),
+            chain_id: Id::try_from("test-chain").unwrap(),
+            fee: None,
+            gas_prices: None,
+            gas_limit: DEFAULT_GAS_LIMIT,
+            memo: "a memo".to_string(),
+            timeout_height: 100,
+            keyring_backend: KeyringBackend::Test,
+            sign_mode: TxSignMode::Direct,
+            account_number: None,
+            sequence: None,
+            tip: None,
+            fee_payer: None,
+            fee_granter: None,
+            generate_only: true,
+            dry_run: false,
+            multisig_signers: vec![],
+            inner: (),
+        };
+
+        let RunTxResponse::GenerateOnly(tx) = run_tx(command, &BankTxHandler).unwrap() else {
+            panic!("the tx is only generated")
+        };
+
+        assert_eq!(tx.body.memo, "a memo");
+        assert_eq!(tx.body.timeout_height, 100);
+        assert_eq!(tx.auth_info.fee.gas_limit, DEFAULT_GAS_LIMIT);
+    }
+
+    #[test]
+    fn dry_run_signs_without_broadcasting() {
+        let home = std::env::temp_dir().join(format!("gears-dry-run-{}", std::process::id()));
+        let keyring_home = home.join(KeyringBackend::Test.get_sub_dir());
+        let mnemonic = "race draft rival universe maid cheese steel logic crowd fork comic easy truth drift tomorrow eye buddy head time cash swing swift midnight borrow";
+        let mnemonic = bip32::Mnemonic::new(mnemonic, bip32::Language::English).unwrap();
+        let hd_path = keyring::bip44_path(keyring::DEFAULT_COIN_TYPE, 0, 0).unwrap();
+        keyring::add_key(
+            "alice",
+            &mnemonic,
+            "",
+            &hd_path,
+            keyring::KeyType::Secp256k1,
+            keyring::Backend::Test(&keyring_home),
+        )
+        .unwrap();
+
+        // a node which records any connection made to it
+        let node = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        node.set_nonblocking(true).unwrap();
+
+        let command = TxCommand {
+            home: home.clone(),
+            node: format!("http://{}", node.local_addr().unwrap())
+                .parse()
+                .unwrap(),
+            from_key: "alice".to_string(),
+            chain_id: Id::try_from("test-chain").unwrap(),
+            fee: None,
+            gas_prices: None,
+            gas_limit: DEFAULT_GAS_LIMIT,
+            memo: String::new(),
+            timeout_height: 0,
+            keyring_backend: KeyringBackend::Test,
+            sign_mode: TxSignMode::Direct,
+            // set so the node isn't queried for the account
+            account_number: Some(9),
+            sequence: Some(3),
+            tip: None,
+            fee_payer: None,
+            fee_granter: None,
+            generate_only: false,
+            dry_run: true,
+            multisig_signers: vec![],
+            inner: (),
+        };
+
+        let res = run_tx(command, &BankTxHandler);
+        std::fs::remove_dir_all(&home).unwrap();
+
+        let RunTxResponse::DryRun(tx) = res.unwrap() else {
+            panic!("the tx is only signed")
+        };
+
+        assert_eq!(tx.signatures.len(), 1);
+        assert_eq!(tx.auth_info.signer_infos[0].sequence, 3);
+        assert_eq!(
+            node.accept().unwrap_err().kind(),
+            std::io::ErrorKind::WouldBlock
+        );
+    }
+
+    #[test]
+    fn multisig_tx_is_generated_signed_by_members_and_combined() {
+        let home = std::env::temp_dir().join(format!("gears-multisig-{}", std::process::id()));
+        let keyring_home = home.join(KeyringBackend::Test.get_sub_dir());
+        let mnemonic = "race draft rival universe maid cheese steel logic crowd fork comic easy truth drift tomorrow eye buddy head time cash swing swift midnight borrow";
+        let mnemonic = bip32::Mnemonic::new(mnemonic, bip32::Language::English).unwrap();
+
+        let members = (0..5)
+            .map(|i| {
+                let hd_path = keyring::bip44_path(keyring::DEFAULT_COIN_TYPE, 0, i).unwrap();
+                keyring::add_key(
+                    format!("member{i}"),
+                    &mnemonic,
+                    "",
+                    &hd_path,
+                    keyring::KeyType::Secp256k1,
+                    keyring::Backend::Test(&keyring_home),
+                )
+                .unwrap()
+            })
+            .collect::<Vec<_>>();
+        let multisig = LegacyAminoPubKey::new(
+            3,
+            members
+                .iter()
+                .map(|key| key.get_gears_public_key())
+                .collect(),
+        )
+        .unwrap();
+        keyring::add_multisig_key("treasury", &multisig, keyring::Backend::Test(&keyring_home))
+            .unwrap();
+
+        let node: url::Url = "http://localhost:26657".parse().unwrap();
+        let chain_id = Id::try_from("test-chain").unwrap();
+
+        // the node is never queried as the sequence and account number are set
+        let generated = run_tx(
+            TxCommand {
+                home: home.clone(),
+                node: node.clone(),
+                from_key: "treasury".to_string(),
+                chain_id: chain_id.clone(),
+                fee: None,
+                gas_prices: None,
+                gas_limit: DEFAULT_GAS_LIMIT,
+                memo: String::new(),
+                timeout_height: 0,
+                keyring_backend: KeyringBackend::Test,
+                sign_mode: TxSignMode::Direct,
+                account_number: None,
+                sequence: Some(4),
+                tip: None,
+                fee_payer: None,
+                fee_granter: None,
+                generate_only: true,
+                dry_run: false,
+                multisig_signers: [4, 0, 2]
+                    .into_iter()
+                    .map(|i| members[i].get_address())
+                    .collect(),
+                inner: (),
+            },
+            &BankTxHandler,
+        )
+        .unwrap();
+        let tx_file = home.join("unsigned.json");
+        std::fs::write(&tx_file, serde_json::to_vec(&generated).unwrap()).unwrap();
+
+        let signature_files = [0, 2, 4]
+            .into_iter()
+            .map(|i| {
+                let signature = run_sign(
+                    SignCommand {
+                        home: home.clone(),
+                        node: node.clone(),
+                        from_key: format!("member{i}"),
+                        chain_id: chain_id.clone(),
+                        keyring_backend: KeyringBackend::Test,
+                        sign_mode: TxSignMode::Direct,
+                        tx_file: tx_file.clone(),
+                        offline: true,
+                        account_number: Some(7),
+                        sequence: None,
+                        multisig: Some("treasury".to_string()),
+                    },
+                    &BankTxHandler,
+                )
+                .unwrap();
+
+                let signature_file = home.join(format!("member{i}.json"));
+                std::fs::write(&signature_file, serde_json::to_vec(&signature).unwrap()).unwrap();
+                signature_file
+            })
+            .collect();
+
+        let signed = run_multi_sign::<MsgSend>(MultiSignCommand {
+            home: home.clone(),
+            keyring_backend: KeyringBackend::Test,
+            tx_file,
+            multisig: "treasury".to_string(),
+            signature_files,
+        });
+        std::fs::remove_dir_all(&home).unwrap();
+
+        // the broadcast command reads the signed tx from its JSON file
+        let signed: Tx<MsgSend> =
+            serde_json::from_slice(&serde_json::to_vec(&signed.unwrap()).unwrap()).unwrap();
+        let tx = Tx::<MsgSend>::decode_vec(&signed.encode_vec()).unwrap();
+
+        assert_eq!(tx.get_signers(), [&multisig.get_address()]);
+        assert_eq!(tx.auth_info.signer_infos[0].sequence, 4);
+
+        let raw_tx = TxRaw::decode(signed.encode_vec().as_slice()).unwrap();
+        let sign_doc = SignDoc {
+            body_bytes: raw_tx.body_bytes,
+            auth_info_bytes: raw_tx.auth_info_bytes,
+            chain_id: chain_id.into(),
+            account_number: 7,
+        }
+        .encode_to_vec();
+        let get_sign_bytes =
+            |_: &SignMode| -> Result<Vec<u8>, SigningError> { Ok(sign_doc.clone()) };
+        let ModeInfo::Multi(multi) = &tx.auth_info.signer_infos[0].mode_info else {
+            panic!("multisig signer info has multi mode info")
+        };
+        multisig
+            .verify_multisignature(multi, &tx.signatures[0], &get_sign_bytes)
+            .unwrap();
+    }
+
+    #[test]
+    fn validate_memo_checks_memo_length() {
+        validate_memo("memo", &auth_params(4)).unwrap();
+
+        let err = validate_memo("a longer memo", &auth_params(4)).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "maximum number of characters is 4 but received 13 characters"
+        );
+    }
+
+    #[test]
+    fn fee_from_gas_prices_rounds_up() {
+        let gas_prices = DecCoins::from_str("0.025uatom,0.1stake").unwrap();
+
+        // 0.025 * 200_001 = 5000.025 is rounded up
+        let fee = fee_from_gas_prices(&gas_prices, 200_001).unwrap();
+
+        assert_eq!(
+            fee,
+            Some(SendCoins::from_str("5001uatom,20001stake").unwrap())
+        );
+    }
+
+    #[test]
+    fn fee_amount_uses_fee_or_gas_prices() {
+        let fee = SendCoins::from_str("10uatom").unwrap();
+        let gas_prices = DecCoins::from_str("0.025uatom").unwrap();
+
+        assert_eq!(
+            fee_amount(Some(fee.clone()), None, DEFAULT_GAS_LIMIT).unwrap(),
+            Some(fee.clone())
+        );
+        assert_eq!(
+            fee_amount(None, Some(gas_prices.clone()), DEFAULT_GAS_LIMIT).unwrap(),
+            Some(SendCoins::from_str("5000uatom").unwrap())
+        );
+        assert!(fee_amount(Some(fee), Some(gas_prices), DEFAULT_GAS_LIMIT).is_err());
+    }
+
+    #[test]
+    fn fee_from_gas_prices_without_gas() {
+        let gas_prices = DecCoins::from_str("0.025uatom").unwrap();
+
+        assert_eq!(fee_from_gas_prices(&gas_prices, 0).unwrap(), None);
+    }
+}
@@ -2,21 +2,86 @@ use std::error::Error;
 use std::fs::{self, File};
 use std::io::Write;
 use std::net::{Ipv4Addr, SocketAddr};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
+use proto_messages::cosmos::base::v1beta1::DecCoins;
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, DisplayFromStr};
 use tendermint::rpc::Url;
 
+use crate::utils::get_config_file_from_home_dir;
+
 pub const DEFAULT_REST_LISTEN_ADDR: SocketAddr =
     SocketAddr::new(std::net::IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 1317);
 pub const DEFAULT_ADDRESS: SocketAddr =
     SocketAddr::new(std::net::IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 26658);
 pub const DEFAULT_TENDERMINT_RPC_ADDRESS: &str = "http://localhost:26657";
 
+/// The database used to persist application state
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, Default, PartialEq, Eq, strum::Display)]
+#[serde(rename_all = "lowercase")]
+pub enum DBBackend {
+    #[default]
+    #[strum(to_string = "rocksdb")]
+    RocksDB,
+    #[strum(to_string = "sled")]
+    Sled,
+}
+
+/// Compression of the RocksDB files
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum RocksDBCompression {
+    None,
+    Snappy,
+    #[default]
+    Lz4,
+    Zstd,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+#[serde(default)]
+pub struct RocksDBConfig {
+    pub cache_size_mb: usize,
+    pub compression: RocksDBCompression,
+    pub max_open_files: i32,
+    pub write_buffer_size_mb: usize,
+}
+
+impl Default for RocksDBConfig {
+    fn default() -> Self {
+        Self {
+            cache_size_mb: 32,
+            compression: RocksDBCompression::default(),
+            max_open_files: -1,
+            write_buffer_size_mb: 64,
+        }
+    }
+}
+
+#[cfg(feature = "rocksdb")]
+impl From<RocksDBConfig> for database::RocksDBOptions {
+    fn from(config: RocksDBConfig) -> Self {
+        Self {
+            cache_size: config.cache_size_mb << 20,
+            compression: match config.compression {
+                RocksDBCompression::None => database::RocksDBCompression::None,
+                RocksDBCompression::Snappy => database::RocksDBCompression::Snappy,
+                RocksDBCompression::Lz4 => database::RocksDBCompression::Lz4,
+                RocksDBCompression::Zstd => database::RocksDBCompression::Zstd,
+            },
+            max_open_files: config.max_open_files,
+            write_buffer_size: config.write_buffer_size_mb << 20,
+        }
+    }
+}
+
 pub trait ApplicationConfig: Serialize + DeserializeOwned + Default + Clone {}
 impl<T: DeserializeOwned + Serialize + Default + Clone> ApplicationConfig for T {}
 
+#[serde_as]
 #[derive(Deserialize, Serialize, Clone)]
 #[serde(deny_unknown_fields)]
 #[serde(default)]
@@ -24,6 +89,13 @@ pub struct Config<AC: Default + Clone> {
     pub tendermint_rpc_address: Url,
     pub rest_listen_addr: SocketAddr,
     pub address: SocketAddr,
+    pub db_backend: DBBackend,
+    /// Minimum gas prices a tx must pay, in at least one denomination, to enter the mempool
+    #[serde_as(as = "DisplayFromStr")]
+    pub minimum_gas_prices: DecCoins,
+    /// Type URLs of messages which are exempt from the minimum gas prices
+    pub bypass_min_fee_msg_types: Vec<String>,
+    pub rocksdb: RocksDBConfig,
     pub app_config: AC,
 }
 
@@ -33,6 +105,13 @@ impl<AC: ApplicationConfig> Config<AC> {
         Ok(toml::from_str(&s)?)
     }
 
+    /// Reads the config file in the home directory
+    pub fn from_home_dir(home: &Path) -> Result<Config<AC>, Box<dyn Error>> {
+        let mut cfg_file_path = home.to_path_buf();
+        get_config_file_from_home_dir(&mut cfg_file_path);
+        Self::from_file(cfg_file_path)
+    }
+
     pub fn write_default(mut file: File) -> Result<(), Box<dyn Error>> {
         let mut handlebars = handlebars::Handlebars::new();
         handlebars
@@ -62,6 +141,10 @@ impl<AC: ApplicationConfig> Default for Config<AC> {
                 .expect("const should be valid"),
             rest_listen_addr: DEFAULT_REST_LISTEN_ADDR,
             address: DEFAULT_ADDRESS,
+            db_backend: DBBackend::default(),
+            minimum_gas_prices: DecCoins::default(),
+            bypass_min_fee_msg_types: vec![],
+            rocksdb: RocksDBConfig::default(),
             app_config: AC::default(),
         }
     }
@@ -82,4 +165,36 @@ rest_listen_addr = "{{rest_listen_addr}}"
 
 # Tendermint node RPC proxy address
 tendermint_rpc_address = "{{tendermint_rpc_address}}"
+
+# Database backend used to store application state, one of "rocksdb" or "sled".
+# The backend must be enabled by the cargo feature of the same name.
+db_backend = "{{db_backend}}"
+
+# The minimum gas prices a validator is willing to accept for processing a
+# transaction, e.g. "0.025uatom,0.0001stake". A transaction's fee must meet the
+# minimum of any denomination specified here. The prices are only checked when a
+# transaction enters the mempool (CheckTx).
+minimum_gas_prices = "{{minimum_gas_prices}}"
+
+# Transactions which only contain messages of these type URLs are exempt from the
+# minimum gas prices, e.g. ["/ibc.core.channel.v1.MsgRecvPacket"]
+bypass_min_fee_msg_types = [{{#each bypass_min_fee_msg_types}}"{{this}}"{{#unless @last}}, {{/unless}}{{/each}}]
+
+#######################################################################
+###                      RocksDB Options                            ###
+#######################################################################
+
+[rocksdb]
+
+# Size of the block cache in MiB
+cache_size_mb = {{rocksdb.cache_size_mb}}
+
+# Compression of the database files, one of "none", "snappy", "lz4" or "zstd"
+compression = "{{rocksdb.compression}}"
+
+# Maximum number of files kept open, -1 means there's no limit
+max_open_files = {{rocksdb.max_open_files}}
+
+# Size of the write buffer (memtable) of each column family in MiB
+write_buffer_size_mb = {{rocksdb.write_buffer_size_mb}}
 "#;
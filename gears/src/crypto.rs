@@ -1,20 +1,26 @@
 use keyring::key_pair::KeyPair;
 use prost::Message;
 use proto_messages::cosmos::{
+    crypto::multisig::LegacyAminoPubKey,
     ibc::{
         protobuf::Protobuf,
         tx::{SignDoc, TxRaw},
     },
     tx::v1beta1::{
         auth_info::AuthInfo,
+        aux_signer_data::{AuxSignerData, SignDocDirectAux},
         fee::Fee,
         message::Message as SDKMessage,
-        mode_info::{ModeInfo, SignMode},
+        mode_info::{CompactBitArray, ModeInfo, Multi, SignMode},
+        public_key::PublicKey,
         signer::SignerInfo,
         tip::Tip,
+        tx::legacy_amino::StdSignDoc,
         tx_body::TxBody,
     },
 };
+use serde::{Deserialize, Serialize};
+use strum::Display;
 use tendermint::informal::chain::Id;
 
 /// Contains info required to sign a Tx
@@ -24,24 +30,56 @@ pub struct SigningInfo {
     pub account_number: u64,
 }
 
+/// Sign modes which can be used to sign a Tx
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq, Display)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum TxSignMode {
+    #[default]
+    #[strum(to_string = "direct")]
+    Direct,
+    #[strum(to_string = "direct-aux")]
+    DirectAux,
+    #[strum(to_string = "amino-json")]
+    AminoJson,
+}
+
+impl From<TxSignMode> for SignMode {
+    fn from(mode: TxSignMode) -> Self {
+        match mode {
+            TxSignMode::Direct => SignMode::Direct,
+            TxSignMode::DirectAux => SignMode::DirectAux,
+            TxSignMode::AminoJson => SignMode::LegacyAminoJson,
+        }
+    }
+}
+
+/// Creates a signed Tx. The aux signers have already signed with `SIGN_MODE_DIRECT_AUX`, their
+/// signer infos and signatures are placed before those of the signing infos.
 pub fn create_signed_transaction<M: SDKMessage>(
     signing_infos: Vec<SigningInfo>,
+    aux_signers: Vec<AuxSignerData>,
     tx_body: TxBody<M>,
     fee: Fee,
     tip: Option<Tip>,
     chain_id: Id,
-) -> TxRaw {
-    let signer_infos: Vec<SignerInfo> = signing_infos
-        .iter()
-        .map(|s| {
+    sign_mode: TxSignMode,
+) -> Result<TxRaw, proto_messages::Error> {
+    let aux_signer_infos = aux_signers.iter().map(|aux| SignerInfo {
+        public_key: aux.sign_doc.public_key.clone(),
+        mode_info: ModeInfo::Single(SignMode::DirectAux),
+        sequence: aux.sign_doc.sequence,
+    });
+
+    let signer_infos: Vec<SignerInfo> = aux_signer_infos
+        .chain(signing_infos.iter().map(|s| {
             let public_key = Some(s.key.get_gears_public_key());
 
             SignerInfo {
                 public_key,
-                mode_info: ModeInfo::Single(SignMode::Direct),
+                mode_info: ModeInfo::Single(sign_mode.into()),
                 sequence: s.sequence,
             }
-        })
+        }))
         .collect();
 
     let auth_info = AuthInfo {
@@ -53,25 +91,398 @@ pub fn create_signed_transaction<M: SDKMessage>(
     let body_bytes = tx_body.encode_vec();
     let auth_info_bytes = auth_info.encode_vec();
 
-    let mut sign_doc = SignDoc {
-        body_bytes: body_bytes.clone(),
-        auth_info_bytes: auth_info_bytes.clone(),
-        chain_id: chain_id.into(),
-        account_number: 0, // This gets overwritten
-    };
+    let signatures: Vec<Vec<u8>> = match sign_mode {
+        TxSignMode::Direct => {
+            let mut sign_doc = SignDoc {
+                body_bytes: body_bytes.clone(),
+                auth_info_bytes: auth_info_bytes.clone(),
+                chain_id: chain_id.into(),
+                account_number: 0, // This gets overwritten
+            };
 
-    let signatures: Vec<Vec<u8>> = signing_infos
-        .iter()
-        .map(|s| {
-            sign_doc.account_number = s.account_number;
+            signing_infos
+                .iter()
+                .map(|s| {
+                    sign_doc.account_number = s.account_number;
+
+                    s.key.sign(&sign_doc.encode_to_vec())
+                })
+                .collect()
+        }
+        TxSignMode::DirectAux => signing_infos
+            .iter()
+            .map(|s| {
+                let sign_doc = SignDocDirectAux {
+                    body_bytes: body_bytes.clone(),
+                    public_key: Some(s.key.get_gears_public_key()),
+                    chain_id: chain_id.to_string(),
+                    account_number: s.account_number,
+                    sequence: s.sequence,
+                    tip: auth_info.tip.clone(),
+                };
+
+                s.key.sign(&sign_doc.encode_vec())
+            })
+            .collect(),
+        TxSignMode::AminoJson => signing_infos
+            .iter()
+            .map(|s| {
+                let sign_doc = StdSignDoc::new(
+                    chain_id.to_string(),
+                    s.account_number,
+                    s.sequence,
+                    &tx_body,
+                    &auth_info.fee,
+                    auth_info.tip.as_ref(),
+                )?;
+
+                Ok(s.key.sign(&sign_doc.to_sign_bytes()))
+            })
+            .collect::<Result<_, proto_messages::Error>>()?,
+    };
 
-            s.key.sign(&sign_doc.encode_to_vec())
-        })
+    let signatures = aux_signers
+        .into_iter()
+        .map(|aux| aux.sig)
+        .chain(signatures)
         .collect();
 
-    TxRaw {
+    Ok(TxRaw {
         body_bytes,
         auth_info_bytes,
         signatures,
+    })
+}
+
+/// Signs the body and tip of a Tx with `SIGN_MODE_DIRECT_AUX`. The returned data is sent to a fee
+/// payer who adds the fee and broadcasts the Tx.
+pub fn create_aux_signer_data<M: SDKMessage>(
+    signing_info: SigningInfo,
+    tx_body: TxBody<M>,
+    tip: Option<Tip>,
+    chain_id: Id,
+) -> AuxSignerData {
+    let sign_doc = SignDocDirectAux {
+        body_bytes: tx_body.encode_vec(),
+        public_key: Some(signing_info.key.get_gears_public_key()),
+        chain_id: chain_id.into(),
+        account_number: signing_info.account_number,
+        sequence: signing_info.sequence,
+        tip,
+    };
+
+    AuxSignerData {
+        address: signing_info.key.get_address(),
+        sig: signing_info.key.sign(&sign_doc.encode_vec()),
+        sign_doc,
+        mode: SignMode::DirectAux,
+    }
+}
+
+/// A signature made by one member of a multisig key
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MultisigMemberSignature {
+    pub public_key: PublicKey,
+    pub mode_info: ModeInfo,
+    #[serde(with = "proto_messages::utils::Base64Standard")]
+    pub signature: Vec<u8>,
+}
+
+/// Returns the signer info of a multisig account which will be signed by the given members.
+///
+/// The signing members are part of the signed auth info so they must be agreed before anyone
+/// signs.
+pub fn multisig_signer_info(
+    multisig: &LegacyAminoPubKey,
+    signers: &[PublicKey],
+    sequence: u64,
+) -> anyhow::Result<SignerInfo> {
+    let mut bitarray = CompactBitArray::new(multisig.public_keys().len());
+    for signer in signers {
+        let index = multisig
+            .public_keys()
+            .iter()
+            .position(|key| key == signer)
+            .ok_or_else(|| {
+                anyhow::anyhow!("key {} is not part of the multisig", signer.get_address())
+            })?;
+        bitarray.set_index(index, true);
+    }
+
+    Ok(SignerInfo {
+        public_key: Some(PublicKey::Multisig(multisig.to_owned())),
+        mode_info: ModeInfo::Multi(Multi {
+            mode_infos: vec![ModeInfo::Single(SignMode::Direct); bitarray.count()],
+            bitarray: Some(bitarray),
+        }),
+        sequence,
+    })
+}
+
+/// Signs a tx on behalf of a multisig account, the account number is the multisig account's
+pub fn sign_as_multisig_member(
+    key: &KeyPair,
+    tx: &TxRaw,
+    chain_id: Id,
+    account_number: u64,
+) -> MultisigMemberSignature {
+    let sign_doc = SignDoc {
+        body_bytes: tx.body_bytes.clone(),
+        auth_info_bytes: tx.auth_info_bytes.clone(),
+        chain_id: chain_id.into(),
+        account_number,
+    };
+
+    MultisigMemberSignature {
+        public_key: key.get_gears_public_key(),
+        mode_info: ModeInfo::Single(SignMode::Direct),
+        signature: key.sign(&sign_doc.encode_to_vec()),
+    }
+}
+
+/// Combines member signatures into the signature of the multisig signer of the tx. The
+/// signing members must match those declared in the tx's signer info.
+pub fn combine_multisig_signatures(
+    mut tx: TxRaw,
+    multisig: &LegacyAminoPubKey,
+    signatures: Vec<MultisigMemberSignature>,
+) -> anyhow::Result<TxRaw> {
+    let auth_info = AuthInfo::decode_vec(&tx.auth_info_bytes)?;
+
+    let multisig_key = Some(PublicKey::Multisig(multisig.to_owned()));
+    let index = auth_info
+        .signer_infos
+        .iter()
+        .position(|info| info.public_key == multisig_key)
+        .ok_or_else(|| anyhow::anyhow!("the multisig key is not a signer of the tx"))?;
+
+    let (multi, signature) = multisig.combine_signatures(
+        signatures
+            .into_iter()
+            .map(|s| (s.public_key, s.mode_info, s.signature))
+            .collect(),
+    )?;
+
+    if auth_info.signer_infos[index].mode_info != ModeInfo::Multi(multi) {
+        anyhow::bail!("the signing members don't match the members declared in the tx");
+    }
+
+    tx.signatures.resize(auth_info.signer_infos.len(), vec![]);
+    tx.signatures[index] = signature;
+
+    Ok(tx)
+}
+
+#[cfg(test)]
+mod tests {
+    use bip32::Mnemonic;
+    use keyring::key_pair::secp256k1_key_pair::Secp256k1KeyPair;
+    use proto_messages::cosmos::{bank::v1beta1::MsgSend, tx::v1beta1::public_key::SigningError};
+
+    use super::*;
+
+    fn key_pair(i: u32) -> KeyPair {
+        let mnemonic = "race draft rival universe maid cheese steel logic crowd fork comic easy truth drift tomorrow eye buddy head time cash swing swift midnight borrow";
+        let mnemonic = Mnemonic::new(mnemonic, bip32::Language::English).unwrap();
+        let hd_path = keyring::bip44_path(keyring::DEFAULT_COIN_TYPE, 0, i).unwrap();
+
+        KeyPair::Secp256k1(Secp256k1KeyPair::from_mnemonic_with_path(
+            &mnemonic, "", &hd_path,
+        ))
+    }
+
+    #[test]
+    fn combine_multisig_signatures_works() {
+        let members: Vec<KeyPair> = (0..3).map(key_pair).collect();
+        let multisig = LegacyAminoPubKey::new(
+            2,
+            members.iter().map(KeyPair::get_gears_public_key).collect(),
+        )
+        .unwrap();
+        let chain_id = Id::try_from("test-chain").unwrap();
+
+        let signers = [
+            members[2].get_gears_public_key(),
+            members[0].get_gears_public_key(),
+        ];
+        let auth_info = AuthInfo {
+            signer_infos: vec![multisig_signer_info(&multisig, &signers, 4).unwrap()],
+            fee: Fee {
+                amount: None,
+                gas_limit: 200000,
+                payer: None,
+                granter: "".into(),
+            },
+            tip: None,
+        };
+        let unsigned = TxRaw {
+            body_bytes: vec![1, 2, 3],
+            auth_info_bytes: auth_info.encode_vec(),
+            signatures: vec![],
+        };
+
+        let signatures: Vec<MultisigMemberSignature> = [&members[0], &members[2]]
+            .into_iter()
+            .map(|key| sign_as_multisig_member(key, &unsigned, chain_id.clone(), 7))
+            .collect();
+
+        // a member which wasn't declared can't sign
+        let undeclared = sign_as_multisig_member(&members[1], &unsigned, chain_id.clone(), 7);
+        combine_multisig_signatures(unsigned.clone(), &multisig, vec![undeclared]).unwrap_err();
+
+        let signed = combine_multisig_signatures(unsigned.clone(), &multisig, signatures).unwrap();
+        assert_eq!(signed.signatures.len(), 1);
+
+        let sign_doc = SignDoc {
+            body_bytes: unsigned.body_bytes,
+            auth_info_bytes: unsigned.auth_info_bytes,
+            chain_id: chain_id.into(),
+            account_number: 7,
+        };
+        let get_sign_bytes =
+            |_: &SignMode| -> Result<Vec<u8>, SigningError> { Ok(sign_doc.encode_to_vec()) };
+        let ModeInfo::Multi(multi) = &auth_info.signer_infos[0].mode_info else {
+            panic!("multisig signer info has multi mode info")
+        };
+        multisig
+            .verify_multisignature(multi, &signed.signatures[0], &get_sign_bytes)
+            .unwrap();
+    }
+
+    #[test]
+    fn create_signed_transaction_amino_json_works() {
+        let key = key_pair(0);
+        let tx_body = TxBody {
+            messages: vec![MsgSend {
+                from_address: key.get_address(),
+                to_address: key_pair(1).get_address(),
+                amount: "10uatom".parse().unwrap(),
+            }],
+            memo: "memo".into(),
+            timeout_height: 0,
+            extension_options: vec![],
+            non_critical_extension_options: vec![],
+        };
+        let fee = Fee {
+            amount: None,
+            gas_limit: 200000,
+            payer: None,
+            granter: "".into(),
+        };
+        let signing_info = SigningInfo {
+            key: key.clone(),
+            sequence: 3,
+            account_number: 9,
+        };
+
+        let signed = create_signed_transaction(
+            vec![signing_info],
+            vec![],
+            tx_body.clone(),
+            fee.clone(),
+            None,
+            Id::try_from("test-chain").unwrap(),
+            TxSignMode::AminoJson,
+        )
+        .unwrap();
+
+        let auth_info = AuthInfo::decode_vec(&signed.auth_info_bytes).unwrap();
+        assert_eq!(
+            auth_info.signer_infos[0].mode_info,
+            ModeInfo::Single(SignMode::LegacyAminoJson)
+        );
+
+        let sign_bytes = StdSignDoc::new("test-chain".into(), 9, 3, &tx_body, &fee, None)
+            .unwrap()
+            .to_sign_bytes();
+        key.get_gears_public_key()
+            .verify_signature(&sign_bytes, &signed.signatures[0])
+            .unwrap();
+    }
+
+    #[test]
+    fn aux_signer_data_works_in_signed_transaction() {
+        let tipper = key_pair(0);
+        let fee_payer = key_pair(1);
+        let chain_id = Id::try_from("test-chain").unwrap();
+        let tx_body = TxBody {
+            messages: vec![MsgSend {
+                from_address: tipper.get_address(),
+                to_address: key_pair(2).get_address(),
+                amount: "10uatom".parse().unwrap(),
+            }],
+            memo: String::new(),
+            timeout_height: 0,
+            extension_options: vec![],
+            non_critical_extension_options: vec![],
+        };
+        let tip = Tip {
+            amount: Some("1uatom".parse().unwrap()),
+            tipper: tipper.get_address(),
+        };
+
+        let aux_signer_data = create_aux_signer_data(
+            SigningInfo {
+                key: tipper.clone(),
+                sequence: 2,
+                account_number: 5,
+            },
+            tx_body.clone(),
+            Some(tip.clone()),
+            chain_id.clone(),
+        );
+        aux_signer_data.validate_basic().unwrap();
+
+        let signed = create_signed_transaction(
+            vec![SigningInfo {
+                key: fee_payer.clone(),
+                sequence: 0,
+                account_number: 6,
+            }],
+            vec![aux_signer_data],
+            tx_body,
+            Fee {
+                amount: None,
+                gas_limit: 200000,
+                payer: Some(fee_payer.get_address()),
+                granter: "".into(),
+            },
+            Some(tip),
+            chain_id.clone(),
+            TxSignMode::Direct,
+        )
+        .unwrap();
+
+        let auth_info = AuthInfo::decode_vec(&signed.auth_info_bytes).unwrap();
+        assert_eq!(
+            auth_info.signer_infos[0].mode_info,
+            ModeInfo::Single(SignMode::DirectAux)
+        );
+        assert_eq!(signed.signatures.len(), 2);
+
+        // the aux signature is verified against the sign doc rebuilt from the tx
+        let sign_doc = SignDocDirectAux {
+            body_bytes: signed.body_bytes.clone(),
+            public_key: Some(tipper.get_gears_public_key()),
+            chain_id: chain_id.to_string(),
+            account_number: 5,
+            sequence: 2,
+            tip: auth_info.tip.clone(),
+        };
+        tipper
+            .get_gears_public_key()
+            .verify_signature(&sign_doc.encode_vec(), &signed.signatures[0])
+            .unwrap();
+
+        let sign_doc = SignDoc {
+            body_bytes: signed.body_bytes,
+            auth_info_bytes: signed.auth_info_bytes,
+            chain_id: chain_id.into(),
+            account_number: 6,
+        };
+        fee_payer
+            .get_gears_public_key()
+            .verify_signature(&sign_doc.encode_to_vec(), &signed.signatures[1])
+            .unwrap();
     }
 }
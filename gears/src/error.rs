@@ -1,5 +1,7 @@
 use std::fmt::{Display, Formatter, Result};
 
+use crate::types::gas::gas_meter::GasErrors;
+
 #[derive(Debug, PartialEq)]
 pub enum AppError {
     Bech32(bech32::Error),
@@ -16,6 +18,9 @@ pub enum AppError {
     IBC(String),
     Genesis(String),
     Query(String),
+    OutOfGas(String),
+    GasOverflow(String),
+    InsufficientFee(String),
 }
 
 impl Display for AppError {
@@ -39,6 +44,11 @@ impl Display for AppError {
             AppError::IBC(msg) => write!(f, "ibc routing error: {}", msg),
             AppError::Genesis(msg) => write!(f, "{}", msg),
             AppError::Query(msg) => write!(f, "Error executing query: {msg}"),
+            AppError::OutOfGas(descriptor) => write!(f, "out of gas in location: {}", descriptor),
+            AppError::GasOverflow(descriptor) => {
+                write!(f, "gas overflow in location: {}", descriptor)
+            }
+            AppError::InsufficientFee(msg) => write!(f, "insufficient fees; {}", msg),
         }
     }
 }
@@ -57,12 +67,29 @@ impl From<bech32::Error> for AppError {
     }
 }
 
+impl From<GasErrors> for AppError {
+    fn from(err: GasErrors) -> AppError {
+        match err {
+            GasErrors::ErrorOutOfGas(descriptor) => AppError::OutOfGas(descriptor),
+            GasErrors::ErrorGasOverflow(descriptor) => AppError::GasOverflow(descriptor),
+        }
+    }
+}
+
 impl From<trees::Error> for AppError {
     fn from(err: trees::Error) -> AppError {
         AppError::Tree(err)
     }
 }
 
+impl From<store_crate::error::Error> for AppError {
+    fn from(err: store_crate::error::Error) -> AppError {
+        match err {
+            store_crate::error::Error::Database(err) => AppError::Tree(err),
+        }
+    }
+}
+
 impl From<proto_messages::Error> for AppError {
     fn from(err: proto_messages::Error) -> AppError {
         AppError::InvalidRequest(err.to_string())
@@ -81,6 +108,8 @@ pub enum SearchError {
     NotFound,
     #[error("Decode error: {0}")]
     DecodeError(String),
+    #[error("{0}")]
+    Store(#[from] store_crate::error::Error),
 }
 
 impl From<prost::DecodeError> for SearchError {
@@ -1,5 +1,6 @@
 use crate::types::context::init_context::InitContext;
 use crate::types::context::tx_context::TxContext;
+use crate::types::gas::gas_meter::GasMeter;
 use database::{Database, PrefixDB};
 use proto_messages::cosmos::tx::v1beta1::tx_metadata::Metadata;
 use store_crate::{KVStore, StoreKey};
@@ -11,6 +12,8 @@ pub trait ContextTrait<T, SK> {
     fn push_event(&mut self, event: Event);
     fn append_events(&mut self, events: Vec<Event>);
     fn metadata_get(&self) -> Metadata;
+    fn gas_meter(&self) -> &dyn GasMeter;
+    fn gas_meter_mut(&mut self) -> &mut dyn GasMeter;
 
     fn get_kv_store(&self, store_key: &SK) -> &KVStore<PrefixDB<T>>;
     fn get_mutable_kv_store(&mut self, store_key: &SK) -> &mut KVStore<PrefixDB<T>>;
@@ -66,6 +69,24 @@ impl<T: Database, SK: StoreKey> Context<'_, '_, T, SK> {
         }
     }
 
+    /// Returns the gas meter of the context
+    pub fn gas_meter(&self) -> &dyn GasMeter {
+        match self {
+            Context::TxContext(ctx) => ctx.gas_meter.as_ref(),
+            Context::InitContext(ctx) => &ctx.gas_meter,
+            Context::DynamicContext(ctx) => ctx.gas_meter(),
+        }
+    }
+
+    /// Returns the gas meter of the context, gas is consumed through this
+    pub fn gas_meter_mut(&mut self) -> &mut dyn GasMeter {
+        match self {
+            Context::TxContext(ctx) => ctx.gas_meter.as_mut(),
+            Context::InitContext(ctx) => &mut ctx.gas_meter,
+            Context::DynamicContext(ctx) => ctx.gas_meter_mut(),
+        }
+    }
+
     ///  Fetches an immutable ref to a KVStore from the MultiStore.
     pub fn get_kv_store(&self, store_key: &SK) -> &KVStore<PrefixDB<T>> {
         match self {
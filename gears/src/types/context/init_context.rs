@@ -1,4 +1,5 @@
 use crate::types::context::context::Context;
+use crate::types::gas::infinite_meter::InfiniteGasMeter;
 use database::{Database, PrefixDB};
 use proto_messages::cosmos::tx::v1beta1::tx_metadata::{DenomUnit, Metadata};
 use store_crate::{KVStore, MultiStore, StoreKey};
@@ -10,6 +11,8 @@ pub struct InitContext<'a, DB, SK> {
     pub height: u64,
     pub events: Vec<Event>,
     pub chain_id: Id,
+    /// Genesis isn't charged gas
+    pub gas_meter: InfiniteGasMeter,
 }
 
 impl<'a, DB: Database, SK: StoreKey> InitContext<'a, DB, SK> {
@@ -19,6 +22,7 @@ impl<'a, DB: Database, SK: StoreKey> InitContext<'a, DB, SK> {
             height,
             events: vec![],
             chain_id,
+            gas_meter: InfiniteGasMeter::new(),
         }
     }
 
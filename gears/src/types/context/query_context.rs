@@ -3,15 +3,15 @@ use database::{Database, PrefixDB};
 use proto_messages::cosmos::ibc::types::core::host::identifiers::ChainId;
 use store_crate::{MultiStore, QueryKVStore, QueryMultiStore, StoreKey};
 
-pub struct QueryContext<'a, DB, SK> {
-    pub multi_store: QueryMultiStore<'a, DB, SK>,
+pub struct QueryContext<DB, SK> {
+    pub multi_store: QueryMultiStore<DB, SK>,
     pub height: u64,
     pub chain_id: ChainId,
 }
 
-impl<'a, DB: Database, SK: StoreKey> QueryContext<'a, DB, SK> {
+impl<DB: Database, SK: StoreKey> QueryContext<DB, SK> {
     pub fn new(
-        multi_store: &'a MultiStore<DB, SK>,
+        multi_store: &MultiStore<DB, SK>,
         version: u32,
         // chain_id: ChainId,
     ) -> Result<Self, AppError> {
@@ -25,7 +25,7 @@ impl<'a, DB: Database, SK: StoreKey> QueryContext<'a, DB, SK> {
     }
 
     ///  Fetches an immutable ref to a KVStore from the MultiStore.
-    pub fn get_kv_store(&self, store_key: &SK) -> &QueryKVStore<'_, PrefixDB<DB>> {
+    pub fn get_kv_store(&self, store_key: &SK) -> &QueryKVStore<PrefixDB<DB>> {
         return self.multi_store.get_kv_store(store_key);
     }
 
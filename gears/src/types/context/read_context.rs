@@ -10,7 +10,7 @@ pub trait ReadContext<SK, DB: Database> {
     fn get_kv_store(&self, store_key: &SK) -> AnyKVStore<'_, PrefixDB<DB>>;
 }
 
-impl<SK: StoreKey, DB: Database> ReadContext<SK, DB> for QueryContext<'_, DB, SK> {
+impl<SK: StoreKey, DB: Database> ReadContext<SK, DB> for QueryContext<DB, SK> {
     fn get_kv_store(&self, store_key: &SK) -> AnyKVStore<'_, PrefixDB<DB>> {
         AnyKVStore::QueryKVStore(self.get_kv_store(store_key))
     }
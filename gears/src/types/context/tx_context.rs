@@ -1,4 +1,5 @@
 use crate::types::context::context::Context;
+use crate::types::gas::gas_meter::GasMeter;
 use database::{Database, PrefixDB};
 use proto_messages::cosmos::tx::v1beta1::tx_metadata::{DenomUnit, Metadata};
 use store_crate::{KVStore, MultiStore, StoreKey};
@@ -9,6 +10,7 @@ pub struct TxContext<'a, DB, SK> {
     pub height: u64,
     pub events: Vec<Event>,
     pub header: Header,
+    pub gas_meter: Box<dyn GasMeter>,
     _tx_bytes: Vec<u8>,
 }
 
@@ -18,12 +20,14 @@ impl<'a, DB: Database, SK: StoreKey> TxContext<'a, DB, SK> {
         height: u64,
         header: Header,
         tx_bytes: Vec<u8>,
+        gas_meter: Box<dyn GasMeter>,
     ) -> Self {
         TxContext {
             multi_store,
             height,
             events: vec![],
             header,
+            gas_meter,
             _tx_bytes: tx_bytes,
         }
     }
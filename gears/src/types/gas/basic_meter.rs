@@ -3,6 +3,7 @@ use std::fmt::Display;
 use super::gas_meter::{ErrorNegativeGasConsumed, Gas, GasErrors, GasMeter};
 
 /// Basic gas meter.
+#[derive(Debug)]
 pub struct BasicGasMeter {
     limit: Gas,
     consumed: Gas,
@@ -45,10 +46,11 @@ impl GasMeter for BasicGasMeter {
 
     fn consume_gas(&mut self, amount: Gas, descriptor: String) -> Result<(), GasErrors> {
         if let Some(sum) = self.consumed.0.checked_add(amount.0) {
+            self.consumed = Gas(sum);
+
             if self.consumed > self.limit {
                 Err(GasErrors::ErrorOutOfGas(descriptor))
             } else {
-                self.consumed = Gas(sum);
                 Ok(())
             }
         } else {
@@ -89,3 +91,21 @@ impl Display for BasicGasMeter {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn consume_gas_checks_limit_after_consuming() {
+        let mut meter = BasicGasMeter::new(Gas(10));
+
+        meter.consume_gas(Gas(10), "first".into()).unwrap();
+        assert!(meter.is_out_of_gas());
+        assert!(!meter.is_past_limit());
+
+        meter.consume_gas(Gas(1), "second".into()).unwrap_err();
+        assert!(meter.is_past_limit());
+        assert_eq!(meter.gas_consumed_to_limit(), Gas(10));
+    }
+}
@@ -1,5 +1,6 @@
 pub mod context;
 pub mod gas;
+pub mod pagination;
 mod tx;
 
 // pub use tx::*;
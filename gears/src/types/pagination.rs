@@ -0,0 +1,158 @@
+use database::Database;
+use proto_messages::cosmos::bank::v1beta1::{PageRequest, PageResponse};
+use store_crate::ImmutablePrefixStore;
+
+use crate::error::AppError;
+
+/// The number of items returned when a request doesn't set a limit
+pub const DEFAULT_LIMIT: u64 = 100;
+
+/// Returns a page of the key value pairs in a prefix store along with the page response.
+///
+/// Follows the semantics of the SDK's `query.Paginate`: a page either starts at `key` or
+/// after skipping `offset` items, a zero `limit` is replaced with [`DEFAULT_LIMIT`] and also
+/// enables `count_total`. The start of the page is found using the tree's subtree sizes, so
+/// a page is served in O(log n + limit).
+pub fn paginate<DB: Database>(
+    store: &ImmutablePrefixStore<'_, DB>,
+    pagination: Option<PageRequest>,
+) -> Result<(Vec<(Vec<u8>, Vec<u8>)>, PageResponse), AppError> {
+    let PageRequest {
+        key,
+        offset,
+        mut limit,
+        mut count_total,
+        reverse,
+    } = pagination.unwrap_or_default();
+
+    if offset > 0 && !key.is_empty() {
+        return Err(AppError::InvalidRequest(
+            "either offset or key is expected, got both".into(),
+        ));
+    }
+
+    if limit == 0 {
+        limit = DEFAULT_LIMIT;
+        count_total = true;
+    }
+
+    // fetch one extra item to find the next key
+    let take = usize::try_from(limit)
+        .unwrap_or(usize::MAX)
+        .saturating_add(1);
+
+    let (mut pairs, total): (Vec<_>, _) = if !key.is_empty() {
+        // the total is not calculated when paginating by key
+        let pairs = if reverse {
+            store
+                .reverse_range(..=key)
+                .take(take)
+                .collect::<Result<_, _>>()?
+        } else {
+            store.range(key..).take(take).collect::<Result<_, _>>()?
+        };
+
+        (pairs, 0)
+    } else {
+        let size = store.size()?;
+        let total = if count_total { size.into() } else { 0 };
+
+        let start = match u32::try_from(offset) {
+            Ok(offset) if offset < size => {
+                let index = if reverse { size - 1 - offset } else { offset };
+                store.get_by_index(index)?
+            }
+            _ => None,
+        };
+
+        let pairs = match start {
+            Some((start_key, _)) if reverse => store
+                .reverse_range(..=start_key)
+                .take(take)
+                .collect::<Result<_, _>>()?,
+            Some((start_key, _)) => store
+                .range(start_key..)
+                .take(take)
+                .collect::<Result<_, _>>()?,
+            None => vec![],
+        };
+
+        (pairs, total)
+    };
+
+    let next_key = if pairs.len() == take {
+        pairs.pop().map(|(key, _)| key).unwrap_or_default()
+    } else {
+        vec![]
+    };
+
+    Ok((pairs, PageResponse { next_key, total }))
+}
+
+#[cfg(test)]
+mod tests {
+    use database::MemDB;
+    use store_crate::KVStore;
+
+    use super::*;
+
+    fn keys(pairs: Vec<(Vec<u8>, Vec<u8>)>) -> Vec<Vec<u8>> {
+        pairs.into_iter().map(|(key, _)| key).collect()
+    }
+
+    fn request(key: Vec<u8>, offset: u64, limit: u64, reverse: bool) -> Option<PageRequest> {
+        Some(PageRequest {
+            key,
+            offset,
+            limit,
+            count_total: true,
+            reverse,
+        })
+    }
+
+    #[test]
+    fn paginate_works() {
+        let mut store = KVStore::new(MemDB::new(), None).unwrap();
+        store.set(vec![0], vec![0]);
+        for i in 1..=5 {
+            store.set(vec![1, i], vec![i]);
+        }
+        store.set(vec![2], vec![0]);
+        store.commit().unwrap();
+        let prefix_store = store.get_immutable_prefix_store(vec![1]);
+
+        // offset
+        let (pairs, page) = paginate(&prefix_store, request(vec![], 1, 2, false)).unwrap();
+        assert_eq!(keys(pairs), vec![vec![2], vec![3]]);
+        assert_eq!(page.next_key, vec![4]);
+        assert_eq!(page.total, 5);
+
+        // key
+        let (pairs, page) = paginate(&prefix_store, request(vec![4], 0, 2, false)).unwrap();
+        assert_eq!(keys(pairs), vec![vec![4], vec![5]]);
+        assert!(page.next_key.is_empty());
+        assert_eq!(page.total, 0);
+
+        // reverse offset
+        let (pairs, page) = paginate(&prefix_store, request(vec![], 1, 2, true)).unwrap();
+        assert_eq!(keys(pairs), vec![vec![4], vec![3]]);
+        assert_eq!(page.next_key, vec![2]);
+
+        // reverse key
+        let (pairs, page) = paginate(&prefix_store, request(vec![2], 0, 2, true)).unwrap();
+        assert_eq!(keys(pairs), vec![vec![2], vec![1]]);
+        assert!(page.next_key.is_empty());
+
+        // offset past the end
+        let (pairs, page) = paginate(&prefix_store, request(vec![], 5, 2, false)).unwrap();
+        assert!(pairs.is_empty());
+        assert_eq!(page.total, 5);
+
+        // default
+        let (pairs, page) = paginate(&prefix_store, None).unwrap();
+        assert_eq!(pairs.len(), 5);
+        assert_eq!(page.total, 5);
+
+        assert!(paginate(&prefix_store, request(vec![1], 1, 2, false)).is_err());
+    }
+}
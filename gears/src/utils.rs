@@ -3,6 +3,8 @@ use std::path::PathBuf;
 const CONFIG_DIR: &str = "config";
 const GENESIS_FILE_NAME: &str = "genesis.json";
 const CONFIG_FILE_NAME: &str = "app.toml";
+const DATA_DIR: &str = "data";
+const DB_DIR_NAME: &str = "application.db";
 
 pub const DEFAULT_DIR_NAME: &str = ".tendermint";
 
@@ -30,3 +32,8 @@ pub fn get_config_file_from_home_dir(home: &mut PathBuf) {
 pub fn get_config_dir_from_home_dir(home: &mut PathBuf) {
     home.push(CONFIG_DIR)
 }
+
+pub fn get_db_dir_from_home_dir(home: &mut PathBuf) {
+    home.push(DATA_DIR);
+    home.push(DB_DIR_NAME)
+}
@@ -23,6 +23,13 @@ pub struct Params {
     pub sig_verify_cost_secp256k1: u64,
 }
 
+impl Params {
+    /// The SDK has no separate param for secp256r1, it charges half the secp256k1 cost
+    pub fn sig_verify_cost_secp256r1(&self) -> u64 {
+        self.sig_verify_cost_secp256k1 / 2
+    }
+}
+
 // const KEY_MAX_MEMO_CHARACTERS: [u8; 17] = [
 //     077, 097, 120, 077, 101, 109, 111, 067, 104, 097, 114, 097, 099, 116, 101, 114, 115,
 // ]; // "MaxMemoCharacters"
@@ -36,6 +36,26 @@ pub enum Error {
         msg: String,
     },
 
+    #[error("the multisig key file at {path} is corrupted: {msg}")]
+    Multisig {
+        source: serde_json::Error,
+        path: String,
+        msg: String,
+    },
+
+    #[error("the public key file at {path} is corrupted: {msg}")]
+    PublicKeyInfo {
+        source: serde_json::Error,
+        path: String,
+        msg: String,
+    },
+
+    #[error("the armored key is invalid or the passphrase is incorrect: {msg}")]
+    Armor {
+        source: k256::pkcs8::Error,
+        msg: String,
+    },
+
     #[error("the key hash file at {path} is corrupted: {msg}")]
     KeyHash {
         source: argon2::password_hash::Error,
@@ -43,6 +63,9 @@ pub enum Error {
         msg: String,
     },
 
+    #[error("invalid HD path {path}: {msg}")]
+    InvalidHDPath { path: String, msg: String },
+
     #[error("incorrect password")]
     IncorrectPassword,
 
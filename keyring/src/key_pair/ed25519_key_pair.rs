@@ -0,0 +1,172 @@
+use bip32::{ChildNumber, DerivationPath, Mnemonic};
+use ed25519_dalek::{Signer, SigningKey};
+use hmac::{Hmac, Mac};
+use pkcs8::{DecodePrivateKey, EncodePrivateKey, LineEnding, SecretDocument};
+use proto_messages::cosmos::crypto::ed25519::v1beta1::PubKey;
+use proto_messages::cosmos::tx::v1beta1::public_key::PublicKey as GearsPublicKey;
+use proto_types::AccAddress;
+use sha2::Sha512;
+
+/// HMAC key used to derive the master key from a seed, see SLIP-10.
+const SLIP10_SEED_KEY: &[u8] = b"ed25519 seed";
+
+/// An ed25519 key pair.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Ed25519KeyPair(SigningKey);
+
+impl Ed25519KeyPair {
+    /// Returns PKCS8 PEM encoded private key.
+    pub fn to_pkcs8_pem(&self) -> k256::elliptic_curve::zeroize::Zeroizing<String> {
+        self.0
+            .to_pkcs8_pem(LineEnding::default())
+            .expect("this can't fail")
+    }
+
+    /// Returns PKCS8 PEM encoded private key encrypted with password.
+    pub fn to_pkcs8_encrypted_pem(
+        &self,
+        password: impl AsRef<[u8]>,
+    ) -> k256::elliptic_curve::zeroize::Zeroizing<String> {
+        super::encrypt_pkcs8_der(self.to_pkcs8_der().as_bytes(), password)
+    }
+
+    /// Returns PKCS8 DER encoded private key.
+    pub fn to_pkcs8_der(&self) -> SecretDocument {
+        self.0.to_pkcs8_der().expect("this can't fail")
+    }
+
+    /// Returns a key pair from a PKCS8 DER encoded private key.
+    pub fn from_pkcs8_der(bytes: &[u8]) -> Result<Self, pkcs8::Error> {
+        Ok(Self(SigningKey::from_pkcs8_der(bytes)?))
+    }
+
+    /// Returns a key pair derived along the HD path from a mnemonic and BIP39 passphrase with
+    /// SLIP-10, so the same key is recovered by other SLIP-10 wallets. SLIP-10 only defines
+    /// hardened derivation for ed25519 so every index is hardened, e.g. `m/44'/118'/0'/0/0` is
+    /// derived as `m/44'/118'/0'/0'/0'`.
+    pub fn from_mnemonic_with_path(
+        mnemonic: &Mnemonic,
+        bip39_passphrase: &str,
+        hd_path: &DerivationPath,
+    ) -> Self {
+        let seed = mnemonic.to_seed(bip39_passphrase);
+
+        Ed25519KeyPair(SigningKey::from_bytes(&slip10_derive(
+            seed.as_bytes(),
+            hd_path,
+        )))
+    }
+
+    /// Returns a Tendermint style address: the first 20 bytes of SHA256(pubkey).
+    pub fn get_address(&self) -> AccAddress {
+        self.get_gears_public_key().get_address()
+    }
+
+    /// Returns a Gears public key.
+    pub fn get_gears_public_key(&self) -> GearsPublicKey {
+        let raw_public_key = self.0.verifying_key().to_bytes().to_vec();
+        let public_key: PubKey = raw_public_key
+            .try_into()
+            .expect("raw public key is a valid ed25519 public key so this will always succeed");
+
+        GearsPublicKey::Ed25519(public_key)
+    }
+
+    /// Signs a message.
+    pub fn sign(&self, message: &[u8]) -> Vec<u8> {
+        self.0.sign(message).to_bytes().to_vec()
+    }
+}
+
+/// Derives the secret key along the HD path from the seed as described by SLIP-10.
+fn slip10_derive(seed: &[u8], hd_path: &DerivationPath) -> [u8; 32] {
+    let (mut key, mut chain_code) = slip10_hmac(SLIP10_SEED_KEY, &[seed]);
+
+    for child in hd_path.iter() {
+        let index = child.index() | ChildNumber::HARDENED_FLAG;
+        (key, chain_code) = slip10_hmac(&chain_code, &[&[0], &key, &index.to_be_bytes()]);
+    }
+
+    key
+}
+
+/// Returns the two halves of HMAC-SHA512, the key and the chain code.
+fn slip10_hmac(key: &[u8], data: &[&[u8]]) -> ([u8; 32], [u8; 32]) {
+    let mut mac = Hmac::<Sha512>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    for data in data {
+        mac.update(data);
+    }
+    let output = mac.finalize().into_bytes();
+
+    let (key, chain_code) = output.split_at(32);
+    (
+        key.try_into().expect("output is 64 bytes long"),
+        chain_code.try_into().expect("output is 64 bytes long"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use data_encoding::HEXLOWER;
+
+    use super::*;
+
+    fn key_pair() -> Ed25519KeyPair {
+        let mnemonic = "race draft rival universe maid cheese steel logic crowd fork comic easy truth drift tomorrow eye buddy head time cash swing swift midnight borrow";
+        let mnemonic = Mnemonic::new(mnemonic, bip32::Language::English).unwrap();
+        let hd_path = crate::parse_hd_path(crate::DEFAULT_HD_PATH).unwrap();
+        Ed25519KeyPair::from_mnemonic_with_path(&mnemonic, "", &hd_path)
+    }
+
+    #[test]
+    fn slip10_test_vector_1() {
+        let seed = [
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d,
+            0x0e, 0x0f,
+        ];
+
+        for (path, expected) in [
+            (
+                "m",
+                "2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7",
+            ),
+            (
+                "m/0'",
+                "68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3",
+            ),
+            (
+                "m/0'/1'/2'/2'/1000000000'",
+                "8f94d394a8e8fd6b1bc2f3f49f5c47e385281d5c17e65324b0f62483e37e8793",
+            ),
+            // non hardened indices are hardened
+            (
+                "m/0/1'/2/2'/1000000000",
+                "8f94d394a8e8fd6b1bc2f3f49f5c47e385281d5c17e65324b0f62483e37e8793",
+            ),
+        ] {
+            let key = slip10_derive(&seed, &path.parse().unwrap());
+            assert_eq!(key.to_vec(), HEXLOWER.decode(expected.as_bytes()).unwrap());
+        }
+    }
+
+    #[test]
+    fn pkcs8_round_trip_works() {
+        let key_pair = key_pair();
+
+        let from_der = Ed25519KeyPair::from_pkcs8_der(key_pair.to_pkcs8_der().as_bytes())
+            .expect("der should be valid");
+        assert_eq!(key_pair, from_der);
+    }
+
+    #[test]
+    fn sign_and_verify_works() {
+        let key_pair = key_pair();
+        let signature = key_pair.sign(b"message");
+
+        let public_key = key_pair.get_gears_public_key();
+        public_key
+            .verify_signature(b"message", &signature)
+            .expect("signature should be valid");
+        assert_eq!(public_key.get_address(), key_pair.get_address());
+    }
+}
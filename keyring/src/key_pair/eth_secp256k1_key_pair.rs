@@ -0,0 +1,134 @@
+use bip32::{DerivationPath, Mnemonic, PublicKey};
+use k256::ecdsa::SigningKey;
+use k256::SecretKey;
+use pkcs8::{DecodePrivateKey, EncodePrivateKey, LineEnding, SecretDocument};
+use proto_messages::cosmos::crypto::ethsecp256k1::v1::PubKey;
+use proto_messages::cosmos::tx::v1beta1::public_key::PublicKey as GearsPublicKey;
+use proto_types::AccAddress;
+use sha3::{Digest, Keccak256};
+
+/// PEM label of an eth_secp256k1 private key. The PKCS8 encoding of the key is the same as a
+/// secp256k1 key so a distinct label is used to keep the key type.
+pub const PEM_LABEL: &str = "ETHSECP256K1 PRIVATE KEY";
+
+/// PEM label of an encrypted eth_secp256k1 private key.
+pub const ENCRYPTED_PEM_LABEL: &str = "ENCRYPTED ETHSECP256K1 PRIVATE KEY";
+
+/// An Ethermint eth_secp256k1 key pair.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EthSecp256k1KeyPair(SecretKey);
+
+impl EthSecp256k1KeyPair {
+    /// Returns PKCS8 PEM encoded private key.
+    pub fn to_pkcs8_pem(&self) -> k256::elliptic_curve::zeroize::Zeroizing<String> {
+        self.to_pkcs8_der()
+            .to_pem(PEM_LABEL, LineEnding::default())
+            .expect("this can't fail")
+    }
+
+    /// Returns PKCS8 PEM encoded private key encrypted with password.
+    pub fn to_pkcs8_encrypted_pem(
+        &self,
+        password: impl AsRef<[u8]>,
+    ) -> k256::elliptic_curve::zeroize::Zeroizing<String> {
+        super::encrypt_pkcs8_der_with_label(
+            self.to_pkcs8_der().as_bytes(),
+            password,
+            ENCRYPTED_PEM_LABEL,
+        )
+    }
+
+    /// Returns PKCS8 DER encoded private key.
+    pub fn to_pkcs8_der(&self) -> SecretDocument {
+        self.0.to_pkcs8_der().expect("this can't fail")
+    }
+
+    /// Returns a key pair from a PKCS8 DER encoded private key.
+    pub fn from_pkcs8_der(bytes: &[u8]) -> Result<Self, k256::pkcs8::Error> {
+        Ok(Self(SecretKey::from_pkcs8_der(bytes)?))
+    }
+
+    /// Returns a key pair derived along the HD path from a mnemonic and BIP39 passphrase.
+    /// Ethermint chains use coin type 60, i.e. `m/44'/60'/0'/0/0`.
+    pub fn from_mnemonic_with_path(
+        mnemonic: &Mnemonic,
+        bip39_passphrase: &str,
+        hd_path: &DerivationPath,
+    ) -> Self {
+        let child_xprv = super::derive_child_key(mnemonic, bip39_passphrase, hd_path);
+
+        EthSecp256k1KeyPair(child_xprv.private_key().into())
+    }
+
+    /// Returns an Ethereum style address: the last 20 bytes of KECCAK256(uncompressed pubkey).
+    pub fn get_address(&self) -> AccAddress {
+        self.get_gears_public_key().get_address()
+    }
+
+    /// Returns a Gears public key.
+    pub fn get_gears_public_key(&self) -> GearsPublicKey {
+        let raw_public_key = self.0.public_key().to_bytes().to_vec();
+        let public_key: PubKey = raw_public_key
+            .try_into()
+            .expect("raw public key is a valid secp256k1 public key so this will always succeed");
+
+        GearsPublicKey::EthSecp256k1(public_key)
+    }
+
+    /// Signs the KECCAK256 digest of a message, the signature is the 65 byte concatenation of
+    /// r, s and the recovery id.
+    pub fn sign(&self, message: &[u8]) -> Vec<u8> {
+        let signing_key: SigningKey = SigningKey::from(&self.0);
+        let (signature, recovery_id) = signing_key
+            .sign_prehash_recoverable(&Keccak256::digest(message))
+            .expect("digest is 32 bytes so signing will never fail");
+
+        let mut signature = signature.to_vec();
+        signature.push(recovery_id.to_byte());
+        signature
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key_pair() -> EthSecp256k1KeyPair {
+        let mnemonic = "race draft rival universe maid cheese steel logic crowd fork comic easy truth drift tomorrow eye buddy head time cash swing swift midnight borrow";
+        let mnemonic = Mnemonic::new(mnemonic, bip32::Language::English).unwrap();
+        let hd_path = crate::bip44_path(60, 0, 0).unwrap();
+
+        EthSecp256k1KeyPair::from_mnemonic_with_path(&mnemonic, "", &hd_path)
+    }
+
+    #[test]
+    fn pem_uses_eth_label() {
+        let key_pair = key_pair();
+
+        let pem = key_pair.to_pkcs8_pem();
+        let (label, doc) = SecretDocument::from_pem(&pem).unwrap();
+        assert_eq!(label, PEM_LABEL);
+        assert_eq!(
+            EthSecp256k1KeyPair::from_pkcs8_der(doc.as_bytes()).unwrap(),
+            key_pair
+        );
+
+        let pem = key_pair.to_pkcs8_encrypted_pem("password");
+        let (label, _) = SecretDocument::from_pem(&pem).unwrap();
+        assert_eq!(label, ENCRYPTED_PEM_LABEL);
+    }
+
+    #[test]
+    fn sign_works() {
+        let key_pair = key_pair();
+        let message = b"sign me";
+
+        let signature = key_pair.sign(message);
+        assert_eq!(signature.len(), 65);
+
+        key_pair
+            .get_gears_public_key()
+            .verify_signature(message, &signature)
+            .unwrap();
+    }
+}
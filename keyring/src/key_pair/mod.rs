@@ -1,14 +1,39 @@
+pub mod ed25519_key_pair;
+#[cfg(feature = "ethsecp256k1")]
+pub mod eth_secp256k1_key_pair;
 pub mod secp256k1_key_pair;
+pub mod secp256r1_key_pair;
 
+use bip32::{DerivationPath, Mnemonic, XPrv};
+use pkcs8::{
+    der::pem::PemLabel,
+    pkcs5::{pbes2, scrypt},
+    rand_core::{OsRng, RngCore},
+    EncryptedPrivateKeyInfo, LineEnding, ObjectIdentifier, PrivateKeyInfo, SecretDocument,
+};
 use proto_messages::cosmos::tx::v1beta1::public_key::PublicKey;
 use proto_types::AccAddress;
 
-use self::secp256k1_key_pair::Secp256k1KeyPair;
+#[cfg(feature = "ethsecp256k1")]
+use self::eth_secp256k1_key_pair::EthSecp256k1KeyPair;
+use self::{
+    ed25519_key_pair::Ed25519KeyPair, secp256k1_key_pair::Secp256k1KeyPair,
+    secp256r1_key_pair::Secp256r1KeyPair,
+};
+
+const ED25519_OID: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.101.112");
+const EC_PUBLIC_KEY_OID: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.2.1");
+const SECP256K1_OID: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.132.0.10");
+const SECP256R1_OID: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.3.1.7");
 
 /// A key pair.
 #[derive(Clone, Debug)]
 pub enum KeyPair {
     Secp256k1(Secp256k1KeyPair),
+    Secp256r1(Secp256r1KeyPair),
+    Ed25519(Ed25519KeyPair),
+    #[cfg(feature = "ethsecp256k1")]
+    EthSecp256k1(EthSecp256k1KeyPair),
 }
 
 impl KeyPair {
@@ -16,6 +41,10 @@ impl KeyPair {
     pub fn get_address(&self) -> AccAddress {
         match self {
             KeyPair::Secp256k1(key) => key.get_address(),
+            KeyPair::Secp256r1(key) => key.get_address(),
+            KeyPair::Ed25519(key) => key.get_address(),
+            #[cfg(feature = "ethsecp256k1")]
+            KeyPair::EthSecp256k1(key) => key.get_address(),
         }
     }
 
@@ -23,6 +52,10 @@ impl KeyPair {
     pub fn to_pkcs8_pem(&self) -> k256::elliptic_curve::zeroize::Zeroizing<String> {
         match self {
             KeyPair::Secp256k1(key) => key.to_pkcs8_pem(),
+            KeyPair::Secp256r1(key) => key.to_pkcs8_pem(),
+            KeyPair::Ed25519(key) => key.to_pkcs8_pem(),
+            #[cfg(feature = "ethsecp256k1")]
+            KeyPair::EthSecp256k1(key) => key.to_pkcs8_pem(),
         }
     }
 
@@ -33,12 +66,27 @@ impl KeyPair {
     ) -> k256::elliptic_curve::zeroize::Zeroizing<String> {
         match self {
             KeyPair::Secp256k1(key) => key.to_pkcs8_encrypted_pem(password),
+            KeyPair::Secp256r1(key) => key.to_pkcs8_encrypted_pem(password),
+            KeyPair::Ed25519(key) => key.to_pkcs8_encrypted_pem(password),
+            #[cfg(feature = "ethsecp256k1")]
+            KeyPair::EthSecp256k1(key) => key.to_pkcs8_encrypted_pem(password),
         }
     }
 
     /// Returns a key pair from a PKCS8 PEM encoded private key.
     pub fn from_pkcs8_pem(s: &str) -> Result<Self, k256::pkcs8::Error> {
-        Ok(Self::Secp256k1(Secp256k1KeyPair::from_pkcs8_pem(s)?))
+        let (label, doc) = SecretDocument::from_pem(s)?;
+
+        #[cfg(feature = "ethsecp256k1")]
+        if label == eth_secp256k1_key_pair::PEM_LABEL {
+            return Ok(Self::EthSecp256k1(EthSecp256k1KeyPair::from_pkcs8_der(
+                doc.as_bytes(),
+            )?));
+        }
+
+        PrivateKeyInfo::validate_pem_label(label)?;
+
+        Self::from_pkcs8_der(doc.as_bytes())
     }
 
     /// Returns a key pair from a PKCS8 PEM encoded private key encrypted with password.
@@ -46,15 +94,57 @@ impl KeyPair {
         s: &str,
         password: impl AsRef<[u8]>,
     ) -> Result<Self, k256::pkcs8::Error> {
-        Ok(Self::Secp256k1(Secp256k1KeyPair::from_pkcs8_encrypted_pem(
-            s, password,
-        )?))
+        let (label, doc) = SecretDocument::from_pem(s)?;
+
+        #[cfg(feature = "ethsecp256k1")]
+        if label == eth_secp256k1_key_pair::ENCRYPTED_PEM_LABEL {
+            let doc = EncryptedPrivateKeyInfo::try_from(doc.as_bytes())?.decrypt(password)?;
+            return Ok(Self::EthSecp256k1(EthSecp256k1KeyPair::from_pkcs8_der(
+                doc.as_bytes(),
+            )?));
+        }
+
+        EncryptedPrivateKeyInfo::validate_pem_label(label)?;
+        let doc = EncryptedPrivateKeyInfo::try_from(doc.as_bytes())?.decrypt(password)?;
+
+        Self::from_pkcs8_der(doc.as_bytes())
+    }
+
+    /// Returns a key pair from a PKCS8 DER encoded private key, the key type is taken from the
+    /// algorithm identifier. eth_secp256k1 keys share the secp256k1 identifier so are only
+    /// recognised from their PEM label.
+    pub fn from_pkcs8_der(bytes: &[u8]) -> Result<Self, k256::pkcs8::Error> {
+        let algorithm = PrivateKeyInfo::try_from(bytes)?.algorithm;
+
+        if algorithm.oid == ED25519_OID {
+            return Ok(Self::Ed25519(Ed25519KeyPair::from_pkcs8_der(bytes)?));
+        }
+
+        if algorithm.oid == EC_PUBLIC_KEY_OID {
+            let curve = algorithm.parameters_oid()?;
+
+            if curve == SECP256K1_OID {
+                return Ok(Self::Secp256k1(Secp256k1KeyPair::from_pkcs8_der(bytes)?));
+            }
+
+            if curve == SECP256R1_OID {
+                return Ok(Self::Secp256r1(Secp256r1KeyPair::from_pkcs8_der(bytes)?));
+            }
+
+            return Err(pkcs8::spki::Error::OidUnknown { oid: curve }.into());
+        }
+
+        Err(pkcs8::spki::Error::OidUnknown { oid: algorithm.oid }.into())
     }
 
     ///Returns a gears public key
     pub fn get_gears_public_key(&self) -> PublicKey {
         match self {
             KeyPair::Secp256k1(key) => key.get_gears_public_key(),
+            KeyPair::Secp256r1(key) => key.get_gears_public_key(),
+            KeyPair::Ed25519(key) => key.get_gears_public_key(),
+            #[cfg(feature = "ethsecp256k1")]
+            KeyPair::EthSecp256k1(key) => key.get_gears_public_key(),
         }
     }
 
@@ -62,6 +152,123 @@ impl KeyPair {
     pub fn sign(&self, message: &[u8]) -> Vec<u8> {
         match self {
             KeyPair::Secp256k1(key) => key.sign(message),
+            KeyPair::Secp256r1(key) => key.sign(message),
+            KeyPair::Ed25519(key) => key.sign(message),
+            #[cfg(feature = "ethsecp256k1")]
+            KeyPair::EthSecp256k1(key) => key.sign(message),
+        }
+    }
+}
+
+/// Derives the BIP32 child key along the HD path. secp256r1 uses the child private key bytes as
+/// its secret key so that it can be recovered from a mnemonic, ed25519 keys are derived with
+/// SLIP-10 instead.
+pub(crate) fn derive_child_key(
+    mnemonic: &Mnemonic,
+    bip39_passphrase: &str,
+    hd_path: &DerivationPath,
+) -> XPrv {
+    let seed = mnemonic.to_seed(bip39_passphrase);
+    XPrv::derive_from_path(&seed, hd_path)
+        .expect("seed has length 64 so this will never return an error")
+}
+
+/// Encrypts a PKCS8 DER encoded private key with password and returns it PEM encoded.
+pub(crate) fn encrypt_pkcs8_der(
+    plain_text_der: &[u8],
+    password: impl AsRef<[u8]>,
+) -> k256::elliptic_curve::zeroize::Zeroizing<String> {
+    encrypt_pkcs8_der_with_label(plain_text_der, password, EncryptedPrivateKeyInfo::PEM_LABEL)
+}
+
+/// Encrypts a PKCS8 DER encoded private key with password and returns it PEM encoded with the
+/// given label.
+pub(crate) fn encrypt_pkcs8_der_with_label(
+    plain_text_der: &[u8],
+    password: impl AsRef<[u8]>,
+    label: &str,
+) -> k256::elliptic_curve::zeroize::Zeroizing<String> {
+    // TODO: The pkcs8 crate doesn't directly support encrypting with the same scrypt params as openssl.
+    // The following implementation is a workaround to achieve the same result.
+    // See https://github.com/RustCrypto/formats/issues/1205
+    // Once this is fixed, we can replace the following code with:
+    // self.secret_key
+    //     .to_pkcs8_encrypted_pem(&mut OsRng, password, LineEnding::default())
+    //     .expect("this can't fail")
+
+    let mut rng = OsRng;
+
+    let mut salt = [0u8; 16];
+    rng.fill_bytes(&mut salt);
+
+    let mut iv = [0u8; 16];
+    rng.fill_bytes(&mut iv);
+
+    // 14 = log_2(16384), 32 bytes = 256 bits
+    let scrypt_params = scrypt::Params::new(14, 8, 1, 32).unwrap();
+    let pbes2_params = pbes2::Parameters::scrypt_aes256cbc(scrypt_params, &salt, &iv).unwrap();
+
+    let private_key_info = PrivateKeyInfo::try_from(plain_text_der).unwrap();
+
+    let secret_doc = private_key_info
+        .encrypt_with_params(pbes2_params, password.as_ref())
+        .unwrap();
+
+    secret_doc.to_pem(label, LineEnding::LF).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key_pairs() -> Vec<KeyPair> {
+        let mnemonic = "race draft rival universe maid cheese steel logic crowd fork comic easy truth drift tomorrow eye buddy head time cash swing swift midnight borrow";
+        let mnemonic = Mnemonic::new(mnemonic, bip32::Language::English).unwrap();
+        let hd_path = crate::parse_hd_path(crate::DEFAULT_HD_PATH).unwrap();
+
+        vec![
+            KeyPair::Secp256k1(Secp256k1KeyPair::from_mnemonic(&mnemonic)),
+            KeyPair::Secp256r1(Secp256r1KeyPair::from_mnemonic_with_path(
+                &mnemonic, "", &hd_path,
+            )),
+            KeyPair::Ed25519(Ed25519KeyPair::from_mnemonic_with_path(
+                &mnemonic, "", &hd_path,
+            )),
+            #[cfg(feature = "ethsecp256k1")]
+            KeyPair::EthSecp256k1(EthSecp256k1KeyPair::from_mnemonic_with_path(
+                &mnemonic, "", &hd_path,
+            )),
+        ]
+    }
+
+    #[test]
+    fn pem_round_trip_keeps_key_type() {
+        for key_pair in key_pairs() {
+            let from_pem =
+                KeyPair::from_pkcs8_pem(&key_pair.to_pkcs8_pem()).expect("pem should be valid");
+            assert_eq!(
+                from_pem.get_gears_public_key(),
+                key_pair.get_gears_public_key()
+            );
+
+            let pem = key_pair.to_pkcs8_encrypted_pem("password");
+            let from_pem =
+                KeyPair::from_pkcs8_encrypted_pem(&pem, "password").expect("pem should be valid");
+            assert_eq!(
+                from_pem.get_gears_public_key(),
+                key_pair.get_gears_public_key()
+            );
+
+            KeyPair::from_pkcs8_encrypted_pem(&pem, "wrong").expect_err("password is wrong");
+        }
+    }
+
+    #[test]
+    fn key_types_have_different_addresses() {
+        let addresses: Vec<AccAddress> = key_pairs().iter().map(KeyPair::get_address).collect();
+
+        for (i, address) in addresses.iter().enumerate() {
+            assert!(!addresses[i + 1..].contains(address));
         }
     }
 }
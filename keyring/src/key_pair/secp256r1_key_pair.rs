@@ -0,0 +1,155 @@
+use bip32::{DerivationPath, Mnemonic};
+use p256::ecdsa::{signature::Signer, Signature, SigningKey};
+use p256::elliptic_curve::ops::Reduce;
+use p256::elliptic_curve::sec1::ToEncodedPoint;
+use p256::{NonZeroScalar, Scalar, SecretKey, U256};
+use pkcs8::{DecodePrivateKey, EncodePrivateKey, LineEnding, SecretDocument};
+use proto_messages::cosmos::crypto::secp256r1::v1beta1::PubKey;
+use proto_messages::cosmos::tx::v1beta1::public_key::PublicKey as GearsPublicKey;
+use proto_types::AccAddress;
+
+/// A secp256r1 (NIST P-256) key pair.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Secp256r1KeyPair(SecretKey);
+
+impl Secp256r1KeyPair {
+    /// Returns PKCS8 PEM encoded private key.
+    pub fn to_pkcs8_pem(&self) -> k256::elliptic_curve::zeroize::Zeroizing<String> {
+        self.0
+            .to_pkcs8_pem(LineEnding::default())
+            .expect("this can't fail")
+    }
+
+    /// Returns PKCS8 PEM encoded private key encrypted with password.
+    pub fn to_pkcs8_encrypted_pem(
+        &self,
+        password: impl AsRef<[u8]>,
+    ) -> k256::elliptic_curve::zeroize::Zeroizing<String> {
+        super::encrypt_pkcs8_der(self.to_pkcs8_der().as_bytes(), password)
+    }
+
+    /// Returns PKCS8 DER encoded private key.
+    pub fn to_pkcs8_der(&self) -> SecretDocument {
+        self.0.to_pkcs8_der().expect("this can't fail")
+    }
+
+    /// Returns a key pair from a PKCS8 DER encoded private key.
+    pub fn from_pkcs8_der(bytes: &[u8]) -> Result<Self, pkcs8::Error> {
+        Ok(Self(SecretKey::from_pkcs8_der(bytes)?))
+    }
+
+    /// Returns a key pair derived along the HD path from a mnemonic and BIP39 passphrase. The
+    /// BIP32 child private key reduced modulo the secp256r1 order is used as the scalar.
+    pub fn from_mnemonic_with_path(
+        mnemonic: &Mnemonic,
+        bip39_passphrase: &str,
+        hd_path: &DerivationPath,
+    ) -> Self {
+        let child_xprv = super::derive_child_key(mnemonic, bip39_passphrase, hd_path);
+
+        Secp256r1KeyPair(secret_key_from_bip32(child_xprv.to_bytes()))
+    }
+
+    /// Returns an ADR-028 address derived from the compressed public key.
+    pub fn get_address(&self) -> AccAddress {
+        self.get_gears_public_key().get_address()
+    }
+
+    /// Returns a Gears public key.
+    pub fn get_gears_public_key(&self) -> GearsPublicKey {
+        let raw_public_key = self
+            .0
+            .public_key()
+            .to_encoded_point(true)
+            .as_bytes()
+            .to_vec();
+        let public_key: PubKey = raw_public_key
+            .try_into()
+            .expect("raw public key is a valid secp256r1 public key so this will always succeed");
+
+        GearsPublicKey::Secp256r1(public_key)
+    }
+
+    /// Signs a message, the signature is normalized to a low s value.
+    pub fn sign(&self, message: &[u8]) -> Vec<u8> {
+        let signing_key = SigningKey::from(&self.0);
+        let signature: Signature = signing_key.sign(message);
+        signature
+            .normalize_s()
+            .unwrap_or(signature)
+            .to_bytes()
+            .to_vec()
+    }
+}
+
+/// BIP32 derives keys over secp256k1, whose order is larger than the secp256r1 order by about
+/// 2^224, so around 1 in 2^32 child keys aren't valid secp256r1 scalars and are reduced. Keys
+/// below the secp256r1 order are unchanged by the reduction.
+fn secret_key_from_bip32(bytes: [u8; 32]) -> SecretKey {
+    let scalar = <Scalar as Reduce<U256>>::reduce_bytes(&bytes.into());
+
+    // the only non zero secp256k1 key which reduces to zero is the secp256r1 order itself
+    let scalar = Option::<NonZeroScalar>::from(NonZeroScalar::new(scalar))
+        .expect("a BIP32 private key is never the secp256r1 order");
+
+    SecretKey::from(scalar)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key_pair() -> Secp256r1KeyPair {
+        let mnemonic = "race draft rival universe maid cheese steel logic crowd fork comic easy truth drift tomorrow eye buddy head time cash swing swift midnight borrow";
+        let mnemonic = Mnemonic::new(mnemonic, bip32::Language::English).unwrap();
+        let hd_path = crate::parse_hd_path(crate::DEFAULT_HD_PATH).unwrap();
+        Secp256r1KeyPair::from_mnemonic_with_path(&mnemonic, "", &hd_path)
+    }
+
+    #[test]
+    fn pkcs8_round_trip_works() {
+        let key_pair = key_pair();
+
+        let from_der = Secp256r1KeyPair::from_pkcs8_der(key_pair.to_pkcs8_der().as_bytes())
+            .expect("der should be valid");
+        assert_eq!(key_pair, from_der);
+    }
+
+    #[test]
+    fn secret_key_from_bip32_reduces_keys_above_the_order() {
+        let below_order = key_pair().0.to_bytes().into();
+        assert_eq!(
+            secret_key_from_bip32(below_order),
+            SecretKey::from_slice(&below_order).unwrap()
+        );
+
+        // larger than the secp256r1 order but smaller than the secp256k1 order
+        let mut above_order = [0xff; 32];
+        above_order[4..8].copy_from_slice(&[0; 4]);
+        SecretKey::from_slice(&above_order).unwrap_err();
+
+        // the key minus the secp256r1 order
+        let mut expected = [0; 32];
+        expected[16..].copy_from_slice(&[
+            67, 25, 5, 82, 88, 232, 97, 123, 12, 70, 53, 61, 3, 156, 218, 174,
+        ]);
+        assert_eq!(
+            secret_key_from_bip32(above_order),
+            SecretKey::from_slice(&expected).unwrap()
+        );
+    }
+
+    #[test]
+    fn sign_and_verify_works() {
+        let key_pair = key_pair();
+
+        // low s normalization must hold for every signature, not just the first
+        for message in [&b"message"[..], b"another message", b"a third message"] {
+            let signature = key_pair.sign(message);
+            key_pair
+                .get_gears_public_key()
+                .verify_signature(message, &signature)
+                .expect("signature should be valid");
+        }
+    }
+}
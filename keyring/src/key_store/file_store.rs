@@ -1,12 +1,15 @@
 use std::{
     fs::{self, remove_file, OpenOptions},
     io::{ErrorKind, Write},
-    path::Path,
+    path::{Path, PathBuf},
 };
 
 use std::fs::File;
 
 use crate::{error::Error, key_pair::KeyPair};
+use proto_messages::cosmos::{
+    crypto::multisig::LegacyAminoPubKey, tx::v1beta1::public_key::PublicKey,
+};
 
 use argon2::{
     password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
@@ -16,6 +19,8 @@ use argon2::{
 use std::os::unix::fs::PermissionsExt;
 
 pub const PEM_EXTENSION: &str = "pem";
+pub const MULTISIG_EXTENSION: &str = "multisig";
+pub const INFO_EXTENSION: &str = "info";
 const KEY_HASH_FILE: &str = "key_hash";
 
 fn verify_password(
@@ -189,25 +194,137 @@ fn open<'a>(
     }
 }
 
-/// Gets the entry with the given name.
-/// Returns [`Error`] if no entry with the given name can be found.
-pub fn get_key_by_name<S>(
-    name: &S,
-    path: impl AsRef<Path>,
-    backend: Backend,
-) -> Result<KeyPair, Error>
-where
-    S: AsRef<str> + ?Sized,
-{
-    let password = open(&path, false, backend)?;
-    let mut path = path.as_ref().join(name.as_ref());
-    path.set_extension(PEM_EXTENSION);
+/// Checks that an existing keyring uses the backend without asking for the password. Used to
+/// read multisig entries which only hold public keys so are never encrypted.
+fn check_backend(path: impl AsRef<Path>, backend: Backend) -> Result<(), Error> {
+    let key_hash_path = path.as_ref().join(KEY_HASH_FILE);
+
+    let password_hash = fs::read_to_string(&key_hash_path).map_err(|e| {
+        if e.kind() == ErrorKind::NotFound {
+            Error::KeyringDoesNotExist(path.as_ref().display().to_string())
+        } else {
+            Error::FileIO {
+                msg: e.to_string(),
+                source: e,
+                path: key_hash_path.display().to_string(),
+            }
+        }
+    })?;
+
+    let found = if password_hash.is_empty() {
+        Backend::Test
+    } else {
+        Backend::Encrypted
+    };
+
+    if found != backend {
+        return Err(Error::IncorrectBackend {
+            path: path.as_ref().display().to_string(),
+            expected: backend.into(),
+            found: found.into(),
+        });
+    }
+
+    Ok(())
+}
+
+/// Returns the path of the file storing the entry with the given name.
+fn key_path(name: &str, path: impl AsRef<Path>) -> PathBuf {
+    entry_path(name, path, PEM_EXTENSION)
+}
+
+/// Returns the path of the file storing the multisig entry with the given name.
+fn multisig_path(name: &str, path: impl AsRef<Path>) -> PathBuf {
+    entry_path(name, path, MULTISIG_EXTENSION)
+}
+
+/// Returns the path of the file storing the public key of the entry with the given name.
+fn info_path(name: &str, path: impl AsRef<Path>) -> PathBuf {
+    entry_path(name, path, INFO_EXTENSION)
+}
 
-    fs::read(&path)
+fn entry_path(name: &str, path: impl AsRef<Path>, extension: &str) -> PathBuf {
+    let mut path = path.as_ref().join(name);
+    path.set_extension(extension);
+    path
+}
+
+/// Returns the path of the key or multisig entry with the given name if either exists.
+fn existing_entry_path(name: &str, path: impl AsRef<Path>) -> Option<PathBuf> {
+    [key_path(name, &path), multisig_path(name, &path)]
+        .into_iter()
+        .find(|path| path.exists())
+}
+
+/// Returns an [`Error`] if a key or multisig entry with the given name already exists.
+fn check_name_is_free(name: &str, path: impl AsRef<Path>) -> Result<(), Error> {
+    match existing_entry_path(name, path) {
+        Some(existing) => Err(Error::AlreadyExists {
+            name: name.into(),
+            location: existing.display().to_string(),
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Creates a new read-write file at the path, fails if the file already exists.
+fn create_new_file(name: &str, path: &Path) -> Result<File, Error> {
+    OpenOptions::new()
+        .create_new(true)
+        .write(true)
+        .open(path)
+        .map_err(|e| {
+            if e.kind() == ErrorKind::AlreadyExists {
+                Error::AlreadyExists {
+                    name: name.into(),
+                    location: path.display().to_string(),
+                }
+            } else {
+                Error::FileIO {
+                    msg: e.to_string(),
+                    source: e,
+                    path: path.display().to_string(),
+                }
+            }
+        })
+}
+
+/// Returns the names and paths of the entries with the extension, sorted by name.
+fn list_entries(path: impl AsRef<Path>, extension: &str) -> Result<Vec<(String, PathBuf)>, Error> {
+    let dir_error = |e: std::io::Error| Error::FileIO {
+        msg: e.to_string(),
+        source: e,
+        path: path.as_ref().display().to_string(),
+    };
+
+    let mut entries = Vec::new();
+    for entry in fs::read_dir(&path).map_err(dir_error)? {
+        let entry_path = entry.map_err(dir_error)?.path();
+
+        if entry_path.extension().and_then(|ext| ext.to_str()) != Some(extension) {
+            continue;
+        }
+
+        let Some(name) = entry_path.file_stem().and_then(|name| name.to_str()) else {
+            continue;
+        };
+
+        let name = name.to_owned();
+        entries.push((name, entry_path));
+    }
+
+    entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    Ok(entries)
+}
+
+/// Reads and decodes the key file at the given path.
+fn read_key(name: &str, path: &Path, password: Option<&str>) -> Result<KeyPair, Error> {
+    fs::read(path)
         .map_err(|e| {
             if e.kind() == ErrorKind::NotFound {
                 Error::DoesNotExist {
-                    name: name.as_ref().into(),
+                    name: name.into(),
                     location: path.display().to_string(),
                 }
             } else {
@@ -238,6 +355,276 @@ where
         })
 }
 
+/// Reads and decodes the multisig file at the given path.
+fn read_multisig_key(name: &str, path: &Path) -> Result<LegacyAminoPubKey, Error> {
+    let raw_key = fs::read(path).map_err(|e| {
+        if e.kind() == ErrorKind::NotFound {
+            Error::DoesNotExist {
+                name: name.into(),
+                location: path.display().to_string(),
+            }
+        } else {
+            Error::FileIO {
+                msg: e.to_string(),
+                source: e,
+                path: path.display().to_string(),
+            }
+        }
+    })?;
+
+    serde_json::from_slice(&raw_key).map_err(|e| Error::Multisig {
+        msg: e.to_string(),
+        source: e,
+        path: path.display().to_string(),
+    })
+}
+
+/// Reads and decodes the public key file at the given path, returns `None` for keys stored
+/// without one.
+fn read_info(path: &Path) -> Result<Option<PublicKey>, Error> {
+    let raw_info = match fs::read(path) {
+        Ok(raw_info) => raw_info,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+        Err(e) => {
+            return Err(Error::FileIO {
+                msg: e.to_string(),
+                source: e,
+                path: path.display().to_string(),
+            })
+        }
+    };
+
+    serde_json::from_slice(&raw_info)
+        .map(Some)
+        .map_err(|e| Error::PublicKeyInfo {
+            msg: e.to_string(),
+            source: e,
+            path: path.display().to_string(),
+        })
+}
+
+/// Stores the public key of a key pair unencrypted next to its key file, so that it can be read
+/// without the keyring password as the Go keyring does.
+fn write_info(name: &str, key_pair: &KeyPair, path: impl AsRef<Path>) -> Result<(), Error> {
+    let path = info_path(name, path);
+    let mut file = create_new_file(name, &path)?;
+
+    let json = serde_json::to_vec_pretty(&key_pair.get_gears_public_key())
+        .expect("serializing a public key can't fail");
+
+    file.write_all(&json).map_err(|e| Error::FileIO {
+        msg: e.to_string(),
+        source: e,
+        path: path.display().to_string(),
+    })?;
+
+    set_readonly(file, path.display().to_string())
+}
+
+/// Removes the public key file of the entry with the given name if there is one.
+fn remove_info(name: &str, path: impl AsRef<Path>) -> Result<(), Error> {
+    let path = info_path(name, path);
+
+    match remove_file(&path) {
+        Err(e) if e.kind() != ErrorKind::NotFound => Err(Error::FileIO {
+            msg: e.to_string(),
+            source: e,
+            path: path.display().to_string(),
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// Gets the entry with the given name.
+/// Returns [`Error`] if no entry with the given name can be found.
+pub fn get_key_by_name<S>(
+    name: &S,
+    path: impl AsRef<Path>,
+    backend: Backend,
+) -> Result<KeyPair, Error>
+where
+    S: AsRef<str> + ?Sized,
+{
+    let password = open(&path, false, backend)?;
+    let path = key_path(name.as_ref(), path);
+
+    read_key(name.as_ref(), &path, password.as_deref())
+}
+
+/// Gets the public key of the entry with the given name, the keyring password is only asked for
+/// if the key was stored without its public key.
+/// Returns [`Error`] if no entry with the given name can be found.
+pub fn get_public_key_by_name<S>(
+    name: &S,
+    path: impl AsRef<Path>,
+    backend: Backend,
+) -> Result<PublicKey, Error>
+where
+    S: AsRef<str> + ?Sized,
+{
+    check_backend(&path, backend)?;
+
+    if key_path(name.as_ref(), &path).exists() {
+        if let Some(public_key) = read_info(&info_path(name.as_ref(), &path))? {
+            return Ok(public_key);
+        }
+    }
+
+    get_key_by_name(name, path, backend).map(|key_pair| key_pair.get_gears_public_key())
+}
+
+/// Gets the public keys of all entries sorted by name. Only keys stored without their public key
+/// are decrypted, so the keyring password is only asked for if there are any.
+pub fn list_keys(
+    path: impl AsRef<Path>,
+    backend: Backend,
+) -> Result<Vec<(String, PublicKey)>, Error> {
+    check_backend(&path, backend)?;
+
+    let mut opened = None;
+    let mut keys = Vec::new();
+    for (name, entry_path) in list_entries(&path, PEM_EXTENSION)? {
+        let public_key = match read_info(&info_path(&name, &path))? {
+            Some(public_key) => public_key,
+            None => {
+                if opened.is_none() {
+                    opened = Some(open(&path, false, backend)?);
+                }
+                let password = opened.as_ref().and_then(Option::as_deref);
+
+                read_key(&name, &entry_path, password)?.get_gears_public_key()
+            }
+        };
+
+        keys.push((name, public_key));
+    }
+
+    Ok(keys)
+}
+
+/// Gets the multisig entry with the given name.
+/// Returns [`Error`] if no multisig entry with the given name can be found.
+pub fn get_multisig_key_by_name<S>(
+    name: &S,
+    path: impl AsRef<Path>,
+    backend: Backend,
+) -> Result<LegacyAminoPubKey, Error>
+where
+    S: AsRef<str> + ?Sized,
+{
+    check_backend(&path, backend)?;
+    let path = multisig_path(name.as_ref(), path);
+
+    read_multisig_key(name.as_ref(), &path)
+}
+
+/// Gets all multisig entries sorted by name.
+pub fn list_multisig_keys(
+    path: impl AsRef<Path>,
+    backend: Backend,
+) -> Result<Vec<(String, LegacyAminoPubKey)>, Error> {
+    check_backend(&path, backend)?;
+
+    list_entries(&path, MULTISIG_EXTENSION)?
+        .into_iter()
+        .map(|(name, entry_path)| {
+            let key = read_multisig_key(&name, &entry_path)?;
+            Ok((name, key))
+        })
+        .collect()
+}
+
+/// Stores a multisig key, multisig entries only hold public keys so they are never encrypted.
+/// Returns an [`Error`] if a key or multisig entry with the same name already exists.
+pub fn set_multisig_key<S: AsRef<str>>(
+    key_name: S,
+    key: &LegacyAminoPubKey,
+    path: impl AsRef<Path>,
+    backend: Backend,
+) -> Result<(), Error> {
+    open(&path, true, backend)?;
+    check_name_is_free(key_name.as_ref(), &path)?;
+
+    let path = multisig_path(key_name.as_ref(), path);
+    let mut file = create_new_file(key_name.as_ref(), &path)?;
+
+    let json = serde_json::to_vec_pretty(key).expect("serializing a public key can't fail");
+
+    file.write_all(&json).map_err(|e| Error::FileIO {
+        msg: e.to_string(),
+        source: e,
+        path: path.display().to_string(),
+    })?;
+
+    set_readonly(file, path.display().to_string())
+}
+
+/// Renames the entry with the given name.
+/// Returns [`Error`] if no entry with the old name can be found or an entry with the new name
+/// already exists.
+pub fn rename_key<S, T>(
+    old_name: S,
+    new_name: T,
+    path: impl AsRef<Path>,
+    backend: Backend,
+) -> Result<(), Error>
+where
+    S: AsRef<str>,
+    T: AsRef<str>,
+{
+    open(&path, false, backend)?;
+
+    let old_path = existing_entry_path(old_name.as_ref(), &path).ok_or(Error::DoesNotExist {
+        name: old_name.as_ref().into(),
+        location: key_path(old_name.as_ref(), &path).display().to_string(),
+    })?;
+    check_name_is_free(new_name.as_ref(), &path)?;
+
+    let mut new_path = path.as_ref().join(new_name.as_ref());
+    if let Some(extension) = old_path.extension() {
+        new_path.set_extension(extension);
+    }
+
+    // linking fails rather than overwrites if the new entry already exists
+    fs::hard_link(&old_path, &new_path).map_err(|e| match e.kind() {
+        ErrorKind::NotFound => Error::DoesNotExist {
+            name: old_name.as_ref().into(),
+            location: old_path.display().to_string(),
+        },
+        ErrorKind::AlreadyExists => Error::AlreadyExists {
+            name: new_name.as_ref().into(),
+            location: new_path.display().to_string(),
+        },
+        _ => Error::FileIO {
+            msg: e.to_string(),
+            source: e,
+            path: new_path.display().to_string(),
+        },
+    })?;
+
+    if old_path.extension().and_then(|ext| ext.to_str()) == Some(PEM_EXTENSION) {
+        let old_info_path = info_path(old_name.as_ref(), &path);
+        let new_info_path = info_path(new_name.as_ref(), &path);
+
+        match fs::rename(&old_info_path, &new_info_path) {
+            Err(e) if e.kind() != ErrorKind::NotFound => {
+                return Err(Error::FileIO {
+                    msg: e.to_string(),
+                    source: e,
+                    path: new_info_path.display().to_string(),
+                })
+            }
+            _ => (),
+        }
+    }
+
+    remove_file(&old_path).map_err(|e| Error::FileIO {
+        msg: e.to_string(),
+        source: e,
+        path: old_path.display().to_string(),
+    })
+}
+
 /// Returns an [`Error`] if an entry with the same name already exists. If an entry already exists for
 /// the given key but with a different name then a new separate entry will be created.
 pub fn set_key_pair<S: AsRef<str>>(
@@ -247,28 +634,10 @@ pub fn set_key_pair<S: AsRef<str>>(
     backend: Backend,
 ) -> Result<(), Error> {
     let password = open(&path, true, backend)?;
+    check_name_is_free(key_name.as_ref(), &path)?;
 
-    let mut path = path.as_ref().join(key_name.as_ref());
-    path.set_extension(PEM_EXTENSION);
-
-    let mut file = OpenOptions::new()
-        .create_new(true)
-        .write(true)
-        .open(&path)
-        .map_err(|e| {
-            if e.kind() == ErrorKind::AlreadyExists {
-                Error::AlreadyExists {
-                    name: key_name.as_ref().into(),
-                    location: path.display().to_string(),
-                }
-            } else {
-                Error::FileIO {
-                    msg: e.to_string(),
-                    source: e,
-                    path: path.display().to_string(),
-                }
-            }
-        })?;
+    let key_path = key_path(key_name.as_ref(), &path);
+    let mut file = create_new_file(key_name.as_ref(), &key_path)?;
 
     let key = match password {
         Some(password) => key_pair.to_pkcs8_encrypted_pem(password),
@@ -278,10 +647,12 @@ pub fn set_key_pair<S: AsRef<str>>(
     file.write_all(key.as_bytes()).map_err(|e| Error::FileIO {
         msg: e.to_string(),
         source: e,
-        path: path.display().to_string(),
+        path: key_path.display().to_string(),
     })?;
 
-    set_readonly(file, path.display().to_string())
+    set_readonly(file, key_path.display().to_string())?;
+
+    write_info(key_name.as_ref(), key_pair, path)
 }
 
 /// Deletes the entry with the given name.
@@ -292,26 +663,30 @@ where
 {
     open(&path, false, backend)?;
 
-    let mut path = path.as_ref().join(name.as_ref());
-    path.set_extension(PEM_EXTENSION);
+    // the public key goes first so that it never outlives its key file
+    let entry_path =
+        existing_entry_path(name.as_ref(), &path).unwrap_or_else(|| key_path(name.as_ref(), &path));
+    if entry_path.extension().and_then(|ext| ext.to_str()) == Some(PEM_EXTENSION) {
+        remove_info(name.as_ref(), &path)?;
+    }
 
-    remove_file(&path).map_err(|e| {
+    remove_file(&entry_path).map_err(|e| {
         if e.kind() == ErrorKind::NotFound {
             Error::DoesNotExist {
                 name: name.as_ref().into(),
-                location: path.display().to_string(),
+                location: entry_path.display().to_string(),
             }
         } else {
             Error::FileIO {
                 msg: e.to_string(),
                 source: e,
-                path: path.display().to_string(),
+                path: entry_path.display().to_string(),
             }
         }
     })
 }
 
-#[derive(PartialEq)]
+#[derive(Clone, Copy, PartialEq)]
 pub enum Backend {
     Test,
     Encrypted,
@@ -1,18 +1,52 @@
 use std::path::Path;
 
+#[cfg(feature = "ethsecp256k1")]
+use crate::key_pair::eth_secp256k1_key_pair::EthSecp256k1KeyPair;
 use crate::{
     error::Error,
-    key_pair::{secp256k1_key_pair::Secp256k1KeyPair, KeyPair},
+    key_pair::{
+        ed25519_key_pair::Ed25519KeyPair, secp256k1_key_pair::Secp256k1KeyPair,
+        secp256r1_key_pair::Secp256r1KeyPair, KeyPair,
+    },
     key_store::file_store,
 };
-use bip32::Mnemonic;
-use k256::elliptic_curve::rand_core;
+use bip32::{DerivationPath, Mnemonic};
+use k256::elliptic_curve::{rand_core, zeroize::Zeroizing};
+use proto_messages::cosmos::{
+    crypto::multisig::LegacyAminoPubKey, tx::v1beta1::public_key::PublicKey,
+};
 
 use rand_core::OsRng;
 
+/// Coin type registered for Cosmos in SLIP-0044.
+pub const DEFAULT_COIN_TYPE: u32 = 118;
+
+/// HD path used to derive keys when none is given, this is the first account of [`DEFAULT_COIN_TYPE`].
+pub const DEFAULT_HD_PATH: &str = "m/44'/118'/0'/0/0";
+
+/// Returns the BIP44 HD path `m/44'/{coin_type}'/{account}'/0/{index}`.
+pub fn bip44_path(coin_type: u32, account: u32, index: u32) -> Result<DerivationPath, Error> {
+    parse_hd_path(&format!("m/44'/{coin_type}'/{account}'/0/{index}"))
+}
+
+/// Parses an HD path such as [`DEFAULT_HD_PATH`].
+pub fn parse_hd_path(hd_path: &str) -> Result<DerivationPath, Error> {
+    hd_path
+        .parse()
+        .map_err(|e: bip32::Error| Error::InvalidHDPath {
+            path: hd_path.into(),
+            msg: e.to_string(),
+        })
+}
+
 /// Used to specify the type of key to generate.
 pub enum KeyType {
     Secp256k1,
+    Secp256r1,
+    Ed25519,
+    /// Ethermint style secp256k1 key with a keccak256 address
+    #[cfg(feature = "ethsecp256k1")]
+    EthSecp256k1,
 }
 
 pub enum Backend<'a> {
@@ -20,10 +54,13 @@ pub enum Backend<'a> {
     Test(&'a Path),
 }
 
-/// Generates a key pair from the mnemonic provided and stores the keypair.
+/// Generates a key pair along the HD path from the mnemonic and BIP39 passphrase provided and
+/// stores the keypair.
 pub fn add_key<S>(
     name: S,
     mnemonic: &Mnemonic,
+    bip39_passphrase: &str,
+    hd_path: &DerivationPath,
     key_type: KeyType,
     backend: Backend,
 ) -> Result<KeyPair, Error>
@@ -31,7 +68,25 @@ where
     S: AsRef<str>,
 {
     let key_pair = match key_type {
-        KeyType::Secp256k1 => KeyPair::Secp256k1(Secp256k1KeyPair::from_mnemonic(mnemonic)),
+        KeyType::Secp256k1 => KeyPair::Secp256k1(Secp256k1KeyPair::from_mnemonic_with_path(
+            mnemonic,
+            bip39_passphrase,
+            hd_path,
+        )),
+        KeyType::Secp256r1 => KeyPair::Secp256r1(Secp256r1KeyPair::from_mnemonic_with_path(
+            mnemonic,
+            bip39_passphrase,
+            hd_path,
+        )),
+        KeyType::Ed25519 => KeyPair::Ed25519(Ed25519KeyPair::from_mnemonic_with_path(
+            mnemonic,
+            bip39_passphrase,
+            hd_path,
+        )),
+        #[cfg(feature = "ethsecp256k1")]
+        KeyType::EthSecp256k1 => KeyPair::EthSecp256k1(
+            EthSecp256k1KeyPair::from_mnemonic_with_path(mnemonic, bip39_passphrase, hd_path),
+        ),
     };
 
     match backend {
@@ -50,6 +105,8 @@ where
 /// returns the generated mnemonic.
 pub fn create_key<S>(
     name: S,
+    bip39_passphrase: &str,
+    hd_path: &DerivationPath,
     key_type: KeyType,
     backend: Backend,
 ) -> Result<(Mnemonic, KeyPair), Error>
@@ -57,7 +114,14 @@ where
     S: AsRef<str>,
 {
     let mnemonic = Mnemonic::random(OsRng, bip32::Language::English);
-    let key_pair = add_key(name, &mnemonic, key_type, backend)?;
+    let key_pair = add_key(
+        name,
+        &mnemonic,
+        bip39_passphrase,
+        hd_path,
+        key_type,
+        backend,
+    )?;
     Ok((mnemonic, key_pair))
 }
 
@@ -75,6 +139,21 @@ where
     //TODO: return key wrapped in Secret
 }
 
+/// Get the public key of a key by name, without the keyring passphrase where possible.
+pub fn get_public_key_by_name<S>(name: &S, backend: Backend) -> Result<PublicKey, Error>
+where
+    S: AsRef<str> + ?Sized,
+{
+    match backend {
+        Backend::File(path) => {
+            file_store::get_public_key_by_name(name, path, file_store::Backend::Encrypted)
+        }
+        Backend::Test(path) => {
+            file_store::get_public_key_by_name(name, path, file_store::Backend::Test)
+        }
+    }
+}
+
 /// Delete a key by name.
 pub fn delete_key_by_name<S>(name: S, backend: Backend) -> Result<(), Error>
 where
@@ -90,6 +169,109 @@ where
     }
 }
 
+/// Get the public keys of all keys and their names, sorted by name.
+pub fn list_keys(backend: Backend) -> Result<Vec<(String, PublicKey)>, Error> {
+    match backend {
+        Backend::File(path) => file_store::list_keys(path, file_store::Backend::Encrypted),
+        Backend::Test(path) => file_store::list_keys(path, file_store::Backend::Test),
+    }
+}
+
+/// Rename a key, fails if a key with the new name already exists.
+pub fn rename_key<S, T>(old_name: S, new_name: T, backend: Backend) -> Result<(), Error>
+where
+    S: AsRef<str>,
+    T: AsRef<str>,
+{
+    match backend {
+        Backend::File(path) => {
+            file_store::rename_key(old_name, new_name, path, file_store::Backend::Encrypted)
+        }
+        Backend::Test(path) => {
+            file_store::rename_key(old_name, new_name, path, file_store::Backend::Test)
+        }
+    }
+}
+
+/// Export a key as an armored PKCS8 PEM encrypted with the passphrase.
+pub fn export_key<S>(
+    name: &S,
+    passphrase: impl AsRef<[u8]>,
+    backend: Backend,
+) -> Result<Zeroizing<String>, Error>
+where
+    S: AsRef<str> + ?Sized,
+{
+    let key_pair = get_key_by_name(name, backend)?;
+    Ok(key_pair.to_pkcs8_encrypted_pem(passphrase))
+}
+
+/// Decrypt an armored key produced by [`export_key`] and store it under the given name.
+pub fn import_key<S>(
+    name: S,
+    armor: &str,
+    passphrase: impl AsRef<[u8]>,
+    backend: Backend,
+) -> Result<KeyPair, Error>
+where
+    S: AsRef<str>,
+{
+    let key_pair =
+        KeyPair::from_pkcs8_encrypted_pem(armor, passphrase).map_err(|e| Error::Armor {
+            msg: e.to_string(),
+            source: e,
+        })?;
+
+    match backend {
+        Backend::File(path) => {
+            file_store::set_key_pair(name, &key_pair, path, file_store::Backend::Encrypted)?;
+        }
+        Backend::Test(path) => {
+            file_store::set_key_pair(name, &key_pair, path, file_store::Backend::Test)?;
+        }
+    };
+
+    Ok(key_pair)
+}
+
+/// Store a multisig key built from public keys, fails if a key with the same name already exists.
+pub fn add_multisig_key<S>(name: S, key: &LegacyAminoPubKey, backend: Backend) -> Result<(), Error>
+where
+    S: AsRef<str>,
+{
+    match backend {
+        Backend::File(path) => {
+            file_store::set_multisig_key(name, key, path, file_store::Backend::Encrypted)
+        }
+        Backend::Test(path) => {
+            file_store::set_multisig_key(name, key, path, file_store::Backend::Test)
+        }
+    }
+}
+
+/// Get a multisig key by name.
+pub fn get_multisig_key_by_name<S>(name: &S, backend: Backend) -> Result<LegacyAminoPubKey, Error>
+where
+    S: AsRef<str> + ?Sized,
+{
+    match backend {
+        Backend::File(path) => {
+            file_store::get_multisig_key_by_name(name, path, file_store::Backend::Encrypted)
+        }
+        Backend::Test(path) => {
+            file_store::get_multisig_key_by_name(name, path, file_store::Backend::Test)
+        }
+    }
+}
+
+/// Get all multisig keys and their names, sorted by name.
+pub fn list_multisig_keys(backend: Backend) -> Result<Vec<(String, LegacyAminoPubKey)>, Error> {
+    match backend {
+        Backend::File(path) => file_store::list_multisig_keys(path, file_store::Backend::Encrypted),
+        Backend::Test(path) => file_store::list_multisig_keys(path, file_store::Backend::Test),
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -105,12 +287,27 @@ mod tests {
         // add key should succeed
         let mnemonic = "race draft rival universe maid cheese steel logic crowd fork comic easy truth drift tomorrow eye buddy head time cash swing swift midnight borrow";
         let mnemonic = Mnemonic::new(mnemonic, bip32::Language::English).unwrap();
-        add_key("bob", &mnemonic, KeyType::Secp256k1, Backend::Test(&path))
-            .expect("key should be added");
+        let hd_path = parse_hd_path(DEFAULT_HD_PATH).unwrap();
+        add_key(
+            "bob",
+            &mnemonic,
+            "",
+            &hd_path,
+            KeyType::Secp256k1,
+            Backend::Test(&path),
+        )
+        .expect("key should be added");
 
         // add key with same name should fail
-        let error = add_key("bob", &mnemonic, KeyType::Secp256k1, Backend::Test(&path))
-            .expect_err("key should not be added");
+        let error = add_key(
+            "bob",
+            &mnemonic,
+            "",
+            &hd_path,
+            KeyType::Secp256k1,
+            Backend::Test(&path),
+        )
+        .expect_err("key should not be added");
         assert!(matches!(error, Error::AlreadyExists { .. }));
 
         // get key should succeed
@@ -130,7 +327,14 @@ mod tests {
         assert!(matches!(error, Error::DoesNotExist { .. }));
 
         // create key should succeed
-        create_key("bob", KeyType::Secp256k1, Backend::Test(&path)).expect("key should be created");
+        create_key(
+            "bob",
+            "",
+            &hd_path,
+            KeyType::Secp256k1,
+            Backend::Test(&path),
+        )
+        .expect("key should be created");
 
         // get key should succeed
         get_key_by_name("bob", Backend::Test(&path)).expect("key should be retrieved");
@@ -142,4 +346,179 @@ mod tests {
             get_key_by_name("bob", Backend::Test(&path)).expect_err("keyring should fail to open");
         assert!(matches!(error, Error::KeyringDoesNotExist(_)));
     }
+
+    #[test]
+    fn hd_paths_parse() {
+        assert_eq!(
+            bip44_path(DEFAULT_COIN_TYPE, 0, 0).unwrap(),
+            parse_hd_path(DEFAULT_HD_PATH).unwrap()
+        );
+        assert_eq!(
+            bip44_path(60, 2, 3).unwrap().to_string(),
+            "m/44'/60'/2'/0/3"
+        );
+
+        let error = parse_hd_path("m/44'/x'").expect_err("path should be invalid");
+        assert!(matches!(error, Error::InvalidHDPath { .. }));
+
+        // hardened indices must be less than 2^31
+        let error = bip44_path(u32::MAX, 0, 0).expect_err("path should be invalid");
+        assert!(matches!(error, Error::InvalidHDPath { .. }));
+    }
+
+    #[test]
+    fn keyring_list_rename_export_import_works() {
+        let path =
+            PathBuf::from("./tmp/keyring/src/keyring/keyring_list_rename_export_import_works");
+        let _ = std::fs::remove_dir_all(&path);
+
+        let mnemonic = "race draft rival universe maid cheese steel logic crowd fork comic easy truth drift tomorrow eye buddy head time cash swing swift midnight borrow";
+        let mnemonic = Mnemonic::new(mnemonic, bip32::Language::English).unwrap();
+        let hd_path = parse_hd_path(DEFAULT_HD_PATH).unwrap();
+        let bob = add_key(
+            "bob",
+            &mnemonic,
+            "",
+            &hd_path,
+            KeyType::Secp256k1,
+            Backend::Test(&path),
+        )
+        .expect("key should be added");
+        create_key(
+            "alice",
+            "",
+            &hd_path,
+            KeyType::Secp256k1,
+            Backend::Test(&path),
+        )
+        .expect("key should be created");
+
+        // list is sorted by name
+        let names: Vec<String> = list_keys(Backend::Test(&path))
+            .expect("keys should be listed")
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+        assert_eq!(names, vec!["alice", "bob"]);
+
+        // rename to an existing name should fail
+        let error = rename_key("bob", "alice", Backend::Test(&path))
+            .expect_err("key should not be renamed");
+        assert!(matches!(error, Error::AlreadyExists { .. }));
+
+        // rename a missing key should fail
+        let error = rename_key("carol", "dave", Backend::Test(&path))
+            .expect_err("key should not be renamed");
+        assert!(matches!(error, Error::DoesNotExist { .. }));
+
+        rename_key("bob", "carol", Backend::Test(&path)).expect("key should be renamed");
+        let carol =
+            get_key_by_name("carol", Backend::Test(&path)).expect("key should be retrieved");
+        assert_eq!(carol.get_address(), bob.get_address());
+        let carol_public_key = get_public_key_by_name("carol", Backend::Test(&path))
+            .expect("public key should be retrieved");
+        assert_eq!(carol_public_key, bob.get_gears_public_key());
+
+        // keys stored without their public key are still listed
+        std::fs::remove_file(path.join("carol.info")).expect("public key file should be deleted");
+        let keys = list_keys(Backend::Test(&path)).expect("keys should be listed");
+        assert_eq!(keys[1], ("carol".to_string(), bob.get_gears_public_key()));
+        get_public_key_by_name("carol", Backend::Test(&path))
+            .expect("public key should be retrieved");
+        let error =
+            get_key_by_name("bob", Backend::Test(&path)).expect_err("key should not be retrieved");
+        assert!(matches!(error, Error::DoesNotExist { .. }));
+
+        // export then import under a new name
+        let armor = export_key("carol", "passphrase", Backend::Test(&path))
+            .expect("key should be exported");
+
+        let error = import_key("dave", &armor, "wrong", Backend::Test(&path))
+            .expect_err("key should not be imported");
+        assert!(matches!(error, Error::Armor { .. }));
+
+        let dave = import_key("dave", &armor, "passphrase", Backend::Test(&path))
+            .expect("key should be imported");
+        assert_eq!(dave.get_address(), bob.get_address());
+
+        let error = import_key("dave", &armor, "passphrase", Backend::Test(&path))
+            .expect_err("key should not be imported");
+        assert!(matches!(error, Error::AlreadyExists { .. }));
+
+        // keys derived along other paths differ
+        let hd_path = bip44_path(DEFAULT_COIN_TYPE, 1, 0).unwrap();
+        let erin = add_key(
+            "erin",
+            &mnemonic,
+            "",
+            &hd_path,
+            KeyType::Secp256k1,
+            Backend::Test(&path),
+        )
+        .expect("key should be added");
+        assert_ne!(erin.get_address(), bob.get_address());
+
+        std::fs::remove_dir_all(path.clone()).expect("tmp directory should be deleted");
+
+        // list should fail
+        let error = list_keys(Backend::Test(&path)).expect_err("keyring should fail to open");
+        assert!(matches!(error, Error::KeyringDoesNotExist(_)));
+    }
+
+    #[test]
+    fn keyring_multisig_works() {
+        let path = PathBuf::from("./tmp/keyring/src/keyring/keyring_multisig_works");
+        let _ = std::fs::remove_dir_all(&path);
+
+        let hd_path = parse_hd_path(DEFAULT_HD_PATH).unwrap();
+        let public_keys = ["alice", "bob", "carol"]
+            .into_iter()
+            .map(|name| {
+                create_key(name, "", &hd_path, KeyType::Secp256k1, Backend::Test(&path))
+                    .expect("key should be created")
+                    .1
+                    .get_gears_public_key()
+            })
+            .collect();
+        let multisig = LegacyAminoPubKey::new(2, public_keys).unwrap();
+
+        // name clashes with a key should fail, either way round
+        let error = add_multisig_key("alice", &multisig, Backend::Test(&path))
+            .expect_err("multisig should not be added");
+        assert!(matches!(error, Error::AlreadyExists { .. }));
+
+        add_multisig_key("treasury", &multisig, Backend::Test(&path))
+            .expect("multisig should be added");
+        let error = create_key(
+            "treasury",
+            "",
+            &hd_path,
+            KeyType::Secp256k1,
+            Backend::Test(&path),
+        )
+        .expect_err("key should not be created");
+        assert!(matches!(error, Error::AlreadyExists { .. }));
+
+        let retrieved = get_multisig_key_by_name("treasury", Backend::Test(&path))
+            .expect("multisig should be retrieved");
+        assert_eq!(retrieved, multisig);
+
+        // multisig entries are listed separately from keys
+        assert_eq!(list_keys(Backend::Test(&path)).unwrap().len(), 3);
+        let multisigs = list_multisig_keys(Backend::Test(&path)).unwrap();
+        assert_eq!(multisigs, vec![("treasury".to_string(), multisig.clone())]);
+
+        rename_key("treasury", "vault", Backend::Test(&path)).expect("multisig should be renamed");
+        get_multisig_key_by_name("vault", Backend::Test(&path))
+            .expect("multisig should be retrieved");
+
+        delete_key_by_name("vault", Backend::Test(&path)).expect("multisig should be deleted");
+        delete_key_by_name("alice", Backend::Test(&path)).expect("key should be deleted");
+        assert!(!path.join("alice.info").exists());
+        let error = get_multisig_key_by_name("vault", Backend::Test(&path))
+            .expect_err("multisig should not be retrieved");
+        assert!(matches!(error, Error::DoesNotExist { .. }));
+
+        std::fs::remove_dir_all(path).expect("tmp directory should be deleted");
+    }
 }
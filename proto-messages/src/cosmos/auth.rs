@@ -2,9 +2,11 @@ pub mod v1beta1 {
 
     use ibc_proto::{
         cosmos::auth::v1beta1::{
-            BaseAccount as RawBaseAccount, ModuleAccount as RawModuleAccount,
+            BaseAccount as RawBaseAccount, ModuleAccount as RawModuleAccount, Params as RawParams,
             QueryAccountRequest as RawQueryAccountRequest,
             QueryAccountResponse as RawQueryAccountResponse,
+            QueryParamsRequest as RawQueryParamsRequest,
+            QueryParamsResponse as RawQueryParamsResponse,
         },
         google::protobuf::Any,
         Protobuf,
@@ -255,6 +257,100 @@ pub mod v1beta1 {
     }
 
     impl Protobuf<RawQueryAccountResponse> for QueryAccountResponse {}
+
+    /// Params defines the parameters for the auth module.
+    #[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+    pub struct Params {
+        #[serde(deserialize_with = "deserialize_number_from_string")]
+        #[serde(serialize_with = "serialize_number_to_string")]
+        pub max_memo_characters: u64,
+        #[serde(deserialize_with = "deserialize_number_from_string")]
+        #[serde(serialize_with = "serialize_number_to_string")]
+        pub tx_sig_limit: u64,
+        #[serde(deserialize_with = "deserialize_number_from_string")]
+        #[serde(serialize_with = "serialize_number_to_string")]
+        pub tx_size_cost_per_byte: u64,
+        #[serde(deserialize_with = "deserialize_number_from_string")]
+        #[serde(serialize_with = "serialize_number_to_string")]
+        pub sig_verify_cost_ed25519: u64,
+        #[serde(deserialize_with = "deserialize_number_from_string")]
+        #[serde(serialize_with = "serialize_number_to_string")]
+        pub sig_verify_cost_secp256k1: u64,
+    }
+
+    impl From<RawParams> for Params {
+        fn from(raw: RawParams) -> Self {
+            Params {
+                max_memo_characters: raw.max_memo_characters,
+                tx_sig_limit: raw.tx_sig_limit,
+                tx_size_cost_per_byte: raw.tx_size_cost_per_byte,
+                sig_verify_cost_ed25519: raw.sig_verify_cost_ed25519,
+                sig_verify_cost_secp256k1: raw.sig_verify_cost_secp256k1,
+            }
+        }
+    }
+
+    impl From<Params> for RawParams {
+        fn from(params: Params) -> RawParams {
+            RawParams {
+                max_memo_characters: params.max_memo_characters,
+                tx_sig_limit: params.tx_sig_limit,
+                tx_size_cost_per_byte: params.tx_size_cost_per_byte,
+                sig_verify_cost_ed25519: params.sig_verify_cost_ed25519,
+                sig_verify_cost_secp256k1: params.sig_verify_cost_secp256k1,
+            }
+        }
+    }
+
+    impl Protobuf<RawParams> for Params {}
+
+    /// QueryParamsRequest is the request type for the Query/Params RPC method.
+    #[derive(Clone, PartialEq, Debug, Default)]
+    pub struct QueryParamsRequest {}
+
+    impl From<RawQueryParamsRequest> for QueryParamsRequest {
+        fn from(_raw: RawQueryParamsRequest) -> Self {
+            QueryParamsRequest {}
+        }
+    }
+
+    impl From<QueryParamsRequest> for RawQueryParamsRequest {
+        fn from(_query: QueryParamsRequest) -> RawQueryParamsRequest {
+            RawQueryParamsRequest {}
+        }
+    }
+
+    impl Protobuf<RawQueryParamsRequest> for QueryParamsRequest {}
+
+    /// QueryParamsResponse is the response type for the Query/Params RPC method.
+    #[derive(Clone, PartialEq, Serialize, Deserialize, Debug)]
+    pub struct QueryParamsResponse {
+        /// params defines the parameters of the module.
+        pub params: Params,
+    }
+
+    impl TryFrom<RawQueryParamsResponse> for QueryParamsResponse {
+        type Error = Error;
+
+        fn try_from(raw: RawQueryParamsResponse) -> Result<Self, Self::Error> {
+            let params = raw
+                .params
+                .ok_or(Error::MissingField("params".into()))?
+                .into();
+
+            Ok(QueryParamsResponse { params })
+        }
+    }
+
+    impl From<QueryParamsResponse> for RawQueryParamsResponse {
+        fn from(query: QueryParamsResponse) -> RawQueryParamsResponse {
+            RawQueryParamsResponse {
+                params: Some(query.params.into()),
+            }
+        }
+    }
+
+    impl Protobuf<RawQueryParamsResponse> for QueryParamsResponse {}
 }
 
 #[cfg(test)]
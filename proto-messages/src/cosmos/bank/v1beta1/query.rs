@@ -7,6 +7,7 @@ use ibc_proto::{
         QueryBalanceRequest as RawQueryBalanceRequest,
         QueryBalanceResponse as RawQueryBalanceResponse,
         QueryDenomMetadataRequest as RawQueryDenomMetadataRequest,
+        QueryTotalSupplyRequest as RawQueryTotalSupplyRequest,
         QueryTotalSupplyResponse as RawQueryTotalSupplyResponse,
     },
     cosmos::base::v1beta1::Coin as RawCoin,
@@ -61,7 +62,7 @@ impl From<QueryBalanceRequest> for RawQueryBalanceRequest {
 
 impl Protobuf<RawQueryBalanceRequest> for QueryBalanceRequest {}
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct PageRequest {
     pub key: Vec<u8>,
     pub offset: u64,
@@ -229,6 +230,34 @@ impl From<QueryBalanceResponse> for RawQueryBalanceResponse {
 
 impl Protobuf<RawQueryBalanceResponse> for QueryBalanceResponse {}
 
+/// QueryTotalSupplyRequest is the request type for the Query/TotalSupply RPC
+/// method
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct QueryTotalSupplyRequest {
+    /// pagination defines an optional pagination for the request.
+    ///
+    /// Since: cosmos-sdk 0.43
+    pub pagination: Option<PageRequest>,
+}
+
+impl From<RawQueryTotalSupplyRequest> for QueryTotalSupplyRequest {
+    fn from(raw: RawQueryTotalSupplyRequest) -> Self {
+        QueryTotalSupplyRequest {
+            pagination: raw.pagination.map(|this| this.into()),
+        }
+    }
+}
+
+impl From<QueryTotalSupplyRequest> for RawQueryTotalSupplyRequest {
+    fn from(query: QueryTotalSupplyRequest) -> RawQueryTotalSupplyRequest {
+        RawQueryTotalSupplyRequest {
+            pagination: query.pagination.map(|this| this.into()),
+        }
+    }
+}
+
+impl Protobuf<RawQueryTotalSupplyRequest> for QueryTotalSupplyRequest {}
+
 /// QueryTotalSupplyResponse is the response type for the Query/TotalSupply RPC
 /// method
 #[derive(Clone, PartialEq, Serialize, Deserialize)]
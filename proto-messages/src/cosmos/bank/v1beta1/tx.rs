@@ -79,6 +79,10 @@ impl Message for MsgSend {
     fn type_url(&self) -> &'static str {
         "/cosmos.bank.v1beta1.MsgSend"
     }
+
+    fn amino_url(&self) -> Option<&'static str> {
+        Some("cosmos-sdk/MsgSend")
+    }
 }
 
 impl TryFrom<Any> for MsgSend {
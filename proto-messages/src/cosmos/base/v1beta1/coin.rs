@@ -1,7 +1,10 @@
-use ibc_proto::{cosmos::base::v1beta1::Coin as RawCoin, Protobuf};
-use proto_types::{Denom, Uint256};
+use ibc_proto::{
+    cosmos::base::v1beta1::{Coin as RawCoin, DecCoin as RawDecCoin},
+    Protobuf,
+};
+use proto_types::{Decimal256, Denom, Uint256};
 use serde::{Deserialize, Serialize};
-use std::str::FromStr;
+use std::{fmt::Display, str::FromStr};
 
 use crate::error::Error;
 
@@ -57,6 +60,12 @@ impl FromStr for Coin {
     }
 }
 
+impl Display for Coin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.amount, self.denom)
+    }
+}
+
 // Represents a list of coins with the following properties:
 // - Contains at least one coin
 // - All coin amounts are positive
@@ -146,6 +155,158 @@ impl FromStr for SendCoins {
     }
 }
 
+impl Display for SendCoins {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let coins: Vec<String> = self.0.iter().map(Coin::to_string).collect();
+        write!(f, "{}", coins.join(","))
+    }
+}
+
+/// DecCoin defines a token with a denomination and a decimal amount.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct DecCoin {
+    pub denom: Denom,
+    pub amount: Decimal256,
+}
+
+impl TryFrom<RawDecCoin> for DecCoin {
+    type Error = Error;
+
+    fn try_from(value: RawDecCoin) -> Result<Self, Self::Error> {
+        let denom = value
+            .denom
+            .try_into()
+            .map_err(|_| Error::Coin(String::from("coin error")))?;
+        let amount = Decimal256::from_str(&value.amount)
+            .map_err(|_| Error::Coin(String::from("coin error")))?;
+
+        Ok(DecCoin { denom, amount })
+    }
+}
+
+impl From<DecCoin> for RawDecCoin {
+    fn from(value: DecCoin) -> RawDecCoin {
+        RawDecCoin {
+            denom: value.denom.to_string(),
+            amount: value.amount.to_string(),
+        }
+    }
+}
+
+impl Protobuf<RawDecCoin> for DecCoin {}
+
+impl FromStr for DecCoin {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        // get the index at which amount ends and denom starts
+        let i = input
+            .find(|c: char| !c.is_numeric() && c != '.')
+            .unwrap_or(input.len());
+
+        let amount = input[..i]
+            .parse::<Decimal256>()
+            .map_err(|e| Error::Coin(format!("coin error: {}", e)))?;
+
+        let denom = input[i..]
+            .parse::<Denom>()
+            .map_err(|e| Error::Coin(format!("coin error: {}", e)))?;
+
+        Ok(DecCoin { denom, amount })
+    }
+}
+
+impl Display for DecCoin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.amount, self.denom)
+    }
+}
+
+// Represents a list of decimal coins with the following properties:
+// - All coin amounts are positive
+// - No duplicate denominations
+// - Sorted lexicographically
+// Unlike SendCoins the list may be empty.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize, Default)]
+pub struct DecCoins(Vec<DecCoin>);
+
+impl DecCoins {
+    pub fn new(coins: Vec<DecCoin>) -> Result<DecCoins, Error> {
+        Self::validate_coins(&coins)?;
+
+        Ok(DecCoins(coins))
+    }
+
+    fn validate_coins(coins: &[DecCoin]) -> Result<(), Error> {
+        if coins.iter().any(|coin| coin.amount.is_zero()) {
+            return Err(Error::Coins(String::from("coin amount must be positive")));
+        }
+
+        for pair in coins.windows(2) {
+            // Less than to ensure lexicographical ordering
+            // Equality to ensure that there are no duplications
+            if pair[1].denom.to_string() <= pair[0].denom.to_string() {
+                return Err(Error::Coins(String::from(
+                    "coins are not sorted and/or contain duplicates",
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, DecCoin> {
+        self.0.iter()
+    }
+
+    pub fn into_inner(self) -> Vec<DecCoin> {
+        self.0
+    }
+}
+
+impl IntoIterator for DecCoins {
+    type Item = DecCoin;
+    type IntoIter = std::vec::IntoIter<Self::Item>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl FromStr for DecCoins {
+    type Err = Error;
+
+    /// Parses a comma separated list of coins, e.g. "0.025uatom,0.1stake". Like the SDK's
+    /// ParseDecCoins zero amounts are removed and the coins are sorted.
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        if input.is_empty() {
+            return Ok(DecCoins::default());
+        }
+
+        let mut coins = vec![];
+        for coin in input.split(',') {
+            let coin = DecCoin::from_str(coin)?;
+            if !coin.amount.is_zero() {
+                coins.push(coin);
+            }
+        }
+        coins.sort_by_key(|coin| coin.denom.to_string());
+
+        Self::new(coins)
+    }
+}
+
+impl Display for DecCoins {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let coins: Vec<String> = self.0.iter().map(DecCoin::to_string).collect();
+        write!(f, "{}", coins.join(","))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::str::FromStr;
@@ -391,4 +552,33 @@ mod tests {
         let raw_coins = "100atom,30uatom ";
         SendCoins::from_str(raw_coins).unwrap_err();
     }
+
+    #[test]
+    fn dec_coins_from_string_successes() {
+        let coins = DecCoins::from_str("0.1stake,0.025uatom").unwrap();
+        assert_eq!(coins.to_string(), "0.025uatom,0.1stake");
+
+        // zero amounts are removed
+        let coins = DecCoins::from_str("0uatom,1stake").unwrap();
+        assert_eq!(coins.to_string(), "1stake");
+
+        let coins = DecCoins::from_str("").unwrap();
+        assert!(coins.is_empty());
+    }
+
+    #[test]
+    fn dec_coins_from_string_failure() {
+        // duplicate denomination
+        let raw_coins = "0.1uatom,0.2uatom";
+        DecCoins::from_str(raw_coins).unwrap_err();
+
+        let raw_coins = "0.1uatom,";
+        DecCoins::from_str(raw_coins).unwrap_err();
+
+        let raw_coins = "0.1.1uatom";
+        DecCoins::from_str(raw_coins).unwrap_err();
+
+        let raw_coins = "-0.1uatom";
+        DecCoins::from_str(raw_coins).unwrap_err();
+    }
 }
@@ -0,0 +1,162 @@
+pub mod v1beta1 {
+    use base64::{
+        engine::general_purpose::{self},
+        Engine,
+    };
+    use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+    use ibc_proto::Protobuf;
+    use proto_types::AccAddress;
+    use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+    use sha2::{Digest, Sha256};
+
+    use crate::{cosmos::tx::v1beta1::public_key::SigningError, Error};
+
+    pub const TYPE_URL: &str = "/cosmos.crypto.ed25519.PubKey";
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct RawPubKey {
+        #[prost(bytes = "vec", tag = "1")]
+        pub key: Vec<u8>,
+    }
+
+    #[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+    pub struct PubKey {
+        #[serde(serialize_with = "serialize_key", deserialize_with = "deserialize_key")]
+        key: VerifyingKey,
+    }
+
+    impl TryFrom<RawPubKey> for PubKey {
+        type Error = Error;
+
+        fn try_from(raw: RawPubKey) -> Result<Self, Self::Error> {
+            PubKey::try_from(raw.key)
+        }
+    }
+
+    impl From<PubKey> for RawPubKey {
+        fn from(key: PubKey) -> RawPubKey {
+            RawPubKey {
+                key: Vec::from(key),
+            }
+        }
+    }
+
+    impl Protobuf<RawPubKey> for PubKey {}
+
+    impl PubKey {
+        /// Returns a Tendermint style address: the first 20 bytes of SHA256(pubkey)
+        pub fn get_address(&self) -> AccAddress {
+            let hash = Sha256::digest(self.key.as_bytes());
+
+            hash[..20]
+                .try_into()
+                .expect("20 bytes is less than AccAddress::MAX_ADDR_LEN")
+        }
+
+        pub fn verify_signature(
+            &self,
+            message: impl AsRef<[u8]>,
+            signature: impl AsRef<[u8]>,
+        ) -> Result<(), SigningError> {
+            let signature = Signature::from_slice(signature.as_ref())?;
+            Ok(self.key.verify(message.as_ref(), &signature)?)
+        }
+    }
+
+    impl From<PubKey> for Vec<u8> {
+        fn from(key: PubKey) -> Vec<u8> {
+            key.key.to_bytes().to_vec()
+        }
+    }
+
+    impl TryFrom<Vec<u8>> for PubKey {
+        type Error = Error;
+
+        fn try_from(value: Vec<u8>) -> Result<Self, Self::Error> {
+            let key = decode_key(&value)
+                .map_err(|e| Error::DecodeGeneral(format!("invalid key: {e}")))?;
+
+            Ok(PubKey { key })
+        }
+    }
+
+    fn decode_key(bytes: &[u8]) -> Result<VerifyingKey, String> {
+        let bytes: &[u8; 32] = bytes
+            .try_into()
+            .map_err(|_| format!("expected 32 bytes, found {}", bytes.len()))?;
+
+        VerifyingKey::from_bytes(bytes).map_err(|e| e.to_string())
+    }
+
+    fn serialize_key<S>(key: &VerifyingKey, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        s.serialize_str(&general_purpose::STANDARD.encode(key.as_bytes()))
+    }
+
+    fn deserialize_key<'de, D>(deserializer: D) -> Result<VerifyingKey, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let v = String::deserialize(deserializer)?;
+        let key = general_purpose::STANDARD
+            .decode(&v)
+            .map_err(|e| de::Error::custom(format!("Error parsing public key '{}': {}", v, e)))?;
+
+        decode_key(&key)
+            .map_err(|e| de::Error::custom(format!("Error parsing public key '{}': {}", v, e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ed25519_dalek::{Signer, SigningKey};
+    use sha2::Digest;
+
+    use super::v1beta1::*;
+
+    fn signing_key() -> SigningKey {
+        SigningKey::from_bytes(&[7; 32])
+    }
+
+    fn pub_key() -> PubKey {
+        let key = signing_key().verifying_key().to_bytes().to_vec();
+        RawPubKey { key }.try_into().unwrap()
+    }
+
+    #[test]
+    fn get_address_works() {
+        let key = pub_key();
+        let address: Vec<u8> = key.get_address().into();
+
+        assert_eq!(address.len(), 20);
+        assert_eq!(address, sha2::Sha256::digest(Vec::from(key))[..20].to_vec());
+    }
+
+    #[test]
+    fn serde_round_trip_works() {
+        let key = pub_key();
+        let json = serde_json::to_string(&key).unwrap();
+        let decoded: PubKey = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(key, decoded);
+    }
+
+    #[test]
+    fn invalid_key_length_fails() {
+        let raw = RawPubKey { key: vec![1; 33] };
+        PubKey::try_from(raw).unwrap_err();
+    }
+
+    #[test]
+    fn verify_signature_works() {
+        let message = b"sign me";
+        let signature = signing_key().sign(message).to_bytes();
+
+        pub_key().verify_signature(message, signature).unwrap();
+        pub_key()
+            .verify_signature(b"other message", signature)
+            .unwrap_err();
+    }
+}
@@ -0,0 +1,190 @@
+pub mod v1 {
+    use base64::{
+        engine::general_purpose::{self},
+        Engine,
+    };
+    use ibc_proto::Protobuf;
+    use proto_types::AccAddress;
+    use secp256k1::{ecdsa::Signature, Message, Secp256k1};
+    use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+    use sha3::{Digest, Keccak256};
+
+    use crate::{
+        cosmos::{
+            crypto::secp256k1::v1beta1::Secp256k1PubKey, tx::v1beta1::public_key::SigningError,
+        },
+        Error,
+    };
+
+    pub const TYPE_URL: &str = "/ethermint.crypto.v1.ethsecp256k1.PubKey";
+
+    /// Length of an Ethereum style signature: the 64 byte concatenation of r and s followed by
+    /// the recovery id
+    pub const RECOVERABLE_SIGNATURE_LENGTH: usize = 65;
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct RawPubKey {
+        #[prost(bytes = "vec", tag = "1")]
+        pub key: Vec<u8>,
+    }
+
+    /// An Ethermint secp256k1 public key, it differs from the Cosmos secp256k1 key in its address
+    /// derivation and signature scheme.
+    #[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+    pub struct PubKey {
+        #[serde(serialize_with = "serialize_key", deserialize_with = "deserialize_key")]
+        key: Secp256k1PubKey,
+    }
+
+    impl TryFrom<RawPubKey> for PubKey {
+        type Error = Error;
+
+        fn try_from(raw: RawPubKey) -> Result<Self, Self::Error> {
+            PubKey::try_from(raw.key)
+        }
+    }
+
+    impl From<PubKey> for RawPubKey {
+        fn from(key: PubKey) -> RawPubKey {
+            RawPubKey {
+                key: Vec::from(key),
+            }
+        }
+    }
+
+    impl Protobuf<RawPubKey> for PubKey {}
+
+    impl PubKey {
+        /// Returns an Ethereum style address: the last 20 bytes of KECCAK256(uncompressed pubkey)
+        /// where the uncompressed pubkey is taken without its 0x04 prefix
+        pub fn get_address(&self) -> AccAddress {
+            let hash = Keccak256::digest(&self.key.serialize_uncompressed()[1..]);
+
+            hash[12..].try_into().expect(
+                "address is 20 bytes which is less than AccAddress::MAX_ADDR_LEN so this will never fail",
+            )
+        }
+
+        /// Verifies an ECDSA signature over the KECCAK256 digest of the message. The signature
+        /// may include the recovery id, which is ignored, and must have a low s.
+        pub fn verify_signature(
+            &self,
+            message: impl AsRef<[u8]>,
+            signature: impl AsRef<[u8]>,
+        ) -> Result<(), SigningError> {
+            let mut signature = signature.as_ref();
+            if signature.len() == RECOVERABLE_SIGNATURE_LENGTH {
+                signature = &signature[..RECOVERABLE_SIGNATURE_LENGTH - 1];
+            }
+
+            // the secp256k1 library rejects signatures with a high s, as Ethereum does
+            let signature = Signature::from_compact(signature)?;
+            let message = Message::from_digest_slice(&Keccak256::digest(message.as_ref()))?;
+            Ok(Secp256k1::verification_only().verify_ecdsa(&message, &signature, &self.key)?)
+        }
+    }
+
+    impl From<PubKey> for Vec<u8> {
+        fn from(key: PubKey) -> Vec<u8> {
+            key.key.serialize().to_vec()
+        }
+    }
+
+    impl TryFrom<Vec<u8>> for PubKey {
+        type Error = Error;
+
+        fn try_from(value: Vec<u8>) -> Result<Self, Self::Error> {
+            let key = Secp256k1PubKey::from_slice(&value)
+                .map_err(|e| Error::DecodeGeneral(format!("invalid key: {e}")))?;
+
+            Ok(PubKey { key })
+        }
+    }
+
+    fn serialize_key<S>(key: &Secp256k1PubKey, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        s.serialize_str(&general_purpose::STANDARD.encode(key.serialize()))
+    }
+
+    fn deserialize_key<'de, D>(deserializer: D) -> Result<Secp256k1PubKey, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let v = String::deserialize(deserializer)?;
+        let key = general_purpose::STANDARD
+            .decode(&v)
+            .map_err(|e| de::Error::custom(format!("Error parsing public key '{}': {}", v, e)))?;
+
+        Secp256k1PubKey::from_slice(&key)
+            .map_err(|e| de::Error::custom(format!("Error parsing public key '{}': {}", v, e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use secp256k1::{Message, Secp256k1, SecretKey};
+    use sha3::{Digest, Keccak256};
+
+    use super::v1::*;
+
+    fn secret_key() -> SecretKey {
+        SecretKey::from_slice(&[7; 32]).unwrap()
+    }
+
+    fn pub_key() -> PubKey {
+        let key = secret_key()
+            .public_key(&Secp256k1::new())
+            .serialize()
+            .to_vec();
+        RawPubKey { key }.try_into().unwrap()
+    }
+
+    #[test]
+    fn get_address_works() {
+        // the go-ethereum address of the private key 0x0101...01
+        let key = SecretKey::from_slice(&[1; 32]).unwrap();
+        let key: PubKey = key
+            .public_key(&Secp256k1::new())
+            .serialize()
+            .to_vec()
+            .try_into()
+            .unwrap();
+        let address: Vec<u8> = key.get_address().into();
+
+        assert_eq!(
+            hex::encode(address),
+            "1a642f0e3c3af545e7acbd38b07251b3990914f1"
+        )
+    }
+
+    #[test]
+    fn serde_round_trip_works() {
+        let key = pub_key();
+        let json = serde_json::to_string(&key).unwrap();
+        let decoded: PubKey = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(key, decoded);
+        assert_eq!(Vec::from(key).len(), 33);
+    }
+
+    #[test]
+    fn verify_signature_works() {
+        let message = b"sign me";
+        let digest = Message::from_digest_slice(&Keccak256::digest(message)).unwrap();
+        let signature = Secp256k1::new()
+            .sign_ecdsa(&digest, &secret_key())
+            .serialize_compact();
+
+        // the recovery id isn't checked
+        let mut recoverable = signature.to_vec();
+        recoverable.push(1);
+
+        pub_key().verify_signature(message, signature).unwrap();
+        pub_key().verify_signature(message, &recoverable).unwrap();
+        pub_key()
+            .verify_signature(b"other message", &recoverable)
+            .unwrap_err();
+    }
+}
@@ -1 +1,6 @@
+pub mod ed25519;
+#[cfg(feature = "ethsecp256k1")]
+pub mod ethsecp256k1;
+pub mod multisig;
 pub mod secp256k1;
+pub mod secp256r1;
@@ -0,0 +1,467 @@
+use ibc_proto::{google::protobuf::Any, Protobuf};
+use prost::Message;
+use proto_types::AccAddress;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    cosmos::tx::v1beta1::{
+        mode_info::{CompactBitArray, ModeInfo, Multi, SignMode},
+        public_key::{PublicKey, SigningError},
+    },
+    Error,
+};
+
+use self::v1beta1::MultiSignature;
+
+pub const TYPE_URL: &str = "/cosmos.crypto.multisig.LegacyAminoPubKey";
+
+// Amino prefixes of the registered Tendermint and Ethermint key types, these are used to derive the address
+const AMINO_PREFIX_MULTISIG: [u8; 4] = [0x22, 0xC1, 0xF7, 0xE2];
+const AMINO_PREFIX_SECP256K1: [u8; 4] = [0xEB, 0x5A, 0xE9, 0x87];
+const AMINO_PREFIX_ED25519: [u8; 4] = [0x16, 0x24, 0xDE, 0x64];
+#[cfg(feature = "ethsecp256k1")]
+const AMINO_PREFIX_ETH_SECP256K1: [u8; 4] = [0xF3, 0xB3, 0xCD, 0x03];
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RawLegacyAminoPubKey {
+    #[prost(uint32, tag = "1")]
+    pub threshold: u32,
+    #[prost(message, repeated, tag = "2")]
+    pub public_keys: Vec<Any>,
+}
+
+/// LegacyAminoPubKey specifies a public key type which nests multiple public keys and a
+/// threshold, it uses legacy amino address rules.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(try_from = "UncheckedLegacyAminoPubKey")]
+pub struct LegacyAminoPubKey {
+    threshold: u32,
+    public_keys: Vec<PublicKey>,
+}
+
+#[derive(Deserialize)]
+struct UncheckedLegacyAminoPubKey {
+    threshold: u32,
+    public_keys: Vec<PublicKey>,
+}
+
+impl TryFrom<UncheckedLegacyAminoPubKey> for LegacyAminoPubKey {
+    type Error = Error;
+
+    fn try_from(value: UncheckedLegacyAminoPubKey) -> Result<Self, Self::Error> {
+        LegacyAminoPubKey::new(value.threshold, value.public_keys)
+    }
+}
+
+impl TryFrom<RawLegacyAminoPubKey> for LegacyAminoPubKey {
+    type Error = Error;
+
+    fn try_from(raw: RawLegacyAminoPubKey) -> Result<Self, Self::Error> {
+        let public_keys = raw
+            .public_keys
+            .into_iter()
+            .map(PublicKey::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        LegacyAminoPubKey::new(raw.threshold, public_keys)
+    }
+}
+
+impl From<LegacyAminoPubKey> for RawLegacyAminoPubKey {
+    fn from(key: LegacyAminoPubKey) -> RawLegacyAminoPubKey {
+        RawLegacyAminoPubKey {
+            threshold: key.threshold,
+            public_keys: key.public_keys.into_iter().map(Any::from).collect(),
+        }
+    }
+}
+
+impl Protobuf<RawLegacyAminoPubKey> for LegacyAminoPubKey {}
+
+impl LegacyAminoPubKey {
+    /// Returns a threshold multisig key, the order of the keys is preserved and determines the
+    /// address.
+    pub fn new(threshold: u32, public_keys: Vec<PublicKey>) -> Result<Self, Error> {
+        if threshold == 0 {
+            return Err(Error::Custom("threshold must be a positive integer".into()));
+        }
+
+        if public_keys.len() < threshold as usize {
+            return Err(Error::Custom(format!(
+                "threshold k of n multisignature: n ({}) < k ({})",
+                public_keys.len(),
+                threshold
+            )));
+        }
+
+        if public_keys
+            .iter()
+            .any(|key| matches!(key, PublicKey::Secp256r1(_)))
+        {
+            return Err(Error::Custom(
+                "secp256r1 keys have no amino encoding so can't be part of a multisig".into(),
+            ));
+        }
+
+        Ok(LegacyAminoPubKey {
+            threshold,
+            public_keys,
+        })
+    }
+
+    pub fn threshold(&self) -> u32 {
+        self.threshold
+    }
+
+    pub fn public_keys(&self) -> &[PublicKey] {
+        &self.public_keys
+    }
+
+    /// Returns the first 20 bytes of SHA256 of the amino encoded key
+    pub fn get_address(&self) -> AccAddress {
+        let hash = Sha256::digest(self.amino_bytes());
+
+        hash[..20]
+            .try_into()
+            .expect("20 bytes is less than AccAddress::MAX_ADDR_LEN")
+    }
+
+    /// Returns the amino binary encoding of the key
+    pub fn amino_bytes(&self) -> Vec<u8> {
+        let mut bytes = AMINO_PREFIX_MULTISIG.to_vec();
+
+        // field 1, varint
+        bytes.push(0x08);
+        encode_uvarint(self.threshold as u64, &mut bytes);
+
+        for key in &self.public_keys {
+            // field 2, length delimited
+            bytes.push(0x12);
+            let encoded = amino_encode(key);
+            encode_uvarint(encoded.len() as u64, &mut bytes);
+            bytes.extend(encoded);
+        }
+
+        bytes
+    }
+
+    /// Verifies that at least `threshold` of the keys flagged in the bit array signed the
+    /// sign bytes of their mode, nested multisig keys are verified recursively.
+    pub fn verify_multisignature(
+        &self,
+        multi: &Multi,
+        signature: &[u8],
+        get_sign_bytes: &impl Fn(&SignMode) -> Result<Vec<u8>, SigningError>,
+    ) -> Result<(), SigningError> {
+        let bitarray = multi
+            .bitarray
+            .as_ref()
+            .ok_or_else(|| SigningError::Multisig("missing bit array".into()))?;
+
+        let signatures = MultiSignature::decode(signature)
+            .map_err(|e| SigningError::Multisig(format!("invalid multisignature: {e}")))?
+            .signatures;
+
+        if bitarray.len() != self.public_keys.len() {
+            return Err(SigningError::Multisig(format!(
+                "bit array size is incorrect; expected {}, got {}",
+                self.public_keys.len(),
+                bitarray.len()
+            )));
+        }
+
+        let count = bitarray.count();
+        if signatures.len() != count || multi.mode_infos.len() != count {
+            return Err(SigningError::Multisig(format!(
+                "number of signatures doesn't match the bit array; expected {}, got {} signatures and {} mode infos",
+                count,
+                signatures.len(),
+                multi.mode_infos.len()
+            )));
+        }
+
+        if count < self.threshold as usize {
+            return Err(SigningError::Multisig(format!(
+                "not enough signatures; threshold {}, got {}",
+                self.threshold, count
+            )));
+        }
+
+        let signed_keys = self
+            .public_keys
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| bitarray.get_index(*i))
+            .map(|(_, key)| key);
+
+        for ((key, mode_info), signature) in signed_keys.zip(&multi.mode_infos).zip(&signatures) {
+            match (key, mode_info) {
+                (PublicKey::Multisig(key), ModeInfo::Multi(multi)) => {
+                    key.verify_multisignature(multi, signature, get_sign_bytes)?
+                }
+                (PublicKey::Multisig(_), ModeInfo::Single(_)) | (_, ModeInfo::Multi(_)) => {
+                    return Err(SigningError::Multisig(
+                        "mode info doesn't match the key type".into(),
+                    ))
+                }
+                (key, ModeInfo::Single(mode)) => {
+                    key.verify_signature(get_sign_bytes(mode)?, signature)?
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Combines signatures made by member keys into the mode info and signature of the
+    /// multisig key. The signatures may be given in any order.
+    pub fn combine_signatures(
+        &self,
+        signatures: Vec<(PublicKey, ModeInfo, Vec<u8>)>,
+    ) -> Result<(Multi, Vec<u8>), SigningError> {
+        let mut bitarray = CompactBitArray::new(self.public_keys.len());
+        let mut indexed = Vec::with_capacity(signatures.len());
+
+        for (key, mode_info, signature) in signatures {
+            let index = self
+                .public_keys
+                .iter()
+                .position(|k| k == &key)
+                .ok_or_else(|| {
+                    SigningError::Multisig(format!(
+                        "key {} is not part of the multisig",
+                        key.get_address()
+                    ))
+                })?;
+
+            if bitarray.get_index(index) {
+                return Err(SigningError::Multisig(format!(
+                    "duplicate signature for key {}",
+                    key.get_address()
+                )));
+            }
+
+            bitarray.set_index(index, true);
+            indexed.push((index, mode_info, signature));
+        }
+
+        indexed.sort_by_key(|(index, _, _)| *index);
+        let (mode_infos, signatures) = indexed
+            .into_iter()
+            .map(|(_, mode_info, signature)| (mode_info, signature))
+            .unzip();
+
+        Ok((
+            Multi {
+                bitarray: Some(bitarray),
+                mode_infos,
+            },
+            MultiSignature { signatures }.encode_to_vec(),
+        ))
+    }
+}
+
+fn amino_encode(key: &PublicKey) -> Vec<u8> {
+    let (prefix, key) = match key {
+        PublicKey::Secp256k1(key) => (AMINO_PREFIX_SECP256K1, Vec::from(key.clone())),
+        PublicKey::Ed25519(key) => (AMINO_PREFIX_ED25519, Vec::from(key.clone())),
+        PublicKey::Multisig(key) => return key.amino_bytes(),
+        #[cfg(feature = "ethsecp256k1")]
+        PublicKey::EthSecp256k1(key) => (AMINO_PREFIX_ETH_SECP256K1, Vec::from(key.clone())),
+        PublicKey::Secp256r1(_) => {
+            unreachable!("secp256r1 keys are rejected when a multisig key is created")
+        }
+    };
+
+    let mut bytes = prefix.to_vec();
+    encode_uvarint(key.len() as u64, &mut bytes);
+    bytes.extend(key);
+    bytes
+}
+
+fn encode_uvarint(mut value: u64, bytes: &mut Vec<u8>) {
+    while value >= 0x80 {
+        bytes.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    bytes.push(value as u8);
+}
+
+pub mod v1beta1 {
+    /// MultiSignature wraps the signatures from a multisig LegacyAminoPubKey, it is the
+    /// signature stored in the tx.
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct MultiSignature {
+        #[prost(bytes = "vec", repeated, tag = "1")]
+        pub signatures: Vec<Vec<u8>>,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ed25519_dalek::{Signer, SigningKey};
+
+    use super::*;
+
+    const SIGN_BYTES: &[u8] = b"sign me";
+
+    fn signing_keys() -> Vec<SigningKey> {
+        (1..=3).map(|i| SigningKey::from_bytes(&[i; 32])).collect()
+    }
+
+    fn public_key(signing_key: &SigningKey) -> PublicKey {
+        let key = signing_key.verifying_key().to_bytes().to_vec();
+        PublicKey::Ed25519(key.try_into().unwrap())
+    }
+
+    fn multisig_key() -> LegacyAminoPubKey {
+        LegacyAminoPubKey::new(2, signing_keys().iter().map(public_key).collect()).unwrap()
+    }
+
+    fn get_sign_bytes(mode: &SignMode) -> Result<Vec<u8>, SigningError> {
+        match mode {
+            SignMode::Direct => Ok(SIGN_BYTES.to_vec()),
+            _ => Err(SigningError::Multisig("sign mode not supported".into())),
+        }
+    }
+
+    fn sign(signers: &[usize]) -> Vec<(PublicKey, ModeInfo, Vec<u8>)> {
+        let signing_keys = signing_keys();
+        signers
+            .iter()
+            .map(|i| {
+                (
+                    public_key(&signing_keys[*i]),
+                    ModeInfo::Single(SignMode::Direct),
+                    signing_keys[*i].sign(SIGN_BYTES).to_vec(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn new_checks_threshold() {
+        let keys: Vec<PublicKey> = signing_keys().iter().map(public_key).collect();
+
+        LegacyAminoPubKey::new(0, keys.clone()).unwrap_err();
+        LegacyAminoPubKey::new(4, keys.clone()).unwrap_err();
+        LegacyAminoPubKey::new(3, keys).unwrap();
+    }
+
+    #[test]
+    fn amino_bytes_works() {
+        let key = multisig_key();
+        let bytes = key.amino_bytes();
+
+        // prefix, threshold then each key with its own prefix and length
+        assert_eq!(bytes[..6], [0x22, 0xC1, 0xF7, 0xE2, 0x08, 0x02]);
+        assert_eq!(bytes[6..12], [0x12, 0x25, 0x16, 0x24, 0xDE, 0x64]);
+        assert_eq!(bytes[12], 0x20);
+        assert_eq!(bytes.len(), 6 + 3 * 39);
+
+        let address: Vec<u8> = key.get_address().into();
+        assert_eq!(address, Sha256::digest(&bytes)[..20].to_vec());
+    }
+
+    #[test]
+    fn any_and_serde_round_trip_works() {
+        let key = PublicKey::Multisig(multisig_key());
+
+        let any = Any::from(key.clone());
+        assert_eq!(any.type_url, TYPE_URL);
+        assert_eq!(PublicKey::try_from(any).unwrap(), key);
+
+        let json = serde_json::to_string(&key).unwrap();
+        assert_eq!(serde_json::from_str::<PublicKey>(&json).unwrap(), key);
+
+        // threshold is validated on deserialization
+        let json = json.replace(r#""threshold":2"#, r#""threshold":4"#);
+        serde_json::from_str::<PublicKey>(&json).unwrap_err();
+    }
+
+    #[test]
+    fn verify_multisignature_works() {
+        let key = multisig_key();
+
+        // signatures out of order are combined in key order
+        let (multi, signature) = key.combine_signatures(sign(&[2, 0])).unwrap();
+        assert_eq!(multi.bitarray.as_ref().unwrap().elems, vec![0b1010_0000]);
+        key.verify_multisignature(&multi, &signature, &get_sign_bytes)
+            .unwrap();
+
+        let (multi, signature) = key.combine_signatures(sign(&[0, 1, 2])).unwrap();
+        key.verify_multisignature(&multi, &signature, &get_sign_bytes)
+            .unwrap();
+    }
+
+    #[test]
+    fn verify_multisignature_below_threshold_fails() {
+        let key = multisig_key();
+
+        let (multi, signature) = key.combine_signatures(sign(&[1])).unwrap();
+        key.verify_multisignature(&multi, &signature, &get_sign_bytes)
+            .unwrap_err();
+    }
+
+    #[test]
+    fn verify_multisignature_with_bad_signature_fails() {
+        let key = multisig_key();
+        let mut signatures = sign(&[0, 1]);
+        signatures[1].2 = signing_keys()[1].sign(b"other message").to_vec();
+
+        let (multi, signature) = key.combine_signatures(signatures).unwrap();
+        key.verify_multisignature(&multi, &signature, &get_sign_bytes)
+            .unwrap_err();
+    }
+
+    #[test]
+    fn combine_signatures_rejects_unknown_and_duplicate_keys() {
+        let key = multisig_key();
+
+        key.combine_signatures(sign(&[0, 0])).unwrap_err();
+
+        let other = SigningKey::from_bytes(&[9; 32]);
+        let signature = other.sign(SIGN_BYTES).to_vec();
+        key.combine_signatures(vec![(
+            public_key(&other),
+            ModeInfo::Single(SignMode::Direct),
+            signature,
+        )])
+        .unwrap_err();
+    }
+
+    #[test]
+    fn nested_multisig_works() {
+        let inner = multisig_key();
+        let outer_member = SigningKey::from_bytes(&[4; 32]);
+        let outer = LegacyAminoPubKey::new(
+            2,
+            vec![
+                PublicKey::Multisig(inner.clone()),
+                public_key(&outer_member),
+            ],
+        )
+        .unwrap();
+
+        let (inner_multi, inner_signature) = inner.combine_signatures(sign(&[0, 1])).unwrap();
+        let (multi, signature) = outer
+            .combine_signatures(vec![
+                (
+                    public_key(&outer_member),
+                    ModeInfo::Single(SignMode::Direct),
+                    outer_member.sign(SIGN_BYTES).to_vec(),
+                ),
+                (
+                    PublicKey::Multisig(inner),
+                    ModeInfo::Multi(inner_multi),
+                    inner_signature,
+                ),
+            ])
+            .unwrap();
+
+        outer
+            .verify_multisignature(&multi, &signature, &get_sign_bytes)
+            .unwrap();
+    }
+}
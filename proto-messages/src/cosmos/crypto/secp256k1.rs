@@ -73,7 +73,7 @@ pub mod v1beta1 {
             //TODO: secp256k1 lib cannot be used for bitcoin sig verification
             let signature = Signature::from_compact(signature.as_ref())?;
             let message = Message::from_hashed_data::<sha256::Hash>(message.as_ref());
-            Secp256k1::verification_only().verify_ecdsa(&message, &signature, &self.key)
+            Ok(Secp256k1::verification_only().verify_ecdsa(&message, &signature, &self.key)?)
         }
     }
 
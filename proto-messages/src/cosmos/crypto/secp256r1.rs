@@ -0,0 +1,178 @@
+pub mod v1beta1 {
+    use base64::{
+        engine::general_purpose::{self},
+        Engine,
+    };
+    use ibc_proto::Protobuf;
+    use p256::ecdsa::{signature::Verifier, Signature, VerifyingKey};
+    use proto_types::AccAddress;
+    use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+    use sha2::{Digest, Sha256};
+
+    use crate::{cosmos::tx::v1beta1::public_key::SigningError, Error};
+
+    pub const TYPE_URL: &str = "/cosmos.crypto.secp256r1.PubKey";
+
+    /// Protobuf message name, used when deriving the address
+    const MESSAGE_NAME: &str = "cosmos.crypto.secp256r1.PubKey";
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct RawPubKey {
+        #[prost(bytes = "vec", tag = "1")]
+        pub key: Vec<u8>,
+    }
+
+    #[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+    pub struct PubKey {
+        #[serde(serialize_with = "serialize_key", deserialize_with = "deserialize_key")]
+        key: VerifyingKey,
+    }
+
+    impl TryFrom<RawPubKey> for PubKey {
+        type Error = Error;
+
+        fn try_from(raw: RawPubKey) -> Result<Self, Self::Error> {
+            PubKey::try_from(raw.key)
+        }
+    }
+
+    impl From<PubKey> for RawPubKey {
+        fn from(key: PubKey) -> RawPubKey {
+            RawPubKey {
+                key: Vec::from(key),
+            }
+        }
+    }
+
+    impl Protobuf<RawPubKey> for PubKey {}
+
+    impl PubKey {
+        /// Returns an ADR-028 address: SHA256(SHA256(message name) || compressed pubkey)
+        pub fn get_address(&self) -> AccAddress {
+            let type_hash = Sha256::digest(MESSAGE_NAME);
+
+            let mut hasher = Sha256::new();
+            hasher.update(type_hash);
+            hasher.update(Vec::from(self.to_owned()));
+            let hash = hasher.finalize();
+
+            hash.as_slice().try_into().expect(
+                "sha256 digest size is 32 bytes which is less than AccAddress::MAX_ADDR_LEN",
+            )
+        }
+
+        /// Verifies an ECDSA signature over the SHA256 digest of the message, the signature must
+        /// be the 64 byte concatenation of r and s with a low s.
+        pub fn verify_signature(
+            &self,
+            message: impl AsRef<[u8]>,
+            signature: impl AsRef<[u8]>,
+        ) -> Result<(), SigningError> {
+            let signature =
+                Signature::from_slice(signature.as_ref()).map_err(SigningError::Secp256r1)?;
+
+            // reject malleable signatures, as the SDK does
+            if signature.normalize_s().is_some() {
+                return Err(SigningError::HighS);
+            }
+
+            self.key
+                .verify(message.as_ref(), &signature)
+                .map_err(SigningError::Secp256r1)
+        }
+    }
+
+    impl From<PubKey> for Vec<u8> {
+        fn from(key: PubKey) -> Vec<u8> {
+            key.key.to_encoded_point(true).as_bytes().to_vec()
+        }
+    }
+
+    impl TryFrom<Vec<u8>> for PubKey {
+        type Error = Error;
+
+        fn try_from(value: Vec<u8>) -> Result<Self, Self::Error> {
+            let key = VerifyingKey::from_sec1_bytes(&value)
+                .map_err(|e| Error::DecodeGeneral(format!("invalid key: {e}")))?;
+
+            Ok(PubKey { key })
+        }
+    }
+
+    fn serialize_key<S>(key: &VerifyingKey, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        s.serialize_str(&general_purpose::STANDARD.encode(key.to_encoded_point(true).as_bytes()))
+    }
+
+    fn deserialize_key<'de, D>(deserializer: D) -> Result<VerifyingKey, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let v = String::deserialize(deserializer)?;
+        let key = general_purpose::STANDARD
+            .decode(&v)
+            .map_err(|e| de::Error::custom(format!("Error parsing public key '{}': {}", v, e)))?;
+
+        VerifyingKey::from_sec1_bytes(&key)
+            .map_err(|e| de::Error::custom(format!("Error parsing public key '{}': {}", v, e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use p256::ecdsa::{signature::Signer, Signature, SigningKey};
+
+    use super::v1beta1::*;
+
+    fn signing_key() -> SigningKey {
+        SigningKey::from_slice(&[7; 32]).unwrap()
+    }
+
+    fn pub_key() -> PubKey {
+        let key = signing_key()
+            .verifying_key()
+            .to_encoded_point(true)
+            .as_bytes()
+            .to_vec();
+        RawPubKey { key }.try_into().unwrap()
+    }
+
+    #[test]
+    fn get_address_works() {
+        let address: Vec<u8> = pub_key().get_address().into();
+        assert_eq!(address.len(), 32);
+    }
+
+    #[test]
+    fn serde_round_trip_works() {
+        let key = pub_key();
+        let json = serde_json::to_string(&key).unwrap();
+        let decoded: PubKey = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(key, decoded);
+        assert_eq!(Vec::from(key).len(), 33);
+    }
+
+    #[test]
+    fn verify_signature_works() {
+        let message = b"sign me";
+        let signature: Signature = signing_key().sign(message);
+        let low_s = signature.normalize_s().unwrap_or(signature);
+
+        pub_key()
+            .verify_signature(message, low_s.to_bytes())
+            .unwrap();
+        pub_key()
+            .verify_signature(b"other message", low_s.to_bytes())
+            .unwrap_err();
+
+        // the high s form of the same signature is rejected
+        let (r, s) = low_s.split_scalars();
+        let high_s = Signature::from_scalars(r, -*s).unwrap();
+        pub_key()
+            .verify_signature(message, high_s.to_bytes())
+            .unwrap_err();
+    }
+}
@@ -1 +1,2 @@
 pub use ibc_proto::cosmos::auth::v1beta1::QueryAccountResponse as RawQueryAccountResponse;
+pub use ibc_proto::cosmos::auth::v1beta1::QueryParamsResponse as RawQueryParamsResponse;
@@ -91,7 +91,7 @@ impl From<RawMsgUpdateClient> for MsgUpdateClient {
     }
 }
 
-pub const RECOVER_CLIENT_TYPE_URL: &str = "ibc.core.client.v1.MsgRecoverClient";
+pub const RECOVER_CLIENT_TYPE_URL: &str = "/ibc.core.client.v1.MsgRecoverClient";
 
 #[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub struct MsgRecoverClient {
@@ -13,4 +13,20 @@ pub trait Message:
     fn validate_basic(&self) -> Result<(), String>;
 
     fn type_url(&self) -> &'static str;
+
+    /// Returns the Amino name of the message, used by the legacy Amino JSON sign mode. `None` if
+    /// the message can't be signed with that sign mode.
+    fn amino_url(&self) -> Option<&'static str> {
+        None
+    }
+
+    /// Returns the legacy Amino JSON encoding of the message: `{"type": <amino url>, "value": <message>}`.
+    /// Message enums should override this to encode the inner message.
+    fn to_amino_json(&self) -> Result<serde_json::Value, Error> {
+        let amino_url = self
+            .amino_url()
+            .ok_or_else(|| Error::AminoJsonUnsupported(self.type_url().to_string()))?;
+
+        Ok(serde_json::json!({ "type": amino_url, "value": self }))
+    }
 }
@@ -0,0 +1,149 @@
+use ibc_proto::{google::protobuf::Any, Protobuf};
+use proto_types::AccAddress;
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use serde_with::DisplayFromStr;
+
+use crate::error::Error;
+
+use super::{
+    mode_info::SignMode,
+    public_key::PublicKey,
+    tip::{RawTip, Tip},
+};
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RawSignDocDirectAux {
+    #[prost(bytes = "vec", tag = "1")]
+    pub body_bytes: Vec<u8>,
+    #[prost(message, optional, tag = "2")]
+    pub public_key: Option<Any>,
+    #[prost(string, tag = "3")]
+    pub chain_id: String,
+    #[prost(uint64, tag = "4")]
+    pub account_number: u64,
+    #[prost(uint64, tag = "5")]
+    pub sequence: u64,
+    #[prost(message, optional, tag = "6")]
+    pub tip: Option<RawTip>,
+}
+
+/// SignDocDirectAux is the type used for generating sign bytes for
+/// SIGN_MODE_DIRECT_AUX.
+///
+/// Since: cosmos-sdk 0.46
+#[serde_as]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SignDocDirectAux {
+    /// body_bytes is protobuf serialization of a TxBody that matches the
+    /// representation in TxRaw.
+    #[serde(with = "crate::utils::Base64Standard")]
+    pub body_bytes: Vec<u8>,
+    /// public_key is the public key of the signing account.
+    pub public_key: Option<PublicKey>,
+    /// chain_id is the identifier of the chain this transaction targets.
+    /// It prevents signed transactions from being used on another chain by an
+    /// attacker.
+    pub chain_id: String,
+    /// account_number is the account number of the account in state.
+    #[serde_as(as = "DisplayFromStr")]
+    pub account_number: u64,
+    /// sequence is the sequence number of the signing account.
+    #[serde_as(as = "DisplayFromStr")]
+    pub sequence: u64,
+    /// tip is the optional tip used for transactions fees paid in another denom.
+    /// It should be left empty if the signer is not the tipper for this
+    /// transaction.
+    pub tip: Option<Tip>,
+}
+
+impl TryFrom<RawSignDocDirectAux> for SignDocDirectAux {
+    type Error = Error;
+
+    fn try_from(raw: RawSignDocDirectAux) -> Result<Self, Self::Error> {
+        let public_key: Option<PublicKey> = match raw.public_key {
+            Some(any) => Some(any.try_into()?),
+            None => None,
+        };
+
+        let tip: Option<Tip> = match raw.tip {
+            Some(tip) => Some(tip.try_into()?),
+            None => None,
+        };
+
+        Ok(SignDocDirectAux {
+            body_bytes: raw.body_bytes,
+            public_key,
+            chain_id: raw.chain_id,
+            account_number: raw.account_number,
+            sequence: raw.sequence,
+            tip,
+        })
+    }
+}
+
+impl From<SignDocDirectAux> for RawSignDocDirectAux {
+    fn from(sign_doc: SignDocDirectAux) -> RawSignDocDirectAux {
+        RawSignDocDirectAux {
+            body_bytes: sign_doc.body_bytes,
+            public_key: sign_doc.public_key.map(Any::from),
+            chain_id: sign_doc.chain_id,
+            account_number: sign_doc.account_number,
+            sequence: sign_doc.sequence,
+            tip: sign_doc.tip.map(RawTip::from),
+        }
+    }
+}
+
+impl Protobuf<RawSignDocDirectAux> for SignDocDirectAux {}
+
+/// AuxSignerData is the intermediary format that an auxiliary signer (e.g. a
+/// tipper) builds and sends to the fee payer (who will build and broadcast the
+/// actual tx).
+///
+/// Since: cosmos-sdk 0.46
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AuxSignerData {
+    /// address is the bech32-encoded address of the auxiliary signer.
+    pub address: AccAddress,
+    /// sign_doc is the SIGN_MODE_DIRECT_AUX sign doc that the auxiliary signer
+    /// signs.
+    pub sign_doc: SignDocDirectAux,
+    /// mode is the signing mode of the auxiliary signer.
+    pub mode: SignMode,
+    /// sig is the signature of the sign doc.
+    #[serde(with = "crate::utils::Base64Standard")]
+    pub sig: Vec<u8>,
+}
+
+impl AuxSignerData {
+    /// Checks the data is complete and consistent, it doesn't verify the signature
+    pub fn validate_basic(&self) -> Result<(), Error> {
+        if self.mode != SignMode::DirectAux {
+            return Err(Error::Custom(format!(
+                "aux signers can only sign with {:?}, got {:?}",
+                SignMode::DirectAux,
+                self.mode
+            )));
+        }
+
+        if self.sig.is_empty() {
+            return Err(Error::MissingField("sig".into()));
+        }
+
+        let public_key = self
+            .sign_doc
+            .public_key
+            .as_ref()
+            .ok_or(Error::MissingField("public_key".into()))?;
+
+        if public_key.get_address() != self.address {
+            return Err(Error::Custom(format!(
+                "public key doesn't match the aux signer address {}",
+                self.address
+            )));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,213 @@
+use proto_types::AccAddress;
+use serde::Serialize;
+use serde_json::Value;
+use serde_with::serde_as;
+use serde_with::DisplayFromStr;
+
+use crate::{
+    cosmos::{
+        base::v1beta1::Coin,
+        tx::v1beta1::{fee::Fee, message::Message, tip::Tip, tx_body::TxBody},
+    },
+    error::Error,
+};
+
+/// StdFee is the legacy Amino JSON representation of a fee.
+#[serde_as]
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct StdFee {
+    pub amount: Vec<Coin>,
+    #[serde_as(as = "DisplayFromStr")]
+    pub gas: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payer: Option<AccAddress>,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub granter: String,
+}
+
+impl From<&Fee> for StdFee {
+    fn from(fee: &Fee) -> Self {
+        StdFee {
+            amount: fee.amount.clone().map(Vec::from).unwrap_or_default(),
+            gas: fee.gas_limit,
+            payer: fee.payer.clone(),
+            granter: fee.granter.clone(),
+        }
+    }
+}
+
+/// StdTip is the legacy Amino JSON representation of a tip.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct StdTip {
+    pub amount: Vec<Coin>,
+    pub tipper: AccAddress,
+}
+
+impl From<&Tip> for StdTip {
+    fn from(tip: &Tip) -> Self {
+        StdTip {
+            amount: tip.amount.clone().map(Vec::from).unwrap_or_default(),
+            tipper: tip.tipper.clone(),
+        }
+    }
+}
+
+/// StdSignDoc is the document signed with `SIGN_MODE_LEGACY_AMINO_JSON`.
+#[serde_as]
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct StdSignDoc {
+    #[serde_as(as = "DisplayFromStr")]
+    pub account_number: u64,
+    pub chain_id: String,
+    pub fee: StdFee,
+    pub memo: String,
+    pub msgs: Vec<Value>,
+    #[serde_as(as = "DisplayFromStr")]
+    pub sequence: u64,
+    #[serde_as(as = "DisplayFromStr")]
+    #[serde(skip_serializing_if = "is_zero")]
+    pub timeout_height: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tip: Option<StdTip>,
+}
+
+impl StdSignDoc {
+    pub fn new<M: Message>(
+        chain_id: String,
+        account_number: u64,
+        sequence: u64,
+        body: &TxBody<M>,
+        fee: &Fee,
+        tip: Option<&Tip>,
+    ) -> Result<Self, Error> {
+        Ok(StdSignDoc {
+            account_number,
+            chain_id,
+            fee: fee.into(),
+            memo: body.memo.clone(),
+            msgs: body
+                .messages
+                .iter()
+                .map(Message::to_amino_json)
+                .collect::<Result<_, _>>()?,
+            sequence,
+            timeout_height: body.timeout_height,
+            tip: tip.map(StdTip::from),
+        })
+    }
+
+    /// Returns the bytes to sign: the canonical JSON of the sign doc, with sorted keys, no
+    /// whitespace and HTML characters escaped as Go's JSON encoder does.
+    pub fn to_sign_bytes(&self) -> Vec<u8> {
+        let value = serde_json::to_value(self).expect("sign doc has string keys so never fails");
+
+        escape_html(&sort_json(value).to_string()).into_bytes()
+    }
+}
+
+/// Returns the JSON value with the keys of every object sorted.
+pub fn sort_json(value: Value) -> Value {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<(String, Value)> = map.into_iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+            Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (key, sort_json(value)))
+                    .collect(),
+            )
+        }
+        Value::Array(values) => Value::Array(values.into_iter().map(sort_json).collect()),
+        value => value,
+    }
+}
+
+// These characters can only appear inside JSON strings so it's safe to replace them everywhere
+fn escape_html(json: &str) -> String {
+    json.replace('<', "\\u003c")
+        .replace('>', "\\u003e")
+        .replace('&', "\\u0026")
+        .replace('\u{2028}', "\\u2028")
+        .replace('\u{2029}', "\\u2029")
+}
+
+fn is_zero(value: &u64) -> bool {
+    *value == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use proto_types::{Denom, Uint256};
+
+    use crate::cosmos::{bank::v1beta1::MsgSend, base::v1beta1::SendCoins};
+
+    use super::*;
+
+    fn tx_body(memo: &str) -> TxBody<MsgSend> {
+        TxBody {
+            messages: vec![MsgSend {
+                from_address: AccAddress::from_bech32(
+                    "cosmos12vrgunwvszgzpykdrqlx3m6puedvcajlxcyw8z",
+                )
+                .unwrap(),
+                to_address: AccAddress::from_bech32(
+                    "cosmos1syavy2npfyt9tcncdtsdzf7kny9lh777pahuux",
+                )
+                .unwrap(),
+                amount: SendCoins::new(vec![Coin {
+                    denom: Denom::try_from("uatom".to_string()).unwrap(),
+                    amount: Uint256::from(1u8),
+                }])
+                .unwrap(),
+            }],
+            memo: memo.to_string(),
+            timeout_height: 0,
+            extension_options: Vec::new(),
+            non_critical_extension_options: Vec::new(),
+        }
+    }
+
+    fn fee() -> Fee {
+        Fee {
+            amount: None,
+            gas_limit: 200000,
+            payer: None,
+            granter: String::new(),
+        }
+    }
+
+    #[test]
+    fn sign_bytes_works() {
+        let sign_doc =
+            StdSignDoc::new("test-chain".into(), 8, 6, &tx_body(""), &fee(), None).unwrap();
+
+        assert_eq!(
+            String::from_utf8(sign_doc.to_sign_bytes()).unwrap(),
+            r#"{"account_number":"8","chain_id":"test-chain","fee":{"amount":[],"gas":"200000"},"memo":"","msgs":[{"type":"cosmos-sdk/MsgSend","value":{"amount":[{"amount":"1","denom":"uatom"}],"from_address":"cosmos12vrgunwvszgzpykdrqlx3m6puedvcajlxcyw8z","to_address":"cosmos1syavy2npfyt9tcncdtsdzf7kny9lh777pahuux"}}],"sequence":"6"}"#
+        );
+    }
+
+    #[test]
+    fn sign_bytes_escapes_html() {
+        let mut body = tx_body("<a & b>");
+        body.timeout_height = 10;
+        let sign_doc = StdSignDoc::new("test-chain".into(), 8, 6, &body, &fee(), None).unwrap();
+
+        let sign_bytes = String::from_utf8(sign_doc.to_sign_bytes()).unwrap();
+
+        assert!(sign_bytes.contains(r#""memo":"\u003ca \u0026 b\u003e""#));
+        assert!(sign_bytes.ends_with(r#""sequence":"6","timeout_height":"10"}"#));
+    }
+
+    #[test]
+    fn sort_json_sorts_nested_objects() {
+        let value = serde_json::json!({ "b": [{ "d": 1, "c": 2 }], "a": { "f": 3, "e": 4 } });
+
+        assert_eq!(
+            sort_json(value).to_string(),
+            r#"{"a":{"e":4,"f":3},"b":[{"c":2,"d":1}]}"#
+        );
+    }
+}
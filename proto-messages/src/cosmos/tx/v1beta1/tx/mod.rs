@@ -1,7 +1,9 @@
 pub mod any_tx;
 pub mod auth_info;
+pub mod aux_signer_data;
 pub mod cbor;
 pub mod fee;
+pub mod legacy_amino;
 pub mod mode_info;
 pub mod public_key;
 pub mod screen;
@@ -45,10 +47,29 @@ mod tests {
     fn deserialize_pubkey_works() {
         let serialized = r#"{"@type":"/cosmos.crypto.secp256k1.PubKey","key":"ApUOHN/LEz1gJBCf1In3NO60UCQY5TjChIHyK84nbySM"}"#;
         let key: PublicKey = serde_json::from_str(serialized).unwrap();
-        let PublicKey::Secp256k1(key) = key;
+        let PublicKey::Secp256k1(key) = key else {
+            panic!("expected a secp256k1 key")
+        };
         assert_eq!(
             hex::encode(Vec::from(key)),
             "02950e1cdfcb133d6024109fd489f734eeb4502418e538c28481f22bce276f248c"
         );
     }
+
+    #[test]
+    fn pubkey_any_round_trip_works() {
+        use ibc_proto::google::protobuf::Any;
+
+        for serialized in [
+            r#"{"@type":"/cosmos.crypto.ed25519.PubKey","key":"SeOJ9s9l2bYHuvnXs2z4ne2Q1uBhLQ4V6fwLQvT6qW4="}"#,
+            r#"{"@type":"/cosmos.crypto.secp256r1.PubKey","key":"AiXjO/il+1VqFNe4g6Qzsq00DTL5keF7p3XNfbUg0lVg"}"#,
+        ] {
+            let key: PublicKey = serde_json::from_str(serialized).unwrap();
+            assert_eq!(serde_json::to_string(&key).unwrap(), serialized);
+
+            let any = Any::from(key.clone());
+            assert_eq!(any.type_url, key.type_url());
+            assert_eq!(PublicKey::try_from(any).unwrap(), key);
+        }
+    }
 }
@@ -100,12 +100,73 @@ impl TryFrom<RawSingle> for SignMode {
     }
 }
 
+/// CompactBitArray is an implementation of a space efficient bit array, bits are stored most
+/// significant bit first.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct CompactBitArray {
     pub extra_bits_stored: u32,
     pub elems: Vec<u8>,
 }
 
+impl CompactBitArray {
+    /// Returns a bit array of the given size with all bits unset
+    pub fn new(bits: usize) -> Self {
+        Self {
+            extra_bits_stored: (bits % 8) as u32,
+            elems: vec![0; (bits + 7) / 8],
+        }
+    }
+
+    /// Returns the number of bits in the array
+    pub fn len(&self) -> usize {
+        if self.extra_bits_stored == 0 {
+            self.elems.len() * 8
+        } else {
+            self.elems.len().saturating_sub(1) * 8 + self.extra_bits_stored as usize
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the bit at the given index, out of bounds bits are unset
+    pub fn get_index(&self, i: usize) -> bool {
+        if i >= self.len() {
+            return false;
+        }
+
+        self.elems[i >> 3] & (1 << (7 - (i % 8))) > 0
+    }
+
+    /// Sets the bit at the given index, returns false if the index is out of bounds
+    pub fn set_index(&mut self, i: usize, value: bool) -> bool {
+        if i >= self.len() {
+            return false;
+        }
+
+        if value {
+            self.elems[i >> 3] |= 1 << (7 - (i % 8));
+        } else {
+            self.elems[i >> 3] &= !(1 << (7 - (i % 8)));
+        }
+
+        true
+    }
+
+    /// Returns the number of set bits
+    pub fn count(&self) -> usize {
+        (0..self.len()).filter(|i| self.get_index(*i)).count()
+    }
+
+    /// Returns the number of set bits before the given index
+    pub fn num_true_bits_before(&self, index: usize) -> usize {
+        (0..index.min(self.len()))
+            .filter(|i| self.get_index(*i))
+            .count()
+    }
+}
+
 impl From<RawCompactBitArray> for CompactBitArray {
     fn from(value: RawCompactBitArray) -> Self {
         let RawCompactBitArray {
@@ -171,3 +232,35 @@ impl From<Multi> for RawMulti {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compact_bit_array_works() {
+        let mut bit_array = CompactBitArray::new(10);
+        assert_eq!(bit_array.len(), 10);
+        assert_eq!(bit_array.elems.len(), 2);
+        assert_eq!(bit_array.count(), 0);
+
+        assert!(bit_array.set_index(0, true));
+        assert!(bit_array.set_index(9, true));
+        assert!(!bit_array.set_index(10, true));
+        assert_eq!(bit_array.elems, vec![0b1000_0000, 0b0100_0000]);
+
+        assert!(bit_array.get_index(0));
+        assert!(!bit_array.get_index(1));
+        assert!(bit_array.get_index(9));
+        assert!(!bit_array.get_index(10));
+        assert_eq!(bit_array.count(), 2);
+        assert_eq!(bit_array.num_true_bits_before(9), 1);
+        assert_eq!(bit_array.num_true_bits_before(10), 2);
+
+        assert!(bit_array.set_index(0, false));
+        assert_eq!(bit_array.count(), 1);
+
+        assert_eq!(CompactBitArray::new(8).len(), 8);
+        assert!(CompactBitArray::new(0).is_empty());
+    }
+}
@@ -3,27 +3,75 @@ use prost::bytes::Bytes;
 use proto_types::AccAddress;
 use serde::{Deserialize, Serialize};
 
-use crate::{cosmos::crypto::secp256k1::v1beta1::PubKey as Secp256k1PubKey, error::Error};
+#[cfg(feature = "ethsecp256k1")]
+use crate::cosmos::crypto::ethsecp256k1::v1::PubKey as EthSecp256k1PubKey;
+use crate::{
+    cosmos::crypto::{
+        ed25519::v1beta1::PubKey as Ed25519PubKey, multisig::LegacyAminoPubKey,
+        secp256k1::v1beta1::PubKey as Secp256k1PubKey,
+        secp256r1::v1beta1::PubKey as Secp256r1PubKey,
+    },
+    error::Error,
+};
 
-pub type SigningError = secp256k1::Error;
+#[derive(Debug, thiserror::Error)]
+pub enum SigningError {
+    #[error(transparent)]
+    Secp256k1(#[from] secp256k1::Error),
+    #[error("ed25519 signature error: {0}")]
+    Ed25519(#[from] ed25519_dalek::SignatureError),
+    #[error("secp256r1 signature error: {0}")]
+    Secp256r1(p256::ecdsa::Error),
+    #[error("signature s value is not in the lower half of the curve order")]
+    HighS,
+    #[error("multisig error: {0}")]
+    Multisig(String),
+    #[error("{0}")]
+    SignBytes(String),
+}
 
 #[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
 #[serde(tag = "@type")]
 pub enum PublicKey {
     #[serde(rename = "/cosmos.crypto.secp256k1.PubKey")]
     Secp256k1(Secp256k1PubKey),
-    //Secp256r1(Vec<u8>),
-    //Ed25519(Vec<u8>),
-    //Multisig(Vec<u8>),
+    #[serde(rename = "/cosmos.crypto.secp256r1.PubKey")]
+    Secp256r1(Secp256r1PubKey),
+    #[serde(rename = "/cosmos.crypto.ed25519.PubKey")]
+    Ed25519(Ed25519PubKey),
+    #[serde(rename = "/cosmos.crypto.multisig.LegacyAminoPubKey")]
+    Multisig(LegacyAminoPubKey),
+    #[cfg(feature = "ethsecp256k1")]
+    #[serde(rename = "/ethermint.crypto.v1.ethsecp256k1.PubKey")]
+    EthSecp256k1(EthSecp256k1PubKey),
 }
 
 impl PublicKey {
     pub fn get_address(&self) -> AccAddress {
         match self {
             PublicKey::Secp256k1(key) => key.get_address(),
+            PublicKey::Secp256r1(key) => key.get_address(),
+            PublicKey::Ed25519(key) => key.get_address(),
+            PublicKey::Multisig(key) => key.get_address(),
+            #[cfg(feature = "ethsecp256k1")]
+            PublicKey::EthSecp256k1(key) => key.get_address(),
+        }
+    }
+
+    /// Returns the protobuf type URL of the key
+    pub fn type_url(&self) -> &'static str {
+        match self {
+            PublicKey::Secp256k1(_) => "/cosmos.crypto.secp256k1.PubKey",
+            PublicKey::Secp256r1(_) => crate::cosmos::crypto::secp256r1::v1beta1::TYPE_URL,
+            PublicKey::Ed25519(_) => crate::cosmos::crypto::ed25519::v1beta1::TYPE_URL,
+            PublicKey::Multisig(_) => crate::cosmos::crypto::multisig::TYPE_URL,
+            #[cfg(feature = "ethsecp256k1")]
+            PublicKey::EthSecp256k1(_) => crate::cosmos::crypto::ethsecp256k1::v1::TYPE_URL,
         }
     }
 
+    /// Verifies a signature made by a single key, multisig signatures are verified with
+    /// [`LegacyAminoPubKey::verify_multisignature`]
     pub fn verify_signature(
         &self,
         message: impl AsRef<[u8]>,
@@ -31,6 +79,13 @@ impl PublicKey {
     ) -> Result<(), SigningError> {
         match self {
             PublicKey::Secp256k1(key) => key.verify_signature(message, signature),
+            PublicKey::Secp256r1(key) => key.verify_signature(message, signature),
+            PublicKey::Ed25519(key) => key.verify_signature(message, signature),
+            PublicKey::Multisig(_) => Err(SigningError::Multisig(
+                "a multisig key requires multisig signature data".into(),
+            )),
+            #[cfg(feature = "ethsecp256k1")]
+            PublicKey::EthSecp256k1(key) => key.verify_signature(message, signature),
         }
     }
 }
@@ -45,6 +100,27 @@ impl TryFrom<Any> for PublicKey {
                     .map_err(|e| Error::DecodeGeneral(e.to_string()))?;
                 Ok(PublicKey::Secp256k1(key))
             }
+            "/cosmos.crypto.secp256r1.PubKey" => {
+                let key = Secp256r1PubKey::decode::<Bytes>(any.value.into())
+                    .map_err(|e| Error::DecodeGeneral(e.to_string()))?;
+                Ok(PublicKey::Secp256r1(key))
+            }
+            "/cosmos.crypto.ed25519.PubKey" => {
+                let key = Ed25519PubKey::decode::<Bytes>(any.value.into())
+                    .map_err(|e| Error::DecodeGeneral(e.to_string()))?;
+                Ok(PublicKey::Ed25519(key))
+            }
+            "/cosmos.crypto.multisig.LegacyAminoPubKey" => {
+                let key = LegacyAminoPubKey::decode::<Bytes>(any.value.into())
+                    .map_err(|e| Error::DecodeGeneral(e.to_string()))?;
+                Ok(PublicKey::Multisig(key))
+            }
+            #[cfg(feature = "ethsecp256k1")]
+            "/ethermint.crypto.v1.ethsecp256k1.PubKey" => {
+                let key = EthSecp256k1PubKey::decode::<Bytes>(any.value.into())
+                    .map_err(|e| Error::DecodeGeneral(e.to_string()))?;
+                Ok(PublicKey::EthSecp256k1(key))
+            }
             _ => Err(Error::DecodeAny(format!(
                 "Key type not recognized: {}",
                 any.type_url
@@ -60,6 +136,23 @@ impl From<PublicKey> for Any {
                 type_url: "/cosmos.crypto.secp256k1.PubKey".to_string(),
                 value: key.encode_vec(),
             },
+            PublicKey::Secp256r1(key) => Any {
+                type_url: "/cosmos.crypto.secp256r1.PubKey".to_string(),
+                value: key.encode_vec(),
+            },
+            PublicKey::Ed25519(key) => Any {
+                type_url: "/cosmos.crypto.ed25519.PubKey".to_string(),
+                value: key.encode_vec(),
+            },
+            PublicKey::Multisig(key) => Any {
+                type_url: "/cosmos.crypto.multisig.LegacyAminoPubKey".to_string(),
+                value: key.encode_vec(),
+            },
+            #[cfg(feature = "ethsecp256k1")]
+            PublicKey::EthSecp256k1(key) => Any {
+                type_url: "/ethermint.crypto.v1.ethsecp256k1.PubKey".to_string(),
+                value: key.encode_vec(),
+            },
         }
     }
 }
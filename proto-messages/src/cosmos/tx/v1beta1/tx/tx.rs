@@ -24,9 +24,13 @@ pub struct Tx<M: Message> {
     /// signatures is a list of signatures that matches the length and order of
     /// AuthInfo's signer_infos to allow connecting signature meta information like
     /// public key and signing mode by position.
-    #[serde(serialize_with = "crate::utils::serialize_vec_of_vec_to_vec_of_base64")]
+    #[serde(
+        serialize_with = "crate::utils::serialize_vec_of_vec_to_vec_of_base64",
+        deserialize_with = "crate::utils::deserialize_vec_of_base64_to_vec_of_vec"
+    )]
     pub signatures: Vec<Vec<u8>>,
-    #[serde(skip_serializing)]
+    /// not part of the JSON encoding, it's only extracted when decoding the protobuf encoding
+    #[serde(skip)]
     pub signatures_data: Vec<SignatureData>,
 }
 
@@ -155,3 +159,50 @@ impl<M: Message> From<Tx<M>> for RawTx {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::cosmos::{bank::v1beta1::MsgSend, tx::v1beta1::fee::Fee};
+
+    use super::*;
+
+    #[test]
+    fn tx_json_round_trip() {
+        let msg: MsgSend = serde_json::from_str(
+            r#"{
+                "from_address": "cosmos1ulav3hsenupswqfkw2y3sup5kgtqwnvqa8eyhs",
+                "to_address": "cosmos1ejrf4cur2wy6kfurg9f2jppp2h3afe5h6pkh5t",
+                "amount": [{ "denom": "uatom", "amount": "10000000" }]
+            }"#,
+        )
+        .expect("hard coded msg is valid");
+
+        let tx = Tx {
+            body: TxBody {
+                messages: vec![msg],
+                memo: "memo".to_string(),
+                timeout_height: 10,
+                extension_options: vec![],
+                non_critical_extension_options: vec![],
+            },
+            auth_info: AuthInfo {
+                signer_infos: vec![],
+                fee: Fee {
+                    amount: None,
+                    gas_limit: 200_000,
+                    payer: None,
+                    granter: String::new(),
+                },
+                tip: None,
+            },
+            signatures: vec![vec![1, 2, 3]],
+            signatures_data: vec![],
+        };
+
+        let json = serde_json::to_value(&tx).expect("tx is serializable");
+        assert_eq!(json["signatures"], serde_json::json!(["AQID"]));
+
+        let deserialized: Tx<MsgSend> = serde_json::from_value(json).expect("tx is deserializable");
+        assert_eq!(tx, deserialized);
+    }
+}
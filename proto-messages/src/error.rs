@@ -24,4 +24,6 @@ pub enum Error {
     Custom(String),
     #[error("invalid sign mode: `{0}`")]
     InvalidSignMode(i32),
+    #[error("message `{0}` can't be signed with the legacy Amino JSON sign mode")]
+    AminoJsonUnsupported(String),
 }
@@ -10,3 +10,11 @@ pub use error::Error;
 pub mod prost {
     pub use prost::*;
 }
+
+pub mod ibc_proto {
+    pub use ibc_proto::*;
+}
+
+pub mod serde_json {
+    pub use serde_json::*;
+}
@@ -3,7 +3,7 @@ use base64::{
     Engine,
 };
 use base64_serde::base64_serde_type;
-use serde::ser::SerializeSeq;
+use serde::{ser::SerializeSeq, Deserialize};
 
 pub fn serialize_number_to_string<T, S>(x: &T, s: S) -> Result<S::Ok, S::Error>
 where
@@ -28,3 +28,17 @@ where
 
     seq.end()
 }
+
+pub fn deserialize_vec_of_base64_to_vec_of_vec<'de, D>(d: D) -> Result<Vec<Vec<u8>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    Vec::<String>::deserialize(d)?
+        .iter()
+        .map(|b64| {
+            general_purpose::STANDARD
+                .decode(b64)
+                .map_err(serde::de::Error::custom)
+        })
+        .collect()
+}
@@ -1,10 +1,12 @@
 #![warn(rust_2018_idioms)]
 
-mod error;
+pub mod error;
 mod hash;
 mod query_store;
 mod store;
+mod tree;
 mod utils;
 
 pub use crate::query_store::*;
 pub use crate::store::*;
+pub use crate::tree::{TreeKind, TreeRange};
@@ -1,21 +1,25 @@
 use std::{collections::HashMap, ops::RangeBounds};
 
 use database::{Database, PrefixDB};
-use trees::iavl::{QueryTree, Range};
+use trees::iavl::{KeyValue, QueryTree};
 
-use crate::{error::Error, ImmutablePrefixStore, KVStore, KVStoreTrait, MultiStore, StoreKey};
+use crate::{
+    error::Error, ImmutablePrefixStore, KVStore, KVStoreTrait, MultiStore, StoreKey, TreeRange,
+};
 
-pub struct QueryMultiStore<'a, DB, SK> {
+/// An immutable view of every store at a committed version. It doesn't borrow the
+/// [`MultiStore`] so queries can run while blocks are being executed and committed.
+pub struct QueryMultiStore<DB, SK> {
     //head_version: u32,
     //head_commit_hash: [u8; 32],
-    stores: HashMap<&'a SK, QueryKVStore<'a, PrefixDB<DB>>>,
+    stores: HashMap<SK, QueryKVStore<PrefixDB<DB>>>,
 }
 
-impl<'a, DB: Database, SK: StoreKey> QueryMultiStore<'a, DB, SK> {
-    pub fn new(multi_store: &'a MultiStore<DB, SK>, version: u32) -> Result<Self, Error> {
+impl<DB: Database, SK: StoreKey> QueryMultiStore<DB, SK> {
+    pub fn new(multi_store: &MultiStore<DB, SK>, version: u32) -> Result<Self, Error> {
         let mut stores = HashMap::new();
         for (store, kv_store) in &multi_store.stores {
-            stores.insert(store, QueryKVStore::new(kv_store, version)?);
+            stores.insert(store.clone(), QueryKVStore::new(kv_store, version)?);
         }
 
         Ok(Self {
@@ -25,20 +29,28 @@ impl<'a, DB: Database, SK: StoreKey> QueryMultiStore<'a, DB, SK> {
         })
     }
 
-    pub fn get_kv_store(&self, store_key: &SK) -> &QueryKVStore<'a, PrefixDB<DB>> {
+    /// Loads every store from the DB at a committed version, the latest version is used if
+    /// `version` is `None`. Nothing is written so the DB may have been opened read only.
+    pub fn from_db(db: DB, version: Option<u32>) -> Result<Self, Error> {
+        let multi_store = MultiStore::new(db)?;
+        let version = version.unwrap_or(multi_store.get_head_version());
+        Self::new(&multi_store, version)
+    }
+
+    pub fn get_kv_store(&self, store_key: &SK) -> &QueryKVStore<PrefixDB<DB>> {
         self.stores
             .get(store_key)
             .expect("a store for every key is guaranteed to exist")
     }
 }
 
-pub struct QueryKVStore<'a, DB> {
-    persistent_store: QueryTree<'a, DB>,
+pub struct QueryKVStore<DB> {
+    persistent_store: QueryTree<DB>,
 }
 
-impl<DB: Database> KVStoreTrait for QueryKVStore<'_, DB> {
-    fn get(&self, k: &(impl AsRef<[u8]> + ?Sized)) -> Option<Vec<u8>> {
-        self.persistent_store.get(k.as_ref())
+impl<DB: Database> KVStoreTrait for QueryKVStore<DB> {
+    fn get(&self, k: &(impl AsRef<[u8]> + ?Sized)) -> Result<Option<Vec<u8>>, Error> {
+        Ok(self.persistent_store.get(k.as_ref())?)
     }
 
     // fn get_keys(&self, key_prefix: &(impl AsRef<[u8]> + ?Sized)) -> Vec<Vec<u8>> {
@@ -50,18 +62,42 @@ impl<DB: Database> KVStoreTrait for QueryKVStore<'_, DB> {
     // }
 }
 
-impl<'a, DB: Database> QueryKVStore<'a, DB> {
-    pub fn new(kv_store: &'a KVStore<DB>, version: u32) -> Result<Self, Error> {
+impl<DB: Database> QueryKVStore<DB> {
+    pub fn new(kv_store: &KVStore<DB>, version: u32) -> Result<Self, Error> {
         Ok(QueryKVStore {
-            persistent_store: QueryTree::new(&kv_store.persistent_store, version)?,
+            persistent_store: kv_store.persistent_store.query_tree(version)?,
         })
     }
 
-    pub fn range<R>(&self, range: R) -> Range<'_, R, DB>
+    pub fn range<R>(&self, range: R) -> TreeRange<'_, R, DB>
+    where
+        R: RangeBounds<Vec<u8>> + Clone,
+    {
+        self.persistent_store.range(range).into()
+    }
+
+    /// Same as [`QueryKVStore::range`] but iterates in descending key order
+    pub fn reverse_range<R>(&self, range: R) -> TreeRange<'_, R, DB>
     where
         R: RangeBounds<Vec<u8>> + Clone,
     {
-        self.persistent_store.range(range)
+        self.persistent_store.reverse_range(range).into()
+    }
+
+    /// Returns the number of keys in the store
+    pub fn size(&self) -> u32 {
+        self.persistent_store.size()
+    }
+
+    /// Returns the key and value at the given index, where keys are indexed in order
+    pub fn get_by_index(&self, index: u32) -> Result<Option<KeyValue>, Error> {
+        Ok(self.persistent_store.get_by_index(index)?)
+    }
+
+    /// Returns `Ok(index)` if the key is in the store, otherwise returns `Err(index)`
+    /// where index is the position at which the key would be inserted
+    pub fn get_index_of_key(&self, key: &[u8]) -> Result<Result<u32, u32>, Error> {
+        Ok(self.persistent_store.get_index_of_key(key)?)
     }
 
     pub fn get_immutable_prefix_store(
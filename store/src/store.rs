@@ -1,26 +1,31 @@
 use std::{
-    collections::BTreeMap,
+    cmp::Ordering,
+    collections::{btree_map, BTreeMap, BTreeSet},
     ops::{Bound, RangeBounds},
-    sync::Arc,
+    sync::{Arc, Mutex, MutexGuard},
 };
 
-use database::{Database, PrefixDB};
+use database::{Column, Database, PrefixDB};
 use std::{collections::HashMap, hash::Hash};
 use strum::IntoEnumIterator;
-use trees::iavl::{Range, Tree};
+use trees::iavl::{migrate_db, needs_migration, verify_db, KeyChange, KeyValue, VerifyReport};
 
-use crate::{error::Error, QueryKVStore};
+use crate::{
+    error::Error,
+    tree::{PersistentTree, TreeKind, TreeRange},
+    utils::MergedRange,
+    QueryKVStore,
+};
 
 use super::hash::{self, StoreInfo};
 
-const TREE_CACHE_SIZE: usize = 100_000;
-
 //TODO:
 // 1. move prefix store into separate file
 // 2. remove unwraps
 
 #[derive(Debug)]
 pub struct MultiStore<DB, SK> {
+    db: Arc<DB>,
     pub(crate) head_version: u32,
     pub(crate) head_commit_hash: [u8; 32],
     pub(crate) stores: HashMap<SK, KVStore<PrefixDB<DB>>>,
@@ -28,19 +33,23 @@ pub struct MultiStore<DB, SK> {
 
 pub trait StoreKey: Hash + Eq + IntoEnumIterator + Clone + Send + Sync + 'static {
     fn name(&self) -> &'static str;
+
+    /// The tree implementation used to persist the store
+    fn tree_kind(&self) -> TreeKind {
+        TreeKind::default()
+    }
 }
 
 impl<DB: Database, SK: StoreKey> MultiStore<DB, SK> {
-    pub fn new(db: DB) -> Self {
+    pub fn new(db: DB) -> Result<Self, Error> {
         let db = Arc::new(db);
         let mut store_infos = vec![];
         let mut stores = HashMap::new();
         let mut head_version = 0;
 
         for store in SK::iter() {
-            // TODO: check that store names are not prefixes
-            let prefix = store.name().as_bytes().to_vec();
-            let kv_store = KVStore::new(PrefixDB::new(db.clone(), prefix), None).unwrap();
+            let kv_store =
+                KVStore::new_with_tree_kind(Self::store_db(&db, &store), None, store.tree_kind())?;
 
             let store_info = StoreInfo {
                 name: store.name().into(),
@@ -53,11 +62,57 @@ impl<DB: Database, SK: StoreKey> MultiStore<DB, SK> {
             store_infos.push(store_info)
         }
 
-        MultiStore {
+        Ok(MultiStore {
+            db,
             head_version,
             head_commit_hash: hash::hash_store_infos(store_infos),
             stores,
+        })
+    }
+
+    /// Verifies a committed version of every store in the DB, or the latest version if `version`
+    /// is `None`. The stores aren't loaded so corruption is reported rather than causing a panic.
+    pub fn verify_db(db: DB, version: Option<u32>) -> Vec<(SK, Result<VerifyReport, Error>)> {
+        let db = Arc::new(db);
+
+        SK::iter()
+            .map(|store| {
+                let report = verify_db(Self::store_db(&db, &store), version).map_err(Error::from);
+                (store, report)
+            })
+            .collect()
+    }
+
+    /// Moves the nodes and roots of every store out of the default column, see
+    /// [`trees::iavl::migrate_db`]. This should be run once against the writable DB before the
+    /// stores are loaded.
+    pub fn migrate_db(db: &DB) -> Result<(), Error> {
+        let db = Arc::new(db.column(Column::Default));
+
+        for store in SK::iter() {
+            migrate_db(&Self::store_db(&db, &store))?;
         }
+
+        Ok(())
+    }
+
+    /// Returns whether [`MultiStore::migrate_db`] still has to be run, stores can't be read from
+    /// the DB until it has been.
+    pub fn needs_migration(db: &DB) -> Result<bool, Error> {
+        let db = Arc::new(db.column(Column::Default));
+
+        for store in SK::iter() {
+            if needs_migration(&Self::store_db(&db, &store))? {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+
+    fn store_db(db: &Arc<DB>, store: &SK) -> PrefixDB<DB> {
+        // TODO: check that store names are not prefixes
+        PrefixDB::new(db.clone(), store.name().as_bytes().to_vec())
     }
 
     pub fn get_kv_store(&self, store_key: &SK) -> &KVStore<PrefixDB<DB>> {
@@ -94,34 +149,100 @@ impl<DB: Database, SK: StoreKey> MultiStore<DB, SK> {
         }
     }
 
-    pub fn commit(&mut self) -> [u8; 32] {
+    /// Commits every store then flushes the DB. If a store fails to commit the multi store is left
+    /// in an inconsistent state, some stores may have been committed while others weren't.
+    pub fn commit(&mut self) -> Result<[u8; 32], Error> {
         let mut store_infos = vec![];
         for (store, kv_store) in &mut self.stores {
             let store_info = StoreInfo {
                 name: store.name().into(),
-                hash: kv_store.commit(),
+                hash: kv_store.commit()?,
             };
 
             store_infos.push(store_info)
         }
 
+        self.db.flush().map_err(trees::Error::from)?;
+
         let hash = hash::hash_store_infos(store_infos);
 
         self.head_commit_hash = hash;
         self.head_version += 1;
-        hash
+        Ok(hash)
     }
 }
 
 #[derive(Debug)]
 pub struct KVStore<DB> {
-    pub(crate) persistent_store: Tree<DB>,
+    pub(crate) persistent_store: PersistentTree<DB>,
     block_cache: BTreeMap<Vec<u8>, Vec<u8>>,
     tx_cache: BTreeMap<Vec<u8>, Vec<u8>>,
+    new_keys: Mutex<NewKeys>,
+}
+
+/// Tracks, in order, the cached keys which are not in the persistent store as keys are set so
+/// the store can be indexed without looking up every cached key
+#[derive(Debug, Default)]
+struct NewKeys {
+    block: CachedKeys,
+    /// Keys in the tx cache which aren't in the block cache
+    tx: CachedKeys,
+}
+
+impl NewKeys {
+    fn len(&self) -> usize {
+        self.block.new.len() + self.tx.new.len()
+    }
+
+    fn iter(&self) -> impl Iterator<Item = &Vec<u8>> {
+        // the sets are disjoint so their union is every new key in order
+        self.block.new.union(&self.tx.new)
+    }
+
+    fn contains(&self, key: &[u8]) -> bool {
+        self.block.new.contains(key) || self.tx.new.contains(key)
+    }
+
+    fn remove(&mut self, key: &[u8]) {
+        self.block.remove(key);
+        self.tx.remove(key);
+    }
+}
+
+#[derive(Debug, Default)]
+struct CachedKeys {
+    /// Keys which aren't in the persistent store
+    new: BTreeSet<Vec<u8>>,
+    /// Keys which haven't yet been looked up in the persistent store
+    unchecked: BTreeSet<Vec<u8>>,
+}
+
+impl CachedKeys {
+    fn check<DB: Database>(&mut self, persistent_store: &PersistentTree<DB>) -> Result<(), Error> {
+        while let Some(key) = self.unchecked.first() {
+            if persistent_store.get(key)?.is_none() {
+                self.new.insert(key.clone());
+            }
+
+            self.unchecked.pop_first();
+        }
+
+        Ok(())
+    }
+
+    fn extend(&mut self, other: CachedKeys) {
+        self.new.extend(other.new);
+        self.unchecked.extend(other.unchecked);
+    }
+
+    fn remove(&mut self, key: &[u8]) {
+        self.new.remove(key);
+        self.unchecked.remove(key);
+    }
 }
 
 impl<DB: Database> KVStoreTrait for KVStore<DB> {
-    fn get(&self, k: &(impl AsRef<[u8]> + ?Sized)) -> Option<Vec<u8>> {
+    fn get(&self, k: &(impl AsRef<[u8]> + ?Sized)) -> Result<Option<Vec<u8>>, Error> {
         let tx_cache_val = self.tx_cache.get(k.as_ref());
 
         if tx_cache_val.is_none() {
@@ -131,10 +252,10 @@ impl<DB: Database> KVStoreTrait for KVStore<DB> {
                 return self.persistent_store.get(k.as_ref());
             };
 
-            return block_cache_val.cloned();
+            return Ok(block_cache_val.cloned());
         }
 
-        tx_cache_val.cloned()
+        Ok(tx_cache_val.cloned())
     }
 
     // fn get_keys(&self, key_prefix: &(impl AsRef<[u8]> + ?Sized)) -> Vec<Vec<u8>> {
@@ -148,14 +269,19 @@ impl<DB: Database> KVStoreTrait for KVStore<DB> {
 
 impl<DB: Database> KVStore<DB> {
     pub fn new(db: DB, target_version: Option<u32>) -> Result<Self, Error> {
+        Self::new_with_tree_kind(db, target_version, TreeKind::default())
+    }
+
+    pub fn new_with_tree_kind(
+        db: DB,
+        target_version: Option<u32>,
+        tree_kind: TreeKind,
+    ) -> Result<Self, Error> {
         Ok(KVStore {
-            persistent_store: Tree::new(
-                db,
-                target_version,
-                TREE_CACHE_SIZE.try_into().expect("tree cache size is > 0"),
-            )?,
+            persistent_store: PersistentTree::new(db, target_version, tree_kind)?,
             block_cache: BTreeMap::new(),
             tx_cache: BTreeMap::new(),
+            new_keys: Mutex::new(NewKeys::default()),
         })
     }
 
@@ -179,21 +305,108 @@ impl<DB: Database> KVStore<DB> {
         }
     }
 
-    pub fn range<R>(&self, range: R) -> Range<'_, R, DB>
+    /// Iterates over the store's key value pairs, including those which have not yet been committed
+    pub fn range<R>(&self, range: R) -> KVStoreRange<'_, R, DB>
     where
         R: RangeBounds<Vec<u8>> + Clone,
     {
-        //TODO: this doesn't iterate over cached values
-        // let tx_cached_values = self.tx_cache.range(range.clone());
-        // let block_cached_values = self.block_cache.range(range.clone());
-        // let persisted_values = self.persistent_store.range(range.clone());
+        KVStoreRange::new(
+            &self.tx_cache,
+            &self.block_cache,
+            self.persistent_store.range(range.clone()),
+            range,
+            false,
+        )
+    }
 
-        // MergedRange::merge(
-        //     tx_cached_values,
-        //     MergedRange::merge(block_cached_values, persisted_values),
-        // );
+    /// Same as [`KVStore::range`] but iterates in descending key order
+    pub fn reverse_range<R>(&self, range: R) -> KVStoreRange<'_, R, DB>
+    where
+        R: RangeBounds<Vec<u8>> + Clone,
+    {
+        KVStoreRange::new(
+            &self.tx_cache,
+            &self.block_cache,
+            self.persistent_store.reverse_range(range.clone()),
+            range,
+            true,
+        )
+    }
 
-        self.persistent_store.range(range)
+    /// Returns the number of keys in the store
+    pub fn size(&self) -> Result<u32, Error> {
+        Ok(self.persistent_store.size() + self.new_keys()?.len() as u32)
+    }
+
+    /// Returns the key and value at the given index, where keys are indexed in order
+    pub fn get_by_index(&self, index: u32) -> Result<Option<KeyValue>, Error> {
+        let new_keys = self.new_keys()?;
+        let new_keys = new_keys.iter().collect::<Vec<_>>();
+
+        // the index of a new key is its insertion index in the tree plus the number of new keys
+        // before it, this increases with the key so the new keys before `index` are found by a
+        // binary search
+        let (mut preceding, mut end) = (0, new_keys.len());
+        while preceding < end {
+            let mid = (preceding + end) / 2;
+            let key = new_keys[mid];
+            let new_key_index = match self.persistent_store.get_index_of_key(key)? {
+                Ok(index) | Err(index) => index + mid as u32,
+            };
+
+            match new_key_index.cmp(&index) {
+                Ordering::Equal => {
+                    return Ok(self
+                        .cached_value(key)
+                        .map(|value| (key.clone(), value.clone())))
+                }
+                Ordering::Less => preceding = mid + 1,
+                Ordering::Greater => end = mid,
+            }
+        }
+
+        let Some((key, value)) = self
+            .persistent_store
+            .get_by_index(index - preceding as u32)?
+        else {
+            return Ok(None);
+        };
+        let value = self.cached_value(&key).cloned().unwrap_or(value);
+
+        Ok(Some((key, value)))
+    }
+
+    /// Returns `Ok(index)` if the key is in the store, otherwise returns `Err(index)`
+    /// where index is the position at which the key would be inserted
+    pub fn get_index_of_key(&self, key: &[u8]) -> Result<Result<u32, u32>, Error> {
+        let new_keys = self.new_keys()?;
+        let preceding = new_keys
+            .iter()
+            .take_while(|new_key| new_key.as_slice() < key)
+            .count();
+        let is_new_key = new_keys.contains(key);
+
+        Ok(match self.persistent_store.get_index_of_key(key)? {
+            Ok(index) => Ok(index + preceding as u32),
+            Err(index) if is_new_key => Ok(index + preceding as u32),
+            Err(index) => Err(index + preceding as u32),
+        })
+    }
+
+    /// Returns the value of a key from the tx cache or block cache
+    fn cached_value(&self, key: &[u8]) -> Option<&Vec<u8>> {
+        self.tx_cache.get(key).or_else(|| self.block_cache.get(key))
+    }
+
+    /// Returns the cached keys which are not in the persistent store, looking up any keys which
+    /// have been set since the last call
+    fn new_keys(&self) -> Result<MutexGuard<'_, NewKeys>, Error> {
+        let mut new_keys = self.new_keys.lock().expect("Mutex will not be poisoned");
+
+        new_keys.block.check(&self.persistent_store)?;
+        new_keys.tx.check(&self.persistent_store)?;
+
+        Ok(new_keys)
     }
 
     pub fn set(&mut self, key: impl IntoIterator<Item = u8>, value: impl IntoIterator<Item = u8>) {
@@ -204,15 +417,29 @@ impl<DB: Database> KVStore<DB> {
             panic!("key is empty")
         }
 
+        // a key already in either cache is already tracked
+        if !self.tx_cache.contains_key(&key) && !self.block_cache.contains_key(&key) {
+            self.new_keys
+                .get_mut()
+                .expect("Mutex will not be poisoned")
+                .tx
+                .unchecked
+                .insert(key.clone());
+        }
+
         self.tx_cache.insert(key, value.into_iter().collect());
     }
 
-    pub fn delete(&mut self, k: &[u8]) -> Option<Vec<u8>> {
+    pub fn delete(&mut self, k: &[u8]) -> Result<Option<Vec<u8>>, Error> {
         let tx_value = self.tx_cache.remove(k);
         let block_value = self.block_cache.remove(k);
-        let persisted_value = self.persistent_store.remove(k);
+        let persisted_value = self.persistent_store.remove(k)?;
+        self.new_keys
+            .get_mut()
+            .expect("Mutex will not be poisoned")
+            .remove(k);
 
-        tx_value.or(block_value).or(persisted_value)
+        Ok(tx_value.or(block_value).or(persisted_value))
     }
 
     /// Writes tx cache into block cache then clears the tx cache
@@ -228,15 +455,23 @@ impl<DB: Database> KVStore<DB> {
             self.block_cache.insert(key.to_owned(), value.to_owned());
         }
         self.tx_cache.clear();
+
+        let new_keys = self.new_keys.get_mut().expect("Mutex will not be poisoned");
+        let tx_keys = std::mem::take(&mut new_keys.tx);
+        new_keys.block.extend(tx_keys);
     }
 
     /// Clears the tx cache
     pub fn clear_tx_cache(&mut self) {
         self.tx_cache.clear();
+        self.new_keys
+            .get_mut()
+            .expect("Mutex will not be poisoned")
+            .tx = CachedKeys::default();
     }
 
     /// Writes block cache into the tree store then clears the block cache
-    fn write_then_clear_block_cache(&mut self) {
+    fn write_then_clear_block_cache(&mut self) -> Result<(), Error> {
         let mut keys: Vec<&Vec<u8>> = self.block_cache.keys().collect();
         keys.sort();
 
@@ -245,16 +480,20 @@ impl<DB: Database> KVStore<DB> {
                 .block_cache
                 .get(key)
                 .expect("key is definitely in the HashMap");
-            self.persistent_store.set(key.to_owned(), value.to_owned())
+            self.persistent_store
+                .set(key.to_owned(), value.to_owned())?;
         }
         self.block_cache.clear();
+        *self.new_keys.get_mut().expect("Mutex will not be poisoned") = NewKeys::default();
+
+        Ok(())
     }
 
-    pub fn commit(&mut self) -> [u8; 32] {
+    pub fn commit(&mut self) -> Result<[u8; 32], Error> {
         self.write_then_clear_tx_cache();
-        self.write_then_clear_block_cache();
-        let (hash, _) = self.persistent_store.save_version().unwrap(); //TODO: is it safe to assume this won't ever error?
-        hash
+        self.write_then_clear_block_cache()?;
+        let (hash, _) = self.persistent_store.save_version()?;
+        Ok(hash)
     }
 
     pub fn head_commit_hash(&self) -> [u8; 32] {
@@ -264,18 +503,28 @@ impl<DB: Database> KVStore<DB> {
     pub fn last_committed_version(&self) -> u32 {
         self.persistent_store.loaded_version()
     }
+
+    /// Returns the changes, ordered by key, between two committed versions
+    pub fn diff(&self, from_version: u32, to_version: u32) -> Result<Vec<KeyChange>, Error> {
+        self.persistent_store.diff(from_version, to_version)
+    }
+
+    /// Checks the integrity of a committed version, see [`trees::iavl::Tree::verify`]
+    pub fn verify(&self, version: u32) -> Result<VerifyReport, Error> {
+        self.persistent_store.verify(version)
+    }
 }
 
 /// Equivalent to [`BasicKVStore`](https://docs.cosmos.network/v0.46/core/store.html#base-layer-kvstores) from cosmos
 pub trait KVStoreTrait {
-    fn get(&self, k: &(impl AsRef<[u8]> + ?Sized)) -> Option<Vec<u8>>;
+    fn get(&self, k: &(impl AsRef<[u8]> + ?Sized)) -> Result<Option<Vec<u8>>, Error>;
     // fn get_keys(&self, key_prefix: &(impl AsRef<[u8]> + ?Sized)) -> Vec<Vec<u8>>;
     // TODO: range after PR merge
 }
 
 pub enum AnyKVStore<'a, DB: Database> {
     KVStore(&'a KVStore<DB>),
-    QueryKVStore(&'a QueryKVStore<'a, DB>),
+    QueryKVStore(&'a QueryKVStore<DB>),
 }
 
 impl<'a, DB: Database> From<&'a KVStore<DB>> for AnyKVStore<'a, DB> {
@@ -284,27 +533,66 @@ impl<'a, DB: Database> From<&'a KVStore<DB>> for AnyKVStore<'a, DB> {
     }
 }
 
-impl<'a, DB: Database> From<&'a QueryKVStore<'a, DB>> for AnyKVStore<'a, DB> {
-    fn from(kv_store: &'a QueryKVStore<'a, DB>) -> Self {
+impl<'a, DB: Database> From<&'a QueryKVStore<DB>> for AnyKVStore<'a, DB> {
+    fn from(kv_store: &'a QueryKVStore<DB>) -> Self {
         Self::QueryKVStore(kv_store)
     }
 }
 
 impl<'a, DB: Database> AnyKVStore<'a, DB> {
-    pub fn get(&self, k: &impl AsRef<[u8]>) -> Option<Vec<u8>> {
+    pub fn get(&self, k: &impl AsRef<[u8]>) -> Result<Option<Vec<u8>>, Error> {
         match self {
             AnyKVStore::KVStore(store) => store.get(k),
             AnyKVStore::QueryKVStore(store) => store.get(k),
         }
     }
 
-    pub fn range<R>(&self, range: R) -> Range<'_, R, DB>
+    pub fn range<R>(&self, range: R) -> KVStoreRange<'_, R, DB>
     where
         R: RangeBounds<Vec<u8>> + Clone,
     {
         match self {
             AnyKVStore::KVStore(store) => store.range(range),
-            AnyKVStore::QueryKVStore(store) => store.range(range),
+            AnyKVStore::QueryKVStore(store) => {
+                KVStoreRange::new(&EMPTY_CACHE, &EMPTY_CACHE, store.range(range), .., false)
+            }
+        }
+    }
+
+    pub fn reverse_range<R>(&self, range: R) -> KVStoreRange<'_, R, DB>
+    where
+        R: RangeBounds<Vec<u8>> + Clone,
+    {
+        match self {
+            AnyKVStore::KVStore(store) => store.reverse_range(range),
+            AnyKVStore::QueryKVStore(store) => KVStoreRange::new(
+                &EMPTY_CACHE,
+                &EMPTY_CACHE,
+                store.reverse_range(range),
+                ..,
+                true,
+            ),
+        }
+    }
+
+    pub fn size(&self) -> Result<u32, Error> {
+        match self {
+            AnyKVStore::KVStore(store) => store.size(),
+            AnyKVStore::QueryKVStore(store) => Ok(store.size()),
+        }
+    }
+
+    pub fn get_by_index(&self, index: u32) -> Result<Option<KeyValue>, Error> {
+        match self {
+            AnyKVStore::KVStore(store) => store.get_by_index(index),
+            AnyKVStore::QueryKVStore(store) => store.get_by_index(index),
+        }
+    }
+
+    pub fn get_index_of_key(&self, key: &[u8]) -> Result<Result<u32, u32>, Error> {
+        match self {
+            AnyKVStore::KVStore(store) => store.get_index_of_key(key),
+            AnyKVStore::QueryKVStore(store) => store.get_index_of_key(key),
         }
     }
 
@@ -323,12 +611,74 @@ pub struct ImmutablePrefixStore<'a, DB: Database> {
 }
 
 impl<'a, DB: Database> ImmutablePrefixStore<'a, DB> {
-    pub fn get(&self, k: &[u8]) -> Option<Vec<u8>> {
+    pub fn get(&self, k: &[u8]) -> Result<Option<Vec<u8>>, Error> {
         let full_key = [&self.prefix, k].concat();
         self.store.get(&full_key)
     }
 
     pub fn range<R: RangeBounds<Vec<u8>>>(&'a self, range: R) -> PrefixRange<'a, DB> {
+        PrefixRange {
+            parent_range: self.store.range(self.prefixed_bounds(range)),
+            prefix_length: self.prefix.len(),
+        }
+    }
+
+    /// Same as [`ImmutablePrefixStore::range`] but iterates in descending key order
+    pub fn reverse_range<R: RangeBounds<Vec<u8>>>(&'a self, range: R) -> PrefixRange<'a, DB> {
+        PrefixRange {
+            parent_range: self.store.reverse_range(self.prefixed_bounds(range)),
+            prefix_length: self.prefix.len(),
+        }
+    }
+
+    /// Returns the number of keys in the prefix store
+    pub fn size(&self) -> Result<u32, Error> {
+        let start = self.position(&self.prefix)?;
+        let end = match prefix_end_bound(self.prefix.clone()) {
+            Bound::Excluded(end) => self.position(&end)?,
+            _ => self.store.size()?,
+        };
+
+        Ok(end - start)
+    }
+
+    /// Returns the key (with the prefix removed) and value at the given index, where keys are indexed in order
+    pub fn get_by_index(&self, index: u32) -> Result<Option<KeyValue>, Error> {
+        let Some(index) = self.position(&self.prefix)?.checked_add(index) else {
+            return Ok(None);
+        };
+        let Some((key, value)) = self.store.get_by_index(index)? else {
+            return Ok(None);
+        };
+
+        Ok(key
+            .starts_with(&self.prefix)
+            .then(|| (key[self.prefix.len()..].to_vec(), value)))
+    }
+
+    /// Returns `Ok(index)` if the key is in the prefix store, otherwise returns `Err(index)`
+    /// where index is the position at which the key would be inserted
+    pub fn get_index_of_key(&self, k: &[u8]) -> Result<Result<u32, u32>, Error> {
+        let start = self.position(&self.prefix)?;
+        let full_key = [&self.prefix, k].concat();
+
+        Ok(match self.store.get_index_of_key(&full_key)? {
+            Ok(index) => Ok(index - start),
+            Err(index) => Err(index - start),
+        })
+    }
+
+    /// Returns the index of the first key in the parent store which is greater than or equal to the given key
+    fn position(&self, key: &[u8]) -> Result<u32, Error> {
+        match self.store.get_index_of_key(key)? {
+            Ok(index) | Err(index) => Ok(index),
+        }
+    }
+
+    fn prefixed_bounds<R: RangeBounds<Vec<u8>>>(
+        &self,
+        range: R,
+    ) -> (Bound<Vec<u8>>, Bound<Vec<u8>>) {
         let new_start = match range.start_bound() {
             Bound::Included(b) => Bound::Included([self.prefix.clone(), b.clone()].concat()),
             Bound::Excluded(b) => Bound::Excluded([self.prefix.clone(), b.clone()].concat()),
@@ -341,29 +691,29 @@ impl<'a, DB: Database> ImmutablePrefixStore<'a, DB> {
             Bound::Unbounded => prefix_end_bound(self.prefix.clone()),
         };
 
-        PrefixRange {
-            parent_range: self.store.range((new_start, new_end)),
-            prefix_length: self.prefix.len(),
-        }
+        (new_start, new_end)
     }
 }
 
 pub struct PrefixRange<'a, DB: Database> {
-    parent_range: Range<'a, (Bound<Vec<u8>>, Bound<Vec<u8>>), DB>,
+    parent_range: KVStoreRange<'a, (Bound<Vec<u8>>, Bound<Vec<u8>>), DB>,
     prefix_length: usize,
 }
 
 impl<'a, DB: Database> Iterator for PrefixRange<'a, DB> {
-    type Item = (Vec<u8>, Vec<u8>);
+    type Item = Result<KeyValue, Error>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let next = self.parent_range.next()?;
+        let (key, value) = match self.parent_range.next()? {
+            Ok(next) => next,
+            Err(e) => return Some(Err(e)),
+        };
 
         // Remove the prefix from the key - this is safe since all returned keys will include the prefix.
         // TODO: what if the key now has zero length, is this safe given the check on KVStore set.
-        let truncated_key = next.0[self.prefix_length..].to_vec();
+        let truncated_key = key[self.prefix_length..].to_vec();
 
-        Some((truncated_key, next.1))
+        Some(Ok((truncated_key, value)))
     }
 }
 
@@ -389,6 +739,95 @@ fn prefix_end_bound(mut prefix: Vec<u8>) -> Bound<Vec<u8>> {
     }
 }
 
+/// Used in place of the caches of a store which has none
+static EMPTY_CACHE: BTreeMap<Vec<u8>, Vec<u8>> = BTreeMap::new();
+
+type CacheMerge<'a, R, DB> =
+    MergedRange<CacheRange<'a>, MergedRange<CacheRange<'a>, TreeRange<'a, R, DB>, Error>, Error>;
+
+/// Iterates over a store's cached and persisted key value pairs, values in the tx cache take
+/// precedence over those in the block cache which take precedence over persisted values
+pub struct KVStoreRange<'a, R: RangeBounds<Vec<u8>>, DB: Database> {
+    merged_range: CacheMerge<'a, R, DB>,
+}
+
+impl<'a, R: RangeBounds<Vec<u8>>, DB: Database> KVStoreRange<'a, R, DB> {
+    fn new(
+        tx_cache: &'a BTreeMap<Vec<u8>, Vec<u8>>,
+        block_cache: &'a BTreeMap<Vec<u8>, Vec<u8>>,
+        persisted_range: TreeRange<'a, R, DB>,
+        range: impl RangeBounds<Vec<u8>>,
+        reverse: bool,
+    ) -> Self {
+        let tx_range = CacheRange::new(tx_cache, &range, reverse);
+        let block_range = CacheRange::new(block_cache, &range, reverse);
+
+        KVStoreRange {
+            merged_range: MergedRange::merge(
+                tx_range,
+                MergedRange::merge(block_range, persisted_range, reverse),
+                reverse,
+            ),
+        }
+    }
+}
+
+impl<'a, R: RangeBounds<Vec<u8>>, DB: Database> Iterator for KVStoreRange<'a, R, DB> {
+    type Item = Result<KeyValue, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.merged_range.next()
+    }
+}
+
+/// Iterates over a range of one of a store's caches
+struct CacheRange<'a> {
+    range: btree_map::Range<'a, Vec<u8>, Vec<u8>>,
+    reverse: bool,
+}
+
+impl<'a> CacheRange<'a> {
+    fn new(
+        cache: &'a BTreeMap<Vec<u8>, Vec<u8>>,
+        range: &impl RangeBounds<Vec<u8>>,
+        reverse: bool,
+    ) -> Self {
+        let start = range.start_bound().cloned();
+        let end = range.end_bound().cloned();
+
+        // BTreeMap::range panics on ranges which the tree treats as empty
+        let is_empty = match (&start, &end) {
+            (Bound::Excluded(start), Bound::Excluded(end)) => start >= end,
+            (
+                Bound::Included(start) | Bound::Excluded(start),
+                Bound::Included(end) | Bound::Excluded(end),
+            ) => start > end,
+            _ => false,
+        };
+
+        let range = match is_empty {
+            false => cache.range((start, end)),
+            true => EMPTY_CACHE.range::<Vec<u8>, _>(..),
+        };
+
+        CacheRange { range, reverse }
+    }
+}
+
+impl<'a> Iterator for CacheRange<'a> {
+    // a cache can't fail, the error type matches that of the persisted range it's merged with
+    type Item = Result<KeyValue, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (key, value) = match self.reverse {
+            false => self.range.next()?,
+            true => self.range.next_back()?,
+        };
+
+        Some(Ok((key.clone(), value.clone())))
+    }
+}
+
 /// Wraps an mutable reference to a KVStore with a prefix
 pub struct MutablePrefixStore<'a, DB: Database> {
     store: &'a mut KVStore<DB>,
@@ -396,7 +835,7 @@ pub struct MutablePrefixStore<'a, DB: Database> {
 }
 
 impl<'a, DB: Database> MutablePrefixStore<'a, DB> {
-    pub fn get(&self, k: &[u8]) -> Option<Vec<u8>> {
+    pub fn get(&self, k: &[u8]) -> Result<Option<Vec<u8>>, Error> {
         let full_key = [&self.prefix, k].concat();
         self.store.get(&full_key)
     }
@@ -407,7 +846,7 @@ impl<'a, DB: Database> MutablePrefixStore<'a, DB> {
         self.store.set(full_key, v)
     }
 
-    pub fn delete(&mut self, k: &[u8]) -> Option<Vec<u8>> {
+    pub fn delete(&mut self, k: &[u8]) -> Result<Option<Vec<u8>>, Error> {
         self.store.delete(k)
     }
 }
@@ -425,6 +864,96 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn tree_kinds_commit_hashes_match() {
+        let mut iavl_store = KVStore::new(MemDB::new(), None).unwrap();
+        let mut cached_store =
+            KVStore::new_with_tree_kind(MemDB::new(), None, TreeKind::Cached).unwrap();
+
+        for store in [&mut iavl_store, &mut cached_store] {
+            store.set(vec![1], vec![1]);
+            store.set(vec![2], vec![2]);
+            store.set(vec![3], vec![3]);
+            store.commit().unwrap();
+            store.set(vec![2], vec![4]);
+            store.delete(&[1]).unwrap();
+        }
+
+        assert_eq!(iavl_store.commit().unwrap(), cached_store.commit().unwrap());
+        assert_eq!(cached_store.get(&[2]).unwrap(), Some(vec![4]));
+        assert_eq!(cached_store.get(&[1]).unwrap(), None);
+    }
+
+    #[test]
+    fn kv_store_index_includes_cached_keys() {
+        let mut store = KVStore::new(MemDB::new(), None).unwrap();
+        store.set(vec![1], vec![1]);
+        store.set(vec![3], vec![3]);
+        store.commit().unwrap();
+
+        store.set(vec![2], vec![2]);
+        store.set(vec![3], vec![4]);
+        store.write_then_clear_tx_cache();
+        store.set(vec![0], vec![0]);
+        store.set(vec![4], vec![4]);
+
+        assert_eq!(store.size().unwrap(), 5);
+        assert_eq!(store.get_by_index(0).unwrap(), Some((vec![0], vec![0])));
+        assert_eq!(store.get_by_index(2).unwrap(), Some((vec![2], vec![2])));
+        assert_eq!(store.get_by_index(3).unwrap(), Some((vec![3], vec![4])));
+        assert_eq!(store.get_by_index(4).unwrap(), Some((vec![4], vec![4])));
+        assert_eq!(store.get_by_index(5).unwrap(), None);
+        assert_eq!(store.get_index_of_key(&[2]).unwrap(), Ok(2));
+        assert_eq!(store.get_index_of_key(&[3]).unwrap(), Ok(3));
+        assert_eq!(store.get_index_of_key(&[2, 1]).unwrap(), Err(3));
+
+        // the keys of a discarded tx are dropped while the block's are kept
+        store.clear_tx_cache();
+        assert_eq!(store.size().unwrap(), 3);
+        assert_eq!(store.get_by_index(1).unwrap(), Some((vec![2], vec![2])));
+        assert_eq!(store.get_index_of_key(&[4]).unwrap(), Err(3));
+
+        store.delete(&[2]).unwrap();
+        assert_eq!(store.size().unwrap(), 2);
+        assert_eq!(store.get_index_of_key(&[2]).unwrap(), Err(1));
+    }
+
+    #[test]
+    fn prefix_store_index_works() {
+        let db = MemDB::new();
+        let mut store = KVStore::new(db, None).unwrap();
+        store.set(vec![0], vec![1]);
+        store.set(vec![1], vec![4]);
+        store.set(vec![1, 1], vec![5]);
+        store.set(vec![1, 3], vec![7]);
+        store.set(vec![2], vec![10]);
+        store.commit().unwrap();
+
+        let prefix_store = store.get_immutable_prefix_store(vec![1]);
+
+        assert_eq!(prefix_store.size().unwrap(), 3);
+        assert_eq!(
+            prefix_store.get_by_index(0).unwrap(),
+            Some((vec![], vec![4]))
+        );
+        assert_eq!(
+            prefix_store.get_by_index(2).unwrap(),
+            Some((vec![3], vec![7]))
+        );
+        assert_eq!(prefix_store.get_by_index(3).unwrap(), None);
+        assert_eq!(prefix_store.get_index_of_key(&[1]).unwrap(), Ok(1));
+        assert_eq!(prefix_store.get_index_of_key(&[2]).unwrap(), Err(2));
+
+        let got_pairs: Vec<(Vec<u8>, Vec<u8>)> =
+            prefix_store.reverse_range(..).map(Result::unwrap).collect();
+        let expected_pairs = vec![(vec![3], vec![7]), (vec![1], vec![5]), (vec![], vec![4])];
+        assert_eq!(expected_pairs, got_pairs);
+
+        let prefix_store = store.get_immutable_prefix_store(vec![255]);
+        assert_eq!(prefix_store.size().unwrap(), 0);
+        assert_eq!(prefix_store.get_by_index(0).unwrap(), None);
+    }
+
     #[test]
     fn prefix_store_range_works() {
         let db = MemDB::new();
@@ -442,12 +971,13 @@ mod tests {
         store.set(vec![2, 1], vec![11]);
         store.set(vec![2, 2], vec![12]);
         store.set(vec![2, 3], vec![13]);
-        store.commit(); //TODO: this won't be needed once the KVStore iterator correctly incorporates cached values
+        store.commit().unwrap(); //TODO: this won't be needed once the KVStore iterator correctly incorporates cached values
 
         let prefix_store = store.get_immutable_prefix_store(vec![1]);
 
         // unbounded
-        let got_pairs: Vec<(Vec<u8>, Vec<u8>)> = prefix_store.range(..).collect();
+        let got_pairs: Vec<(Vec<u8>, Vec<u8>)> =
+            prefix_store.range(..).map(Result::unwrap).collect();
         let expected_pairs = vec![
             (vec![], vec![4]),
             (vec![1], vec![5]),
@@ -464,7 +994,10 @@ mod tests {
         }));
 
         // [,]
-        let got_pairs: Vec<(Vec<u8>, Vec<u8>)> = prefix_store.range(vec![1]..=vec![3]).collect();
+        let got_pairs: Vec<(Vec<u8>, Vec<u8>)> = prefix_store
+            .range(vec![1]..=vec![3])
+            .map(Result::unwrap)
+            .collect();
         let expected_pairs = vec![(vec![1], vec![5]), (vec![2], vec![6]), (vec![3], vec![7])];
 
         assert_eq!(expected_pairs.len(), got_pairs.len());
@@ -478,6 +1011,7 @@ mod tests {
         let stop = vec![3];
         let got_pairs: Vec<(Vec<u8>, Vec<u8>)> = prefix_store
             .range((Bound::Excluded(start), Bound::Excluded(stop)))
+            .map(Result::unwrap)
             .collect();
         let expected_pairs = vec![(vec![2], vec![6])];
 
@@ -516,50 +1050,67 @@ mod tests {
         assert!(matches!(prefix_end_bound(prefix), Bound::Unbounded));
     }
 
-    // TODO: uncomment this test once merged range works
-    // /// Tests whether kv range works with cached and persisted values
-    // #[test]
-    // fn kv_store_merged_range_works() {
-    //     let db = MemDB::new();
-    //     let mut store = KVStore::new(db, None).unwrap();
-
-    //     // values in this group will be in the persistent store
-    //     store.set(vec![1], vec![1]);
-    //     store.set(vec![7], vec![13]); // shadowed by value in tx cache
-    //     store.set(vec![10], vec![2]); // shadowed by value in block cache
-    //     store.set(vec![14], vec![234]); // shadowed by value in block cache and tx cache
-    //     store.commit();
-
-    //     // values in this group will be in the block cache
-    //     store.set(vec![2], vec![3]);
-    //     store.set(vec![9], vec![4]); // shadowed by value in tx cache
-    //     store.set(vec![10], vec![7]); // shadows a persisted value
-    //     store.set(vec![14], vec![212]); // shadows a persisted value AND shadowed by value in tx cache
-    //     store.write_then_clear_tx_cache();
-
-    //     // values in this group will be in the tx cache
-    //     store.set(vec![3], vec![5]);
-    //     store.set(vec![8], vec![6]);
-    //     store.set(vec![7], vec![5]); // shadows a persisted value
-    //     store.set(vec![9], vec![6]); // shadows a block cache value
-    //     store.set(vec![14], vec![212]); // shadows a persisted value which shadows a persisted value
-
-    //     let start = vec![0];
-    //     let stop = vec![20];
-    //     let got_pairs: Vec<(Vec<u8>, Vec<u8>)> = store
-    //         .range((Bound::Excluded(start), Bound::Excluded(stop)))
-    //         .collect();
-    //     let expected_pairs = vec![
-    //         (vec![1], vec![1]),
-    //         (vec![2], vec![3]),
-    //         (vec![3], vec![5]),
-    //         (vec![7], vec![5]),
-    //         (vec![8], vec![6]),
-    //         (vec![9], vec![6]),
-    //         (vec![10], vec![7]),
-    //         (vec![14], vec![212]),
-    //     ];
-
-    //     assert_eq!(expected_pairs, got_pairs);
-    // }
+    /// Tests whether kv range works with cached and persisted values
+    #[test]
+    fn kv_store_merged_range_works() {
+        let db = MemDB::new();
+        let mut store = KVStore::new(db, None).unwrap();
+
+        // values in this group will be in the persistent store
+        store.set(vec![1], vec![1]);
+        store.set(vec![7], vec![13]); // shadowed by value in tx cache
+        store.set(vec![10], vec![2]); // shadowed by value in block cache
+        store.set(vec![14], vec![234]); // shadowed by value in block cache and tx cache
+        store.commit().unwrap();
+
+        // values in this group will be in the block cache
+        store.set(vec![2], vec![3]);
+        store.set(vec![9], vec![4]); // shadowed by value in tx cache
+        store.set(vec![10], vec![7]); // shadows a persisted value
+        store.set(vec![14], vec![212]); // shadows a persisted value AND shadowed by value in tx cache
+        store.write_then_clear_tx_cache();
+
+        // values in this group will be in the tx cache
+        store.set(vec![3], vec![5]);
+        store.set(vec![8], vec![6]);
+        store.set(vec![7], vec![5]); // shadows a persisted value
+        store.set(vec![9], vec![6]); // shadows a block cache value
+        store.set(vec![14], vec![212]); // shadows a persisted value which shadows a persisted value
+
+        let start = vec![0];
+        let stop = vec![20];
+        let got_pairs: Vec<(Vec<u8>, Vec<u8>)> = store
+            .range((Bound::Excluded(start), Bound::Excluded(stop)))
+            .map(Result::unwrap)
+            .collect();
+        let expected_pairs = vec![
+            (vec![1], vec![1]),
+            (vec![2], vec![3]),
+            (vec![3], vec![5]),
+            (vec![7], vec![5]),
+            (vec![8], vec![6]),
+            (vec![9], vec![6]),
+            (vec![10], vec![7]),
+            (vec![14], vec![212]),
+        ];
+
+        assert_eq!(expected_pairs, got_pairs);
+
+        let got_pairs: Vec<(Vec<u8>, Vec<u8>)> =
+            store.reverse_range(..).map(Result::unwrap).collect();
+        assert!(got_pairs.iter().eq(expected_pairs.iter().rev()));
+
+        assert_eq!(store.size().unwrap(), 8);
+        for (index, (key, value)) in expected_pairs.into_iter().enumerate() {
+            let index = index as u32;
+            assert_eq!(
+                store.get_by_index(index).unwrap(),
+                Some((key.clone(), value))
+            );
+            assert_eq!(store.get_index_of_key(&key).unwrap(), Ok(index));
+        }
+        assert_eq!(store.get_by_index(8).unwrap(), None);
+        assert_eq!(store.get_index_of_key(&[4]).unwrap(), Err(3));
+        assert_eq!(store.get_index_of_key(&[20]).unwrap(), Err(8));
+    }
 }
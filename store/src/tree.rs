@@ -0,0 +1,171 @@
+use std::ops::RangeBounds;
+
+use database::Database;
+use trees::iavl::{
+    CachedRange, CachedTree, KeyChange, KeyValue, QueryTree, Range, Tree, VerifyReport,
+};
+
+use crate::error::Error;
+
+const TREE_CACHE_SIZE: usize = 100_000;
+
+/// The tree implementation used to persist a store. Both implementations
+/// produce identical root hashes so a store can be switched between them
+/// without affecting its app hash.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TreeKind {
+    /// Nodes are fetched from the node DB on every access
+    #[default]
+    Iavl,
+    /// Nodes are lazily loaded and kept in memory along with their hashes
+    Cached,
+}
+
+#[derive(Debug)]
+pub(crate) enum PersistentTree<DB> {
+    Iavl(Tree<DB>),
+    Cached(CachedTree<DB>),
+}
+
+impl<DB: Database> PersistentTree<DB> {
+    pub fn new(db: DB, target_version: Option<u32>, kind: TreeKind) -> Result<Self, Error> {
+        let cache_size = TREE_CACHE_SIZE.try_into().expect("tree cache size is > 0");
+
+        Ok(match kind {
+            TreeKind::Iavl => Self::Iavl(Tree::new(db, target_version, cache_size)?),
+            TreeKind::Cached => Self::Cached(CachedTree::new(db, target_version, cache_size)?),
+        })
+    }
+
+    pub fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>, Error> {
+        Ok(match self {
+            Self::Iavl(tree) => tree.get(key)?,
+            Self::Cached(tree) => tree.get(key)?,
+        })
+    }
+
+    pub fn set(&mut self, key: Vec<u8>, value: Vec<u8>) -> Result<(), Error> {
+        match self {
+            Self::Iavl(tree) => tree.set(key, value)?,
+            Self::Cached(tree) => tree.set(key, value)?,
+        }
+
+        Ok(())
+    }
+
+    pub fn remove(&mut self, key: &[u8]) -> Result<Option<Vec<u8>>, Error> {
+        Ok(match self {
+            Self::Iavl(tree) => tree.remove(key)?,
+            Self::Cached(tree) => tree.remove(key)?,
+        })
+    }
+
+    pub fn range<R>(&self, range: R) -> TreeRange<'_, R, DB>
+    where
+        R: RangeBounds<Vec<u8>>,
+    {
+        match self {
+            Self::Iavl(tree) => TreeRange::Iavl(tree.range(range)),
+            Self::Cached(tree) => TreeRange::Cached(tree.range(range)),
+        }
+    }
+
+    pub fn reverse_range<R>(&self, range: R) -> TreeRange<'_, R, DB>
+    where
+        R: RangeBounds<Vec<u8>>,
+    {
+        match self {
+            Self::Iavl(tree) => TreeRange::Iavl(tree.reverse_range(range)),
+            Self::Cached(tree) => TreeRange::Cached(tree.reverse_range(range)),
+        }
+    }
+
+    pub fn size(&self) -> u32 {
+        match self {
+            Self::Iavl(tree) => tree.size(),
+            Self::Cached(tree) => tree.size(),
+        }
+    }
+
+    pub fn get_by_index(&self, index: u32) -> Result<Option<KeyValue>, Error> {
+        Ok(match self {
+            Self::Iavl(tree) => tree.get_by_index(index)?,
+            Self::Cached(tree) => tree.get_by_index(index)?,
+        })
+    }
+
+    pub fn get_index_of_key(&self, key: &[u8]) -> Result<Result<u32, u32>, Error> {
+        Ok(match self {
+            Self::Iavl(tree) => tree.get_index_of_key(key)?,
+            Self::Cached(tree) => tree.get_index_of_key(key)?,
+        })
+    }
+
+    pub fn save_version(&mut self) -> Result<([u8; 32], u32), Error> {
+        Ok(match self {
+            Self::Iavl(tree) => tree.save_version()?,
+            Self::Cached(tree) => tree.save_version()?,
+        })
+    }
+
+    pub fn root_hash(&self) -> [u8; 32] {
+        match self {
+            Self::Iavl(tree) => tree.root_hash(),
+            Self::Cached(tree) => tree.root_hash(),
+        }
+    }
+
+    pub fn loaded_version(&self) -> u32 {
+        match self {
+            Self::Iavl(tree) => tree.loaded_version(),
+            Self::Cached(tree) => tree.loaded_version(),
+        }
+    }
+
+    pub fn diff(&self, from_version: u32, to_version: u32) -> Result<Vec<KeyChange>, Error> {
+        Ok(match self {
+            Self::Iavl(tree) => tree.diff(from_version, to_version)?,
+            Self::Cached(tree) => tree.diff(from_version, to_version)?,
+        })
+    }
+
+    pub fn verify(&self, version: u32) -> Result<VerifyReport, Error> {
+        Ok(match self {
+            Self::Iavl(tree) => tree.verify(version)?,
+            Self::Cached(tree) => tree.verify(version)?,
+        })
+    }
+
+    /// Returns an immutable tree which shares this tree's node DB and is "checked out" at the given version
+    pub fn query_tree(&self, version: u32) -> Result<QueryTree<DB>, Error> {
+        Ok(match self {
+            Self::Iavl(tree) => QueryTree::new(tree, version)?,
+            Self::Cached(tree) => QueryTree::from_cached(tree, version)?,
+        })
+    }
+}
+
+/// Iterates over a range of a store's tree
+pub enum TreeRange<'a, R: RangeBounds<Vec<u8>>, DB: Database> {
+    Iavl(Range<'a, R, DB>),
+    Cached(CachedRange<'a, R, DB>),
+}
+
+impl<'a, R: RangeBounds<Vec<u8>>, DB: Database> From<Range<'a, R, DB>> for TreeRange<'a, R, DB> {
+    fn from(range: Range<'a, R, DB>) -> Self {
+        Self::Iavl(range)
+    }
+}
+
+impl<'a, R: RangeBounds<Vec<u8>>, DB: Database> Iterator for TreeRange<'a, R, DB> {
+    type Item = Result<KeyValue, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let next = match self {
+            Self::Iavl(range) => range.next()?,
+            Self::Cached(range) => range.next()?,
+        };
+
+        Some(next.map_err(Error::from))
+    }
+}
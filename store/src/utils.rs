@@ -1,54 +1,61 @@
-use std::iter::Peekable;
+use std::{cmp::Ordering, iter::Peekable};
 
-/// Favours a over b if keys are equal (so make a the cache)
-pub struct MergedRange<'a, A, B>
+/// Merges two iterators which are ordered by key, favours a over b if keys are equal (so make a the cache).
+/// An error from either iterator is returned as soon as it is reached.
+pub struct MergedRange<A, B, E>
 where
-    A: Iterator<Item = (&'a Vec<u8>, &'a Vec<u8>)>,
-    B: Iterator<Item = (&'a Vec<u8>, &'a Vec<u8>)>,
+    A: Iterator<Item = Result<(Vec<u8>, Vec<u8>), E>>,
+    B: Iterator<Item = Result<(Vec<u8>, Vec<u8>), E>>,
 {
     a: Peekable<A>,
     b: Peekable<B>,
+    /// The iterators are in descending key order
+    reverse: bool,
 }
 
-impl<'a, A, B> MergedRange<'a, A, B>
+impl<A, B, E> MergedRange<A, B, E>
 where
-    A: Iterator<Item = (&'a Vec<u8>, &'a Vec<u8>)>,
-    B: Iterator<Item = (&'a Vec<u8>, &'a Vec<u8>)>,
+    A: Iterator<Item = Result<(Vec<u8>, Vec<u8>), E>>,
+    B: Iterator<Item = Result<(Vec<u8>, Vec<u8>), E>>,
 {
-    pub fn _merge(a: A, b: B) -> MergedRange<'a, A, B>
-    where
-        A: Iterator<Item = (&'a Vec<u8>, &'a Vec<u8>)>,
-        B: Iterator<Item = (&'a Vec<u8>, &'a Vec<u8>)>,
-    {
+    pub fn merge(a: A, b: B, reverse: bool) -> MergedRange<A, B, E> {
         MergedRange {
             a: a.peekable(),
             b: b.peekable(),
+            reverse,
         }
     }
 }
 
-impl<'a, A, B> Iterator for MergedRange<'a, A, B>
+impl<A, B, E> Iterator for MergedRange<A, B, E>
 where
-    A: Iterator<Item = (&'a Vec<u8>, &'a Vec<u8>)>,
-    B: Iterator<Item = (&'a Vec<u8>, &'a Vec<u8>)>,
+    A: Iterator<Item = Result<(Vec<u8>, Vec<u8>), E>>,
+    B: Iterator<Item = Result<(Vec<u8>, Vec<u8>), E>>,
 {
-    type Item = (&'a Vec<u8>, &'a Vec<u8>);
+    type Item = Result<(Vec<u8>, Vec<u8>), E>;
 
     fn next(&mut self) -> Option<Self::Item> {
         let peek_a = self.a.peek();
         let peek_b = self.b.peek();
 
         match peek_a {
-            Some(peek_a) => match peek_b {
-                Some(peek_b) => {
+            Some(Err(_)) => self.a.next(),
+            Some(Ok(peek_a)) => match peek_b {
+                Some(Err(_)) => self.b.next(),
+                Some(Ok(peek_b)) => {
                     // Both are valid.  Compare keys.
-                    if peek_a.0 < peek_b.0 {
-                        self.a.next()
-                    } else if peek_a.0 == peek_b.0 {
-                        self.b.next(); // effectively skip this
-                        return self.a.next();
-                    } else {
-                        return self.b.next();
+                    let ordering = match self.reverse {
+                        false => peek_a.0.cmp(&peek_b.0),
+                        true => peek_b.0.cmp(&peek_a.0),
+                    };
+
+                    match ordering {
+                        Ordering::Less => self.a.next(),
+                        Ordering::Equal => {
+                            self.b.next(); // effectively skip this
+                            self.a.next()
+                        }
+                        Ordering::Greater => self.b.next(),
                     }
                 }
                 None => self.a.next(),
@@ -58,89 +65,145 @@ where
     }
 }
 
-// // TODO: are we assuming a and/or b are sorted? Does IAVL tree order items in range, BTreeMap does?
-// #[cfg(test)]
-// mod tests {
-
-//     use super::*;
-
-//     #[test]
-//     fn merge_works() {
-//         let a = vec![
-//             (vec![1u8], vec![10u8]),
-//             (vec![3], vec![11]),
-//             (vec![5], vec![12]),
-//         ]
-//         .iter();
-//         let b = vec![
-//             (vec![2 as u8], vec![13 as u8]),
-//             (vec![4], vec![14]),
-//             (vec![5], vec![15]),
-//         ]
-//         .iter();
-
-//         let got_pairs = MergedRange::merge(a, b).collect();
-
-//         let expected_pairs = &vec![
-//             (&vec![1u8], &vec![10u8]),
-//             (&vec![2u8], &vec![13u8]),
-//             (&vec![3u8], &vec![11u8]),
-//             (&vec![4u8], &vec![14u8]),
-//             (&vec![5u8], &vec![12u8]),
-//         ];
-
-//         assert_eq!(expected_pairs, got_pairs);
-//     }
-
-//     // This differs from the previous test in that iterator b reaches the duplicated value first
-//     #[test]
-//     fn merge_works_a_duplicates_b() {
-//         let a = vec![
-//             (vec![1], vec![10]),
-//             (vec![3], vec![11]),
-//             (vec![5], vec![12]),
-//         ]
-//         .into_iter();
-//         let b = vec![(vec![2], vec![13]), (vec![5], vec![15])].into_iter();
-
-//         let got_pairs: Vec<(Vec<u8>, Vec<u8>)> = MergedRange::merge(a, b).collect();
-
-//         let expected_pairs = vec![
-//             (vec![1], vec![10]),
-//             (vec![2], vec![13]),
-//             (vec![3], vec![11]),
-//             (vec![5], vec![12]),
-//         ];
-
-//         assert_eq!(expected_pairs, got_pairs);
-//     }
-
-//     // This differs from the previous test in that the duplicated value is in the middle of the range
-//     #[test]
-//     fn merge_works_mid_range_duplicate() {
-//         let a = vec![
-//             (vec![1], vec![10]),
-//             (vec![3], vec![11]),
-//             (vec![5], vec![12]),
-//         ]
-//         .into_iter();
-//         let b = vec![
-//             (vec![2], vec![13]),
-//             (vec![3], vec![15]),
-//             (vec![4], vec![14]),
-//         ]
-//         .into_iter();
-
-//         let got_pairs: Vec<(Vec<u8>, Vec<u8>)> = MergedRange::merge(a, b).collect();
-
-//         let expected_pairs = vec![
-//             (vec![1], vec![10]),
-//             (vec![2], vec![13]),
-//             (vec![3], vec![11]),
-//             (vec![4], vec![14]),
-//             (vec![5], vec![12]),
-//         ];
-
-//         assert_eq!(expected_pairs, got_pairs);
-//     }
-// }
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn merge_works() {
+        let a = vec![
+            (vec![1u8], vec![10u8]),
+            (vec![3], vec![11]),
+            (vec![5], vec![12]),
+        ]
+        .into_iter()
+        .map(Ok::<_, ()>);
+        let b = vec![
+            (vec![2u8], vec![13u8]),
+            (vec![4], vec![14]),
+            (vec![5], vec![15]),
+        ]
+        .into_iter()
+        .map(Ok::<_, ()>);
+
+        let got_pairs: Vec<(Vec<u8>, Vec<u8>)> = MergedRange::merge(a, b, false)
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        let expected_pairs = vec![
+            (vec![1u8], vec![10u8]),
+            (vec![2u8], vec![13u8]),
+            (vec![3u8], vec![11u8]),
+            (vec![4u8], vec![14u8]),
+            (vec![5u8], vec![12u8]),
+        ];
+
+        assert_eq!(expected_pairs, got_pairs);
+    }
+
+    // This differs from the previous test in that iterator b reaches the duplicated value first
+    #[test]
+    fn merge_works_a_duplicates_b() {
+        let a = vec![
+            (vec![1], vec![10]),
+            (vec![3], vec![11]),
+            (vec![5], vec![12]),
+        ]
+        .into_iter()
+        .map(Ok::<_, ()>);
+        let b = vec![(vec![2], vec![13]), (vec![5], vec![15])]
+            .into_iter()
+            .map(Ok::<_, ()>);
+
+        let got_pairs: Vec<(Vec<u8>, Vec<u8>)> = MergedRange::merge(a, b, false)
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        let expected_pairs = vec![
+            (vec![1], vec![10]),
+            (vec![2], vec![13]),
+            (vec![3], vec![11]),
+            (vec![5], vec![12]),
+        ];
+
+        assert_eq!(expected_pairs, got_pairs);
+    }
+
+    // This differs from the previous test in that the duplicated value is in the middle of the range
+    #[test]
+    fn merge_works_mid_range_duplicate() {
+        let a = vec![
+            (vec![1], vec![10]),
+            (vec![3], vec![11]),
+            (vec![5], vec![12]),
+        ]
+        .into_iter()
+        .map(Ok::<_, ()>);
+        let b = vec![
+            (vec![2], vec![13]),
+            (vec![3], vec![15]),
+            (vec![4], vec![14]),
+        ]
+        .into_iter()
+        .map(Ok::<_, ()>);
+
+        let got_pairs: Vec<(Vec<u8>, Vec<u8>)> = MergedRange::merge(a, b, false)
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        let expected_pairs = vec![
+            (vec![1], vec![10]),
+            (vec![2], vec![13]),
+            (vec![3], vec![11]),
+            (vec![4], vec![14]),
+            (vec![5], vec![12]),
+        ];
+
+        assert_eq!(expected_pairs, got_pairs);
+    }
+
+    #[test]
+    fn merge_works_reverse() {
+        let a = vec![(vec![5], vec![12]), (vec![3], vec![11])]
+            .into_iter()
+            .map(Ok::<_, ()>);
+        let b = vec![
+            (vec![4], vec![14]),
+            (vec![3], vec![15]),
+            (vec![2], vec![13]),
+        ]
+        .into_iter()
+        .map(Ok::<_, ()>);
+
+        let got_pairs: Vec<(Vec<u8>, Vec<u8>)> = MergedRange::merge(a, b, true)
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        let expected_pairs = vec![
+            (vec![5], vec![12]),
+            (vec![4], vec![14]),
+            (vec![3], vec![11]),
+            (vec![2], vec![13]),
+        ];
+
+        assert_eq!(expected_pairs, got_pairs);
+    }
+
+    #[test]
+    fn merge_returns_errors() {
+        let a = vec![Ok((vec![1], vec![10])), Ok((vec![3], vec![11]))].into_iter();
+        let b = vec![Ok((vec![2], vec![13])), Err(())].into_iter();
+
+        let got: Vec<_> = MergedRange::merge(a, b, false).collect();
+
+        let expected = vec![
+            Ok((vec![1], vec![10])),
+            Ok((vec![2], vec![13])),
+            Err(()),
+            Ok((vec![3], vec![11])),
+        ];
+
+        assert_eq!(expected, got);
+    }
+}
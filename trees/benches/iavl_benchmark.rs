@@ -34,7 +34,7 @@ fn iavl_query_miss_benchmark(c: &mut Criterion, all_params: &Vec<Params>) {
                     .sample_iter(Standard)
                     .take(params.key_length)
                     .collect();
-                tree.get(black_box(&key));
+                tree.get(black_box(&key)).unwrap();
             })
         });
     }
@@ -51,7 +51,7 @@ fn iavl_query_hits_benchmark(c: &mut Criterion, all_params: &Vec<Params>) {
                 let key: &Vec<u8> = keys
                     .get(rand::thread_rng().gen_range(0..params.init_size))
                     .unwrap();
-                tree.get(black_box(key));
+                tree.get(black_box(key)).unwrap();
             })
         });
     }
@@ -75,7 +75,8 @@ fn iavl_update_benchmark(c: &mut Criterion, all_params: &Vec<Params>) {
                         .take(params.data_length)
                         .collect();
 
-                    tree.set(black_box(key.clone()), black_box(data.to_vec()));
+                    tree.set(black_box(key.clone()), black_box(data.to_vec()))
+                        .unwrap();
 
                     if i % params.block_size == 0 {
                         commit_tree(&mut tree)
@@ -94,7 +95,7 @@ fn iavl_range_benchmark(c: &mut Criterion, all_params: &Vec<Params>) {
         let (tree, _) = prepare_tree(params);
         group.bench_with_input(BenchmarkId::from_parameter(params), &params, |b, _| {
             b.iter(|| {
-                let _range: Vec<(Vec<u8>, Vec<u8>)> = tree.range(..).collect();
+                let _range: Vec<(Vec<u8>, Vec<u8>)> = tree.range(..).map(Result::unwrap).collect();
             })
         });
     }
@@ -127,8 +128,8 @@ fn iavl_run_blocks_benchmark(c: &mut Criterion, all_params: &Vec<Params>) {
                             .take(params.data_length)
                             .collect();
 
-                        tree.get(&key);
-                        tree.set(key, data)
+                        tree.get(&key).unwrap();
+                        tree.set(key, data).unwrap()
                     }
 
                     commit_tree(&mut tree);
@@ -205,7 +206,7 @@ fn prepare_tree(params: &Params) -> (Tree<RocksDB>, Vec<Vec<u8>>) {
             .take(params.data_length)
             .collect();
 
-        tree.set(key.clone(), data);
+        tree.set(key.clone(), data).unwrap();
         keys.push(key);
     }
 
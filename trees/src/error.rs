@@ -12,10 +12,16 @@ pub enum Error {
     Overwrite,
     #[error("requested node is not exists")]
     NodeNotExists, // TODO: More specific and special errors for removing node
+    #[error("node is missing from the DB - possible database corruption")]
+    MissingNode,
+    #[error("invalid data in DB - possible database corruption")]
+    Corrupt,
     #[error("custom error: {0}")]
     CustomError(String),
     #[error("cannot balance a node with balance factor >2 or <-2")]
     Balancing,
+    #[error(transparent)]
+    Database(#[from] database::error::Error),
 }
 
 pub mod constants {
@@ -0,0 +1,286 @@
+use std::{cmp::Ordering, collections::BTreeSet};
+
+use database::Database;
+
+use crate::{
+    error::Error,
+    merkle::{Sha256Hash, EMPTY_HASH},
+};
+
+use super::{node_db::NodeDB, CachedTree, Node, Tree};
+
+/// A change to a single key between two versions of a tree
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeyChange {
+    Inserted {
+        key: Vec<u8>,
+        value: Vec<u8>,
+    },
+    Updated {
+        key: Vec<u8>,
+        old_value: Vec<u8>,
+        new_value: Vec<u8>,
+    },
+    Removed {
+        key: Vec<u8>,
+        old_value: Vec<u8>,
+    },
+}
+
+impl KeyChange {
+    pub fn key(&self) -> &[u8] {
+        match self {
+            KeyChange::Inserted { key, .. } => key,
+            KeyChange::Updated { key, .. } => key,
+            KeyChange::Removed { key, .. } => key,
+        }
+    }
+}
+
+impl<T: Database> Tree<T> {
+    /// Returns the changes, ordered by key, needed to go from one saved version to another
+    pub fn diff(&self, from_version: u32, to_version: u32) -> Result<Vec<KeyChange>, Error> {
+        diff(&self.node_db, &self.versions, from_version, to_version)
+    }
+}
+
+impl<T: Database> CachedTree<T> {
+    /// Returns the changes, ordered by key, needed to go from one saved version to another
+    pub fn diff(&self, from_version: u32, to_version: u32) -> Result<Vec<KeyChange>, Error> {
+        diff(&self.node_db, &self.versions, from_version, to_version)
+    }
+}
+
+/// An in order traversal of a saved tree which can skip whole subtrees
+struct Frontier<'a, T> {
+    /// Nodes which haven't been visited yet, the next node is at the end
+    stack: Vec<(Sha256Hash, Box<Node>)>,
+    node_db: &'a NodeDB<T>,
+}
+
+impl<'a, T: Database> Frontier<'a, T> {
+    fn new(node_db: &'a NodeDB<T>, version: u32) -> Result<Self, Error> {
+        let root_hash = node_db.get_root_hash(version)?;
+
+        let stack = match node_db.get_root_node(version)? {
+            Some(root) => vec![(root_hash, root)],
+            None => vec![],
+        };
+
+        Ok(Self { stack, node_db })
+    }
+
+    fn peek(&self) -> Option<&(Sha256Hash, Box<Node>)> {
+        self.stack.last()
+    }
+
+    fn next_hash(&self) -> Sha256Hash {
+        self.peek().map(|(hash, _)| *hash).unwrap_or(EMPTY_HASH)
+    }
+
+    fn next_height(&self) -> Option<u8> {
+        self.peek().map(|(_, node)| node.get_height())
+    }
+
+    fn pop(&mut self) -> Option<Box<Node>> {
+        self.stack.pop().map(|(_, node)| node)
+    }
+
+    /// Replaces the next node with its children, has no effect on a leaf node
+    fn expand(&mut self) -> Result<(), Error> {
+        let Some((hash, node)) = self.stack.pop() else {
+            return Ok(());
+        };
+
+        match *node {
+            Node::Leaf(_) => self.stack.push((hash, node)),
+            Node::Inner(inner) => {
+                let right = match inner.right_node {
+                    Some(right) => right,
+                    None => self.node_db.fetch_node(&inner.right_hash)?,
+                };
+                let left = match inner.left_node {
+                    Some(left) => left,
+                    None => self.node_db.fetch_node(&inner.left_hash)?,
+                };
+
+                self.stack.push((inner.right_hash, right));
+                self.stack.push((inner.left_hash, left));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Walks the two versions in key order. Subtrees with equal hashes contain the same
+/// keys and values so they are skipped without being loaded.
+fn diff<T: Database>(
+    node_db: &NodeDB<T>,
+    versions: &BTreeSet<u32>,
+    from_version: u32,
+    to_version: u32,
+) -> Result<Vec<KeyChange>, Error> {
+    if !versions.contains(&from_version) || !versions.contains(&to_version) {
+        return Err(Error::VersionNotFound);
+    }
+
+    let mut from = Frontier::new(node_db, from_version)?;
+    let mut to = Frontier::new(node_db, to_version)?;
+    let mut changes = vec![];
+
+    loop {
+        match (from.next_height(), to.next_height()) {
+            (None, None) => return Ok(changes),
+            (Some(_), Some(_)) if from.next_hash() == to.next_hash() => {
+                from.pop();
+                to.pop();
+            }
+            (Some(0), Some(0)) => {
+                let (Some((_, from_node)), Some((_, to_node))) = (from.peek(), to.peek()) else {
+                    unreachable!("both frontiers are non empty")
+                };
+
+                match from_node.get_key().cmp(to_node.get_key()) {
+                    Ordering::Less => push_removed(&mut changes, from.pop()),
+                    Ordering::Greater => push_inserted(&mut changes, to.pop()),
+                    Ordering::Equal => {
+                        let (Some(from_node), Some(to_node)) = (from.pop(), to.pop()) else {
+                            unreachable!("both frontiers are non empty")
+                        };
+
+                        if let (Node::Leaf(from_leaf), Node::Leaf(to_leaf)) = (*from_node, *to_node)
+                        {
+                            if from_leaf.value != to_leaf.value {
+                                changes.push(KeyChange::Updated {
+                                    key: to_leaf.key,
+                                    old_value: from_leaf.value,
+                                    new_value: to_leaf.value,
+                                })
+                            }
+                        }
+                    }
+                }
+            }
+            (Some(0), None) => push_removed(&mut changes, from.pop()),
+            (None, Some(0)) => push_inserted(&mut changes, to.pop()),
+            // expand the taller subtree first, this keeps the two frontiers aligned so that
+            // equal subtrees are found at the same time
+            (Some(from_height), Some(to_height)) if from_height >= to_height => from.expand()?,
+            (Some(_), Some(_)) => to.expand()?,
+            (Some(_), None) => from.expand()?,
+            (None, Some(_)) => to.expand()?,
+        }
+    }
+}
+
+fn push_removed(changes: &mut Vec<KeyChange>, node: Option<Box<Node>>) {
+    if let Some(Node::Leaf(leaf)) = node.map(|node| *node) {
+        changes.push(KeyChange::Removed {
+            key: leaf.key,
+            old_value: leaf.value,
+        })
+    }
+}
+
+fn push_inserted(changes: &mut Vec<KeyChange>, node: Option<Box<Node>>) {
+    if let Some(Node::Leaf(leaf)) = node.map(|node| *node) {
+        changes.push(KeyChange::Inserted {
+            key: leaf.key,
+            value: leaf.value,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    use database::MemDB;
+
+    #[test]
+    fn diff_works() {
+        let db = MemDB::new();
+        let mut tree = Tree::new(db, None, 100.try_into().unwrap()).unwrap();
+        tree.set(b"alice".to_vec(), b"abc".to_vec()).unwrap();
+        tree.set(b"bob".to_vec(), b"123".to_vec()).unwrap();
+        tree.set(b"c".to_vec(), b"1".to_vec()).unwrap();
+        tree.set(b"q".to_vec(), b"1".to_vec()).unwrap();
+        tree.save_version().unwrap();
+
+        tree.set(b"bob".to_vec(), b"456".to_vec()).unwrap();
+        tree.set(b"d".to_vec(), b"2".to_vec()).unwrap();
+        tree.remove(b"c").unwrap();
+        tree.save_version().unwrap();
+
+        let expected = vec![
+            KeyChange::Updated {
+                key: b"bob".to_vec(),
+                old_value: b"123".to_vec(),
+                new_value: b"456".to_vec(),
+            },
+            KeyChange::Removed {
+                key: b"c".to_vec(),
+                old_value: b"1".to_vec(),
+            },
+            KeyChange::Inserted {
+                key: b"d".to_vec(),
+                value: b"2".to_vec(),
+            },
+        ];
+
+        assert_eq!(tree.diff(1, 2).unwrap(), expected);
+        assert_eq!(tree.diff(2, 2).unwrap(), vec![]);
+        assert_eq!(tree.diff(1, 3), Err(Error::VersionNotFound));
+    }
+
+    #[test]
+    fn diff_matches_full_scan() {
+        let db = MemDB::new();
+        let mut tree = CachedTree::new(db, None, 100.try_into().unwrap()).unwrap();
+
+        for i in 0..200u8 {
+            tree.set(vec![i], vec![i]).unwrap();
+        }
+        tree.save_version().unwrap();
+
+        for i in (0..200u8).step_by(7) {
+            tree.set(vec![i], vec![i, i]).unwrap();
+        }
+        for i in (1..200u8).step_by(11) {
+            tree.remove(&[i]).unwrap();
+        }
+        for i in 200..220u8 {
+            tree.set(vec![i], vec![i]).unwrap();
+        }
+        tree.save_version().unwrap();
+
+        let from: BTreeMap<Vec<u8>, Vec<u8>> = (0..200u8).map(|i| (vec![i], vec![i])).collect();
+        let to: BTreeMap<Vec<u8>, Vec<u8>> = tree.range(..).map(Result::unwrap).collect();
+
+        let mut expected = vec![];
+        for key in from.keys().chain(to.keys()).collect::<BTreeSet<_>>() {
+            match (from.get(key), to.get(key)) {
+                (Some(old_value), None) => expected.push(KeyChange::Removed {
+                    key: key.clone(),
+                    old_value: old_value.clone(),
+                }),
+                (None, Some(value)) => expected.push(KeyChange::Inserted {
+                    key: key.clone(),
+                    value: value.clone(),
+                }),
+                (Some(old_value), Some(new_value)) if old_value != new_value => {
+                    expected.push(KeyChange::Updated {
+                        key: key.clone(),
+                        old_value: old_value.clone(),
+                        new_value: new_value.clone(),
+                    })
+                }
+                _ => (),
+            }
+        }
+
+        assert_eq!(tree.diff(1, 2).unwrap(), expected);
+    }
+}
@@ -1,8 +1,13 @@
+mod diff;
 mod node_db;
 mod query_tree;
 mod tree;
-#[allow(dead_code)]
-pub mod tree_v3;
+mod tree_v3;
+mod verify;
 
+pub use diff::KeyChange;
+pub use node_db::{migrate_db, needs_migration};
 pub use query_tree::*;
 pub use tree::*;
+pub use tree_v3::*;
+pub use verify::{verify_db, Problem, VerifyReport};
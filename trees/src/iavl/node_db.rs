@@ -1,7 +1,7 @@
 use std::{collections::BTreeSet, sync::Mutex};
 
 use caches::{Cache, DefaultHashBuilder, LRUCache};
-use database::{ext::UnwrapCorrupt, Database};
+use database::{Column, Database};
 use integer_encoding::VarInt;
 
 use crate::{merkle::EMPTY_HASH, Error};
@@ -10,40 +10,101 @@ use super::{CacheSize, Node};
 
 #[derive(Debug)]
 pub struct NodeDB<T> {
-    db: T,
+    nodes: T,
+    roots: T,
     cache: Mutex<LRUCache<[u8; 32], Node, DefaultHashBuilder>>,
 }
 
 const ROOTS_PREFIX: [u8; 1] = [1];
+const ROOTS_PREFIX_END: [u8; 1] = [2];
 const NODES_PREFIX: [u8; 1] = [2];
+const NODES_PREFIX_END: [u8; 1] = [3];
+
+/// The number of keys moved at a time by [`migrate_db`]
+const MIGRATION_BATCH_SIZE: usize = 1000;
+
+/// Nodes and roots used to be stored in the default column, this moves any which are left
+/// there into their own columns. It scans the whole default column so it's run once against a
+/// writable DB at startup, before any tree is loaded. Each key is written to its new column
+/// before it's deleted so an interrupted migration is finished the next time it's run.
+pub fn migrate_db<T: Database>(db: &T) -> Result<(), Error> {
+    for (prefix, prefix_end, column) in [
+        (NODES_PREFIX, NODES_PREFIX_END, Column::Nodes),
+        (ROOTS_PREFIX, ROOTS_PREFIX_END, Column::Roots),
+    ] {
+        let target = db.column(column);
+
+        loop {
+            let batch = db
+                .range(Some(&prefix), Some(&prefix_end))
+                .take(MIGRATION_BATCH_SIZE)
+                .collect::<Result<Vec<_>, _>>()?;
+
+            if batch.is_empty() {
+                break;
+            }
+
+            for (key, value) in batch {
+                target.put(key.to_vec(), value.into_vec())?;
+                db.delete(&key)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns whether any node or root is left in the default column, in which case the DB has to be
+/// migrated by [`migrate_db`] before trees can be loaded from it.
+pub fn needs_migration<T: Database>(db: &T) -> Result<bool, Error> {
+    for (prefix, prefix_end) in [
+        (NODES_PREFIX, NODES_PREFIX_END),
+        (ROOTS_PREFIX, ROOTS_PREFIX_END),
+    ] {
+        if let Some(res) = db.range(Some(&prefix), Some(&prefix_end)).next() {
+            res?;
+            return Ok(true);
+        }
+    }
+
+    Ok(false)
+}
 
 // TODO: batch writes
-// TODO: fast nodes
+// TODO: fast nodes, they'll be stored in `Column::FastNodes`
 impl<T> NodeDB<T>
 where
     T: Database,
 {
     pub fn new(db: T, cache_size: CacheSize) -> NodeDB<T> {
         NodeDB {
-            db,
+            nodes: db.column(Column::Nodes),
+            roots: db.column(Column::Roots),
             cache: Mutex::new(
                 LRUCache::new(cache_size.into()).expect("won't panic since cache_size > zero"),
             ),
         }
     }
 
-    pub fn get_versions(&self) -> BTreeSet<u32> {
-        self.db
-            .prefix_iterator(ROOTS_PREFIX.into())
-            .map(|(k, _)| u32::decode_var(&k).unwrap_or_corrupt().0)
+    pub fn get_versions(&self) -> Result<BTreeSet<u32>, Error> {
+        self.roots
+            // a range rather than a prefix iterator since a PrefixDB strips the iterated prefix
+            .range(Some(&ROOTS_PREFIX), Some(&ROOTS_PREFIX_END))
+            .map(|res| {
+                let key = res?.0;
+                Ok(u32::decode_var(&key[ROOTS_PREFIX.len()..])
+                    .ok_or(Error::Corrupt)?
+                    .0)
+            })
             .collect()
     }
 
     pub(crate) fn get_root_hash(&self, version: u32) -> Result<[u8; 32], Error> {
-        self.db
-            .get(&Self::get_root_key(version))
-            .map(|hash| hash.try_into().ok().unwrap_or_corrupt())
-            .ok_or(Error::VersionNotFound)
+        self.roots
+            .get(&Self::get_root_key(version))?
+            .ok_or(Error::VersionNotFound)?
+            .try_into()
+            .map_err(|_| Error::Corrupt)
     }
 
     pub(crate) fn get_root_node(&self, version: u32) -> Result<Option<Box<Node>>, Error> {
@@ -53,49 +114,68 @@ where
             return Ok(None);
         }
 
-        Ok(Some(
-            self.get_node(&root_hash).unwrap_or_corrupt(), // this node should be in the DB, if it isn't then better to panic
-        ))
+        Ok(Some(self.fetch_node(&root_hash)?))
+    }
+
+    /// Returns the bytes stored as the root hash of a version, unlike [`NodeDB::get_root_hash`]
+    /// this doesn't fail if they are corrupt
+    pub(crate) fn get_raw_root_hash(&self, version: u32) -> Result<Option<Vec<u8>>, Error> {
+        Ok(self.roots.get(&Self::get_root_key(version))?)
+    }
+
+    /// Returns the bytes stored for a node, bypassing the cache
+    pub(crate) fn get_raw_node(&self, hash: &[u8; 32]) -> Result<Option<Vec<u8>>, Error> {
+        Ok(self.nodes.get(&Self::get_node_key(hash))?)
     }
 
-    fn get_root_key(version: u32) -> Vec<u8> {
+    pub(crate) fn get_root_key(version: u32) -> Vec<u8> {
         [ROOTS_PREFIX.into(), version.encode_var_vec()].concat()
     }
 
-    fn get_node_key(hash: &[u8; 32]) -> Vec<u8> {
+    pub(crate) fn get_node_key(hash: &[u8; 32]) -> Vec<u8> {
         [NODES_PREFIX.to_vec(), hash.to_vec()].concat()
     }
 
-    pub(crate) fn get_node(&self, hash: &[u8; 32]) -> Option<Box<Node>> {
+    pub(crate) fn get_node(&self, hash: &[u8; 32]) -> Result<Option<Box<Node>>, Error> {
         let cache = &mut self.cache.lock().expect("Lock will not be poisoned");
         let cache_node = cache.get(hash);
 
         if cache_node.is_some() {
-            return cache_node.map(|v| Box::new(v.to_owned()));
+            return Ok(cache_node.map(|v| Box::new(v.to_owned())));
         };
 
-        let node_bytes = self.db.get(&Self::get_node_key(hash))?;
-        let node = Node::deserialize(node_bytes).ok().unwrap_or_corrupt();
+        let Some(node_bytes) = self.nodes.get(&Self::get_node_key(hash))? else {
+            return Ok(None);
+        };
+        let node = Node::deserialize(node_bytes)?;
 
         cache.put(*hash, node.clone());
-        Some(Box::new(node))
+        Ok(Some(Box::new(node)))
+    }
+
+    /// Returns a node which is referenced by an already loaded node or by a root, so
+    /// it must be in the DB
+    pub(crate) fn fetch_node(&self, hash: &[u8; 32]) -> Result<Box<Node>, Error> {
+        self.get_node(hash)?.ok_or(Error::MissingNode)
     }
 
-    fn save_node(&mut self, node: &Node, hash: &[u8; 32]) {
-        self.db.put(Self::get_node_key(hash), node.serialize());
+    pub(crate) fn save_node(&self, node: &Node, hash: &[u8; 32]) -> Result<(), Error> {
+        self.nodes.put(Self::get_node_key(hash), node.serialize())?;
         self.cache
             .lock()
             .expect("Lock will not be poisoned")
             .put(*hash, node.shallow_clone());
+
+        Ok(())
     }
 
-    fn recursive_tree_save(&mut self, node: &Node, hash: &[u8; 32]) {
+    fn recursive_tree_save(&self, node: &Node, hash: &[u8; 32]) -> Result<(), Error> {
         if let Node::Inner(inner) = node {
             if let Some(left_node) = &inner.left_node {
-                self.recursive_tree_save(left_node, &inner.left_hash);
+                self.recursive_tree_save(left_node, &inner.left_hash)?;
             }
             if let Some(right_node) = &inner.right_node {
-                self.recursive_tree_save(right_node, &inner.right_hash);
+                self.recursive_tree_save(right_node, &inner.right_hash)?;
             }
         }
 
@@ -104,21 +184,23 @@ where
 
     /// Saves the given node and all of its descendants.
     /// Clears left_node/right_node on the root.
-    pub(crate) fn save_tree(&mut self, root: &mut Node) -> [u8; 32] {
+    pub(crate) fn save_tree(&self, root: &mut Node) -> Result<[u8; 32], Error> {
         let root_hash = root.hash();
-        self.recursive_tree_save(root, &root_hash);
+        self.recursive_tree_save(root, &root_hash)?;
 
         if let Node::Inner(inner) = root {
             inner.left_node = None;
             inner.right_node = None;
         }
 
-        root_hash
+        Ok(root_hash)
     }
 
-    pub(crate) fn save_version(&mut self, version: u32, hash: &[u8; 32]) {
+    pub(crate) fn save_version(&self, version: u32, hash: &[u8; 32]) -> Result<(), Error> {
         let key = Self::get_root_key(version);
-        self.db.put(key, hash.to_vec());
+        self.roots.put(key, hash.to_vec())?;
+
+        Ok(())
     }
 }
 
@@ -151,15 +233,14 @@ mod tests {
     #[test]
     fn get_versions_works() {
         let db = MemDB::new();
-        db.put(NodeDB::<MemDB>::get_root_key(1u32), vec![]);
-        let node_db = NodeDB {
-            db,
-            cache: Mutex::new(LRUCache::new(2).unwrap()),
-        };
+        db.column(Column::Roots)
+            .put(NodeDB::<MemDB>::get_root_key(1u32), vec![])
+            .unwrap();
+        let node_db = NodeDB::new(db, CacheSize::try_from(2).unwrap());
 
         let mut expected_versions = BTreeSet::new();
         expected_versions.insert(1);
-        let versions = node_db.get_versions();
+        let versions = node_db.get_versions().unwrap();
 
         assert_eq!(expected_versions, versions)
     }
@@ -171,14 +252,36 @@ mod tests {
             253, 226, 35, 230, 65, 214, 244, 35, 69, 39, 223, 90,
         ];
         let db = MemDB::new();
-        db.put(NodeDB::<MemDB>::get_root_key(1u32), root_hash.into());
-        let node_db = NodeDB {
-            db,
-            cache: Mutex::new(LRUCache::new(2).unwrap()),
-        };
+        db.column(Column::Roots)
+            .put(NodeDB::<MemDB>::get_root_key(1u32), root_hash.into())
+            .unwrap();
+        let node_db = NodeDB::new(db, CacheSize::try_from(2).unwrap());
 
         let got_root_hash = node_db.get_root_hash(1).unwrap();
 
         assert_eq!(root_hash, got_root_hash);
     }
+
+    #[test]
+    fn migrate_db_moves_nodes_and_roots() {
+        let db = MemDB::new();
+        let node_key = NodeDB::<MemDB>::get_node_key(&[1; 32]);
+        let root_key = NodeDB::<MemDB>::get_root_key(1u32);
+        db.put(node_key.clone(), vec![1]).unwrap();
+        db.put(root_key.clone(), vec![2]).unwrap();
+        assert!(needs_migration(&db).unwrap());
+
+        migrate_db(&db).unwrap();
+
+        assert!(!needs_migration(&db).unwrap());
+        assert!(db.iterator().next().is_none());
+        assert_eq!(
+            db.column(Column::Nodes).get(&node_key).unwrap(),
+            Some(vec![1])
+        );
+        assert_eq!(
+            db.column(Column::Roots).get(&root_key).unwrap(),
+            Some(vec![2])
+        );
+    }
 }
@@ -1,47 +1,61 @@
-use std::ops::RangeBounds;
+use std::{collections::BTreeSet, ops::RangeBounds, sync::Arc};
 
 use database::Database;
 
 use crate::Error;
 
-use super::{node_db::NodeDB, Node, Range, Tree};
+use super::{node_db::NodeDB, CachedTree, KeyValue, Node, Range, Tree};
 
-/// QueryTree is a "checked out" Tree at a given height which
-/// borrows a Tree's NodeDb
-pub struct QueryTree<'a, DB> {
+/// QueryTree is an immutable "checked out" Tree at a given height. It shares
+/// the Tree's NodeDB, and so its node cache, but doesn't borrow the Tree, so it
+/// can be read from another thread while the Tree is modified and saved.
+#[derive(Debug)]
+pub struct QueryTree<DB> {
     pub(crate) root: Option<Box<Node>>,
-    pub(crate) node_db: &'a NodeDB<DB>,
+    pub(crate) node_db: Arc<NodeDB<DB>>,
 }
 
-impl<'a, DB: Database> QueryTree<'a, DB> {
-    pub fn new(tree: &'a Tree<DB>, mut version: u32) -> Result<QueryTree<'a, DB>, Error> {
+impl<DB: Database> QueryTree<DB> {
+    pub fn new(tree: &Tree<DB>, version: u32) -> Result<QueryTree<DB>, Error> {
+        Self::checkout(&tree.node_db, &tree.versions, tree.loaded_version, version)
+    }
+
+    pub fn from_cached(tree: &CachedTree<DB>, version: u32) -> Result<QueryTree<DB>, Error> {
+        Self::checkout(&tree.node_db, &tree.versions, tree.loaded_version, version)
+    }
+
+    fn checkout(
+        node_db: &Arc<NodeDB<DB>>,
+        versions: &BTreeSet<u32>,
+        loaded_version: u32,
+        mut version: u32,
+    ) -> Result<QueryTree<DB>, Error> {
         if version == 0 {
-            version = tree.loaded_version;
+            version = loaded_version;
         }
 
-        if tree.versions.contains(&version) {
-            let root = tree.node_db.get_root_node(version).expect(
-                "the requested version is in the list of versions so the node should be in the db",
-            );
+        if versions.contains(&version) {
+            // the requested version is in the list of versions so only reading the DB can fail
+            let root = node_db.get_root_node(version)?;
 
             Ok(QueryTree {
                 root,
-                node_db: &tree.node_db,
+                node_db: Arc::clone(node_db),
             })
         } else {
             Err(Error::VersionNotFound)
         }
     }
 
-    pub fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+    pub fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>, Error> {
         match &self.root {
             Some(root) => self.get_(key, root),
-            None => None,
+            None => Ok(None),
         }
     }
 
     // TODO: can we share this function with a regular tree's get_ method?
-    fn get_(&self, key: &[u8], root: &Node) -> Option<Vec<u8>> {
+    fn get_(&self, key: &[u8], root: &Node) -> Result<Option<Vec<u8>>, Error> {
         let mut loop_node = root;
         let mut cached_node;
 
@@ -49,9 +63,9 @@ impl<'a, DB: Database> QueryTree<'a, DB> {
             match loop_node {
                 Node::Leaf(leaf) => {
                     if leaf.key == key {
-                        return Some(leaf.value.clone());
+                        return Ok(Some(leaf.value.clone()));
                     } else {
-                        return None;
+                        return Ok(None);
                     }
                 }
                 Node::Inner(node) => {
@@ -59,10 +73,7 @@ impl<'a, DB: Database> QueryTree<'a, DB> {
                         match &node.left_node {
                             Some(left_node) => loop_node = left_node,
                             None => {
-                                let left_node = self
-                                    .node_db
-                                    .get_node(&node.left_hash)
-                                    .expect("node db should contain all nodes");
+                                let left_node = self.node_db.fetch_node(&node.left_hash)?;
 
                                 cached_node = left_node;
                                 loop_node = &cached_node;
@@ -72,10 +83,7 @@ impl<'a, DB: Database> QueryTree<'a, DB> {
                         match &node.right_node {
                             Some(right_node) => loop_node = right_node,
                             None => {
-                                let right_node = self
-                                    .node_db
-                                    .get_node(&node.right_hash)
-                                    .expect("node db should contain all nodes");
+                                let right_node = self.node_db.fetch_node(&node.right_hash)?;
 
                                 cached_node = right_node;
                                 loop_node = &cached_node;
@@ -87,22 +95,43 @@ impl<'a, DB: Database> QueryTree<'a, DB> {
         }
     }
 
+    /// Returns the number of keys in the tree
+    pub fn size(&self) -> u32 {
+        self.root.as_ref().map(|root| root.get_size()).unwrap_or(0)
+    }
+
+    /// Returns the key and value at the given index, where keys are indexed in order
+    pub fn get_by_index(&self, index: u32) -> Result<Option<KeyValue>, Error> {
+        match &self.root {
+            Some(root) => root.get_by_index(&self.node_db, index),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns `Ok(index)` if the key is in the tree, otherwise returns `Err(index)`
+    /// where index is the position at which the key would be inserted
+    pub fn get_index_of_key(&self, key: &[u8]) -> Result<Result<u32, u32>, Error> {
+        match &self.root {
+            Some(root) => root.get_index_of_key(&self.node_db, key),
+            None => Ok(Err(0)),
+        }
+    }
+
     pub fn range<R>(&self, range: R) -> Range<'_, R, DB>
     where
         R: RangeBounds<Vec<u8>>,
     {
-        match &self.root {
-            Some(root) => Range {
-                range,
-                delayed_nodes: vec![root.clone()], //TODO: remove clone
-                node_db: self.node_db,
-            },
-            None => Range {
-                range,
-                delayed_nodes: vec![],
-                node_db: self.node_db,
-            },
-        }
+        //TODO: remove clone
+        Range::new(range, self.root.clone(), &self.node_db, false)
+    }
+
+    /// Same as [`QueryTree::range`] but iterates in descending key order
+    pub fn reverse_range<R>(&self, range: R) -> Range<'_, R, DB>
+    where
+        R: RangeBounds<Vec<u8>>,
+    {
+        //TODO: remove clone
+        Range::new(range, self.root.clone(), &self.node_db, true)
     }
 }
 #[cfg(test)]
@@ -114,17 +143,17 @@ mod tests {
     fn new_query_tree_works() {
         let db = MemDB::new();
         let mut tree = Tree::new(db, None, 100.try_into().unwrap()).unwrap();
-        tree.set(b"alice".to_vec(), b"abc".to_vec());
+        tree.set(b"alice".to_vec(), b"abc".to_vec()).unwrap();
         tree.save_version().unwrap();
-        tree.set(b"alice".to_vec(), b"123".to_vec());
+        tree.set(b"alice".to_vec(), b"123".to_vec()).unwrap();
 
         let query_tree = QueryTree::new(&tree, 1).unwrap();
-        let result = query_tree.get(b"alice".as_slice()).unwrap();
+        let result = query_tree.get(b"alice".as_slice()).unwrap().unwrap();
 
         let expected = b"abc".to_vec();
         assert_eq!(expected, result);
 
-        let result = tree.get(b"alice".as_slice()).unwrap();
+        let result = tree.get(b"alice".as_slice()).unwrap().unwrap();
         let expected = b"123".to_vec();
         assert_eq!(expected, result);
     }
@@ -136,9 +165,35 @@ mod tests {
         tree.save_version().unwrap();
 
         let query_tree = QueryTree::new(&tree, 1).unwrap();
-        let result = query_tree.get(b"alice".as_slice());
+        let result = query_tree.get(b"alice".as_slice()).unwrap();
 
         let expected = None;
         assert_eq!(expected, result);
     }
+
+    #[test]
+    fn query_tree_reads_while_tree_is_saved() {
+        let db = MemDB::new();
+        let mut tree = Tree::new(db, None, 100.try_into().unwrap()).unwrap();
+        for i in 0..100u8 {
+            tree.set(vec![i], vec![i]).unwrap();
+        }
+        tree.save_version().unwrap();
+
+        let query_tree = QueryTree::new(&tree, 1).unwrap();
+        let reader = std::thread::spawn(move || {
+            (0..100u8)
+                .map(|i| query_tree.get(&[i]).unwrap())
+                .collect::<Vec<_>>()
+        });
+
+        for i in 0..100u8 {
+            tree.set(vec![i], vec![i, i]).unwrap();
+        }
+        tree.save_version().unwrap();
+
+        let expected: Vec<_> = (0..100u8).map(|i| Some(vec![i])).collect();
+        assert_eq!(reader.join().unwrap(), expected);
+        assert_eq!(tree.get(&[1]).unwrap(), Some(vec![1, 1]));
+    }
 }
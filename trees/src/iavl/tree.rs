@@ -3,9 +3,10 @@ use std::{
     collections::BTreeSet,
     mem,
     ops::{Bound, RangeBounds},
+    sync::Arc,
 };
 
-use database::{ext::UnwrapCorrupt, Database};
+use database::Database;
 use integer_encoding::VarInt;
 use nutype::nutype;
 use sha2::{Digest, Sha256};
@@ -17,35 +18,40 @@ use crate::{
 
 use super::node_db::NodeDB;
 
+/// A key and its value
+pub type KeyValue = (Vec<u8>, Vec<u8>);
+
 #[derive(Debug, Clone, PartialEq, Hash, Default)]
 pub(crate) struct InnerNode {
     // TODO: consider removing left/right_hash and making left/right_node an enum of Box<Node> and
     // Sha256Hash to avoid needing to calculate hashes until save_version is called
     pub(crate) left_node: Option<Box<Node>>, // None means value is the same as what's in the DB
     pub(crate) right_node: Option<Box<Node>>,
-    height: u8,
-    size: u32, // number of leaf nodes in this node's subtrees
+    pub(crate) height: u8,
+    pub(crate) size: u32, // number of leaf nodes in this node's subtrees
     pub(crate) left_hash: Sha256Hash,
     pub(crate) right_hash: Sha256Hash,
     pub(crate) key: Vec<u8>,
-    version: u32,
+    pub(crate) version: u32,
 }
 
 impl InnerNode {
-    fn get_mut_left_node<T: Database>(&mut self, node_db: &NodeDB<T>) -> &mut Node {
-        self.left_node.get_or_insert_with(|| {
-            node_db
-                .get_node(&self.left_hash)
-                .expect("node should be in db")
-        })
+    fn get_mut_left_node<T: Database>(&mut self, node_db: &NodeDB<T>) -> Result<&mut Node, Error> {
+        let left_node = match self.left_node.take() {
+            Some(left_node) => left_node,
+            None => node_db.fetch_node(&self.left_hash)?,
+        };
+
+        Ok(self.left_node.insert(left_node))
     }
 
-    fn get_mut_right_node<T: Database>(&mut self, node_db: &NodeDB<T>) -> &mut Node {
-        self.right_node.get_or_insert_with(|| {
-            node_db
-                .get_node(&self.right_hash)
-                .expect("node should be in db")
-        })
+    fn get_mut_right_node<T: Database>(&mut self, node_db: &NodeDB<T>) -> Result<&mut Node, Error> {
+        let right_node = match self.right_node.take() {
+            Some(right_node) => right_node,
+            None => node_db.fetch_node(&self.right_hash)?,
+        };
+
+        Ok(self.right_node.insert(right_node))
     }
 
     fn update_left_hash(&mut self) {
@@ -60,13 +66,11 @@ impl InnerNode {
         }
     }
 
-    fn get_balance_factor<T: Database>(&self, node_db: &NodeDB<T>) -> i16 {
+    fn get_balance_factor<T: Database>(&self, node_db: &NodeDB<T>) -> Result<i16, Error> {
         let left_height = match &self.left_node {
             Some(left_node) => left_node.get_height(),
             None => {
-                let left_node = node_db
-                    .get_node(&self.left_hash)
-                    .expect("node db should contain all nodes");
+                let left_node = node_db.fetch_node(&self.left_hash)?;
 
                 left_node.get_height()
             }
@@ -75,28 +79,24 @@ impl InnerNode {
         let right_height = match &self.right_node {
             Some(right_node) => right_node.get_height(),
             None => {
-                let right_node = node_db
-                    .get_node(&self.right_hash)
-                    .expect("node db should contain all nodes");
+                let right_node = node_db.fetch_node(&self.right_hash)?;
 
                 right_node.get_height()
             }
         };
 
-        left_height as i16 - right_height as i16
+        Ok(left_height as i16 - right_height as i16)
     }
 
     /// This does three things at once to prevent repeating the same process for getting the left and right nodes
     fn update_height_and_size_get_balance_factor<T: Database>(
         &mut self,
         node_db: &NodeDB<T>,
-    ) -> i16 {
+    ) -> Result<i16, Error> {
         let (left_height, left_size) = match &self.left_node {
             Some(left_node) => (left_node.get_height(), left_node.get_size()),
             None => {
-                let left_node = node_db
-                    .get_node(&self.left_hash)
-                    .expect("node db should contain all nodes");
+                let left_node = node_db.fetch_node(&self.left_hash)?;
 
                 (left_node.get_height(), left_node.get_size())
             }
@@ -105,9 +105,7 @@ impl InnerNode {
         let (right_height, right_size) = match &self.right_node {
             Some(right_node) => (right_node.get_height(), right_node.get_size()),
             None => {
-                let right_node = node_db
-                    .get_node(&self.right_hash)
-                    .expect("node db should contain all nodes");
+                let right_node = node_db.fetch_node(&self.right_hash)?;
 
                 (right_node.get_height(), right_node.get_size())
             }
@@ -116,7 +114,7 @@ impl InnerNode {
         self.height = 1 + cmp::max(left_height, right_height);
         self.size = left_size + right_size;
 
-        left_height as i16 - right_height as i16
+        Ok(left_height as i16 - right_height as i16)
     }
 
     fn shallow_clone(&self) -> Self {
@@ -137,11 +135,11 @@ impl InnerNode {
 pub(crate) struct LeafNode {
     pub(crate) value: Vec<u8>,
     pub(crate) key: Vec<u8>,
-    version: u32,
+    pub(crate) version: u32,
 }
 
 impl LeafNode {
-    fn hash_serialize(&self) -> Vec<u8> {
+    pub(crate) fn hash_serialize(&self) -> Vec<u8> {
         // NOTE: i64 is used here for parameters for compatibility wih cosmos
         let height: i64 = 0;
         let size: i64 = 1;
@@ -171,9 +169,9 @@ impl Default for Node {
 }
 
 impl Node {
-    fn get_balance_factor<T: Database>(&self, node_db: &NodeDB<T>) -> i16 {
+    fn get_balance_factor<T: Database>(&self, node_db: &NodeDB<T>) -> Result<i16, Error> {
         match self {
-            Node::Leaf(_) => 0,
+            Node::Leaf(_) => Ok(0),
             Node::Inner(inner) => inner.get_balance_factor(node_db),
         }
     }
@@ -185,7 +183,7 @@ impl Node {
     ) -> Result<(), Error> {
         if let Node::Inner(z) = self {
             let mut z = mem::take(z);
-            let y = mem::take(z.get_mut_left_node(node_db));
+            let y = mem::take(z.get_mut_left_node(node_db)?);
 
             let mut y = match y {
                 Node::Inner(y) => y,
@@ -197,14 +195,14 @@ impl Node {
             // Perform rotation on z and update height and hash
             z.left_node = t3;
             z.left_hash = y.right_hash;
-            z.update_height_and_size_get_balance_factor(node_db);
+            z.update_height_and_size_get_balance_factor(node_db)?;
             z.version = version;
             let z = Node::Inner(z);
 
             // Perform rotation on y, update hash and update height
             y.right_hash = z.hash();
             y.right_node = Some(Box::new(z));
-            y.update_height_and_size_get_balance_factor(node_db);
+            y.update_height_and_size_get_balance_factor(node_db)?;
             y.version = version;
 
             *self = Node::Inner(y);
@@ -219,7 +217,7 @@ impl Node {
     fn left_rotate<T: Database>(&mut self, version: u32, node_db: &NodeDB<T>) -> Result<(), Error> {
         if let Node::Inner(z) = self {
             let mut z = mem::take(z);
-            let y = mem::take(z.get_mut_right_node(node_db));
+            let y = mem::take(z.get_mut_right_node(node_db)?);
 
             let mut y = match y {
                 Node::Inner(y) => y,
@@ -231,14 +229,14 @@ impl Node {
             // Perform rotation on z and update height and hash
             z.right_node = t2;
             z.right_hash = y.left_hash;
-            z.update_height_and_size_get_balance_factor(node_db);
+            z.update_height_and_size_get_balance_factor(node_db)?;
             z.version = version;
             let z = Node::Inner(z);
 
             // Perform rotation on y, update hash and update height
             y.left_hash = z.hash();
             y.left_node = Some(Box::new(z));
-            y.update_height_and_size_get_balance_factor(node_db);
+            y.update_height_and_size_get_balance_factor(node_db)?;
             y.version = version;
 
             *self = Node::Inner(y);
@@ -262,41 +260,28 @@ impl Node {
                 // A leaf node is always balanced
                 Ok(())
             }
-            Node::Inner(inner) => match inner.update_height_and_size_get_balance_factor(node_db) {
+            // given the imbalance, rotation can only fail if a node can't be loaded from the DB
+            Node::Inner(inner) => match inner.update_height_and_size_get_balance_factor(node_db)? {
                 -2 => {
-                    let right_node = inner.get_mut_right_node(node_db);
+                    let right_node = inner.get_mut_right_node(node_db)?;
 
-                    if right_node.get_balance_factor(node_db) <= 0 {
-                        return {
-                            Self::left_rotate(self, version, node_db)
-                                .expect("given the imbalance, expect rotation to always succeed");
-                            Ok(())
-                        };
+                    if right_node.get_balance_factor(node_db)? <= 0 {
+                        return Self::left_rotate(self, version, node_db);
                     }
 
-                    Self::right_rotate(right_node, version, node_db)
-                        .expect("given the imbalance, expect rotation to always succeed");
+                    Self::right_rotate(right_node, version, node_db)?;
                     Self::left_rotate(self, version, node_db)
-                        .expect("given the imbalance, expect rotation to always succeed");
-                    Ok(())
                 }
 
                 2 => {
-                    let left_node = inner.get_mut_left_node(node_db);
+                    let left_node = inner.get_mut_left_node(node_db)?;
 
-                    if left_node.get_balance_factor(node_db) >= 0 {
-                        return {
-                            Self::right_rotate(self, version, node_db)
-                                .expect("given the imbalance, expect rotation to always succeed");
-                            Ok(())
-                        };
+                    if left_node.get_balance_factor(node_db)? >= 0 {
+                        return Self::right_rotate(self, version, node_db);
                     }
 
-                    Self::left_rotate(left_node, version, node_db)
-                        .expect("given the imbalance, expect rotation to always succeed");
+                    Self::left_rotate(left_node, version, node_db)?;
                     Self::right_rotate(self, version, node_db)
-                        .expect("given the imbalance, expect rotation to always succeed");
-                    Ok(())
                 }
                 -1..=1 => {
                     // The node is balanced
@@ -370,20 +355,13 @@ impl Node {
     fn hash_serialize(&self) -> Vec<u8> {
         match &self {
             Node::Leaf(node) => node.hash_serialize(),
-            Node::Inner(node) => {
-                // NOTE: i64 is used here for parameters for compatibility wih cosmos
-                let height: i64 = node.height.into();
-                let size: i64 = node.size.into();
-                let version: i64 = node.version.into();
-
-                let mut serialized = height.encode_var_vec();
-                serialized.extend(size.encode_var_vec());
-                serialized.extend(version.encode_var_vec());
-                serialized.extend(encode_bytes(&node.left_hash));
-                serialized.extend(encode_bytes(&node.right_hash));
-
-                serialized
-            }
+            Node::Inner(node) => inner_hash_serialize(
+                node.height,
+                node.size,
+                node.version,
+                &node.left_hash,
+                &node.right_hash,
+            ),
         }
     }
 
@@ -450,24 +428,130 @@ impl Node {
         }
     }
 
-    fn get_size(&self) -> u32 {
+    pub(crate) fn get_size(&self) -> u32 {
         match &self {
             Node::Leaf(_) => 1,
             Node::Inner(n) => n.size,
         }
     }
+
+    /// Returns the key and value of the leaf at the given index of this node's subtree, where
+    /// leaves are indexed in key order. Uses the subtree sizes to skip subtrees.
+    pub(crate) fn get_by_index<T: Database>(
+        &self,
+        node_db: &NodeDB<T>,
+        mut index: u32,
+    ) -> Result<Option<KeyValue>, Error> {
+        let mut loop_node = self;
+        let mut cached_node;
+
+        loop {
+            match loop_node {
+                Node::Leaf(leaf) => {
+                    if index == 0 {
+                        return Ok(Some((leaf.key.clone(), leaf.value.clone())));
+                    } else {
+                        return Ok(None);
+                    }
+                }
+                Node::Inner(node) => {
+                    let left_size = match &node.left_node {
+                        Some(left_node) => left_node.get_size(),
+                        None => node_db.fetch_node(&node.left_hash)?.get_size(),
+                    };
+
+                    if index < left_size {
+                        match &node.left_node {
+                            Some(left_node) => loop_node = left_node,
+                            None => {
+                                let left_node = node_db.fetch_node(&node.left_hash)?;
+
+                                cached_node = left_node;
+                                loop_node = &cached_node;
+                            }
+                        }
+                    } else {
+                        index -= left_size;
+
+                        match &node.right_node {
+                            Some(right_node) => loop_node = right_node,
+                            None => {
+                                let right_node = node_db.fetch_node(&node.right_hash)?;
+
+                                cached_node = right_node;
+                                loop_node = &cached_node;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns `Ok(index)` if the key is in this node's subtree, otherwise returns
+    /// `Err(index)` where index is the position at which the key would be inserted.
+    pub(crate) fn get_index_of_key<T: Database>(
+        &self,
+        node_db: &NodeDB<T>,
+        key: &[u8],
+    ) -> Result<Result<u32, u32>, Error> {
+        let mut loop_node = self;
+        let mut cached_node;
+        let mut index = 0;
+
+        loop {
+            match loop_node {
+                Node::Leaf(leaf) => {
+                    return Ok(match key.cmp(&leaf.key) {
+                        Ordering::Less => Err(index),
+                        Ordering::Equal => Ok(index),
+                        Ordering::Greater => Err(index + 1),
+                    });
+                }
+                Node::Inner(node) => {
+                    if key < &node.key {
+                        match &node.left_node {
+                            Some(left_node) => loop_node = left_node,
+                            None => {
+                                let left_node = node_db.fetch_node(&node.left_hash)?;
+
+                                cached_node = left_node;
+                                loop_node = &cached_node;
+                            }
+                        }
+                    } else {
+                        let left_size = match &node.left_node {
+                            Some(left_node) => left_node.get_size(),
+                            None => node_db.fetch_node(&node.left_hash)?.get_size(),
+                        };
+                        index += left_size;
+
+                        match &node.right_node {
+                            Some(right_node) => loop_node = right_node,
+                            None => {
+                                let right_node = node_db.fetch_node(&node.right_hash)?;
+
+                                cached_node = right_node;
+                                loop_node = &cached_node;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
 }
 
 // TODO: rename loaded_version to head_version introduce a working_version (+ remove redundant loaded_version?). this will allow the first committed version to be version 0 rather than 1 (there is no version 0 currently!)
 #[derive(Debug)]
 pub struct Tree<T> {
     root: Option<Box<Node>>,
-    pub(crate) node_db: NodeDB<T>,
+    pub(crate) node_db: Arc<NodeDB<T>>,
     pub(crate) loaded_version: u32,
     pub(crate) versions: BTreeSet<u32>,
 }
 
-#[nutype(validate(greater = 0), derive(TryFrom, Into))]
+#[nutype(validate(greater = 0), derive(TryFrom, Into, Clone, Copy))]
 pub struct CacheSize(usize);
 
 impl<T> Tree<T>
@@ -479,8 +563,8 @@ where
         target_version: Option<u32>,
         cache_size: CacheSize,
     ) -> Result<Tree<T>, Error> {
-        let node_db = NodeDB::new(db, cache_size);
-        let versions = node_db.get_versions();
+        let node_db = Arc::new(NodeDB::new(db, cache_size));
+        let versions = node_db.get_versions()?;
 
         if let Some(target_version) = target_version {
             let root = node_db.get_root_node(target_version)?;
@@ -495,10 +579,7 @@ where
             // use the latest version available
             if let Some(latest_version) = versions.last() {
                 Ok(Tree {
-                    root: node_db
-                        .get_root_node(*latest_version)
-                        .ok()
-                        .unwrap_or_corrupt(),
+                    root: node_db.get_root_node(*latest_version)?,
                     loaded_version: *latest_version,
                     node_db,
                     versions,
@@ -523,7 +604,7 @@ where
             // If the version already exists, return an error as we're attempting to overwrite.
             // However, the same hash means idempotent (i.e. no-op).
             // TODO: do we really need to be doing this?
-            let saved_hash = self.node_db.get_root_hash(version).ok().unwrap_or_corrupt();
+            let saved_hash = self.node_db.get_root_hash(version)?;
             let working_hash = self.root_hash();
 
             if saved_hash == working_hash {
@@ -543,11 +624,11 @@ where
 
         let root = self.root.as_mut();
         let root_hash = if let Some(root) = root {
-            let root_hash = self.node_db.save_tree(root);
-            self.node_db.save_version(version, &root_hash);
+            let root_hash = self.node_db.save_tree(root)?;
+            self.node_db.save_version(version, &root_hash)?;
             root_hash
         } else {
-            self.node_db.save_version(version, &EMPTY_HASH);
+            self.node_db.save_version(version, &EMPTY_HASH)?;
             EMPTY_HASH
         };
 
@@ -568,14 +649,14 @@ where
         self.loaded_version
     }
 
-    pub fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+    pub fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>, Error> {
         match &self.root {
             Some(root) => self.get_(key, root),
-            None => None,
+            None => Ok(None),
         }
     }
 
-    fn get_(&self, key: &[u8], root: &Node) -> Option<Vec<u8>> {
+    fn get_(&self, key: &[u8], root: &Node) -> Result<Option<Vec<u8>>, Error> {
         let mut loop_node = root;
         let mut cached_node;
 
@@ -583,9 +664,9 @@ where
             match loop_node {
                 Node::Leaf(leaf) => {
                     if leaf.key == key {
-                        return Some(leaf.value.clone());
+                        return Ok(Some(leaf.value.clone()));
                     } else {
-                        return None;
+                        return Ok(None);
                     }
                 }
                 Node::Inner(node) => {
@@ -593,10 +674,7 @@ where
                         match &node.left_node {
                             Some(left_node) => loop_node = left_node,
                             None => {
-                                let left_node = self
-                                    .node_db
-                                    .get_node(&node.left_hash)
-                                    .expect("node db should contain all nodes");
+                                let left_node = self.node_db.fetch_node(&node.left_hash)?;
 
                                 cached_node = left_node;
                                 loop_node = &cached_node;
@@ -606,10 +684,7 @@ where
                         match &node.right_node {
                             Some(right_node) => loop_node = right_node,
                             None => {
-                                let right_node = self
-                                    .node_db
-                                    .get_node(&node.right_hash)
-                                    .expect("node db should contain all nodes");
+                                let right_node = self.node_db.fetch_node(&node.right_hash)?;
 
                                 cached_node = right_node;
                                 loop_node = &cached_node;
@@ -621,10 +696,11 @@ where
         }
     }
 
-    pub fn remove(&mut self, key: &(impl AsRef<[u8]> + ?Sized)) -> Option<Vec<u8>> {
+    pub fn remove(&mut self, key: &(impl AsRef<[u8]> + ?Sized)) -> Result<Option<Vec<u8>>, Error> {
         // We use this struct to be 100% sure in output of `recursive_remove`
         struct NodeKey(pub Vec<u8>);
         struct NodeValue(pub Vec<u8>);
+        type Removed = (Option<NodeValue>, Option<Sha256Hash>, bool, Option<NodeKey>);
 
         return match self.root {
             Some(ref mut root) => {
@@ -637,11 +713,11 @@ where
                     key,
                     &mut orphans,
                     self.loaded_version + 1,
-                );
+                )?;
 
-                value.map(|val| val.0)
+                Ok(value.map(|val| val.0))
             }
-            None => None,
+            None => Ok(None),
         };
 
         /// Returns the value corresponding to the key if it was found
@@ -654,27 +730,27 @@ where
             key: &(impl AsRef<[u8]> + ?Sized),
             orphaned: &mut Vec<Node>,
             version: u32,
-        ) -> (Option<NodeValue>, Option<Sha256Hash>, bool, Option<NodeKey>) {
+        ) -> Result<Removed, Error> {
             match node {
                 Node::Leaf(leaf) => {
                     if leaf.key != key.as_ref() {
-                        (None, None, false, None)
+                        Ok((None, None, false, None))
                     } else {
                         orphaned.push(Node::Leaf(leaf.clone()));
-                        (Some(NodeValue(leaf.value.clone())), None, true, None)
+                        Ok((Some(NodeValue(leaf.value.clone())), None, true, None))
                     }
                 }
                 Node::Inner(inner) => {
                     match key.as_ref().cmp(&inner.key) {
                         Ordering::Less => {
-                            let left_node = inner.get_mut_left_node(node_db);
+                            let left_node = inner.get_mut_left_node(node_db)?;
 
                             let (value, new_hash, leaf_cut, new_key) =
-                                recursive_remove(left_node, node_db, key, orphaned, version);
+                                recursive_remove(left_node, node_db, key, orphaned, version)?;
 
                             if value.is_none() {
                                 // The key was not found in the left subtree, so nothing changed
-                                (None, None, false, None)
+                                Ok((None, None, false, None))
                             } else {
                                 // The key was found in the left subtree, either we just removed a leaf node
                                 // or we updated the left subtree's root hash. Either way, we need to orphan
@@ -686,7 +762,7 @@ where
                                 if leaf_cut {
                                     // The left node was a leaf node and was removed.
                                     // We promote the right node to the root of the subtree
-                                    let right_node = inner.get_mut_right_node(node_db);
+                                    let right_node = inner.get_mut_right_node(node_db)?;
                                     *node = right_node.shallow_clone();
 
                                     // The right node was already balanced, so we don't need to call balance
@@ -694,12 +770,12 @@ where
                                     // Also, the right node's height and size were correct so don't need re-calculating
                                     // on the new root node.
                                     // The new leftmost leaf key for the subtree has changed so we return it
-                                    return (
+                                    return Ok((
                                         value,
                                         Some(node.hash()),
                                         false,
                                         Some(NodeKey(node.get_key().to_vec())),
-                                    );
+                                    ));
                                 } else if let Some(new_hash) = new_hash {
                                     // The left subtree's root hash has changed, so update the node's hash
                                     // By updating the node's hash we're essentially creating a new node, so we need to
@@ -707,23 +783,22 @@ where
                                     // Bubble up the new leftmost leaf key for the subtree
                                     inner.version = version;
                                     node.left_hash_set(new_hash);
-                                    node.update_height_and_size_and_balance(version, node_db)
-                                        .expect("balance factor is between -2 and 2 inclusive, so this should never fail");
-                                    return (value, Some(node.hash()), false, new_key);
+                                    node.update_height_and_size_and_balance(version, node_db)?;
+                                    return Ok((value, Some(node.hash()), false, new_key));
                                 } else {
                                     unreachable!("either a leaf was removed or the left subtree's root hash changed")
                                 }
                             }
                         }
                         Ordering::Greater | Ordering::Equal => {
-                            let right_node = inner.get_mut_right_node(node_db);
+                            let right_node = inner.get_mut_right_node(node_db)?;
 
                             let (value, new_hash, leaf_cut, new_key) =
-                                recursive_remove(right_node, node_db, key, orphaned, version);
+                                recursive_remove(right_node, node_db, key, orphaned, version)?;
 
                             if value.is_none() {
                                 // The key was not found in the right subtree, so nothing changed
-                                (None, None, false, None)
+                                Ok((None, None, false, None))
                             } else {
                                 // The key was found in the right subtree, either we just removed a leaf node
                                 // or we updated the right subtree's root hash. Either way, we need to orphan
@@ -735,7 +810,7 @@ where
                                 if leaf_cut {
                                     // The right node was a leaf node and was removed.
                                     // We promote the left node to the root of the subtree
-                                    let left_node = inner.get_mut_left_node(node_db);
+                                    let left_node = inner.get_mut_left_node(node_db)?;
                                     *node = left_node.shallow_clone();
 
                                     // The left node was balanced, so we don't need to call balance
@@ -743,7 +818,7 @@ where
                                     // Since we promoted the left node to the root of the subtree, the leftmost leaf key remains the same
                                     // Also, the left node's height and size were correct so don't need re-calculating
                                     // on the new root node.
-                                    (value, Some(node.hash()), false, None)
+                                    Ok((value, Some(node.hash()), false, None))
                                 } else if let Some(new_hash) = new_hash {
                                     // The right subtree's root hash has changed, so update the node's hash
                                     // By updating the node's hash we're essentially creating a new node, so we need to
@@ -755,9 +830,8 @@ where
                                     if let Some(new_key) = new_key {
                                         node.set_key(new_key.0);
                                     }
-                                    node.update_height_and_size_and_balance(version, node_db)
-                                    .expect("balance factor is between -2 and 2 inclusive, so this should never fail");
-                                    return (value, Some(node.hash()), false, None);
+                                    node.update_height_and_size_and_balance(version, node_db)?;
+                                    return Ok((value, Some(node.hash()), false, None));
                                 } else {
                                     unreachable!("either a leaf was removed or the right subtree's root hash changed")
                                 }
@@ -769,10 +843,10 @@ where
         }
     }
 
-    pub fn set(&mut self, key: Vec<u8>, value: Vec<u8>) {
+    pub fn set(&mut self, key: Vec<u8>, value: Vec<u8>) -> Result<(), Error> {
         match &mut self.root {
             Some(root) => {
-                Self::recursive_set(root, key, value, self.loaded_version + 1, &mut self.node_db)
+                Self::recursive_set(root, key, value, self.loaded_version + 1, &self.node_db)
             }
             None => {
                 self.root = Some(Box::new(Node::Leaf(LeafNode {
@@ -780,8 +854,10 @@ where
                     version: self.loaded_version + 1,
                     value,
                 })));
+
+                Ok(())
             }
-        };
+        }
     }
 
     fn recursive_set(
@@ -789,8 +865,8 @@ where
         key: Vec<u8>,
         value: Vec<u8>,
         version: u32,
-        node_db: &mut NodeDB<T>,
-    ) {
+        node_db: &NodeDB<T>,
+    ) -> Result<(), Error> {
         match &mut node {
             Node::Leaf(leaf_node) => match key.cmp(&leaf_node.key) {
                 cmp::Ordering::Less => {
@@ -836,80 +912,97 @@ where
                 // Perform normal BST
                 if key < root_node.key {
                     Self::recursive_set(
-                        root_node.get_mut_left_node(node_db),
+                        root_node.get_mut_left_node(node_db)?,
                         key.clone(),
                         value,
                         version,
                         node_db,
-                    );
+                    )?;
                     root_node.update_left_hash();
                 } else {
                     Self::recursive_set(
-                        root_node.get_mut_right_node(node_db),
+                        root_node.get_mut_right_node(node_db)?,
                         key.clone(),
                         value,
                         version,
                         node_db,
-                    );
+                    )?;
                     root_node.update_right_hash();
                 }
 
                 // Update height + size + version
-                let balance_factor = root_node.update_height_and_size_get_balance_factor(node_db);
+                let balance_factor =
+                    root_node.update_height_and_size_get_balance_factor(node_db)?;
                 root_node.version = version;
 
-                // If the tree is unbalanced then try out the usual four cases
+                // If the tree is unbalanced then try out the usual four cases. Given the
+                // imbalance, rotation can only fail if a node can't be loaded from the DB
                 if balance_factor > 1 {
-                    let left_node = root_node.get_mut_left_node(node_db);
+                    let left_node = root_node.get_mut_left_node(node_db)?;
 
                     if key[..] < *left_node.get_key() {
                         // Case 1 - Right
-                        node.right_rotate(version, node_db)
-                            .expect("Given the imbalance, expect rotation to always succeed");
+                        node.right_rotate(version, node_db)?;
                     } else {
                         // Case 2 - Left Right
-                        left_node
-                            .left_rotate(version, node_db)
-                            .expect("Given the imbalance, expect rotation to always succeed");
-                        node.right_rotate(version, node_db)
-                            .expect("Given the imbalance, expect rotation to always succeed");
+                        left_node.left_rotate(version, node_db)?;
+                        node.right_rotate(version, node_db)?;
                     }
                 } else if balance_factor < -1 {
-                    let right_node = root_node.get_mut_right_node(node_db);
+                    let right_node = root_node.get_mut_right_node(node_db)?;
 
                     if key[..] > *right_node.get_key() {
                         // Case 3 - Left
-                        node.left_rotate(version, node_db)
-                            .expect("Given the imbalance, expect rotation to always succeed");
+                        node.left_rotate(version, node_db)?;
                     } else {
                         // Case 4 - Right Left
-                        right_node
-                            .right_rotate(version, node_db)
-                            .expect("Given the imbalance, expect rotation to always succeed");
-                        node.left_rotate(version, node_db)
-                            .expect("Given the imbalance, expect rotation to always succeed");
+                        right_node.right_rotate(version, node_db)?;
+                        node.left_rotate(version, node_db)?;
                     }
                 }
             }
         }
+
+        Ok(())
+    }
+
+    /// Returns the number of keys in the tree
+    pub fn size(&self) -> u32 {
+        self.root.as_ref().map(|root| root.get_size()).unwrap_or(0)
+    }
+
+    /// Returns the key and value at the given index, where keys are indexed in order
+    pub fn get_by_index(&self, index: u32) -> Result<Option<KeyValue>, Error> {
+        match &self.root {
+            Some(root) => root.get_by_index(&self.node_db, index),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns `Ok(index)` if the key is in the tree, otherwise returns `Err(index)`
+    /// where index is the position at which the key would be inserted
+    pub fn get_index_of_key(&self, key: &[u8]) -> Result<Result<u32, u32>, Error> {
+        match &self.root {
+            Some(root) => root.get_index_of_key(&self.node_db, key),
+            None => Ok(Err(0)),
+        }
     }
 
     pub fn range<R>(&self, range: R) -> Range<'_, R, T>
     where
         R: RangeBounds<Vec<u8>>,
     {
-        match &self.root {
-            Some(root) => Range {
-                range,
-                delayed_nodes: vec![root.clone()], //TODO: remove clone
-                node_db: &self.node_db,
-            },
-            None => Range {
-                range,
-                delayed_nodes: vec![],
-                node_db: &self.node_db,
-            },
-        }
+        //TODO: remove clone
+        Range::new(range, self.root.clone(), &self.node_db, false)
+    }
+
+    /// Same as [`Tree::range`] but iterates in descending key order
+    pub fn reverse_range<R>(&self, range: R) -> Range<'_, R, T>
+    where
+        R: RangeBounds<Vec<u8>>,
+    {
+        //TODO: remove clone
+        Range::new(range, self.root.clone(), &self.node_db, true)
     }
 }
 
@@ -920,10 +1013,26 @@ where
     pub(crate) range: R,
     pub(crate) delayed_nodes: Vec<Box<Node>>,
     pub(crate) node_db: &'a NodeDB<T>,
+    /// Iterate in descending key order
+    pub(crate) reverse: bool,
 }
 
 impl<'a, T: RangeBounds<Vec<u8>>, R: Database> Range<'a, T, R> {
-    fn traverse(&mut self) -> Option<(Vec<u8>, Vec<u8>)> {
+    pub(crate) fn new(
+        range: T,
+        root: Option<Box<Node>>,
+        node_db: &'a NodeDB<R>,
+        reverse: bool,
+    ) -> Self {
+        Range {
+            range,
+            delayed_nodes: root.into_iter().collect(),
+            node_db,
+            reverse,
+        }
+    }
+
+    fn traverse(&mut self) -> Option<Result<KeyValue, Error>> {
         let node = self.delayed_nodes.pop()?;
 
         let after_start = match self.range.start_bound() {
@@ -940,58 +1049,82 @@ impl<'a, T: RangeBounds<Vec<u8>>, R: Database> Range<'a, T, R> {
 
         match *node {
             Node::Inner(inner) => {
-                // Traverse through the left subtree, then the right subtree.
-                if before_end {
-                    match inner.right_node {
-                        Some(right_node) => self.delayed_nodes.push(right_node),
-                        None => {
-                            let right_node = self
-                                .node_db
-                                .get_node(&inner.right_hash)
-                                .expect("node db should contain all nodes");
-
-                            self.delayed_nodes.push(right_node);
-                        }
-                    }
-                }
+                let right_node = match (before_end, inner.right_node) {
+                    (false, _) => None,
+                    (true, Some(right_node)) => Some(right_node),
+                    (true, None) => match self.node_db.fetch_node(&inner.right_hash) {
+                        Ok(right_node) => Some(right_node),
+                        Err(e) => return Some(Err(self.stop(e))),
+                    },
+                };
 
-                if after_start {
-                    match inner.left_node {
-                        Some(left_node) => self.delayed_nodes.push(left_node),
-                        None => {
-                            let left_node = self
-                                .node_db
-                                .get_node(&inner.left_hash)
-                                .expect("node db should contain all nodes");
-
-                            //self.cached_nodes.push(left_node);
-                            self.delayed_nodes.push(left_node);
-                        }
-                    }
+                let left_node = match (after_start, inner.left_node) {
+                    (false, _) => None,
+                    (true, Some(left_node)) => Some(left_node),
+                    (true, None) => match self.node_db.fetch_node(&inner.left_hash) {
+                        Ok(left_node) => Some(left_node),
+                        Err(e) => return Some(Err(self.stop(e))),
+                    },
+                };
 
-                    //self.delayed_nodes.push(inner.get_left_node(self.node_db));
+                // Traverse through the left subtree, then the right subtree (or the
+                // reverse when iterating in descending order). The last node pushed is visited first.
+                if self.reverse {
+                    self.delayed_nodes.extend(left_node);
+                    self.delayed_nodes.extend(right_node);
+                } else {
+                    self.delayed_nodes.extend(right_node);
+                    self.delayed_nodes.extend(left_node);
                 }
             }
             Node::Leaf(leaf) => {
                 if self.range.contains(&leaf.key) {
                     // we have a leaf node within the range
-                    return Some((leaf.key.clone(), leaf.value.clone()));
+                    return Some(Ok((leaf.key.clone(), leaf.value.clone())));
                 }
             }
         }
 
         self.traverse()
     }
+
+    /// Ends the iteration after a node failed to load, so that the error is the last item
+    fn stop(&mut self, err: Error) -> Error {
+        self.delayed_nodes.clear();
+        err
+    }
 }
 
 impl<'a, T: RangeBounds<Vec<u8>>, R: Database> Iterator for Range<'a, T, R> {
-    type Item = (Vec<u8>, Vec<u8>);
+    type Item = Result<KeyValue, Error>;
 
     fn next(&mut self) -> Option<Self::Item> {
         self.traverse()
     }
 }
 
+/// Serializes the fields of an inner node which contribute to its hash
+pub(crate) fn inner_hash_serialize(
+    height: u8,
+    size: u32,
+    version: u32,
+    left_hash: &Sha256Hash,
+    right_hash: &Sha256Hash,
+) -> Vec<u8> {
+    // NOTE: i64 is used here for parameters for compatibility wih cosmos
+    let height: i64 = height.into();
+    let size: i64 = size.into();
+    let version: i64 = version.into();
+
+    let mut serialized = height.encode_var_vec();
+    serialized.extend(size.encode_var_vec());
+    serialized.extend(version.encode_var_vec());
+    serialized.extend(encode_bytes(left_hash));
+    serialized.extend(encode_bytes(right_hash));
+
+    serialized
+}
+
 fn encode_bytes(bz: &[u8]) -> Vec<u8> {
     let mut enc_bytes = bz.len().encode_var_vec();
     enc_bytes.extend_from_slice(bz);
@@ -1001,7 +1134,11 @@ fn encode_bytes(bz: &[u8]) -> Vec<u8> {
 
 fn decode_bytes(bz: &[u8]) -> Result<(Vec<u8>, usize), Error> {
     let (bz_length, n_consumed) = usize::decode_var(bz).ok_or(Error::NodeDeserialize)?;
-    let bytes = bz[n_consumed..n_consumed + bz_length].to_vec();
+    let bytes = n_consumed
+        .checked_add(bz_length)
+        .and_then(|end| bz.get(n_consumed..end))
+        .ok_or(Error::NodeDeserialize)?
+        .to_vec();
 
     Ok((bytes, n_consumed + bz_length))
 }
@@ -1009,7 +1146,34 @@ fn decode_bytes(bz: &[u8]) -> Result<(Vec<u8>, usize), Error> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use database::MemDB;
+    use database::{Column, MemDB};
+
+    #[test]
+    fn missing_node_returns_error() {
+        let db = MemDB::new();
+        let nodes = db.column(Column::Nodes);
+        let reopened_db = db.column(Column::Default);
+        let mut tree = Tree::new(db, None, 100.try_into().unwrap()).unwrap();
+        tree.set(vec![1], vec![1]).unwrap();
+        tree.set(vec![2], vec![2]).unwrap();
+        tree.save_version().unwrap();
+
+        let Some(Node::Inner(root)) = tree.root.as_deref() else {
+            unreachable!("a tree with two keys has an inner root")
+        };
+        nodes
+            .delete(&NodeDB::<MemDB>::get_node_key(&root.left_hash))
+            .unwrap();
+
+        let tree = Tree::new(reopened_db, None, 100.try_into().unwrap()).unwrap();
+
+        assert!(matches!(tree.get(&[1]), Err(Error::MissingNode)));
+        assert!(matches!(tree.get(&[2]), Ok(Some(_))));
+        assert!(matches!(
+            tree.range(..).next(),
+            Some(Err(Error::MissingNode))
+        ));
+    }
 
     #[test]
     fn remove_leaf_from_tree() -> anyhow::Result<()> {
@@ -1045,7 +1209,7 @@ mod tests {
 
         tree.root = Some(Box::new(Node::Inner(root)));
 
-        let node = tree.remove(&[19]);
+        let node = tree.remove(&[19]).unwrap();
 
         assert_eq!(node, Some(vec![3, 2, 1]));
         assert!(tree.root.is_some());
@@ -1067,11 +1231,11 @@ mod tests {
     fn remove_leaf_works() {
         let db = MemDB::new();
         let mut tree = Tree::new(db, None, 100.try_into().unwrap()).unwrap();
-        tree.set(vec![1], vec![4]);
-        tree.set(vec![2], vec![5]);
-        tree.set(vec![3], vec![6]);
+        tree.set(vec![1], vec![4]).unwrap();
+        tree.set(vec![2], vec![5]).unwrap();
+        tree.set(vec![3], vec![6]).unwrap();
 
-        let val = tree.remove(&[2]);
+        let val = tree.remove(&[2]).unwrap();
 
         assert_eq!(val, Some(vec![5]));
         assert!(tree.root.is_some());
@@ -1084,7 +1248,7 @@ mod tests {
         assert_eq!(hash, expected);
 
         // re-insert the removed key
-        tree.set(vec![2], vec![5]);
+        tree.set(vec![2], vec![5]).unwrap();
 
         let hash = tree.root_hash();
         let expected = [
@@ -1098,13 +1262,13 @@ mod tests {
     fn remove_leaf_after_save_works() {
         let db = MemDB::new();
         let mut tree = Tree::new(db, None, 100.try_into().unwrap()).unwrap();
-        tree.set(vec![1], vec![4]);
-        tree.set(vec![2], vec![5]);
-        tree.set(vec![3], vec![6]);
+        tree.set(vec![1], vec![4]).unwrap();
+        tree.set(vec![2], vec![5]).unwrap();
+        tree.set(vec![3], vec![6]).unwrap();
 
         tree.save_version().unwrap();
 
-        let val = tree.remove(&[2]);
+        let val = tree.remove(&[2]).unwrap();
 
         assert_eq!(val, Some(vec![5]));
         assert!(tree.root.is_some());
@@ -1287,8 +1451,8 @@ mod tests {
     fn set_equal_leaf_works() {
         let db = MemDB::new();
         let mut tree = Tree::new(db, None, 100.try_into().unwrap()).unwrap();
-        tree.set(vec![1], vec![2]);
-        tree.set(vec![1], vec![3]);
+        tree.set(vec![1], vec![2]).unwrap();
+        tree.set(vec![1], vec![3]).unwrap();
 
         let hash = tree.root_hash();
         let expected = [
@@ -1302,8 +1466,8 @@ mod tests {
     fn set_less_than_leaf_works() {
         let db = MemDB::new();
         let mut tree = Tree::new(db, None, 100.try_into().unwrap()).unwrap();
-        tree.set(vec![3], vec![2]);
-        tree.set(vec![1], vec![3]);
+        tree.set(vec![3], vec![2]).unwrap();
+        tree.set(vec![1], vec![3]).unwrap();
 
         let hash = tree.root_hash();
         let expected = [
@@ -1317,8 +1481,8 @@ mod tests {
     fn set_greater_than_leaf_works() {
         let db = MemDB::new();
         let mut tree = Tree::new(db, None, 100.try_into().unwrap()).unwrap();
-        tree.set(vec![1], vec![2]);
-        tree.set(vec![3], vec![3]);
+        tree.set(vec![1], vec![2]).unwrap();
+        tree.set(vec![3], vec![3]).unwrap();
 
         let hash = tree.root_hash();
         let expected = [
@@ -1332,10 +1496,10 @@ mod tests {
     fn repeated_set_works() {
         let db = MemDB::new();
         let mut tree = Tree::new(db, None, 100.try_into().unwrap()).unwrap();
-        tree.set(b"alice".to_vec(), b"abc".to_vec());
-        tree.set(b"bob".to_vec(), b"123".to_vec());
-        tree.set(b"c".to_vec(), b"1".to_vec());
-        tree.set(b"q".to_vec(), b"1".to_vec());
+        tree.set(b"alice".to_vec(), b"abc".to_vec()).unwrap();
+        tree.set(b"bob".to_vec(), b"123".to_vec()).unwrap();
+        tree.set(b"c".to_vec(), b"1".to_vec()).unwrap();
+        tree.set(b"q".to_vec(), b"1".to_vec()).unwrap();
 
         let expected = [
             202, 52, 159, 10, 210, 166, 72, 207, 248, 190, 60, 114, 172, 147, 84, 27, 120, 202,
@@ -1349,17 +1513,17 @@ mod tests {
     fn save_version_works() {
         let db = MemDB::new();
         let mut tree = Tree::new(db, None, 100.try_into().unwrap()).unwrap();
-        tree.set(b"alice".to_vec(), b"abc".to_vec());
-        tree.set(b"bob".to_vec(), b"123".to_vec());
-        tree.set(b"c".to_vec(), b"1".to_vec());
-        tree.set(b"q".to_vec(), b"1".to_vec());
+        tree.set(b"alice".to_vec(), b"abc".to_vec()).unwrap();
+        tree.set(b"bob".to_vec(), b"123".to_vec()).unwrap();
+        tree.set(b"c".to_vec(), b"1".to_vec()).unwrap();
+        tree.set(b"q".to_vec(), b"1".to_vec()).unwrap();
 
         tree.save_version().unwrap();
         tree.save_version().unwrap();
-        tree.set(b"qwerty".to_vec(), b"312".to_vec());
-        tree.set(b"-32".to_vec(), b"gamma".to_vec());
+        tree.set(b"qwerty".to_vec(), b"312".to_vec()).unwrap();
+        tree.set(b"-32".to_vec(), b"gamma".to_vec()).unwrap();
         tree.save_version().unwrap();
-        tree.set(b"alice".to_vec(), b"123".to_vec());
+        tree.set(b"alice".to_vec(), b"123".to_vec()).unwrap();
         tree.save_version().unwrap();
 
         let expected = [
@@ -1374,30 +1538,35 @@ mod tests {
     fn get_works() {
         let db = MemDB::new();
         let mut tree = Tree::new(db, None, 100.try_into().unwrap()).unwrap();
-        tree.set(b"alice".to_vec(), b"abc".to_vec());
-        tree.set(b"bob".to_vec(), b"123".to_vec());
-        tree.set(b"c".to_vec(), b"1".to_vec());
-        tree.set(b"q".to_vec(), b"1".to_vec());
+        tree.set(b"alice".to_vec(), b"abc".to_vec()).unwrap();
+        tree.set(b"bob".to_vec(), b"123".to_vec()).unwrap();
+        tree.set(b"c".to_vec(), b"1".to_vec()).unwrap();
+        tree.set(b"q".to_vec(), b"1".to_vec()).unwrap();
 
-        assert_eq!(tree.get(b"alice"), Some(String::from("abc").into()));
-        assert_eq!(tree.get(b"bob"), Some(String::from("123").into()));
-        assert_eq!(tree.get(b"c"), Some(String::from("1").into()));
-        assert_eq!(tree.get(b"q"), Some(String::from("1").into()));
-        assert_eq!(tree.get(b"house"), None);
+        assert_eq!(
+            tree.get(b"alice").unwrap(),
+            Some(String::from("abc").into())
+        );
+        assert_eq!(tree.get(b"bob").unwrap(), Some(String::from("123").into()));
+        assert_eq!(tree.get(b"c").unwrap(), Some(String::from("1").into()));
+        assert_eq!(tree.get(b"q").unwrap(), Some(String::from("1").into()));
+        assert_eq!(tree.get(b"house").unwrap(), None);
     }
 
     #[test]
     fn scenario_works() {
         let db = MemDB::new();
         let mut tree = Tree::new(db, None, 100.try_into().unwrap()).unwrap();
-        tree.set(vec![0, 117, 97, 116, 111, 109], vec![51, 52]);
+        tree.set(vec![0, 117, 97, 116, 111, 109], vec![51, 52])
+            .unwrap();
         tree.set(
             vec![
                 2, 20, 129, 58, 194, 42, 97, 73, 22, 85, 226, 120, 106, 224, 209, 39, 214, 153, 11,
                 251, 251, 222, 117, 97, 116, 111, 109,
             ],
             vec![10, 5, 117, 97, 116, 111, 109, 18, 2, 51, 52],
-        );
+        )
+        .unwrap();
 
         tree.save_version().unwrap();
         tree.save_version().unwrap();
@@ -1413,21 +1582,24 @@ mod tests {
                 72, 143, 236, 46, 117, 97, 116, 111, 109,
             ],
             vec![10, 5, 117, 97, 116, 111, 109, 18, 2, 49, 48],
-        );
+        )
+        .unwrap();
         tree.set(
             vec![
                 2, 20, 129, 58, 194, 42, 97, 73, 22, 85, 226, 120, 106, 224, 209, 39, 214, 153, 11,
                 251, 251, 222, 117, 97, 116, 111, 109,
             ],
             vec![10, 5, 117, 97, 116, 111, 109, 18, 2, 50, 51],
-        );
+        )
+        .unwrap();
         tree.set(
             vec![
                 2, 20, 241, 130, 150, 118, 219, 87, 118, 130, 233, 68, 252, 52, 147, 212, 81, 182,
                 127, 243, 226, 159, 117, 97, 116, 111, 109,
             ],
             vec![10, 5, 117, 97, 116, 111, 109, 18, 1, 49],
-        );
+        )
+        .unwrap();
 
         let expected = [
             34, 215, 64, 141, 118, 237, 192, 198, 47, 22, 34, 81, 0, 146, 145, 66, 182, 59, 101,
@@ -1443,19 +1615,20 @@ mod tests {
     fn bounded_range_works() {
         let db = MemDB::new();
         let mut tree = Tree::new(db, None, 100.try_into().unwrap()).unwrap();
-        tree.set(b"1".to_vec(), b"abc1".to_vec());
+        tree.set(b"1".to_vec(), b"abc1".to_vec()).unwrap();
 
-        tree.set(b"2".to_vec(), b"abc2".to_vec());
-        tree.set(b"3".to_vec(), b"abc3".to_vec());
-        tree.set(b"4".to_vec(), b"abc4".to_vec());
-        tree.set(b"5".to_vec(), b"abc5".to_vec());
-        tree.set(b"6".to_vec(), b"abc6".to_vec());
-        tree.set(b"7".to_vec(), b"abc7".to_vec());
+        tree.set(b"2".to_vec(), b"abc2".to_vec()).unwrap();
+        tree.set(b"3".to_vec(), b"abc3".to_vec()).unwrap();
+        tree.set(b"4".to_vec(), b"abc4".to_vec()).unwrap();
+        tree.set(b"5".to_vec(), b"abc5".to_vec()).unwrap();
+        tree.set(b"6".to_vec(), b"abc6".to_vec()).unwrap();
+        tree.set(b"7".to_vec(), b"abc7".to_vec()).unwrap();
 
         // [,)
         let start = b"3".to_vec();
         let stop = b"6".to_vec();
-        let got_pairs: Vec<(Vec<u8>, Vec<u8>)> = tree.range(start..stop).collect();
+        let got_pairs: Vec<(Vec<u8>, Vec<u8>)> =
+            tree.range(start..stop).map(Result::unwrap).collect();
         let expected_pairs = vec![
             (b"3".to_vec(), b"abc3".to_vec()),
             (b"4".to_vec(), b"abc4".to_vec()),
@@ -1471,7 +1644,8 @@ mod tests {
         // [,]
         let start = b"3".to_vec();
         let stop = b"6".to_vec();
-        let got_pairs: Vec<(Vec<u8>, Vec<u8>)> = tree.range(start..=stop).collect();
+        let got_pairs: Vec<(Vec<u8>, Vec<u8>)> =
+            tree.range(start..=stop).map(Result::unwrap).collect();
         let expected_pairs = vec![
             (b"3".to_vec(), b"abc3".to_vec()),
             (b"4".to_vec(), b"abc4".to_vec()),
@@ -1490,6 +1664,7 @@ mod tests {
         let stop = b"6".to_vec();
         let got_pairs: Vec<(Vec<u8>, Vec<u8>)> = tree
             .range((Bound::Excluded(start), Bound::Excluded(stop)))
+            .map(Result::unwrap)
             .collect();
         let expected_pairs = vec![
             (b"4".to_vec(), b"abc4".to_vec()),
@@ -1507,11 +1682,11 @@ mod tests {
     fn full_range_unique_keys_works() {
         let db = MemDB::new();
         let mut tree = Tree::new(db, None, 100.try_into().unwrap()).unwrap();
-        tree.set(b"alice".to_vec(), b"abc".to_vec());
-        tree.set(b"bob".to_vec(), b"123".to_vec());
-        tree.set(b"c".to_vec(), b"1".to_vec());
-        tree.set(b"q".to_vec(), b"1".to_vec());
-        let got_pairs: Vec<(Vec<u8>, Vec<u8>)> = tree.range(..).collect();
+        tree.set(b"alice".to_vec(), b"abc".to_vec()).unwrap();
+        tree.set(b"bob".to_vec(), b"123".to_vec()).unwrap();
+        tree.set(b"c".to_vec(), b"1".to_vec()).unwrap();
+        tree.set(b"q".to_vec(), b"1".to_vec()).unwrap();
+        let got_pairs: Vec<(Vec<u8>, Vec<u8>)> = tree.range(..).map(Result::unwrap).collect();
 
         let expected_pairs = vec![
             (b"alice".to_vec(), b"abc".to_vec()),
@@ -1531,9 +1706,9 @@ mod tests {
     fn full_range_duplicate_keys_works() {
         let db = MemDB::new();
         let mut tree = Tree::new(db, None, 100.try_into().unwrap()).unwrap();
-        tree.set(b"alice".to_vec(), b"abc".to_vec());
-        tree.set(b"alice".to_vec(), b"abc".to_vec());
-        let got_pairs: Vec<(Vec<u8>, Vec<u8>)> = tree.range(..).collect();
+        tree.set(b"alice".to_vec(), b"abc".to_vec()).unwrap();
+        tree.set(b"alice".to_vec(), b"abc".to_vec()).unwrap();
+        let got_pairs: Vec<(Vec<u8>, Vec<u8>)> = tree.range(..).map(Result::unwrap).collect();
 
         let expected_pairs = vec![(b"alice".to_vec(), b"abc".to_vec())];
 
@@ -1548,7 +1723,7 @@ mod tests {
     fn empty_tree_range_works() {
         let db = MemDB::new();
         let tree = Tree::new(db, None, 100.try_into().unwrap()).unwrap();
-        let got_pairs: Vec<(Vec<u8>, Vec<u8>)> = tree.range(..).collect();
+        let got_pairs: Vec<(Vec<u8>, Vec<u8>)> = tree.range(..).map(Result::unwrap).collect();
 
         let expected_pairs: Vec<(Vec<u8>, Vec<u8>)> = vec![];
 
@@ -1559,6 +1734,84 @@ mod tests {
         }));
     }
 
+    #[test]
+    fn reverse_range_works() {
+        let db = MemDB::new();
+        let mut tree = Tree::new(db, None, 100.try_into().unwrap()).unwrap();
+        tree.set(b"1".to_vec(), b"abc1".to_vec()).unwrap();
+        tree.set(b"2".to_vec(), b"abc2".to_vec()).unwrap();
+        tree.set(b"3".to_vec(), b"abc3".to_vec()).unwrap();
+        tree.save_version().unwrap();
+        tree.set(b"4".to_vec(), b"abc4".to_vec()).unwrap();
+        tree.set(b"5".to_vec(), b"abc5".to_vec()).unwrap();
+        tree.set(b"6".to_vec(), b"abc6".to_vec()).unwrap();
+
+        let got_pairs: Vec<(Vec<u8>, Vec<u8>)> = tree
+            .reverse_range(b"2".to_vec()..=b"5".to_vec())
+            .map(Result::unwrap)
+            .collect();
+        let expected_pairs = vec![
+            (b"5".to_vec(), b"abc5".to_vec()),
+            (b"4".to_vec(), b"abc4".to_vec()),
+            (b"3".to_vec(), b"abc3".to_vec()),
+            (b"2".to_vec(), b"abc2".to_vec()),
+        ];
+        assert_eq!(expected_pairs, got_pairs);
+
+        let mut got_keys: Vec<Vec<u8>> = tree
+            .reverse_range(..)
+            .map(Result::unwrap)
+            .map(|(key, _)| key)
+            .collect();
+        got_keys.reverse();
+        let expected_keys: Vec<Vec<u8>> = tree
+            .range(..)
+            .map(Result::unwrap)
+            .map(|(key, _)| key)
+            .collect();
+        assert_eq!(expected_keys, got_keys);
+    }
+
+    #[test]
+    fn get_by_index_works() {
+        let db = MemDB::new();
+        let mut tree = Tree::new(db, None, 100.try_into().unwrap()).unwrap();
+
+        for i in (0..100u8).rev() {
+            tree.set(vec![i * 2], vec![i]).unwrap();
+            if i % 10 == 0 {
+                tree.save_version().unwrap();
+            }
+        }
+
+        assert_eq!(tree.size(), 100);
+        for i in 0..100u8 {
+            assert_eq!(
+                tree.get_by_index(i.into()).unwrap(),
+                Some((vec![i * 2], vec![i]))
+            );
+        }
+        assert_eq!(tree.get_by_index(100).unwrap(), None);
+    }
+
+    #[test]
+    fn get_index_of_key_works() {
+        let db = MemDB::new();
+        let mut tree = Tree::new(db, None, 100.try_into().unwrap()).unwrap();
+        assert_eq!(tree.get_index_of_key(&[1]).unwrap(), Err(0));
+
+        for i in 0..100u8 {
+            tree.set(vec![i * 2 + 1], vec![i]).unwrap();
+        }
+        tree.save_version().unwrap();
+
+        for i in 0..100u8 {
+            assert_eq!(tree.get_index_of_key(&[i * 2 + 1]).unwrap(), Ok(i.into()));
+            assert_eq!(tree.get_index_of_key(&[i * 2]).unwrap(), Err(i.into()));
+        }
+        assert_eq!(tree.get_index_of_key(&[255]).unwrap(), Err(100));
+    }
+
     #[test]
     fn serialize_deserialize_inner_works() {
         let orig_node = Node::Inner(InnerNode {
@@ -1611,13 +1864,13 @@ mod tests {
     fn bug_scenario_works() {
         let db = MemDB::new();
         let mut tree = Tree::new(db, None, 100.try_into().unwrap()).unwrap();
-        tree.set(vec![0], vec![8, 244, 162, 237, 1]);
+        tree.set(vec![0], vec![8, 244, 162, 237, 1]).unwrap();
         tree.save_version().unwrap();
-        tree.set(vec![0], vec![8, 133, 164, 237, 1]);
+        tree.set(vec![0], vec![8, 133, 164, 237, 1]).unwrap();
         tree.save_version().unwrap();
-        tree.set(vec![0], vec![8, 133, 164, 237, 1]);
+        tree.set(vec![0], vec![8, 133, 164, 237, 1]).unwrap();
         tree.save_version().unwrap();
-        tree.set(vec![0], vec![8, 135, 164, 237, 1]);
+        tree.set(vec![0], vec![8, 135, 164, 237, 1]).unwrap();
         tree.set(
             vec![
                 1, 173, 86, 59, 0, 0, 0, 0, 0, 1, 129, 58, 194, 42, 97, 73, 22, 85, 226, 120, 106,
@@ -1629,13 +1882,15 @@ mod tests {
                 57, 108, 104, 55, 55, 55, 112, 97, 104, 117, 117, 120, 16, 173, 173, 237, 1, 24, 1,
                 34, 3, 1, 2, 3,
             ],
-        );
+        )
+        .unwrap();
         tree.set(
             vec![2, 173, 86, 59, 0, 0, 0, 0, 0, 1],
             vec![8, 173, 173, 237, 1, 16, 1],
-        );
+        )
+        .unwrap();
         tree.save_version().unwrap();
-        tree.set(vec![0], vec![8, 137, 164, 237, 1]);
+        tree.set(vec![0], vec![8, 137, 164, 237, 1]).unwrap();
         tree.set(
             vec![
                 1, 173, 86, 59, 0, 0, 0, 0, 0, 1, 133, 145, 191, 185, 82, 168, 56, 30, 164, 88, 69,
@@ -1647,13 +1902,15 @@ mod tests {
                 122, 102, 101, 54, 57, 108, 97, 48, 104, 120, 122, 16, 173, 173, 237, 1, 24, 1, 34,
                 3, 1, 2, 3,
             ],
-        );
+        )
+        .unwrap();
         tree.set(
             vec![2, 173, 86, 59, 0, 0, 0, 0, 0, 1],
             vec![8, 173, 173, 237, 1, 16, 1],
-        );
+        )
+        .unwrap();
         tree.save_version().unwrap();
-        tree.set(vec![0], vec![8, 138, 164, 237, 1]);
+        tree.set(vec![0], vec![8, 138, 164, 237, 1]).unwrap();
         tree.set(
             vec![
                 1, 174, 86, 59, 0, 0, 0, 0, 0, 1, 133, 145, 191, 185, 82, 168, 56, 30, 164, 88, 69,
@@ -1665,15 +1922,17 @@ mod tests {
                 122, 102, 101, 54, 57, 108, 97, 48, 104, 120, 122, 16, 174, 173, 237, 1, 24, 1, 34,
                 3, 1, 2, 3,
             ],
-        );
+        )
+        .unwrap();
         tree.set(
             vec![2, 174, 86, 59, 0, 0, 0, 0, 0, 1],
             vec![8, 174, 173, 237, 1, 16, 1],
-        );
+        )
+        .unwrap();
         tree.save_version().unwrap();
-        tree.set(vec![0], vec![8, 140, 164, 237, 1]);
+        tree.set(vec![0], vec![8, 140, 164, 237, 1]).unwrap();
         tree.save_version().unwrap();
-        tree.set(vec![0], vec![8, 142, 164, 237, 1]);
+        tree.set(vec![0], vec![8, 142, 164, 237, 1]).unwrap();
 
         tree.set(
             vec![
@@ -1686,7 +1945,8 @@ mod tests {
                 57, 108, 104, 55, 55, 55, 112, 97, 104, 117, 117, 120, 16, 174, 173, 237, 1, 24, 1,
                 34, 3, 1, 2, 3,
             ],
-        );
+        )
+        .unwrap();
 
         tree.save_version().unwrap();
 
@@ -1711,7 +1971,8 @@ mod tests {
             vec![
                 0, 0, 0, 0, 0, 0, 0, 0, 24, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 58,
             ],
-        );
+        )
+        .unwrap();
 
         tree.set(
             vec![
@@ -1720,7 +1981,8 @@ mod tests {
             vec![
                 0, 0, 0, 24, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
             ],
-        );
+        )
+        .unwrap();
         tree.set(
             vec![
                 0, 0, 0, 0, 0, 0, 0, 0, 58, 0, 0, 0, 24, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
@@ -1728,7 +1990,8 @@ mod tests {
             vec![
                 0, 0, 0, 0, 0, 0, 0, 0, 58, 0, 0, 0, 24, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
             ],
-        );
+        )
+        .unwrap();
 
         tree.set(
             vec![
@@ -1737,7 +2000,8 @@ mod tests {
             vec![
                 0, 0, 0, 0, 0, 0, 0, 36, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
             ],
-        );
+        )
+        .unwrap();
 
         tree.save_version().unwrap();
 
@@ -1761,16 +2025,12 @@ mod tests {
             Node::Inner(node) => {
                 let left_node = match &node.left_node {
                     Some(left_node) => left_node.clone(),
-                    None => node_db
-                        .get_node(&node.left_hash)
-                        .expect("node db should contain all nodes"),
+                    None => node_db.fetch_node(&node.left_hash).unwrap(),
                 };
 
                 let right_node = match &node.right_node {
                     Some(right_node) => right_node.clone(),
-                    None => node_db
-                        .get_node(&node.right_hash)
-                        .expect("node db should contain all nodes"),
+                    None => node_db.fetch_node(&node.right_hash).unwrap(),
                 };
 
                 dbg!("left_node: {:?}", &left_node);
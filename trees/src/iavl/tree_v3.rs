@@ -1,131 +1,1191 @@
-use std::collections::HashMap;
+use std::{
+    cmp::{self, Ordering},
+    collections::BTreeSet,
+    mem,
+    ops::{Bound, RangeBounds},
+    sync::{Arc, OnceLock},
+};
 
-enum BTreeMap<K: Clone, V: Clone> {
-    Leaf(Leaf<K, V>),
-    Inner(Inner<K, V>),
+use database::Database;
+use sha2::{Digest, Sha256};
+
+use crate::{
+    error::{constants::LEAF_ROTATE_ERROR, Error},
+    merkle::{Sha256Hash, EMPTY_HASH},
+};
+
+use super::{
+    node_db::NodeDB,
+    tree::{inner_hash_serialize, InnerNode, KeyValue, LeafNode, Node},
+    CacheSize,
+};
+
+/// A child of an inner node. The child is loaded from the DB the first time it is
+/// accessed and is then kept in memory, across versions, until it is evicted by save_version.
+#[derive(Debug)]
+struct Child {
+    /// Hash of the node in the DB, None if the node has never been saved
+    stored_hash: Option<Sha256Hash>,
+    node: OnceLock<Box<CachedNode>>,
 }
 
-impl<K: Clone, V: Clone> BTreeMap<K, V> {
-    fn iter(&self) -> BTreeMapIterator<'_, K, V> {
-        BTreeMapIterator::new(self)
+impl Child {
+    fn new(node: CachedNode) -> Self {
+        Self {
+            stored_hash: None,
+            node: OnceLock::from(Box::new(node)),
+        }
+    }
+
+    fn stored(hash: Sha256Hash) -> Self {
+        Self {
+            stored_hash: Some(hash),
+            node: OnceLock::new(),
+        }
+    }
+
+    fn hash(&self) -> Sha256Hash {
+        match self.node.get() {
+            Some(node) => node.hash(),
+            None => self
+                .stored_hash
+                .expect("a child is always either loaded or stored"),
+        }
+    }
+
+    fn get<T: Database>(&self, node_db: &NodeDB<T>) -> Result<&CachedNode, Error> {
+        if let Some(node) = self.node.get() {
+            return Ok(node);
+        }
+
+        let hash = self
+            .stored_hash
+            .expect("a child is always either loaded or stored");
+        let node = node_db.fetch_node(&hash)?;
+
+        Ok(self
+            .node
+            .get_or_init(|| Box::new(CachedNode::from_stored(*node, hash))))
+    }
+
+    fn get_mut<T: Database>(&mut self, node_db: &NodeDB<T>) -> Result<&mut CachedNode, Error> {
+        self.get(node_db)?;
+        Ok(self.node.get_mut().expect("node was loaded above"))
     }
-}
 
-struct Inner<K: Clone, V: Clone> {
-    left_subtree: Option<Box<BTreeMap<K, V>>>,
-    right_subtree: Option<Box<BTreeMap<K, V>>>,
-    left_hash: [u8; 32],
-    right_hash: [u8; 32],
+    fn take<T: Database>(&mut self, node_db: &NodeDB<T>) -> Result<CachedNode, Error> {
+        self.get(node_db)?;
+        Ok(*self.node.take().expect("node was loaded above"))
+    }
 }
 
-#[derive(Clone)]
+#[derive(Debug)]
 struct CachedInner {
-    left_hash: [u8; 32],
-    right_hash: [u8; 32],
+    key: Vec<u8>,
+    height: u8,
+    size: u32, // number of leaf nodes in this node's subtrees
+    version: u32,
+    left: Child,
+    right: Child,
 }
 
-#[derive(Clone)]
-enum CachedBTreeMap<K, V> {
-    Leaf(Leaf<K, V>),
+impl CachedInner {
+    /// Updates the node's height and size and returns its balance factor
+    fn update_height_and_size_get_balance_factor<T: Database>(
+        &mut self,
+        node_db: &NodeDB<T>,
+    ) -> Result<i16, Error> {
+        let left = self.left.get(node_db)?;
+        let (left_height, left_size) = (left.height(), left.size());
+
+        let right = self.right.get(node_db)?;
+        let (right_height, right_size) = (right.height(), right.size());
+
+        self.height = 1 + cmp::max(left_height, right_height);
+        self.size = left_size + right_size;
+
+        Ok(left_height as i16 - right_height as i16)
+    }
+}
+
+#[derive(Debug)]
+enum NodeKind {
+    Leaf(LeafNode),
     Inner(CachedInner),
 }
 
-#[derive(Clone)]
-struct Leaf<K, V> {
-    key: K,
-    value: V,
+impl Default for NodeKind {
+    fn default() -> Self {
+        NodeKind::Leaf(Default::default())
+    }
 }
 
-enum BorrowedOrCached<'a, K: Clone, V: Clone> {
-    Cached(CachedBTreeMap<K, V>),
-    Borrowed(&'a BTreeMap<K, V>),
+/// The removed value, whether the node was a leaf which was removed and the subtree's new
+/// leftmost key, see [`CachedNode::recursive_remove`]
+type Removed = (Option<Vec<u8>>, bool, Option<Vec<u8>>);
+
+/// A tree node which caches its own hash. The cached hash is cleared whenever
+/// the node is mutated, so it is only recomputed when it is next needed.
+#[derive(Debug, Default)]
+struct CachedNode {
+    kind: NodeKind,
+    hash: OnceLock<Sha256Hash>,
 }
 
-struct BTreeMapIterator<'a, K: Clone, V: Clone> {
-    right_subtrees: Vec<BorrowedOrCached<'a, K, V>>,
-    node_store: HashMap<[u8; 32], CachedBTreeMap<K, V>>,
+impl CachedNode {
+    fn new(kind: NodeKind) -> Self {
+        Self {
+            kind,
+            hash: OnceLock::new(),
+        }
+    }
+
+    fn new_leaf(key: Vec<u8>, value: Vec<u8>, version: u32) -> Self {
+        Self::new(NodeKind::Leaf(LeafNode {
+            value,
+            key,
+            version,
+        }))
+    }
+
+    /// Converts a node read from the DB, whose hash is already known
+    fn from_stored(node: Node, hash: Sha256Hash) -> Self {
+        let kind = match node {
+            Node::Leaf(leaf) => NodeKind::Leaf(leaf),
+            Node::Inner(inner) => NodeKind::Inner(CachedInner {
+                key: inner.key,
+                height: inner.height,
+                size: inner.size,
+                version: inner.version,
+                left: Child::stored(inner.left_hash),
+                right: Child::stored(inner.right_hash),
+            }),
+        };
+
+        Self {
+            kind,
+            hash: OnceLock::from(hash),
+        }
+    }
+
+    /// Returns a mutable reference to the node's contents and clears the cached hash
+    fn kind_mut(&mut self) -> &mut NodeKind {
+        self.hash.take();
+        &mut self.kind
+    }
+
+    fn hash(&self) -> Sha256Hash {
+        *self.hash.get_or_init(|| {
+            let serialized = match &self.kind {
+                NodeKind::Leaf(leaf) => leaf.hash_serialize(),
+                NodeKind::Inner(inner) => inner_hash_serialize(
+                    inner.height,
+                    inner.size,
+                    inner.version,
+                    &inner.left.hash(),
+                    &inner.right.hash(),
+                ),
+            };
+
+            Sha256::digest(serialized).into()
+        })
+    }
+
+    fn key(&self) -> &[u8] {
+        match &self.kind {
+            NodeKind::Leaf(leaf) => &leaf.key,
+            NodeKind::Inner(inner) => &inner.key,
+        }
+    }
+
+    fn version(&self) -> u32 {
+        match &self.kind {
+            NodeKind::Leaf(leaf) => leaf.version,
+            NodeKind::Inner(inner) => inner.version,
+        }
+    }
+
+    fn height(&self) -> u8 {
+        match &self.kind {
+            NodeKind::Leaf(_) => 0,
+            NodeKind::Inner(inner) => inner.height,
+        }
+    }
+
+    fn size(&self) -> u32 {
+        match &self.kind {
+            NodeKind::Leaf(_) => 1,
+            NodeKind::Inner(inner) => inner.size,
+        }
+    }
+
+    fn balance_factor<T: Database>(&self, node_db: &NodeDB<T>) -> Result<i16, Error> {
+        match &self.kind {
+            NodeKind::Leaf(_) => Ok(0),
+            NodeKind::Inner(inner) => Ok(inner.left.get(node_db)?.height() as i16
+                - inner.right.get(node_db)?.height() as i16),
+        }
+    }
+
+    /// Converts the node into a [`Node`] without any of its descendants
+    fn to_shallow_node(&self) -> Node {
+        match &self.kind {
+            NodeKind::Leaf(leaf) => Node::Leaf(leaf.clone()),
+            NodeKind::Inner(inner) => Node::Inner(InnerNode {
+                left_node: None,
+                right_node: None,
+                height: inner.height,
+                size: inner.size,
+                left_hash: inner.left.hash(),
+                right_hash: inner.right.hash(),
+                key: inner.key.clone(),
+                version: inner.version,
+            }),
+        }
+    }
+
+    /// Returns the number of nodes in the node's subtree which are loaded in memory.
+    /// Counting stops once the count exceeds the given limit.
+    fn loaded_count(&self, limit: usize) -> usize {
+        let mut count = 0;
+        let mut nodes = vec![self];
+
+        while let Some(node) = nodes.pop() {
+            count += 1;
+
+            if count > limit {
+                break;
+            }
+
+            if let NodeKind::Inner(inner) = &node.kind {
+                nodes.extend(inner.left.node.get().map(AsRef::as_ref));
+                nodes.extend(inner.right.node.get().map(AsRef::as_ref));
+            }
+        }
+
+        count
+    }
+
+    /// Drops the node's descendants which are more than `depth` levels below it from memory,
+    /// they will be reloaded from the DB when needed. The descendants must have been saved.
+    fn unload_below(&mut self, depth: u32) {
+        // the node's hash doesn't change so there's no need to go through kind_mut
+        if let NodeKind::Inner(inner) = &mut self.kind {
+            for child in [&mut inner.left, &mut inner.right] {
+                if depth == 0 {
+                    *child = Child::stored(child.hash());
+                } else if let Some(node) = child.node.get_mut() {
+                    node.unload_below(depth - 1);
+                }
+            }
+        }
+    }
+
+    fn right_rotate<T: Database>(
+        &mut self,
+        version: u32,
+        node_db: &NodeDB<T>,
+    ) -> Result<(), Error> {
+        if let NodeKind::Leaf(_) = self.kind {
+            // Can't rotate a leaf node
+            return Err(Error::RotateError(LEAF_ROTATE_ERROR.to_owned()));
+        }
+
+        let NodeKind::Inner(mut z) = mem::take(self.kind_mut()) else {
+            unreachable!("node was checked to be an inner node")
+        };
+
+        let NodeKind::Inner(mut y) = z.left.take(node_db)?.kind else {
+            return Err(Error::RotateError(LEAF_ROTATE_ERROR.to_owned()));
+        };
+
+        // Perform rotation on z and update height
+        z.left = y.right;
+        z.update_height_and_size_get_balance_factor(node_db)?;
+        z.version = version;
+
+        // Perform rotation on y and update height
+        y.right = Child::new(CachedNode::new(NodeKind::Inner(z)));
+        y.update_height_and_size_get_balance_factor(node_db)?;
+        y.version = version;
+
+        self.kind = NodeKind::Inner(y);
+
+        Ok(())
+    }
+
+    fn left_rotate<T: Database>(&mut self, version: u32, node_db: &NodeDB<T>) -> Result<(), Error> {
+        if let NodeKind::Leaf(_) = self.kind {
+            // Can't rotate a leaf node
+            return Err(Error::RotateError(LEAF_ROTATE_ERROR.to_owned()));
+        }
+
+        let NodeKind::Inner(mut z) = mem::take(self.kind_mut()) else {
+            unreachable!("node was checked to be an inner node")
+        };
+
+        let NodeKind::Inner(mut y) = z.right.take(node_db)?.kind else {
+            return Err(Error::RotateError(LEAF_ROTATE_ERROR.to_owned()));
+        };
+
+        // Perform rotation on z and update height
+        z.right = y.left;
+        z.update_height_and_size_get_balance_factor(node_db)?;
+        z.version = version;
+
+        // Perform rotation on y and update height
+        y.left = Child::new(CachedNode::new(NodeKind::Inner(z)));
+        y.update_height_and_size_get_balance_factor(node_db)?;
+        y.version = version;
+
+        self.kind = NodeKind::Inner(y);
+
+        Ok(())
+    }
+
+    /// Updates the node's height and size and balance factor
+    /// Balances a node if it's balance factor is between -2 and 2 (inclusive)
+    fn update_height_and_size_and_balance<T: Database>(
+        &mut self,
+        version: u32,
+        node_db: &NodeDB<T>,
+    ) -> Result<(), Error> {
+        let NodeKind::Inner(inner) = self.kind_mut() else {
+            // A leaf node is always balanced
+            return Ok(());
+        };
+
+        // given the imbalance, rotation can only fail if a node can't be loaded from the DB
+        match inner.update_height_and_size_get_balance_factor(node_db)? {
+            -2 => {
+                if inner.right.get(node_db)?.balance_factor(node_db)? > 0 {
+                    inner
+                        .right
+                        .get_mut(node_db)?
+                        .right_rotate(version, node_db)?;
+                }
+
+                self.left_rotate(version, node_db)
+            }
+            2 => {
+                if inner.left.get(node_db)?.balance_factor(node_db)? < 0 {
+                    inner.left.get_mut(node_db)?.left_rotate(version, node_db)?;
+                }
+
+                self.right_rotate(version, node_db)
+            }
+            -1..=1 => {
+                // The node is balanced
+                Ok(())
+            }
+            _ => Err(Error::Balancing),
+        }
+    }
+
+    fn recursive_set<T: Database>(
+        &mut self,
+        key: Vec<u8>,
+        value: Vec<u8>,
+        version: u32,
+        node_db: &NodeDB<T>,
+    ) -> Result<(), Error> {
+        match self.kind_mut() {
+            NodeKind::Leaf(leaf_node) => match key.cmp(&leaf_node.key) {
+                Ordering::Less => {
+                    let leaf_node = mem::take(leaf_node);
+
+                    self.kind = NodeKind::Inner(CachedInner {
+                        key: leaf_node.key.clone(),
+                        height: 1,
+                        size: 2,
+                        version,
+                        left: Child::new(CachedNode::new_leaf(key, value, version)),
+                        right: Child::new(CachedNode::new(NodeKind::Leaf(leaf_node))),
+                    });
+                }
+                Ordering::Equal => {
+                    leaf_node.value = value;
+                    leaf_node.version = version;
+                }
+                Ordering::Greater => {
+                    let leaf_node = mem::take(leaf_node);
+
+                    self.kind = NodeKind::Inner(CachedInner {
+                        key: key.clone(),
+                        height: 1,
+                        size: 2,
+                        version,
+                        left: Child::new(CachedNode::new(NodeKind::Leaf(leaf_node))),
+                        right: Child::new(CachedNode::new_leaf(key, value, version)),
+                    });
+                }
+            },
+            NodeKind::Inner(inner) => {
+                // Perform normal BST
+                if key < inner.key {
+                    inner.left.get_mut(node_db)?.recursive_set(
+                        key.clone(),
+                        value,
+                        version,
+                        node_db,
+                    )?;
+                } else {
+                    inner.right.get_mut(node_db)?.recursive_set(
+                        key.clone(),
+                        value,
+                        version,
+                        node_db,
+                    )?;
+                }
+
+                // Update height + size + version
+                let balance_factor = inner.update_height_and_size_get_balance_factor(node_db)?;
+                inner.version = version;
+
+                // If the tree is unbalanced then try out the usual four cases. Given the
+                // imbalance, rotation can only fail if a node can't be loaded from the DB
+                if balance_factor > 1 {
+                    let left_node = inner.left.get_mut(node_db)?;
+
+                    if key[..] >= *left_node.key() {
+                        // Case 2 - Left Right
+                        left_node.left_rotate(version, node_db)?;
+                    }
+
+                    // Case 1 - Right
+                    self.right_rotate(version, node_db)?;
+                } else if balance_factor < -1 {
+                    let right_node = inner.right.get_mut(node_db)?;
+
+                    if key[..] <= *right_node.key() {
+                        // Case 4 - Right Left
+                        right_node.right_rotate(version, node_db)?;
+                    }
+
+                    // Case 3 - Left
+                    self.left_rotate(version, node_db)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the value corresponding to the key if it was found
+    /// Whether the node passed in was a leaf node and was removed
+    /// The new leftmost leaf key for the subtree (if it has changed) after successfully removing 'key'
+    fn recursive_remove<T: Database>(
+        &mut self,
+        key: &[u8],
+        version: u32,
+        node_db: &NodeDB<T>,
+    ) -> Result<Removed, Error> {
+        // self.kind is only accessed mutably through kind_mut once we know that the key is present
+        match &mut self.kind {
+            NodeKind::Leaf(leaf) => {
+                if leaf.key != key {
+                    Ok((None, false, None))
+                } else {
+                    Ok((Some(leaf.value.clone()), true, None))
+                }
+            }
+            NodeKind::Inner(inner) => {
+                if key < &inner.key[..] {
+                    let (value, leaf_cut, new_key) = inner
+                        .left
+                        .get_mut(node_db)?
+                        .recursive_remove(key, version, node_db)?;
+
+                    if value.is_none() {
+                        // The key was not found in the left subtree, so nothing changed
+                        return Ok((None, false, None));
+                    }
+
+                    if leaf_cut {
+                        // The left node was a leaf node and was removed.
+                        // We promote the right node to the root of the subtree, it was
+                        // already balanced and its height and size are correct.
+                        // The promoted node's key is returned as the new leftmost key, as
+                        // Tree does, so that the trees' keys and hashes stay the same
+                        *self = inner.right.take(node_db)?;
+                        return Ok((value, false, Some(self.key().to_vec())));
+                    }
+
+                    // The left subtree has changed so we're essentially creating a new node,
+                    // we need to update the version.
+                    // Bubble up the new leftmost leaf key for the subtree
+                    inner.version = version;
+                    self.update_height_and_size_and_balance(version, node_db)?;
+                    Ok((value, false, new_key))
+                } else {
+                    let (value, leaf_cut, new_key) = inner
+                        .right
+                        .get_mut(node_db)?
+                        .recursive_remove(key, version, node_db)?;
+
+                    if value.is_none() {
+                        // The key was not found in the right subtree, so nothing changed
+                        return Ok((None, false, None));
+                    }
+
+                    if leaf_cut {
+                        // The right node was a leaf node and was removed.
+                        // We promote the left node to the root of the subtree, the leftmost
+                        // leaf key remains the same.
+                        *self = inner.left.take(node_db)?;
+                        return Ok((value, false, None));
+                    }
+
+                    // The right subtree has changed so we're essentially creating a new node,
+                    // we need to update the version.
+                    inner.version = version;
+
+                    // If the right subtree's leftmost key has changed, set this node's key to the new key
+                    if let Some(new_key) = new_key {
+                        inner.key = new_key;
+                    }
+
+                    self.update_height_and_size_and_balance(version, node_db)?;
+                    Ok((value, false, None))
+                }
+            }
+        }
+    }
 }
 
-impl<'a, K: Clone, V: Clone> BTreeMapIterator<'a, K, V> {
-    fn new(root: &'a BTreeMap<K, V>) -> BTreeMapIterator<'a, K, V> {
-        BTreeMapIterator {
-            right_subtrees: vec![BorrowedOrCached::Borrowed(root)],
-            node_store: HashMap::new(),
+/// An IAVL tree which produces identical root hashes to [`super::Tree`].
+///
+/// Nodes are loaded lazily from the DB and are kept in memory once loaded, rather than
+/// being fetched from the NodeDB on every access. Node hashes are cached and are only
+/// recomputed when a node has been modified and its hash is requested, which avoids
+/// rehashing the path to the root on every call to set and remove.
+///
+/// Loaded nodes are kept in memory after a version is saved. If more than `cache_size`
+/// nodes are loaded when a version is saved then only the top levels of the tree are kept.
+#[derive(Debug)]
+pub struct CachedTree<T> {
+    root: Option<CachedNode>,
+    pub(crate) node_db: Arc<NodeDB<T>>,
+    pub(crate) loaded_version: u32,
+    pub(crate) versions: BTreeSet<u32>,
+    max_loaded_nodes: usize,
+}
+
+impl<T> CachedTree<T>
+where
+    T: Database,
+{
+    pub fn new(
+        db: T,
+        target_version: Option<u32>,
+        cache_size: CacheSize,
+    ) -> Result<CachedTree<T>, Error> {
+        let max_loaded_nodes = cache_size.into();
+
+        Self::from_node_db(
+            Arc::new(NodeDB::new(db, cache_size)),
+            target_version,
+            max_loaded_nodes,
+        )
+    }
+
+    fn from_node_db(
+        node_db: Arc<NodeDB<T>>,
+        target_version: Option<u32>,
+        max_loaded_nodes: usize,
+    ) -> Result<CachedTree<T>, Error> {
+        let versions = node_db.get_versions()?;
+
+        if let Some(target_version) = target_version {
+            let root = Self::load_root(&node_db, target_version)?;
+
+            Ok(CachedTree {
+                root,
+                loaded_version: target_version,
+                node_db,
+                versions,
+                max_loaded_nodes,
+            })
+        } else {
+            // use the latest version available
+            if let Some(latest_version) = versions.last() {
+                Ok(CachedTree {
+                    root: Self::load_root(&node_db, *latest_version)?,
+                    loaded_version: *latest_version,
+                    node_db,
+                    versions,
+                    max_loaded_nodes,
+                })
+            } else {
+                Ok(CachedTree {
+                    root: None,
+                    loaded_version: 0,
+                    node_db,
+                    versions,
+                    max_loaded_nodes,
+                })
+            }
         }
     }
 
-    fn traverse(&mut self) -> Option<V> {
-        let mut subtree = self.right_subtrees.pop()?;
+    fn load_root(node_db: &NodeDB<T>, version: u32) -> Result<Option<CachedNode>, Error> {
+        let root_hash = node_db.get_root_hash(version)?;
+
+        if root_hash == EMPTY_HASH {
+            return Ok(None);
+        }
+
+        let root = node_db.fetch_node(&root_hash)?;
+
+        Ok(Some(CachedNode::from_stored(*root, root_hash)))
+    }
+
+    /// Save the current tree to disk.
+    /// Returns an error if saving would overwrite an existing version
+    pub fn save_version(&mut self) -> Result<([u8; 32], u32), Error> {
+        let version = self.loaded_version + 1;
+
+        if self.versions.contains(&version) {
+            // If the version already exists, return an error as we're attempting to overwrite.
+            // However, the same hash means idempotent (i.e. no-op).
+            let saved_hash = self.node_db.get_root_hash(version)?;
+            let working_hash = self.root_hash();
+
+            if saved_hash == working_hash {
+                self.loaded_version = version;
+                self.evict();
+
+                return Ok((saved_hash, self.loaded_version));
+            }
+            return Err(Error::Overwrite);
+        }
+
+        let root_hash = match &mut self.root {
+            Some(root) => {
+                Self::save_tree(&self.node_db, root, version)?;
+                root.hash()
+            }
+            None => EMPTY_HASH,
+        };
+
+        self.node_db.save_version(version, &root_hash)?;
+        self.versions.insert(version);
+
+        self.loaded_version = version;
+        self.evict();
+
+        Ok((root_hash, self.loaded_version))
+    }
+
+    /// If more than `max_loaded_nodes` nodes are loaded then drops all but the top levels
+    /// of the tree from memory. All nodes must have been saved.
+    fn evict(&mut self) {
+        let Some(root) = &mut self.root else {
+            return;
+        };
+
+        if root.loaded_count(self.max_loaded_nodes) > self.max_loaded_nodes {
+            // the top d + 1 levels of a tree contain at most 2^(d + 1) - 1 nodes
+            let depth = (self.max_loaded_nodes + 1).ilog2().saturating_sub(1);
+            root.unload_below(depth);
+        }
+    }
+
+    /// Saves the given node and all of its descendants which were created in the given version
+    fn save_tree(node_db: &NodeDB<T>, node: &CachedNode, version: u32) -> Result<(), Error> {
+        if node.version() < version {
+            // the node was created in an earlier version so it, and all of its descendants,
+            // are already in the DB
+            return Ok(());
+        }
+
+        if let NodeKind::Inner(inner) = &node.kind {
+            if let Some(left_node) = inner.left.node.get() {
+                Self::save_tree(node_db, left_node, version)?;
+            }
+            if let Some(right_node) = inner.right.node.get() {
+                Self::save_tree(node_db, right_node, version)?;
+            }
+        }
+
+        node_db.save_node(&node.to_shallow_node(), &node.hash())
+    }
+
+    pub fn root_hash(&self) -> [u8; 32] {
+        match &self.root {
+            Some(root) => root.hash(),
+            None => EMPTY_HASH,
+        }
+    }
+
+    pub fn loaded_version(&self) -> u32 {
+        self.loaded_version
+    }
+
+    pub fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>, Error> {
+        let Some(mut node) = self.root.as_ref() else {
+            return Ok(None);
+        };
 
         loop {
-            match &subtree {
-                BorrowedOrCached::Cached(cached_subtree) => match cached_subtree {
-                    CachedBTreeMap::Leaf(leaf) => return Some(leaf.value.clone()),
-                    CachedBTreeMap::Inner(inner) => {
-                        let right_subtree = self
-                            .node_store
-                            .get(&inner.right_hash)
-                            .expect("db will have all nodes")
-                            .clone(); // clone to simulate non borrowing cache
-
-                        self.right_subtrees
-                            .push(BorrowedOrCached::Cached(right_subtree));
-
-                        let left_subtree = self
-                            .node_store
-                            .get(&inner.left_hash)
-                            .expect("db will have all nodes")
-                            .clone(); // clone to simulate non borrowing cache
-
-                        subtree = BorrowedOrCached::Cached(left_subtree)
+            match &node.kind {
+                NodeKind::Leaf(leaf) => {
+                    if leaf.key == key {
+                        return Ok(Some(leaf.value.clone()));
+                    } else {
+                        return Ok(None);
                     }
-                },
-
-                BorrowedOrCached::Borrowed(borrowed_subtree) => match borrowed_subtree {
-                    BTreeMap::Leaf(leaf) => return Some(leaf.value.clone()),
-                    BTreeMap::Inner(inner) => {
-                        match &inner.right_subtree {
-                            Some(right_subtree) => {
-                                self.right_subtrees
-                                    .push(BorrowedOrCached::Borrowed(right_subtree));
-                            }
-                            None => {
-                                let right_subtree = self
-                                    .node_store
-                                    .get(&inner.right_hash)
-                                    .expect("db will have all nodes")
-                                    .clone(); // clone to simulate non borrowing cache
-
-                                self.right_subtrees
-                                    .push(BorrowedOrCached::Cached(right_subtree));
-                            }
-                        }
-
-                        match &inner.left_subtree {
-                            Some(left_subtree) => {
-                                subtree = BorrowedOrCached::Borrowed(left_subtree)
-                            }
-                            None => {
-                                let left_subtree = self
-                                    .node_store
-                                    .get(&inner.left_hash)
-                                    .expect("db will have all nodes")
-                                    .clone(); // clone to simulate non borrowing cache
-
-                                subtree = BorrowedOrCached::Cached(left_subtree)
-                            }
-                        }
+                }
+                NodeKind::Inner(inner) => {
+                    if key < &inner.key[..] {
+                        node = inner.left.get(&self.node_db)?;
+                    } else {
+                        node = inner.right.get(&self.node_db)?;
                     }
-                },
+                }
             }
         }
     }
+
+    pub fn remove(&mut self, key: &(impl AsRef<[u8]> + ?Sized)) -> Result<Option<Vec<u8>>, Error> {
+        let Some(root) = self.root.as_mut() else {
+            return Ok(None);
+        };
+
+        // as with Tree, a root leaf is kept when its key is removed
+        let (value, _, _) =
+            root.recursive_remove(key.as_ref(), self.loaded_version + 1, &self.node_db)?;
+
+        Ok(value)
+    }
+
+    pub fn set(&mut self, key: Vec<u8>, value: Vec<u8>) -> Result<(), Error> {
+        let version = self.loaded_version + 1;
+
+        match &mut self.root {
+            Some(root) => root.recursive_set(key, value, version, &self.node_db),
+            None => {
+                self.root = Some(CachedNode::new_leaf(key, value, version));
+
+                Ok(())
+            }
+        }
+    }
+
+    /// Returns the number of keys in the tree
+    pub fn size(&self) -> u32 {
+        self.root.as_ref().map(|root| root.size()).unwrap_or(0)
+    }
+
+    /// Returns the key and value at the given index, where keys are indexed in order
+    pub fn get_by_index(&self, mut index: u32) -> Result<Option<KeyValue>, Error> {
+        let Some(mut node) = self.root.as_ref() else {
+            return Ok(None);
+        };
+
+        loop {
+            match &node.kind {
+                NodeKind::Leaf(leaf) => {
+                    if index == 0 {
+                        return Ok(Some((leaf.key.clone(), leaf.value.clone())));
+                    } else {
+                        return Ok(None);
+                    }
+                }
+                NodeKind::Inner(inner) => {
+                    let left_node = inner.left.get(&self.node_db)?;
+
+                    if index < left_node.size() {
+                        node = left_node;
+                    } else {
+                        index -= left_node.size();
+                        node = inner.right.get(&self.node_db)?;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns `Ok(index)` if the key is in the tree, otherwise returns `Err(index)`
+    /// where index is the position at which the key would be inserted
+    pub fn get_index_of_key(&self, key: &[u8]) -> Result<Result<u32, u32>, Error> {
+        let Some(mut node) = self.root.as_ref() else {
+            return Ok(Err(0));
+        };
+        let mut index = 0;
+
+        loop {
+            match &node.kind {
+                NodeKind::Leaf(leaf) => {
+                    return Ok(match key.cmp(&leaf.key) {
+                        Ordering::Less => Err(index),
+                        Ordering::Equal => Ok(index),
+                        Ordering::Greater => Err(index + 1),
+                    });
+                }
+                NodeKind::Inner(inner) => {
+                    if key < &inner.key[..] {
+                        node = inner.left.get(&self.node_db)?;
+                    } else {
+                        index += inner.left.get(&self.node_db)?.size();
+                        node = inner.right.get(&self.node_db)?;
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn range<R>(&self, range: R) -> CachedRange<'_, R, T>
+    where
+        R: RangeBounds<Vec<u8>>,
+    {
+        CachedRange::new(range, self.root.as_ref(), &self.node_db, false)
+    }
+
+    /// Same as [`CachedTree::range`] but iterates in descending key order
+    pub fn reverse_range<R>(&self, range: R) -> CachedRange<'_, R, T>
+    where
+        R: RangeBounds<Vec<u8>>,
+    {
+        CachedRange::new(range, self.root.as_ref(), &self.node_db, true)
+    }
+}
+
+/// Iterates over the nodes of a [`CachedTree`] by reference. Nodes which aren't in
+/// memory are loaded into the tree as they are visited.
+pub struct CachedRange<'a, R: RangeBounds<Vec<u8>>, T>
+where
+    T: Database,
+{
+    range: R,
+    delayed_nodes: Vec<&'a CachedNode>,
+    node_db: &'a NodeDB<T>,
+    /// Iterate in descending key order
+    reverse: bool,
+}
+
+impl<'a, R: RangeBounds<Vec<u8>>, T: Database> CachedRange<'a, R, T> {
+    fn new(range: R, root: Option<&'a CachedNode>, node_db: &'a NodeDB<T>, reverse: bool) -> Self {
+        CachedRange {
+            range,
+            delayed_nodes: root.into_iter().collect(),
+            node_db,
+            reverse,
+        }
+    }
+
+    /// Ends the iteration after a node failed to load, so that the error is the last item
+    fn stop(&mut self, err: Error) -> Error {
+        self.delayed_nodes.clear();
+        err
+    }
 }
 
-impl<'a, K: Clone, V: Clone> Iterator for BTreeMapIterator<'a, K, V> {
-    type Item = V;
+impl<'a, R: RangeBounds<Vec<u8>>, T: Database> Iterator for CachedRange<'a, R, T> {
+    type Item = Result<KeyValue, Error>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.traverse()
+        while let Some(node) = self.delayed_nodes.pop() {
+            match &node.kind {
+                NodeKind::Inner(inner) => {
+                    let after_start = match self.range.start_bound() {
+                        Bound::Included(l) | Bound::Excluded(l) => inner.key > *l,
+                        Bound::Unbounded => true,
+                    };
+
+                    let before_end = match self.range.end_bound() {
+                        Bound::Included(u) => inner.key <= *u,
+                        Bound::Excluded(u) => inner.key < *u,
+                        Bound::Unbounded => true,
+                    };
+
+                    let right_node = match before_end
+                        .then(|| inner.right.get(self.node_db))
+                        .transpose()
+                    {
+                        Ok(right_node) => right_node,
+                        Err(e) => return Some(Err(self.stop(e))),
+                    };
+
+                    let left_node = match after_start
+                        .then(|| inner.left.get(self.node_db))
+                        .transpose()
+                    {
+                        Ok(left_node) => left_node,
+                        Err(e) => return Some(Err(self.stop(e))),
+                    };
+
+                    // Traverse through the left subtree, then the right subtree (or the
+                    // reverse when iterating in descending order). The last node pushed is visited first.
+                    if self.reverse {
+                        self.delayed_nodes.extend(left_node);
+                        self.delayed_nodes.extend(right_node);
+                    } else {
+                        self.delayed_nodes.extend(right_node);
+                        self.delayed_nodes.extend(left_node);
+                    }
+                }
+                NodeKind::Leaf(leaf) => {
+                    if self.range.contains(&leaf.key) {
+                        // we have a leaf node within the range
+                        return Some(Ok((leaf.key.clone(), leaf.value.clone())));
+                    }
+                }
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    use crate::iavl::{QueryTree, Tree};
+    use database::{MemDB, PrefixDB};
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    #[test]
+    fn get_works() {
+        let db = MemDB::new();
+        let mut tree = CachedTree::new(db, None, 100.try_into().unwrap()).unwrap();
+        tree.set(b"alice".to_vec(), b"abc".to_vec()).unwrap();
+        tree.set(b"bob".to_vec(), b"123".to_vec()).unwrap();
+        tree.set(b"c".to_vec(), b"1".to_vec()).unwrap();
+        tree.set(b"q".to_vec(), b"1".to_vec()).unwrap();
+
+        assert_eq!(
+            tree.get(b"alice").unwrap(),
+            Some(String::from("abc").into())
+        );
+        assert_eq!(tree.get(b"bob").unwrap(), Some(String::from("123").into()));
+        assert_eq!(tree.get(b"c").unwrap(), Some(String::from("1").into()));
+        assert_eq!(tree.get(b"q").unwrap(), Some(String::from("1").into()));
+        assert_eq!(tree.get(b"house").unwrap(), None);
+    }
+
+    #[test]
+    fn repeated_set_works() {
+        let db = MemDB::new();
+        let mut tree = CachedTree::new(db, None, 100.try_into().unwrap()).unwrap();
+        tree.set(b"alice".to_vec(), b"abc".to_vec()).unwrap();
+        tree.set(b"bob".to_vec(), b"123".to_vec()).unwrap();
+        tree.set(b"c".to_vec(), b"1".to_vec()).unwrap();
+        tree.set(b"q".to_vec(), b"1".to_vec()).unwrap();
+
+        let expected = [
+            202, 52, 159, 10, 210, 166, 72, 207, 248, 190, 60, 114, 172, 147, 84, 27, 120, 202,
+            189, 127, 230, 108, 58, 127, 251, 149, 9, 33, 87, 249, 158, 138,
+        ];
+
+        assert_eq!(expected, tree.root_hash());
+    }
+
+    #[test]
+    fn save_version_works() {
+        let db = MemDB::new();
+        let mut tree = CachedTree::new(db, None, 100.try_into().unwrap()).unwrap();
+        tree.set(b"alice".to_vec(), b"abc".to_vec()).unwrap();
+        tree.set(b"bob".to_vec(), b"123".to_vec()).unwrap();
+        tree.set(b"c".to_vec(), b"1".to_vec()).unwrap();
+        tree.set(b"q".to_vec(), b"1".to_vec()).unwrap();
+
+        tree.save_version().unwrap();
+        tree.save_version().unwrap();
+        tree.set(b"qwerty".to_vec(), b"312".to_vec()).unwrap();
+        tree.set(b"-32".to_vec(), b"gamma".to_vec()).unwrap();
+        tree.save_version().unwrap();
+        tree.set(b"alice".to_vec(), b"123".to_vec()).unwrap();
+        tree.save_version().unwrap();
+
+        let expected = [
+            37, 155, 233, 229, 243, 173, 29, 241, 235, 234, 85, 10, 36, 129, 53, 79, 77, 11, 29,
+            118, 201, 233, 133, 60, 78, 187, 37, 81, 42, 96, 105, 150,
+        ];
+
+        assert_eq!(expected, tree.root_hash());
+    }
+
+    #[test]
+    fn remove_leaf_after_save_works() {
+        let db = MemDB::new();
+        let mut tree = CachedTree::new(db, None, 100.try_into().unwrap()).unwrap();
+        tree.set(vec![1], vec![4]).unwrap();
+        tree.set(vec![2], vec![5]).unwrap();
+        tree.set(vec![3], vec![6]).unwrap();
+
+        tree.save_version().unwrap();
+
+        let val = tree.remove(&[2]).unwrap();
+
+        assert_eq!(val, Some(vec![5]));
+
+        let hash = tree.root_hash();
+        let expected = [
+            157, 211, 3, 179, 46, 81, 187, 161, 109, 233, 192, 198, 57, 27, 36, 234, 79, 230, 161,
+            49, 123, 3, 121, 162, 182, 58, 126, 93, 17, 215, 95, 248,
+        ];
+        assert_eq!(hash, expected);
+    }
+
+    #[test]
+    fn remove_only_key_matches_tree() {
+        let mut tree = Tree::new(MemDB::new(), None, 100.try_into().unwrap()).unwrap();
+        let mut cached_tree = CachedTree::new(MemDB::new(), None, 100.try_into().unwrap()).unwrap();
+        tree.set(vec![1], vec![4]).unwrap();
+        cached_tree.set(vec![1], vec![4]).unwrap();
+
+        assert_eq!(tree.remove(&[1]).unwrap(), Some(vec![4]));
+        assert_eq!(cached_tree.remove(&[1]).unwrap(), Some(vec![4]));
+        assert_eq!(tree.get(&[1]).unwrap(), cached_tree.get(&[1]).unwrap());
+        assert_eq!(tree.root_hash(), cached_tree.root_hash());
+    }
+
+    #[test]
+    fn load_saved_version_works() {
+        let db = PrefixDB::new(Arc::new(MemDB::new()), vec![]);
+        let mut tree = Tree::new(db, None, 100.try_into().unwrap()).unwrap();
+        tree.set(b"alice".to_vec(), b"abc".to_vec()).unwrap();
+        tree.set(b"bob".to_vec(), b"123".to_vec()).unwrap();
+        tree.save_version().unwrap();
+        tree.set(b"c".to_vec(), b"1".to_vec()).unwrap();
+        let (expected_hash, _) = tree.save_version().unwrap();
+
+        // reopen the DB written by a Tree
+        let tree = CachedTree::from_node_db(tree.node_db, None, 100).unwrap();
+
+        assert_eq!(expected_hash, tree.root_hash());
+        assert_eq!(tree.loaded_version(), 2);
+        assert_eq!(tree.get(b"bob").unwrap(), Some(b"123".to_vec()));
+
+        let query_tree = QueryTree::from_cached(&tree, 1).unwrap();
+        assert_eq!(query_tree.get(b"c").unwrap(), None);
+        assert_eq!(query_tree.get(b"alice").unwrap(), Some(b"abc".to_vec()));
+    }
+
+    #[test]
+    fn range_works() {
+        let db = MemDB::new();
+        let mut tree = CachedTree::new(db, None, 100.try_into().unwrap()).unwrap();
+        tree.set(b"1".to_vec(), b"abc1".to_vec()).unwrap();
+        tree.set(b"2".to_vec(), b"abc2".to_vec()).unwrap();
+        tree.set(b"3".to_vec(), b"abc3".to_vec()).unwrap();
+        tree.save_version().unwrap();
+        tree.set(b"4".to_vec(), b"abc4".to_vec()).unwrap();
+        tree.set(b"5".to_vec(), b"abc5".to_vec()).unwrap();
+
+        let got_pairs: Vec<(Vec<u8>, Vec<u8>)> = tree
+            .range(b"2".to_vec()..b"5".to_vec())
+            .map(Result::unwrap)
+            .collect();
+        let expected_pairs = vec![
+            (b"2".to_vec(), b"abc2".to_vec()),
+            (b"3".to_vec(), b"abc3".to_vec()),
+            (b"4".to_vec(), b"abc4".to_vec()),
+        ];
+
+        assert_eq!(expected_pairs, got_pairs);
+    }
+
+    #[test]
+    fn save_version_keeps_loaded_nodes() {
+        let db = MemDB::new();
+        let mut tree = CachedTree::new(db, None, 100.try_into().unwrap()).unwrap();
+        for key in 0..16 {
+            tree.set(vec![key], vec![key]).unwrap();
+        }
+
+        tree.save_version().unwrap();
+
+        assert_eq!(root_loaded_count(&tree), 31);
+    }
+
+    #[test]
+    fn save_version_evicts_nodes_over_cache_size() {
+        let db = MemDB::new();
+        let mut tree = CachedTree::new(db, None, 10.try_into().unwrap()).unwrap();
+        for key in 0..16 {
+            tree.set(vec![key], vec![key]).unwrap();
+        }
+
+        tree.save_version().unwrap();
+
+        // only the top three levels are kept
+        assert_eq!(root_loaded_count(&tree), 7);
+
+        // evicted nodes are reloaded when they are next needed
+        for key in 0..16 {
+            assert_eq!(tree.get(&[key]).unwrap(), Some(vec![key]));
+        }
+        assert_eq!(root_loaded_count(&tree), 31);
+
+        let pairs: Vec<(Vec<u8>, Vec<u8>)> = tree.range(..).map(Result::unwrap).collect();
+        assert_eq!(pairs.len(), 16);
+    }
+
+    fn root_loaded_count<T: Database>(tree: &CachedTree<T>) -> usize {
+        tree.root
+            .as_ref()
+            .map_or(0, |root| root.loaded_count(usize::MAX))
+    }
+
+    /// Applies the same random operations to a Tree and a CachedTree and
+    /// checks that the root hashes always match
+    #[test]
+    fn root_hashes_match_tree() {
+        let mut rng = StdRng::seed_from_u64(42);
+
+        let mut tree = Tree::new(MemDB::new(), None, 100.try_into().unwrap()).unwrap();
+        let mut cached_tree = CachedTree::new(MemDB::new(), None, 100.try_into().unwrap()).unwrap();
+
+        for _ in 0..100 {
+            // Tree drops the unsaved descendants of a node promoted by a remove, so a key is
+            // only removed from a saved tree
+            if rng.gen_bool(0.7) {
+                let key = vec![rng.gen_range(0..64), rng.gen_range(0..4)];
+                assert_eq!(
+                    tree.remove(&key).unwrap(),
+                    cached_tree.remove(&key).unwrap()
+                );
+                assert_eq!(tree.get(&key).unwrap(), cached_tree.get(&key).unwrap());
+                assert_eq!(tree.root_hash(), cached_tree.root_hash());
+            }
+
+            for _ in 0..rng.gen_range(1..50) {
+                let key = vec![rng.gen_range(0..64), rng.gen_range(0..4)];
+                let value = vec![rng.gen()];
+                tree.set(key.clone(), value.clone()).unwrap();
+                cached_tree.set(key.clone(), value).unwrap();
+
+                assert_eq!(tree.get(&key).unwrap(), cached_tree.get(&key).unwrap());
+                assert_eq!(tree.root_hash(), cached_tree.root_hash());
+            }
+
+            assert_eq!(tree.save_version(), cached_tree.save_version());
+        }
+
+        let pairs: Vec<(Vec<u8>, Vec<u8>)> = tree.range(..).map(Result::unwrap).collect();
+        let cached_pairs: Vec<(Vec<u8>, Vec<u8>)> =
+            cached_tree.range(..).map(Result::unwrap).collect();
+        assert_eq!(pairs, cached_pairs);
+
+        let reverse_pairs: Vec<(Vec<u8>, Vec<u8>)> =
+            cached_tree.reverse_range(..).map(Result::unwrap).collect();
+        assert!(reverse_pairs.into_iter().eq(pairs.iter().cloned().rev()));
+
+        assert_eq!(tree.size(), cached_tree.size());
+        for (index, (key, value)) in pairs.into_iter().enumerate() {
+            let index = index as u32;
+            assert_eq!(
+                cached_tree.get_by_index(index).unwrap(),
+                Some((key.clone(), value.clone()))
+            );
+            assert_eq!(
+                cached_tree.get_index_of_key(&key).unwrap(),
+                tree.get_index_of_key(&key).unwrap()
+            );
+        }
     }
 }
@@ -0,0 +1,453 @@
+use std::fmt::{self, Display};
+
+use database::Database;
+
+use crate::{
+    error::Error,
+    merkle::{Sha256Hash, EMPTY_HASH},
+};
+
+use super::{node_db::NodeDB, CacheSize, CachedTree, Node, Tree};
+
+/// A problem found while verifying a saved version of a tree
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Problem {
+    /// The root hash stored for the version isn't a hash
+    CorruptRootHash,
+    /// A node referenced by its parent isn't in the DB
+    MissingNode { hash: Sha256Hash },
+    /// A node's bytes can't be deserialized
+    CorruptNode { hash: Sha256Hash },
+    /// A node doesn't hash to the hash it's stored under
+    HashMismatch {
+        hash: Sha256Hash,
+        computed: Sha256Hash,
+    },
+    /// A node was created after the version being verified
+    FutureVersion { hash: Sha256Hash, node_version: u32 },
+    /// An inner node's height isn't one more than the height of its tallest child
+    WrongHeight {
+        hash: Sha256Hash,
+        stored: u8,
+        computed: u8,
+    },
+    /// An inner node's size isn't the number of leaves below it
+    WrongSize {
+        hash: Sha256Hash,
+        stored: u32,
+        computed: u32,
+    },
+    /// The heights of an inner node's children differ by more than one
+    Unbalanced {
+        hash: Sha256Hash,
+        balance_factor: i16,
+    },
+    /// An inner node's key doesn't separate the keys in its left and right subtrees
+    KeyOrder { hash: Sha256Hash },
+}
+
+impl Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Problem::CorruptRootHash => write!(f, "root hash is corrupt"),
+            Problem::MissingNode { hash } => write!(f, "node {} is missing", Hex(hash)),
+            Problem::CorruptNode { hash } => write!(f, "node {} can't be deserialized", Hex(hash)),
+            Problem::HashMismatch { hash, computed } => {
+                write!(f, "node {} hashes to {}", Hex(hash), Hex(computed))
+            }
+            Problem::FutureVersion { hash, node_version } => write!(
+                f,
+                "node {} was created in a later version {}",
+                Hex(hash),
+                node_version
+            ),
+            Problem::WrongHeight {
+                hash,
+                stored,
+                computed,
+            } => write!(
+                f,
+                "node {} has height {} but its children imply {}",
+                Hex(hash),
+                stored,
+                computed
+            ),
+            Problem::WrongSize {
+                hash,
+                stored,
+                computed,
+            } => write!(
+                f,
+                "node {} has size {} but its children imply {}",
+                Hex(hash),
+                stored,
+                computed
+            ),
+            Problem::Unbalanced {
+                hash,
+                balance_factor,
+            } => write!(
+                f,
+                "node {} has balance factor {}",
+                Hex(hash),
+                balance_factor
+            ),
+            Problem::KeyOrder { hash } => {
+                write!(f, "node {} has keys out of order", Hex(hash))
+            }
+        }
+    }
+}
+
+struct Hex<'a>(&'a [u8]);
+
+impl Display for Hex<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.iter().try_for_each(|byte| write!(f, "{byte:02x}"))
+    }
+}
+
+/// The result of verifying a saved version of a tree
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerifyReport {
+    pub version: u32,
+    /// The number of nodes which were read
+    pub nodes: u64,
+    pub problems: Vec<Problem>,
+}
+
+impl VerifyReport {
+    pub fn is_ok(&self) -> bool {
+        self.problems.is_empty()
+    }
+}
+
+impl<T: Database> Tree<T> {
+    /// Walks every node of a saved version, checking hashes, heights, sizes, balance and key
+    /// order. Nodes are read from the DB rather than the cache.
+    pub fn verify(&self, version: u32) -> Result<VerifyReport, Error> {
+        if !self.versions.contains(&version) {
+            return Err(Error::VersionNotFound);
+        }
+
+        Verifier::new(&self.node_db, version).run()
+    }
+}
+
+impl<T: Database> CachedTree<T> {
+    /// Walks every node of a saved version, checking hashes, heights, sizes, balance and key
+    /// order. Nodes are read from the DB rather than the cache.
+    pub fn verify(&self, version: u32) -> Result<VerifyReport, Error> {
+        if !self.versions.contains(&version) {
+            return Err(Error::VersionNotFound);
+        }
+
+        Verifier::new(&self.node_db, version).run()
+    }
+}
+
+/// Verifies a saved version of the tree stored in `db`, or the latest version if `version` is
+/// `None`. Unlike [`Tree::verify`] the tree isn't loaded first so a corrupt root node is reported
+/// rather than causing a panic.
+pub fn verify_db<T: Database>(db: T, version: Option<u32>) -> Result<VerifyReport, Error> {
+    let cache_size = CacheSize::try_from(1).expect("1 is > 0");
+    let node_db = NodeDB::new(db, cache_size);
+    let versions = node_db.get_versions()?;
+
+    let version = match version {
+        Some(version) if versions.contains(&version) => version,
+        Some(_) => return Err(Error::VersionNotFound),
+        None => *versions.last().ok_or(Error::VersionNotFound)?,
+    };
+
+    Verifier::new(&node_db, version).run()
+}
+
+/// What a verified subtree looks like to its parent
+struct Subtree {
+    height: u8,
+    size: u32,
+    min_key: Vec<u8>,
+    max_key: Vec<u8>,
+}
+
+struct Verifier<'a, T> {
+    node_db: &'a NodeDB<T>,
+    version: u32,
+    nodes: u64,
+    problems: Vec<Problem>,
+}
+
+impl<'a, T: Database> Verifier<'a, T> {
+    fn new(node_db: &'a NodeDB<T>, version: u32) -> Self {
+        Self {
+            node_db,
+            version,
+            nodes: 0,
+            problems: vec![],
+        }
+    }
+
+    /// Returns an error if the DB can't be read
+    fn run(mut self) -> Result<VerifyReport, Error> {
+        match self.node_db.get_raw_root_hash(self.version)? {
+            Some(bytes) => match Sha256Hash::try_from(bytes) {
+                Ok(root_hash) if root_hash != EMPTY_HASH => {
+                    self.verify_node(&root_hash)?;
+                }
+                Ok(_) => (),
+                Err(_) => self.problems.push(Problem::CorruptRootHash),
+            },
+            // the version was listed so its root should be present
+            None => self.problems.push(Problem::CorruptRootHash),
+        }
+
+        Ok(VerifyReport {
+            version: self.version,
+            nodes: self.nodes,
+            problems: self.problems,
+        })
+    }
+
+    /// Verifies the subtree rooted at the given node. Returns `None` if the subtree couldn't be
+    /// fully read, in which case its parent can't be checked. Returns an error if the DB can't
+    /// be read.
+    fn verify_node(&mut self, hash: &Sha256Hash) -> Result<Option<Subtree>, Error> {
+        let Some(bytes) = self.node_db.get_raw_node(hash)? else {
+            self.problems.push(Problem::MissingNode { hash: *hash });
+            return Ok(None);
+        };
+        self.nodes += 1;
+
+        let Ok(node) = Node::deserialize(bytes) else {
+            self.problems.push(Problem::CorruptNode { hash: *hash });
+            return Ok(None);
+        };
+
+        let computed = node.hash();
+        if computed != *hash {
+            // the node's children can't be trusted so they aren't visited
+            self.problems.push(Problem::HashMismatch {
+                hash: *hash,
+                computed,
+            });
+            return Ok(None);
+        }
+
+        let node_version = match &node {
+            Node::Leaf(leaf) => leaf.version,
+            Node::Inner(inner) => inner.version,
+        };
+        if node_version > self.version {
+            self.problems.push(Problem::FutureVersion {
+                hash: *hash,
+                node_version,
+            });
+        }
+
+        let inner = match node {
+            Node::Leaf(leaf) => {
+                return Ok(Some(Subtree {
+                    height: 0,
+                    size: 1,
+                    min_key: leaf.key.clone(),
+                    max_key: leaf.key,
+                }))
+            }
+            Node::Inner(inner) => inner,
+        };
+
+        let left = self.verify_node(&inner.left_hash)?;
+        let right = self.verify_node(&inner.right_hash)?;
+        let (Some(left), Some(right)) = (left, right) else {
+            return Ok(None);
+        };
+
+        let computed_height = left.height.max(right.height) + 1;
+        if inner.height != computed_height {
+            self.problems.push(Problem::WrongHeight {
+                hash: *hash,
+                stored: inner.height,
+                computed: computed_height,
+            });
+        }
+
+        let computed_size = left.size.saturating_add(right.size);
+        if inner.size != computed_size {
+            self.problems.push(Problem::WrongSize {
+                hash: *hash,
+                stored: inner.size,
+                computed: computed_size,
+            });
+        }
+
+        let balance_factor = i16::from(left.height) - i16::from(right.height);
+        if !(-1..=1).contains(&balance_factor) {
+            self.problems.push(Problem::Unbalanced {
+                hash: *hash,
+                balance_factor,
+            });
+        }
+
+        if !(left.max_key < inner.key && inner.key <= right.min_key) {
+            self.problems.push(Problem::KeyOrder { hash: *hash });
+        }
+
+        Ok(Some(Subtree {
+            height: computed_height,
+            size: computed_size,
+            min_key: left.min_key,
+            max_key: right.max_key,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use database::{Column, MemDB, PrefixDB};
+
+    use super::*;
+    use crate::iavl::tree::{InnerNode, LeafNode};
+
+    fn saved_tree() -> (Arc<MemDB>, Tree<PrefixDB<MemDB>>) {
+        let db = Arc::new(MemDB::new());
+        let mut tree = Tree::new(
+            PrefixDB::new(db.clone(), vec![]),
+            None,
+            100.try_into().unwrap(),
+        )
+        .unwrap();
+        for i in 0..50u8 {
+            tree.set(vec![i], vec![i]).unwrap();
+        }
+        tree.save_version().unwrap();
+
+        (db, tree)
+    }
+
+    #[test]
+    fn verify_works() {
+        let (db, tree) = saved_tree();
+
+        let report = tree.verify(1).unwrap();
+        assert!(report.is_ok());
+        assert_eq!(report.nodes, 99);
+
+        let report = verify_db(PrefixDB::new(db, vec![]), None).unwrap();
+        assert!(report.is_ok());
+        assert_eq!(report.version, 1);
+
+        assert_eq!(tree.verify(2), Err(Error::VersionNotFound));
+    }
+
+    #[test]
+    fn verify_reports_corrupt_nodes() {
+        let (db, tree) = saved_tree();
+        let Node::Inner(root) = *tree.node_db.get_root_node(1).unwrap().unwrap() else {
+            panic!("root should be an inner node")
+        };
+
+        // overwrite the left child with garbage and the right child with another node
+        let nodes = db.column(Column::Nodes);
+        nodes
+            .put(
+                NodeDB::<MemDB>::get_node_key(&root.left_hash),
+                vec![1, 2, 3],
+            )
+            .unwrap();
+        let leaf = Node::Leaf(LeafNode {
+            key: vec![100],
+            value: vec![100],
+            version: 1,
+        });
+        nodes
+            .put(
+                NodeDB::<MemDB>::get_node_key(&root.right_hash),
+                leaf.serialize(),
+            )
+            .unwrap();
+
+        let report = tree.verify(1).unwrap();
+        assert_eq!(
+            report.problems,
+            vec![
+                Problem::CorruptNode {
+                    hash: root.left_hash
+                },
+                Problem::HashMismatch {
+                    hash: root.right_hash,
+                    computed: leaf.hash()
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn verify_reports_invalid_structure() {
+        let db = Arc::new(MemDB::new());
+        let save = |node: &Node| {
+            let hash = node.hash();
+            db.column(Column::Nodes)
+                .put(NodeDB::<MemDB>::get_node_key(&hash), node.serialize())
+                .unwrap();
+            hash
+        };
+        let leaf = |key: u8| {
+            Node::Leaf(LeafNode {
+                key: vec![key],
+                value: vec![key],
+                version: 1,
+            })
+        };
+
+        let root = Node::Inner(InnerNode {
+            left_node: None,
+            right_node: None,
+            height: 3,
+            size: 5,
+            left_hash: save(&leaf(2)),
+            right_hash: save(&leaf(1)),
+            key: vec![2],
+            version: 2,
+        });
+        let root_hash = save(&root);
+        db.column(Column::Roots)
+            .put(NodeDB::<MemDB>::get_root_key(1), root_hash.to_vec())
+            .unwrap();
+
+        let report = verify_db(PrefixDB::new(db.clone(), vec![]), Some(1)).unwrap();
+        assert_eq!(
+            report.problems,
+            vec![
+                Problem::FutureVersion {
+                    hash: root_hash,
+                    node_version: 2
+                },
+                Problem::WrongHeight {
+                    hash: root_hash,
+                    stored: 3,
+                    computed: 1
+                },
+                Problem::WrongSize {
+                    hash: root_hash,
+                    stored: 5,
+                    computed: 2
+                },
+                Problem::KeyOrder { hash: root_hash },
+            ]
+        );
+
+        // a root hash which points nowhere
+        db.column(Column::Roots)
+            .put(NodeDB::<MemDB>::get_root_key(2), [7; 32].to_vec())
+            .unwrap();
+        let report = verify_db(PrefixDB::new(db, vec![]), None).unwrap();
+        assert_eq!(report.version, 2);
+        assert_eq!(
+            report.problems,
+            vec![Problem::MissingNode { hash: [7; 32] }]
+        );
+    }
+}
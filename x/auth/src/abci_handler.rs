@@ -2,7 +2,7 @@ use database::Database;
 use gears::types::context::init_context::InitContext;
 use gears::types::context::query_context::QueryContext;
 use gears::{error::AppError, x::params::ParamsSubspaceKey};
-use proto_messages::cosmos::auth::v1beta1::QueryAccountRequest;
+use proto_messages::cosmos::auth::v1beta1::{QueryAccountRequest, QueryParamsRequest};
 use proto_messages::cosmos::ibc::protobuf::Protobuf;
 use store::StoreKey;
 
@@ -20,7 +20,7 @@ impl<SK: StoreKey, PSK: ParamsSubspaceKey> ABCIHandler<SK, PSK> {
 
     pub fn query<DB: Database>(
         &self,
-        ctx: &QueryContext<'_, DB, SK>,
+        ctx: &QueryContext<DB, SK>,
         query: tendermint::proto::abci::RequestQuery,
     ) -> std::result::Result<bytes::Bytes, AppError> {
         match query.path.as_str() {
@@ -32,11 +32,115 @@ impl<SK: StoreKey, PSK: ParamsSubspaceKey> ABCIHandler<SK, PSK> {
                     .query_account(ctx, req)
                     .map(|res| res.encode_vec().into())
             }
+            "/cosmos.auth.v1beta1.Query/Params" => {
+                let req = QueryParamsRequest::decode(query.data)
+                    .map_err(|e| AppError::InvalidRequest(e.to_string()))?;
+
+                Ok(self.keeper.query_params(ctx, req)?.encode_vec().into())
+            }
             _ => Err(AppError::InvalidRequest("query path not found".into())),
         }
     }
 
-    pub fn genesis<DB: Database>(&self, ctx: &mut InitContext<'_, DB, SK>, genesis: GenesisState) {
+    pub fn genesis<DB: Database>(
+        &self,
+        ctx: &mut InitContext<'_, DB, SK>,
+        genesis: GenesisState,
+    ) -> Result<(), AppError> {
         self.keeper.init_genesis(ctx, genesis)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use database::MemDB;
+    use proto_messages::cosmos::auth::v1beta1::{Params, QueryParamsResponse};
+    use store::MultiStore;
+    use strum::EnumIter;
+    use tendermint::{informal::chain::Id, proto::abci::RequestQuery};
+
+    use super::*;
+
+    #[derive(EnumIter, Debug, PartialEq, Eq, Hash, Clone)]
+    enum TestStoreKey {
+        Auth,
+        Params,
+    }
+
+    impl StoreKey for TestStoreKey {
+        fn name(&self) -> &'static str {
+            match self {
+                TestStoreKey::Auth => "acc",
+                TestStoreKey::Params => "params",
+            }
+        }
+    }
+
+    #[derive(EnumIter, Debug, PartialEq, Eq, Hash, Clone)]
+    enum TestParamsKey {
+        Auth,
+    }
+
+    impl ParamsSubspaceKey for TestParamsKey {
+        fn name(&self) -> &'static str {
+            match self {
+                TestParamsKey::Auth => "auth/",
+            }
+        }
+    }
+
+    #[test]
+    fn query_params_returns_stored_params() {
+        let mut store = MultiStore::new(MemDB::new()).unwrap();
+        let keeper = Keeper::new(
+            TestStoreKey::Auth,
+            gears::x::params::Keeper::new(TestStoreKey::Params),
+            TestParamsKey::Auth,
+        );
+
+        let params = gears::x::auth::Params {
+            max_memo_characters: 100,
+            tx_sig_limit: 3,
+            tx_size_cost_per_byte: 20,
+            sig_verify_cost_ed25519: 600,
+            sig_verify_cost_secp256k1: 1200,
+        };
+        let mut ctx = InitContext::new(&mut store, 0, Id::try_from("test-chain").unwrap());
+        keeper
+            .init_genesis(
+                &mut ctx,
+                GenesisState {
+                    accounts: vec![],
+                    params,
+                },
+            )
+            .unwrap();
+        store.write_then_clear_tx_caches();
+        store.commit().unwrap();
+
+        let handler = ABCIHandler::new(keeper);
+        let ctx = QueryContext::new(&store, 0).unwrap();
+        let response = handler
+            .query(
+                &ctx,
+                RequestQuery {
+                    data: QueryParamsRequest {}.encode_vec().into(),
+                    path: "/cosmos.auth.v1beta1.Query/Params".into(),
+                    height: 0,
+                    prove: false,
+                },
+            )
+            .unwrap();
+
+        assert_eq!(
+            QueryParamsResponse::decode(response).unwrap().params,
+            Params {
+                max_memo_characters: 100,
+                tx_sig_limit: 3,
+                tx_size_cost_per_byte: 20,
+                sig_verify_cost_ed25519: 600,
+                sig_verify_cost_secp256k1: 1200,
+            }
+        );
+    }
+}
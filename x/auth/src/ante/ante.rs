@@ -7,25 +7,22 @@ use gears::{
     types::context::{context::Context, read_context::ReadContext},
     x::auth::{Module, Params},
 };
-use prost::Message as ProstMessage;
 use proto_messages::cosmos::{
     auth::v1beta1::Account,
     base::v1beta1::SendCoins,
-    ibc::tx::SignDoc,
-    tx::v1beta1::{
-        message::Message,
-        mode_info::{ModeInfo, SignMode},
-        signer_data::SignerData,
-        tx::tx::Tx,
-        tx_data::TxData,
-        tx_metadata::Metadata,
-        tx_raw::TxWithRaw,
-    },
+    tx::v1beta1::{message::Message, tx_metadata::Metadata, tx_raw::TxWithRaw},
 };
 use proto_types::{AccAddress, Denom};
 use store::StoreKey;
 
-use crate::signing::{handler::SignModeHandler, renderer::value_renderer::ValueRenderer};
+use crate::signing::renderer::value_renderer::ValueRenderer;
+
+use super::decorators::{
+    ConsumeGasForTxSizeDecorator, DeductFeeDecorator, IncrementSequenceDecorator,
+    SetPubKeyDecorator, SigGasConsumeDecorator, SigVerificationDecorator, TipDecorator,
+    TxTimeoutHeightDecorator, ValidateBasicDecorator, ValidateMemoDecorator,
+    ValidateSigCountDecorator,
+};
 
 pub trait BankKeeper<SK: StoreKey>: Clone + Send + Sync + 'static {
     fn send_coins_from_account_to_module<DB: Database>(
@@ -36,330 +33,295 @@ pub trait BankKeeper<SK: StoreKey>: Clone + Send + Sync + 'static {
         amount: SendCoins,
     ) -> Result<(), AppError>;
 
+    fn send_coins_between_accounts<DB: Database>(
+        &self,
+        ctx: &mut Context<'_, '_, DB, SK>,
+        from_address: AccAddress,
+        to_address: AccAddress,
+        amount: SendCoins,
+    ) -> Result<(), AppError>;
+
     fn get_denom_metadata<DB: Database, CTX: ReadContext<SK, DB>>(
         &self,
         ctx: &CTX,
         base: &Denom,
-    ) -> Option<Metadata>;
+    ) -> Result<Option<Metadata>, AppError>;
 }
 
 pub trait AuthKeeper<SK: StoreKey>: Clone + Send + Sync + 'static {
-    fn get_auth_params<DB: Database>(&self, ctx: &Context<'_, '_, DB, SK>) -> Params;
+    fn get_auth_params<DB: Database>(
+        &self,
+        ctx: &Context<'_, '_, DB, SK>,
+    ) -> Result<Params, AppError>;
 
-    fn has_account<DB: Database>(&self, ctx: &Context<'_, '_, DB, SK>, addr: &AccAddress) -> bool;
+    fn has_account<DB: Database>(
+        &self,
+        ctx: &Context<'_, '_, DB, SK>,
+        addr: &AccAddress,
+    ) -> Result<bool, AppError>;
 
     fn get_account<DB: Database>(
         &self,
         ctx: &Context<'_, '_, DB, SK>,
         addr: &AccAddress,
-    ) -> Option<Account>;
+    ) -> Result<Option<Account>, AppError>;
 
     fn set_account<DB: Database>(&self, ctx: &mut Context<'_, '_, DB, SK>, acct: Account);
 }
 
-pub trait AnteHandlerTrait<SK: StoreKey>: Clone + Send + Sync + 'static {
-    fn run<DB: Database, M: Message + ValueRenderer>(
+pub trait FeeGrantKeeper<SK: StoreKey>: Clone + Send + Sync + 'static {
+    /// Deducts the fee from the allowance the granter gave the grantee, it errors if there's no
+    /// allowance or it doesn't cover the fee or the messages
+    fn use_granted_fees<DB: Database, M: Message>(
         &self,
         ctx: &mut Context<'_, '_, DB, SK>,
-        tx: &TxWithRaw<M>,
+        granter: &AccAddress,
+        grantee: &AccAddress,
+        fee: Option<&SendCoins>,
+        msgs: &[M],
     ) -> Result<(), AppError>;
 }
 
-#[derive(Debug, Clone)]
-pub struct BaseAnteHandler<BK: BankKeeper<SK>, AK: AuthKeeper<SK>, SK: StoreKey> {
-    bank_keeper: BK,
-    auth_keeper: AK,
-    sk: PhantomData<SK>,
-}
+/// The fee grant keeper of apps without fee grants, it rejects every fee grant
+#[derive(Debug, Clone, Default)]
+pub struct NoFeeGrantKeeper;
 
-impl<SK, BK, AK> AnteHandlerTrait<SK> for BaseAnteHandler<BK, AK, SK>
-where
-    SK: StoreKey,
-    BK: BankKeeper<SK>,
-    AK: AuthKeeper<SK>,
-{
-    fn run<DB: Database, M: Message + ValueRenderer>(
+impl<SK: StoreKey> FeeGrantKeeper<SK> for NoFeeGrantKeeper {
+    fn use_granted_fees<DB: Database, M: Message>(
         &self,
-        ctx: &mut Context<'_, '_, DB, SK>,
-        tx: &TxWithRaw<M>,
+        _ctx: &mut Context<'_, '_, DB, SK>,
+        _granter: &AccAddress,
+        _grantee: &AccAddress,
+        _fee: Option<&SendCoins>,
+        _msgs: &[M],
     ) -> Result<(), AppError> {
-        BaseAnteHandler::run(self, ctx, tx)
+        Err(AppError::InvalidRequest(
+            "fee grants are not enabled".into(),
+        ))
     }
 }
 
-impl<BK: BankKeeper<SK>, AK: AuthKeeper<SK>, SK: StoreKey> BaseAnteHandler<BK, AK, SK> {
-    pub fn new(bank_keeper: BK, auth_keeper: AK) -> BaseAnteHandler<BK, AK, SK> {
-        BaseAnteHandler {
-            bank_keeper,
-            auth_keeper,
-            sk: PhantomData,
-        }
-    }
-    pub fn run<DB: Database, M: Message + ValueRenderer>(
+pub trait AnteHandlerTrait<SK: StoreKey>: Clone + Send + Sync + 'static {
+    fn run<DB: Database, M: Message + ValueRenderer>(
         &self,
         ctx: &mut Context<'_, '_, DB, SK>,
         tx: &TxWithRaw<M>,
-    ) -> Result<(), AppError> {
-        self.validate_basic_ante_handler(&tx.tx)?;
-        self.tx_timeout_height_ante_handler(ctx, &tx.tx)?;
-        self.validate_memo_ante_handler(ctx, &tx.tx)?;
-        self.deduct_fee_ante_handler(ctx, &tx.tx)?;
-        self.set_pub_key_ante_handler(ctx, &tx.tx)?;
-        self.sig_verification_handler(ctx, tx)?;
-        self.increment_sequence_ante_handler(ctx, &tx.tx)?;
-
-        //  ** ante.NewSetUpContextDecorator(),
-        //  - ante.NewRejectExtensionOptionsDecorator(), // Covered in tx parsing code
-        //  - NewMempoolFeeDecorator(opts.BypassMinFeeMsgTypes), // NOT USED FOR DELIVER_TX
-        //  - ante.NewValidateBasicDecorator(),
-        //  - ante.NewTxTimeoutHeightDecorator(),
-        //  - ante.NewValidateMemoDecorator(opts.AccountKeeper),
-        //  ** ante.NewConsumeGasForTxSizeDecorator(opts.AccountKeeper),
-        //  - ante.NewDeductFeeDecorator(opts.AccountKeeper, opts.BankKeeper, opts.FeegrantKeeper),
-        // // SetPubKeyDecorator must be called before all signature verification decorators
-        //  - ante.NewSetPubKeyDecorator(opts.AccountKeeper),
-        //  ** ante.NewValidateSigCountDecorator(opts.AccountKeeper),
-        //  ** ante.NewSigGasConsumeDecorator(opts.AccountKeeper, sigGasConsumer),
-        //  - ante.NewSigVerificationDecorator(opts.AccountKeeper, opts.SignModeHandler),
-        //  - ante.NewIncrementSequenceDecorator(opts.AccountKeeper),
-        //  ** ibcante.NewAnteDecorator(opts.IBCkeeper),
-
-        Ok(())
-    }
-
-    fn validate_basic_ante_handler<M: Message>(&self, tx: &Tx<M>) -> Result<(), AppError> {
-        // Not sure if we need to explicitly check this given the check which follows.
-        // We'll leave it in for now since it's in the SDK.
-        let sigs = tx.get_signatures();
-        if sigs.is_empty() {
-            return Err(AppError::TxValidation("signature list is empty".into()));
-        }
-
-        if sigs.len() != tx.get_signers().len() {
-            return Err(AppError::TxValidation(format!(
-                "wrong number of signatures; expected {}, got {}",
-                tx.get_signers().len(),
-                sigs.len()
-            )));
-        }
-
-        Ok(())
-    }
+    ) -> Result<(), AppError>;
+}
 
-    fn tx_timeout_height_ante_handler<DB: Database, M: Message>(
+/// A single step of an ante handler. Decorators are composed into a chain, either a tuple of
+/// decorators or an [`AnteDecoratorChain`], which runs them in order and stops at the first error.
+pub trait AnteDecorator<SK: StoreKey>: Clone + Send + Sync + 'static {
+    fn ante_handle<DB: Database, M: Message + ValueRenderer>(
         &self,
-        ctx: &Context<'_, '_, DB, SK>,
-        tx: &Tx<M>,
-    ) -> Result<(), AppError> {
-        let timeout_height = tx.get_timeout_height();
+        ctx: &mut Context<'_, '_, DB, SK>,
+        tx: &TxWithRaw<M>,
+    ) -> Result<(), AppError>;
+}
 
-        // timeout_height of zero means no timeout height
-        if timeout_height == 0 {
-            return Ok(());
+macro_rules! impl_ante_decorator_for_tuple {
+    ($($decorator:ident),+) => {
+        impl<SK, $($decorator),+> AnteDecorator<SK> for ($($decorator,)+)
+        where
+            SK: StoreKey,
+            $($decorator: AnteDecorator<SK>),+
+        {
+            #[allow(non_snake_case)]
+            fn ante_handle<DB: Database, M: Message + ValueRenderer>(
+                &self,
+                ctx: &mut Context<'_, '_, DB, SK>,
+                tx: &TxWithRaw<M>,
+            ) -> Result<(), AppError> {
+                let ($($decorator,)+) = self;
+                $($decorator.ante_handle(ctx, tx)?;)+
+
+                Ok(())
+            }
         }
+    };
+}
 
-        let block_height = ctx.get_height();
+impl_ante_decorator_for_tuple!(D1);
+impl_ante_decorator_for_tuple!(D1, D2);
+impl_ante_decorator_for_tuple!(D1, D2, D3);
+impl_ante_decorator_for_tuple!(D1, D2, D3, D4);
+impl_ante_decorator_for_tuple!(D1, D2, D3, D4, D5);
+impl_ante_decorator_for_tuple!(D1, D2, D3, D4, D5, D6);
+impl_ante_decorator_for_tuple!(D1, D2, D3, D4, D5, D6, D7);
+impl_ante_decorator_for_tuple!(D1, D2, D3, D4, D5, D6, D7, D8);
+impl_ante_decorator_for_tuple!(D1, D2, D3, D4, D5, D6, D7, D8, D9);
+impl_ante_decorator_for_tuple!(D1, D2, D3, D4, D5, D6, D7, D8, D9, D10);
+impl_ante_decorator_for_tuple!(D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11);
+impl_ante_decorator_for_tuple!(D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12);
+impl_ante_decorator_for_tuple!(D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13);
+impl_ante_decorator_for_tuple!(D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14);
+impl_ante_decorator_for_tuple!(D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15);
+impl_ante_decorator_for_tuple!(
+    D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15, D16
+);
+
+/// An ante handler which runs a chain of decorators in order.
+///
+/// A chain is built from a decorator (usually a tuple of decorators) and can be extended with
+/// [`AnteDecoratorChain::prepend`] and [`AnteDecoratorChain::append`]. To remove or reorder
+/// decorators of the default chain destructure [`BaseAnteHandler::default_decorators`] and build
+/// a new chain from the parts.
+#[derive(Debug, Clone)]
+pub struct AnteDecoratorChain<SK: StoreKey, D: AnteDecorator<SK>> {
+    decorators: D,
+    sk: PhantomData<SK>,
+}
 
-        if ctx.get_height() > timeout_height {
-            return Err(AppError::Timeout {
-                timeout: timeout_height,
-                current: block_height,
-            });
+impl<SK: StoreKey, D: AnteDecorator<SK>> AnteDecoratorChain<SK, D> {
+    pub fn new(decorators: D) -> Self {
+        AnteDecoratorChain {
+            decorators,
+            sk: PhantomData,
         }
-
-        Ok(())
     }
 
-    fn validate_memo_ante_handler<DB: Database, M: Message>(
-        &self,
-        ctx: &Context<'_, '_, DB, SK>,
-        tx: &Tx<M>,
-    ) -> Result<(), AppError> {
-        let max_memo_chars = self.auth_keeper.get_auth_params(ctx).max_memo_characters;
-        let memo_length: u64 = tx
-            .get_memo()
-            .len()
-            .try_into()
-            .map_err(|_| AppError::Memo(max_memo_chars))?;
-
-        if memo_length > max_memo_chars {
-            return Err(AppError::Memo(max_memo_chars));
-        };
-        Ok(())
+    /// Returns a chain which runs the decorator before the decorators of this chain
+    pub fn prepend<N: AnteDecorator<SK>>(self, decorator: N) -> AnteDecoratorChain<SK, (N, D)> {
+        AnteDecoratorChain::new((decorator, self.decorators))
     }
 
-    fn deduct_fee_ante_handler<DB: Database, M: Message>(
-        &self,
-        ctx: &mut Context<'_, '_, DB, SK>,
-        tx: &Tx<M>,
-    ) -> Result<(), AppError> {
-        let fee = tx.get_fee();
-        let fee_payer = tx.get_fee_payer();
-
-        if !self.auth_keeper.has_account(ctx, fee_payer) {
-            return Err(AppError::AccountNotFound);
-        }
+    /// Returns a chain which runs the decorator after the decorators of this chain
+    pub fn append<N: AnteDecorator<SK>>(self, decorator: N) -> AnteDecoratorChain<SK, (D, N)> {
+        AnteDecoratorChain::new((self.decorators, decorator))
+    }
 
-        if let Some(fee) = fee {
-            self.bank_keeper.send_coins_from_account_to_module(
-                ctx,
-                fee_payer.to_owned(),
-                Module::FeeCollector,
-                fee.to_owned(),
-            )?;
-        }
+    pub fn decorators(&self) -> &D {
+        &self.decorators
+    }
 
-        Ok(())
+    pub fn into_decorators(self) -> D {
+        self.decorators
     }
+}
 
-    fn set_pub_key_ante_handler<DB: Database, M: Message>(
+impl<SK: StoreKey, D: AnteDecorator<SK>> AnteHandlerTrait<SK> for AnteDecoratorChain<SK, D> {
+    fn run<DB: Database, M: Message + ValueRenderer>(
         &self,
         ctx: &mut Context<'_, '_, DB, SK>,
-        tx: &Tx<M>,
+        tx: &TxWithRaw<M>,
     ) -> Result<(), AppError> {
-        let public_keys = tx.get_public_keys();
-        let signers = tx.get_signers();
-
-        // additional check not found in the sdk - this prevents a panic
-        if signers.len() != public_keys.len() {
-            return Err(AppError::TxValidation(format!(
-                "wrong number of signer info; expected {}, got {}",
-                signers.len(),
-                public_keys.len()
-            )));
-        }
-
-        for (i, key) in public_keys.into_iter().enumerate() {
-            if let Some(key) = key {
-                let addr = key.get_address();
-
-                if &addr != signers[i] {
-                    return Err(AppError::InvalidPublicKey);
-                }
-
-                let mut acct = self
-                    .auth_keeper
-                    .get_account(ctx, &addr)
-                    .ok_or(AppError::AccountNotFound)?;
-
-                if acct.get_public_key().is_some() {
-                    continue;
-                }
+        self.decorators.ante_handle(ctx, tx)
+    }
+}
 
-                acct.set_public_key(key.clone());
-                self.auth_keeper.set_account(ctx, acct)
-            }
-        }
+/// The decorators run by [`BaseAnteHandler`], in order
+pub type DefaultAnteDecorators<BK, AK, FK = NoFeeGrantKeeper> = (
+    ValidateBasicDecorator,
+    TxTimeoutHeightDecorator,
+    ValidateMemoDecorator<AK>,
+    ConsumeGasForTxSizeDecorator<AK>,
+    DeductFeeDecorator<BK, AK, FK>,
+    SetPubKeyDecorator<AK>,
+    ValidateSigCountDecorator<AK>,
+    SigGasConsumeDecorator<AK>,
+    SigVerificationDecorator<BK, AK>,
+    TipDecorator<BK>,
+    IncrementSequenceDecorator<AK>,
+);
 
-        Ok(())
-    }
+#[derive(Debug, Clone)]
+pub struct BaseAnteHandler<
+    BK: BankKeeper<SK>,
+    AK: AuthKeeper<SK>,
+    SK: StoreKey,
+    FK: FeeGrantKeeper<SK> = NoFeeGrantKeeper,
+> {
+    chain: AnteDecoratorChain<SK, DefaultAnteDecorators<BK, AK, FK>>,
+}
 
-    fn sig_verification_handler<DB: Database, M: Message + ValueRenderer>(
+impl<SK, BK, AK, FK> AnteHandlerTrait<SK> for BaseAnteHandler<BK, AK, SK, FK>
+where
+    SK: StoreKey,
+    BK: BankKeeper<SK>,
+    AK: AuthKeeper<SK>,
+    FK: FeeGrantKeeper<SK>,
+{
+    fn run<DB: Database, M: Message + ValueRenderer>(
         &self,
         ctx: &mut Context<'_, '_, DB, SK>,
         tx: &TxWithRaw<M>,
     ) -> Result<(), AppError> {
-        let signers = tx.tx.get_signers();
-        let signature_data = tx.tx.get_signatures_data();
-
-        // NOTE: this is also checked in validate_basic_ante_handler
-        if signature_data.len() != signers.len() {
-            return Err(AppError::TxValidation(format!(
-                "wrong number of signatures; expected {}, got {}",
-                signers.len(),
-                signature_data.len()
-            )));
-        }
+        BaseAnteHandler::run(self, ctx, tx)
+    }
+}
 
-        for (i, signature_data) in signature_data.iter().enumerate() {
-            let signer = signers[i];
-
-            // check sequence number
-            let acct = self
-                .auth_keeper
-                .get_account(ctx, signer)
-                .ok_or(AppError::AccountNotFound)?;
-            let account_seq = acct.get_sequence();
-            if account_seq != signature_data.sequence {
-                return Err(AppError::TxValidation(format!(
-                    "incorrect tx sequence; expected {}, got {}",
-                    account_seq, signature_data.sequence
-                )));
-            }
+impl<BK: BankKeeper<SK>, AK: AuthKeeper<SK>, SK: StoreKey> BaseAnteHandler<BK, AK, SK> {
+    /// Returns an ante handler which rejects txs with a fee granter
+    pub fn new(bank_keeper: BK, auth_keeper: AK) -> BaseAnteHandler<BK, AK, SK> {
+        Self::with_fee_grant_keeper(bank_keeper, auth_keeper, NoFeeGrantKeeper)
+    }
+}
 
-            let public_key = acct
-                .get_public_key()
-                .as_ref()
-                .expect("account pub keys are set in set_pub_key_ante_handler"); //TODO: but can't they be set to None?
-
-            let sign_bytes = match &signature_data.mode_info {
-                ModeInfo::Single(mode) => match mode {
-                    SignMode::Direct => SignDoc {
-                        body_bytes: tx.raw.body_bytes.clone(),
-                        auth_info_bytes: tx.raw.auth_info_bytes.clone(),
-                        chain_id: ctx.get_chain_id().to_string(),
-                        account_number: acct.get_account_number(),
-                    }
-                    .encode_to_vec(),
-                    SignMode::Textual => {
-                        let handler = SignModeHandler;
-
-                        let signer_data = SignerData {
-                            address: signer.to_owned(),
-                            chain_id: ctx.get_chain_id().to_owned(),
-                            account_number: acct.get_account_number(),
-                            sequence: account_seq,
-                            pub_key: public_key.to_owned(),
-                        };
-
-                        let tx_data = TxData {
-                            body: tx.tx.body.clone(),
-                            auth_info: tx.tx.auth_info.clone(),
-                        };
-
-                        let f = |denom: &Denom| -> Option<Metadata> {
-                            self.bank_keeper.get_denom_metadata(ctx, denom)
-                        };
-
-                        handler.sign_bytes_get(&f, signer_data, tx_data).unwrap()
-                        //TODO: remove unwrap
-                    }
-                    _ => {
-                        return Err(AppError::TxValidation(
-                            "sign mode not supported".to_string(),
-                        ))
-                    }
-                },
-                ModeInfo::Multi(_) => {
-                    return Err(AppError::TxValidation(
-                        "multi sig not supported".to_string(),
-                    ));
-                }
-            };
-
-            public_key
-                .verify_signature(&sign_bytes, &signature_data.signature)
-                .map_err(|e| AppError::TxValidation(format!("invalid signature: {}", e)))?;
+impl<BK, AK, SK, FK> BaseAnteHandler<BK, AK, SK, FK>
+where
+    BK: BankKeeper<SK>,
+    AK: AuthKeeper<SK>,
+    SK: StoreKey,
+    FK: FeeGrantKeeper<SK>,
+{
+    pub fn with_fee_grant_keeper(
+        bank_keeper: BK,
+        auth_keeper: AK,
+        fee_grant_keeper: FK,
+    ) -> BaseAnteHandler<BK, AK, SK, FK> {
+        BaseAnteHandler {
+            chain: AnteDecoratorChain::new(Self::default_decorators(
+                bank_keeper,
+                auth_keeper,
+                fee_grant_keeper,
+            )),
         }
+    }
 
-        Ok(())
+    /// Returns the decorators of the default chain, which follows the SDK ante handler
+    pub fn default_decorators(
+        bank_keeper: BK,
+        auth_keeper: AK,
+        fee_grant_keeper: FK,
+    ) -> DefaultAnteDecorators<BK, AK, FK> {
+        //  - ante.NewSetUpContextDecorator(), // Covered in baseapp run_tx
+        //  - ante.NewRejectExtensionOptionsDecorator(), // Covered in tx parsing code
+        //  - NewMempoolFeeDecorator(opts.BypassMinFeeMsgTypes), // Covered in baseapp check_tx, NOT USED FOR DELIVER_TX
+        //  - ante.NewValidateBasicDecorator(),
+        //  - ante.NewTxTimeoutHeightDecorator(),
+        //  - ante.NewValidateMemoDecorator(opts.AccountKeeper),
+        //  - ante.NewConsumeGasForTxSizeDecorator(opts.AccountKeeper),
+        //  - ante.NewDeductFeeDecorator(opts.AccountKeeper, opts.BankKeeper, opts.FeegrantKeeper),
+        // // SetPubKeyDecorator must be called before all signature verification decorators
+        //  - ante.NewSetPubKeyDecorator(opts.AccountKeeper),
+        //  - ante.NewValidateSigCountDecorator(opts.AccountKeeper),
+        //  - ante.NewSigGasConsumeDecorator(opts.AccountKeeper, sigGasConsumer),
+        //  - ante.NewSigVerificationDecorator(opts.AccountKeeper, opts.SignModeHandler),
+        //  - ante.NewTipDecorator(opts.BankKeeper),
+        //  - ante.NewIncrementSequenceDecorator(opts.AccountKeeper),
+        //  ** ibcante.NewAnteDecorator(opts.IBCkeeper),
+        (
+            ValidateBasicDecorator,
+            TxTimeoutHeightDecorator,
+            ValidateMemoDecorator::new(auth_keeper.clone()),
+            ConsumeGasForTxSizeDecorator::new(auth_keeper.clone()),
+            DeductFeeDecorator::new(bank_keeper.clone(), auth_keeper.clone())
+                .with_fee_grant_keeper(fee_grant_keeper),
+            SetPubKeyDecorator::new(auth_keeper.clone()),
+            ValidateSigCountDecorator::new(auth_keeper.clone()),
+            SigGasConsumeDecorator::new(auth_keeper.clone()),
+            SigVerificationDecorator::new(bank_keeper.clone(), auth_keeper.clone()),
+            TipDecorator::new(bank_keeper),
+            IncrementSequenceDecorator::new(auth_keeper),
+        )
     }
 
-    fn increment_sequence_ante_handler<DB: Database, M: Message>(
+    pub fn run<DB: Database, M: Message + ValueRenderer>(
         &self,
         ctx: &mut Context<'_, '_, DB, SK>,
-        tx: &Tx<M>,
+        tx: &TxWithRaw<M>,
     ) -> Result<(), AppError> {
-        for signer in tx.get_signers() {
-            let mut acct = self
-                .auth_keeper
-                .get_account(ctx, signer)
-                .ok_or(AppError::AccountNotFound)?;
-            acct.increment_sequence();
-            self.auth_keeper.set_account(ctx, acct)
-        }
-
-        Ok(())
+        self.chain.run(ctx, tx)
     }
 }
 
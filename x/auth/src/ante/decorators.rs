@@ -0,0 +1,1266 @@
+use std::cell::RefCell;
+
+use database::Database;
+
+use gears::{
+    error::AppError,
+    types::{context::context::Context, gas::gas_meter::Gas},
+    x::auth::{Module, Params},
+};
+use prost::Message as ProstMessage;
+use proto_messages::cosmos::{
+    ibc::{
+        protobuf::Protobuf,
+        tx::{SignDoc, TxRaw as RawTxRaw},
+    },
+    tx::v1beta1::{
+        message::Message,
+        mode_info::{ModeInfo, SignMode},
+        public_key::{PublicKey, SigningError},
+        signer_data::SignerData,
+        tx::{aux_signer_data::SignDocDirectAux, legacy_amino::StdSignDoc},
+        tx_data::TxData,
+        tx_metadata::Metadata,
+        tx_raw::TxWithRaw,
+    },
+};
+use proto_types::{AccAddress, Denom};
+use store::StoreKey;
+
+use crate::signing::{handler::SignModeHandler, renderer::value_renderer::ValueRenderer};
+
+use super::{AnteDecorator, AuthKeeper, BankKeeper, FeeGrantKeeper, NoFeeGrantKeeper};
+
+/// Checks the tx has signatures and a signature for each signer
+#[derive(Debug, Clone, Default)]
+pub struct ValidateBasicDecorator;
+
+impl<SK: StoreKey> AnteDecorator<SK> for ValidateBasicDecorator {
+    fn ante_handle<DB: Database, M: Message + ValueRenderer>(
+        &self,
+        _ctx: &mut Context<'_, '_, DB, SK>,
+        tx: &TxWithRaw<M>,
+    ) -> Result<(), AppError> {
+        let tx = &tx.tx;
+
+        // Not sure if we need to explicitly check this given the check which follows.
+        // We'll leave it in for now since it's in the SDK.
+        let sigs = tx.get_signatures();
+        if sigs.is_empty() {
+            return Err(AppError::TxValidation("signature list is empty".into()));
+        }
+
+        if sigs.len() != tx.get_signers().len() {
+            return Err(AppError::TxValidation(format!(
+                "wrong number of signatures; expected {}, got {}",
+                tx.get_signers().len(),
+                sigs.len()
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+/// Rejects the tx if the block height is past the tx timeout height
+#[derive(Debug, Clone, Default)]
+pub struct TxTimeoutHeightDecorator;
+
+impl<SK: StoreKey> AnteDecorator<SK> for TxTimeoutHeightDecorator {
+    fn ante_handle<DB: Database, M: Message + ValueRenderer>(
+        &self,
+        ctx: &mut Context<'_, '_, DB, SK>,
+        tx: &TxWithRaw<M>,
+    ) -> Result<(), AppError> {
+        let timeout_height = tx.tx.get_timeout_height();
+
+        // timeout_height of zero means no timeout height
+        if timeout_height == 0 {
+            return Ok(());
+        }
+
+        let block_height = ctx.get_height();
+
+        if ctx.get_height() > timeout_height {
+            return Err(AppError::Timeout {
+                timeout: timeout_height,
+                current: block_height,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Rejects the tx if the memo is longer than the `max_memo_characters` auth param
+#[derive(Debug, Clone)]
+pub struct ValidateMemoDecorator<AK> {
+    auth_keeper: AK,
+}
+
+impl<AK> ValidateMemoDecorator<AK> {
+    pub fn new(auth_keeper: AK) -> Self {
+        Self { auth_keeper }
+    }
+}
+
+impl<SK: StoreKey, AK: AuthKeeper<SK>> AnteDecorator<SK> for ValidateMemoDecorator<AK> {
+    fn ante_handle<DB: Database, M: Message + ValueRenderer>(
+        &self,
+        ctx: &mut Context<'_, '_, DB, SK>,
+        tx: &TxWithRaw<M>,
+    ) -> Result<(), AppError> {
+        let max_memo_chars = self.auth_keeper.get_auth_params(ctx)?.max_memo_characters;
+        let memo_length: u64 = tx
+            .tx
+            .get_memo()
+            .len()
+            .try_into()
+            .map_err(|_| AppError::Memo(max_memo_chars))?;
+
+        if memo_length > max_memo_chars {
+            return Err(AppError::Memo(max_memo_chars));
+        };
+        Ok(())
+    }
+}
+
+/// Charges `tx_size_cost_per_byte` gas for each byte of the encoded tx
+#[derive(Debug, Clone)]
+pub struct ConsumeGasForTxSizeDecorator<AK> {
+    auth_keeper: AK,
+}
+
+impl<AK> ConsumeGasForTxSizeDecorator<AK> {
+    pub fn new(auth_keeper: AK) -> Self {
+        Self { auth_keeper }
+    }
+}
+
+impl<SK: StoreKey, AK: AuthKeeper<SK>> AnteDecorator<SK> for ConsumeGasForTxSizeDecorator<AK> {
+    fn ante_handle<DB: Database, M: Message + ValueRenderer>(
+        &self,
+        ctx: &mut Context<'_, '_, DB, SK>,
+        tx: &TxWithRaw<M>,
+    ) -> Result<(), AppError> {
+        let params = self.auth_keeper.get_auth_params(ctx)?;
+        let tx_size = RawTxRaw::from(tx.raw.clone()).encoded_len() as u64;
+
+        ctx.gas_meter_mut().consume_gas(
+            Gas(params.tx_size_cost_per_byte.saturating_mul(tx_size)),
+            "txSize".into(),
+        )?;
+
+        Ok(())
+    }
+}
+
+/// Sends the tx fee to the fee collector module, the fee is paid by the fee granter if the tx sets
+/// one, otherwise by the fee payer
+#[derive(Debug, Clone)]
+pub struct DeductFeeDecorator<BK, AK, FK = NoFeeGrantKeeper> {
+    bank_keeper: BK,
+    auth_keeper: AK,
+    fee_grant_keeper: FK,
+}
+
+impl<BK, AK> DeductFeeDecorator<BK, AK> {
+    pub fn new(bank_keeper: BK, auth_keeper: AK) -> Self {
+        Self {
+            bank_keeper,
+            auth_keeper,
+            fee_grant_keeper: NoFeeGrantKeeper,
+        }
+    }
+}
+
+impl<BK, AK, FK> DeductFeeDecorator<BK, AK, FK> {
+    /// Returns a decorator which uses the keeper to charge fees to fee granters
+    pub fn with_fee_grant_keeper<F>(self, fee_grant_keeper: F) -> DeductFeeDecorator<BK, AK, F> {
+        DeductFeeDecorator {
+            bank_keeper: self.bank_keeper,
+            auth_keeper: self.auth_keeper,
+            fee_grant_keeper,
+        }
+    }
+}
+
+impl<SK, BK, AK, FK> AnteDecorator<SK> for DeductFeeDecorator<BK, AK, FK>
+where
+    SK: StoreKey,
+    BK: BankKeeper<SK>,
+    AK: AuthKeeper<SK>,
+    FK: FeeGrantKeeper<SK>,
+{
+    fn ante_handle<DB: Database, M: Message + ValueRenderer>(
+        &self,
+        ctx: &mut Context<'_, '_, DB, SK>,
+        tx: &TxWithRaw<M>,
+    ) -> Result<(), AppError> {
+        let fee = tx.tx.get_fee();
+        let fee_payer = tx.tx.get_fee_payer();
+
+        let fee_granter = match tx.tx.auth_info.fee.granter.as_str() {
+            "" => None,
+            granter => Some(AccAddress::from_bech32(granter).map_err(|e| {
+                AppError::TxValidation(format!("invalid fee granter address: {}", e))
+            })?),
+        };
+
+        let deduct_fees_from = match &fee_granter {
+            Some(fee_granter) => {
+                if fee_granter != fee_payer {
+                    self.fee_grant_keeper
+                        .use_granted_fees(
+                            ctx,
+                            fee_granter,
+                            fee_payer,
+                            fee.as_ref(),
+                            tx.tx.get_msgs(),
+                        )
+                        .map_err(|e| {
+                            AppError::TxValidation(format!(
+                                "{} does not allow to pay fees for {}: {}",
+                                fee_granter, fee_payer, e
+                            ))
+                        })?;
+                }
+
+                fee_granter
+            }
+            None => fee_payer,
+        };
+
+        if !self.auth_keeper.has_account(ctx, deduct_fees_from)? {
+            return Err(AppError::AccountNotFound);
+        }
+
+        if let Some(fee) = fee {
+            self.bank_keeper.send_coins_from_account_to_module(
+                ctx,
+                deduct_fees_from.to_owned(),
+                Module::FeeCollector,
+                fee.to_owned(),
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Sets the public keys of the signer accounts which don't have one yet, it must run before
+/// any decorator which uses the account public keys
+#[derive(Debug, Clone)]
+pub struct SetPubKeyDecorator<AK> {
+    auth_keeper: AK,
+}
+
+impl<AK> SetPubKeyDecorator<AK> {
+    pub fn new(auth_keeper: AK) -> Self {
+        Self { auth_keeper }
+    }
+}
+
+impl<SK: StoreKey, AK: AuthKeeper<SK>> AnteDecorator<SK> for SetPubKeyDecorator<AK> {
+    fn ante_handle<DB: Database, M: Message + ValueRenderer>(
+        &self,
+        ctx: &mut Context<'_, '_, DB, SK>,
+        tx: &TxWithRaw<M>,
+    ) -> Result<(), AppError> {
+        let public_keys = tx.tx.get_public_keys();
+        let signers = tx.tx.get_signers();
+
+        // additional check not found in the sdk - this prevents a panic
+        if signers.len() != public_keys.len() {
+            return Err(AppError::TxValidation(format!(
+                "wrong number of signer info; expected {}, got {}",
+                signers.len(),
+                public_keys.len()
+            )));
+        }
+
+        for (i, key) in public_keys.into_iter().enumerate() {
+            if let Some(key) = key {
+                let addr = key.get_address();
+
+                if &addr != signers[i] {
+                    return Err(AppError::InvalidPublicKey);
+                }
+
+                let mut acct = self
+                    .auth_keeper
+                    .get_account(ctx, &addr)?
+                    .ok_or(AppError::AccountNotFound)?;
+
+                if acct.get_public_key().is_some() {
+                    continue;
+                }
+
+                acct.set_public_key(key.clone());
+                self.auth_keeper.set_account(ctx, acct)
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Rejects the tx if its public keys hold more keys than the `tx_sig_limit` auth param
+#[derive(Debug, Clone)]
+pub struct ValidateSigCountDecorator<AK> {
+    auth_keeper: AK,
+}
+
+impl<AK> ValidateSigCountDecorator<AK> {
+    pub fn new(auth_keeper: AK) -> Self {
+        Self { auth_keeper }
+    }
+}
+
+impl<SK: StoreKey, AK: AuthKeeper<SK>> AnteDecorator<SK> for ValidateSigCountDecorator<AK> {
+    fn ante_handle<DB: Database, M: Message + ValueRenderer>(
+        &self,
+        ctx: &mut Context<'_, '_, DB, SK>,
+        tx: &TxWithRaw<M>,
+    ) -> Result<(), AppError> {
+        let tx_sig_limit = self.auth_keeper.get_auth_params(ctx)?.tx_sig_limit;
+
+        let mut sig_count: u64 = 0;
+        for public_key in tx.tx.get_public_keys().into_iter().flatten() {
+            sig_count = sig_count.saturating_add(count_sub_keys(public_key));
+
+            if sig_count > tx_sig_limit {
+                return Err(AppError::TxValidation(format!(
+                    "too many signatures; signatures: {}, limit: {}",
+                    sig_count, tx_sig_limit
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Charges gas for verifying each of the tx signatures
+#[derive(Debug, Clone)]
+pub struct SigGasConsumeDecorator<AK> {
+    auth_keeper: AK,
+}
+
+impl<AK> SigGasConsumeDecorator<AK> {
+    pub fn new(auth_keeper: AK) -> Self {
+        Self { auth_keeper }
+    }
+}
+
+impl<SK: StoreKey, AK: AuthKeeper<SK>> AnteDecorator<SK> for SigGasConsumeDecorator<AK> {
+    fn ante_handle<DB: Database, M: Message + ValueRenderer>(
+        &self,
+        ctx: &mut Context<'_, '_, DB, SK>,
+        tx: &TxWithRaw<M>,
+    ) -> Result<(), AppError> {
+        let params = self.auth_keeper.get_auth_params(ctx)?;
+
+        for (signer, signature_data) in tx
+            .tx
+            .get_signers()
+            .into_iter()
+            .zip(tx.tx.get_signatures_data())
+        {
+            let acct = self
+                .auth_keeper
+                .get_account(ctx, signer)?
+                .ok_or(AppError::AccountNotFound)?;
+
+            // the key is only set by SetPubKeyDecorator when the tx includes it
+            let public_key = acct
+                .get_public_key()
+                .as_ref()
+                .ok_or(AppError::InvalidPublicKey)?;
+
+            let gas = sig_verification_gas(public_key, &signature_data.mode_info, &params)?;
+
+            ctx.gas_meter_mut()
+                .consume_gas(Gas(gas), "ante verify: signature".into())?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Checks the sequence and verifies the signature of each signer
+#[derive(Debug, Clone)]
+pub struct SigVerificationDecorator<BK, AK> {
+    bank_keeper: BK,
+    auth_keeper: AK,
+}
+
+impl<BK, AK> SigVerificationDecorator<BK, AK> {
+    pub fn new(bank_keeper: BK, auth_keeper: AK) -> Self {
+        Self {
+            bank_keeper,
+            auth_keeper,
+        }
+    }
+}
+
+impl<SK, BK, AK> AnteDecorator<SK> for SigVerificationDecorator<BK, AK>
+where
+    SK: StoreKey,
+    BK: BankKeeper<SK>,
+    AK: AuthKeeper<SK>,
+{
+    fn ante_handle<DB: Database, M: Message + ValueRenderer>(
+        &self,
+        ctx: &mut Context<'_, '_, DB, SK>,
+        tx: &TxWithRaw<M>,
+    ) -> Result<(), AppError> {
+        let signers = tx.tx.get_signers();
+        let signature_data = tx.tx.get_signatures_data();
+
+        // NOTE: this is also checked in ValidateBasicDecorator
+        if signature_data.len() != signers.len() {
+            return Err(AppError::TxValidation(format!(
+                "wrong number of signatures; expected {}, got {}",
+                signers.len(),
+                signature_data.len()
+            )));
+        }
+
+        for (i, signature_data) in signature_data.iter().enumerate() {
+            let signer = signers[i];
+
+            // check sequence number
+            let acct = self
+                .auth_keeper
+                .get_account(ctx, signer)?
+                .ok_or(AppError::AccountNotFound)?;
+            let account_seq = acct.get_sequence();
+            if account_seq != signature_data.sequence {
+                return Err(AppError::TxValidation(format!(
+                    "incorrect tx sequence; expected {}, got {}",
+                    account_seq, signature_data.sequence
+                )));
+            }
+
+            let public_key = acct
+                .get_public_key()
+                .as_ref()
+                .ok_or(AppError::InvalidPublicKey)?;
+
+            // a store error can't be returned through the renderer's metadata lookup, so it's
+            // kept here and returned instead of the signature check result
+            let metadata_error = RefCell::new(None);
+
+            let get_sign_bytes = |mode: &SignMode| -> Result<Vec<u8>, SigningError> {
+                match mode {
+                    SignMode::Direct => Ok(SignDoc {
+                        body_bytes: tx.raw.body_bytes.clone(),
+                        auth_info_bytes: tx.raw.auth_info_bytes.clone(),
+                        chain_id: ctx.get_chain_id().to_string(),
+                        account_number: acct.get_account_number(),
+                    }
+                    .encode_to_vec()),
+                    SignMode::Textual => {
+                        let handler = SignModeHandler;
+
+                        let signer_data = SignerData {
+                            address: signer.to_owned(),
+                            chain_id: ctx.get_chain_id().to_owned(),
+                            account_number: acct.get_account_number(),
+                            sequence: account_seq,
+                            pub_key: public_key.to_owned(),
+                        };
+
+                        let tx_data = TxData {
+                            body: tx.tx.body.clone(),
+                            auth_info: tx.tx.auth_info.clone(),
+                        };
+
+                        let f = |denom: &Denom| -> Option<Metadata> {
+                            self.bank_keeper
+                                .get_denom_metadata(ctx, denom)
+                                .unwrap_or_else(|e| {
+                                    metadata_error.borrow_mut().get_or_insert(e);
+                                    None
+                                })
+                        };
+
+                        handler
+                            .sign_bytes_get(&f, signer_data, tx_data)
+                            .map_err(|e| SigningError::SignBytes(e.to_string()))
+                    }
+                    SignMode::DirectAux => {
+                        let fee_payer = tx.tx.get_fee_payer();
+                        if signer == fee_payer {
+                            return Err(SigningError::SignBytes(format!(
+                                "fee payer {} cannot sign with SIGN_MODE_DIRECT_AUX",
+                                fee_payer
+                            )));
+                        }
+
+                        Ok(SignDocDirectAux {
+                            body_bytes: tx.raw.body_bytes.clone(),
+                            public_key: Some(public_key.to_owned()),
+                            chain_id: ctx.get_chain_id().to_string(),
+                            account_number: acct.get_account_number(),
+                            sequence: account_seq,
+                            tip: tx.tx.auth_info.tip.clone(),
+                        }
+                        .encode_vec())
+                    }
+                    SignMode::LegacyAminoJson => StdSignDoc::new(
+                        ctx.get_chain_id().to_string(),
+                        acct.get_account_number(),
+                        account_seq,
+                        &tx.tx.body,
+                        &tx.tx.auth_info.fee,
+                        tx.tx.auth_info.tip.as_ref(),
+                    )
+                    .map(|sign_doc| sign_doc.to_sign_bytes())
+                    .map_err(|e| SigningError::SignBytes(e.to_string())),
+                    _ => Err(SigningError::SignBytes(
+                        "sign mode not supported".to_string(),
+                    )),
+                }
+            };
+
+            match (&signature_data.mode_info, public_key) {
+                (ModeInfo::Single(mode), _) => {
+                    let sign_bytes = get_sign_bytes(mode);
+                    if let Some(e) = metadata_error.take() {
+                        return Err(e);
+                    }
+                    let sign_bytes =
+                        sign_bytes.map_err(|e| AppError::TxValidation(e.to_string()))?;
+
+                    public_key
+                        .verify_signature(&sign_bytes, &signature_data.signature)
+                        .map_err(|e| AppError::TxValidation(format!("invalid signature: {}", e)))?;
+                }
+                (ModeInfo::Multi(multi), PublicKey::Multisig(key)) => {
+                    let verified = key.verify_multisignature(
+                        multi,
+                        &signature_data.signature,
+                        &get_sign_bytes,
+                    );
+                    if let Some(e) = metadata_error.take() {
+                        return Err(e);
+                    }
+                    verified
+                        .map_err(|e| AppError::TxValidation(format!("invalid signature: {}", e)))?;
+                }
+                (ModeInfo::Multi(_), _) => {
+                    return Err(AppError::TxValidation(
+                        "multisig mode info requires a multisig public key".to_string(),
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Transfers the tip from the tipper to the fee payer, the tipper must be one of the signers
+#[derive(Debug, Clone)]
+pub struct TipDecorator<BK> {
+    bank_keeper: BK,
+}
+
+impl<BK> TipDecorator<BK> {
+    pub fn new(bank_keeper: BK) -> Self {
+        Self { bank_keeper }
+    }
+}
+
+impl<SK: StoreKey, BK: BankKeeper<SK>> AnteDecorator<SK> for TipDecorator<BK> {
+    fn ante_handle<DB: Database, M: Message + ValueRenderer>(
+        &self,
+        ctx: &mut Context<'_, '_, DB, SK>,
+        tx: &TxWithRaw<M>,
+    ) -> Result<(), AppError> {
+        let Some(tip) = &tx.tx.auth_info.tip else {
+            return Ok(());
+        };
+
+        if !tx.tx.get_signers().contains(&&tip.tipper) {
+            return Err(AppError::TxValidation(format!(
+                "tipper {} must be a signer of the tx",
+                tip.tipper
+            )));
+        }
+
+        if let Some(amount) = &tip.amount {
+            self.bank_keeper.send_coins_between_accounts(
+                ctx,
+                tip.tipper.to_owned(),
+                tx.tx.get_fee_payer().to_owned(),
+                amount.to_owned(),
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Increments the sequence of each signer account
+#[derive(Debug, Clone)]
+pub struct IncrementSequenceDecorator<AK> {
+    auth_keeper: AK,
+}
+
+impl<AK> IncrementSequenceDecorator<AK> {
+    pub fn new(auth_keeper: AK) -> Self {
+        Self { auth_keeper }
+    }
+}
+
+impl<SK: StoreKey, AK: AuthKeeper<SK>> AnteDecorator<SK> for IncrementSequenceDecorator<AK> {
+    fn ante_handle<DB: Database, M: Message + ValueRenderer>(
+        &self,
+        ctx: &mut Context<'_, '_, DB, SK>,
+        tx: &TxWithRaw<M>,
+    ) -> Result<(), AppError> {
+        for signer in tx.tx.get_signers() {
+            let mut acct = self
+                .auth_keeper
+                .get_account(ctx, signer)?
+                .ok_or(AppError::AccountNotFound)?;
+            acct.increment_sequence();
+            self.auth_keeper.set_account(ctx, acct)
+        }
+
+        Ok(())
+    }
+}
+/// Returns the number of keys of the public key, a multisig key counts each of its (possibly
+/// nested) keys
+fn count_sub_keys(public_key: &PublicKey) -> u64 {
+    match public_key {
+        PublicKey::Multisig(key) => key.public_keys().iter().map(count_sub_keys).sum(),
+        _ => 1,
+    }
+}
+
+/// Ethermint charges a fixed cost for eth_secp256k1 signatures which isn't set by the auth params
+#[cfg(feature = "ethsecp256k1")]
+const SIG_VERIFY_COST_ETH_SECP256K1: u64 = 21_000;
+
+/// Returns the gas charged for verifying a signature made with the key, for a multisig key only
+/// the keys which signed are charged
+fn sig_verification_gas(
+    public_key: &PublicKey,
+    mode_info: &ModeInfo,
+    params: &Params,
+) -> Result<u64, AppError> {
+    match (public_key, mode_info) {
+        (PublicKey::Multisig(key), ModeInfo::Multi(multi)) => {
+            let bitarray = multi.bitarray.as_ref().ok_or(AppError::TxValidation(
+                "multisig mode info is missing a bit array".to_string(),
+            ))?;
+
+            let signed_keys = key
+                .public_keys()
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| bitarray.get_index(*i))
+                .map(|(_, key)| key);
+
+            let mut gas: u64 = 0;
+            for (i, key) in signed_keys.enumerate() {
+                let mode_info = multi.mode_infos.get(i).ok_or(AppError::TxValidation(
+                    "multisig mode info doesn't match the bit array".to_string(),
+                ))?;
+
+                gas = gas.saturating_add(sig_verification_gas(key, mode_info, params)?);
+            }
+
+            Ok(gas)
+        }
+        (PublicKey::Multisig(_), ModeInfo::Single(_)) | (_, ModeInfo::Multi(_)) => Err(
+            AppError::TxValidation("mode info doesn't match the public key type".to_string()),
+        ),
+        (PublicKey::Secp256k1(_), _) => Ok(params.sig_verify_cost_secp256k1),
+        (PublicKey::Secp256r1(_), _) => Ok(params.sig_verify_cost_secp256r1()),
+        (PublicKey::Ed25519(_), _) => Ok(params.sig_verify_cost_ed25519),
+        #[cfg(feature = "ethsecp256k1")]
+        (PublicKey::EthSecp256k1(_), _) => Ok(SIG_VERIFY_COST_ETH_SECP256K1),
+        // reached when another crate enables the proto-messages ethsecp256k1 feature but this
+        // crate's feature isn't enabled
+        #[allow(unreachable_patterns)]
+        _ => Err(AppError::TxValidation(
+            "unsupported public key type".to_string(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use database::MemDB;
+    use gears::{
+        types::{context::init_context::InitContext, gas::gas_meter::GasMeter},
+        x::params::ParamsSubspaceKey,
+    };
+    use proto_messages::cosmos::{
+        auth::v1beta1::BaseAccount,
+        bank::v1beta1::MsgSend,
+        base::v1beta1::{Coin, SendCoins},
+        crypto::multisig::LegacyAminoPubKey,
+        tx::v1beta1::{
+            auth_info::AuthInfo, fee::Fee, signer::SignerInfo, tx_body::TxBody,
+            tx_metadata::DenomUnit,
+        },
+    };
+    use proto_types::Uint256;
+    use std::{
+        str::FromStr,
+        sync::{Arc, Mutex},
+    };
+    use store::MultiStore;
+    use strum::EnumIter;
+    use tendermint::informal::chain::Id;
+
+    use crate::{
+        ante::{AnteDecoratorChain, AnteHandlerTrait},
+        GenesisState, Keeper,
+    };
+
+    use super::*;
+
+    #[test]
+    fn sig_verification_gas_uses_key_type() {
+        let params = crate::params::DEFAULT_PARAMS;
+
+        for (serialized, expected) in [
+            (
+                r#"{"@type":"/cosmos.crypto.secp256k1.PubKey","key":"ApUOHN/LEz1gJBCf1In3NO60UCQY5TjChIHyK84nbySM"}"#,
+                1000,
+            ),
+            (
+                r#"{"@type":"/cosmos.crypto.secp256r1.PubKey","key":"AiXjO/il+1VqFNe4g6Qzsq00DTL5keF7p3XNfbUg0lVg"}"#,
+                500,
+            ),
+            (
+                r#"{"@type":"/cosmos.crypto.ed25519.PubKey","key":"SeOJ9s9l2bYHuvnXs2z4ne2Q1uBhLQ4V6fwLQvT6qW4="}"#,
+                590,
+            ),
+            #[cfg(feature = "ethsecp256k1")]
+            (
+                r#"{"@type":"/ethermint.crypto.v1.ethsecp256k1.PubKey","key":"ApUOHN/LEz1gJBCf1In3NO60UCQY5TjChIHyK84nbySM"}"#,
+                21000,
+            ),
+        ] {
+            let public_key: PublicKey = serde_json::from_str(serialized).unwrap();
+            let mode_info = ModeInfo::Single(SignMode::Direct);
+            assert_eq!(
+                sig_verification_gas(&public_key, &mode_info, &params).unwrap(),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn count_sub_keys_counts_nested_multisig_keys() {
+        use proto_messages::cosmos::crypto::multisig::LegacyAminoPubKey;
+
+        let secp256k1: PublicKey = serde_json::from_str(
+            r#"{"@type":"/cosmos.crypto.secp256k1.PubKey","key":"ApUOHN/LEz1gJBCf1In3NO60UCQY5TjChIHyK84nbySM"}"#,
+        )
+        .unwrap();
+        let ed25519: PublicKey = serde_json::from_str(
+            r#"{"@type":"/cosmos.crypto.ed25519.PubKey","key":"SeOJ9s9l2bYHuvnXs2z4ne2Q1uBhLQ4V6fwLQvT6qW4="}"#,
+        )
+        .unwrap();
+
+        let inner = LegacyAminoPubKey::new(1, vec![secp256k1.clone(), ed25519.clone()]).unwrap();
+        let outer = LegacyAminoPubKey::new(
+            2,
+            vec![PublicKey::Multisig(inner), secp256k1.clone(), ed25519],
+        )
+        .unwrap();
+
+        assert_eq!(count_sub_keys(&secp256k1), 1);
+        assert_eq!(count_sub_keys(&PublicKey::Multisig(outer)), 4);
+    }
+
+    #[test]
+    fn sig_verification_gas_charges_multisig_signers() {
+        use proto_messages::cosmos::tx::v1beta1::mode_info::{CompactBitArray, Multi};
+
+        let params = crate::params::DEFAULT_PARAMS;
+
+        let public_key: PublicKey = serde_json::from_str(
+            r#"{
+            "@type": "/cosmos.crypto.multisig.LegacyAminoPubKey",
+            "threshold": 2,
+            "public_keys": [
+                {"@type":"/cosmos.crypto.secp256k1.PubKey","key":"ApUOHN/LEz1gJBCf1In3NO60UCQY5TjChIHyK84nbySM"},
+                {"@type":"/cosmos.crypto.secp256k1.PubKey","key":"Auvdf+T963bciiBe9l15DNMOijdaXCUo6zqSOvH7TXlN"},
+                {"@type":"/cosmos.crypto.ed25519.PubKey","key":"SeOJ9s9l2bYHuvnXs2z4ne2Q1uBhLQ4V6fwLQvT6qW4="}
+            ]
+        }"#,
+        )
+        .unwrap();
+
+        let mut bitarray = CompactBitArray::new(3);
+        bitarray.set_index(1, true);
+        bitarray.set_index(2, true);
+        let mode_info = ModeInfo::Multi(Multi {
+            bitarray: Some(bitarray),
+            mode_infos: vec![
+                ModeInfo::Single(SignMode::Direct),
+                ModeInfo::Single(SignMode::Direct),
+            ],
+        });
+
+        assert_eq!(
+            sig_verification_gas(&public_key, &mode_info, &params).unwrap(),
+            1590
+        );
+
+        sig_verification_gas(&public_key, &ModeInfo::Single(SignMode::Direct), &params)
+            .unwrap_err();
+    }
+
+    #[derive(EnumIter, Debug, PartialEq, Eq, Hash, Clone)]
+    enum TestStoreKey {
+        Auth,
+        Params,
+    }
+
+    impl StoreKey for TestStoreKey {
+        fn name(&self) -> &'static str {
+            match self {
+                TestStoreKey::Auth => "acc",
+                TestStoreKey::Params => "params",
+            }
+        }
+    }
+
+    #[derive(EnumIter, Debug, PartialEq, Eq, Hash, Clone)]
+    enum TestParamsKey {
+        Auth,
+    }
+
+    impl ParamsSubspaceKey for TestParamsKey {
+        fn name(&self) -> &'static str {
+            match self {
+                TestParamsKey::Auth => "auth/",
+            }
+        }
+    }
+
+    type TestKeeper = Keeper<TestStoreKey, TestParamsKey>;
+
+    const PUBLIC_KEY: &str = r#"{"@type":"/cosmos.crypto.secp256k1.PubKey","key":"Auvdf+T963bciiBe9l15DNMOijdaXCUo6zqSOvH7TXlN"}"#;
+
+    /// The address of `PUBLIC_KEY`
+    fn signer() -> AccAddress {
+        AccAddress::from_bech32("cosmos1ulav3hsenupswqfkw2y3sup5kgtqwnvqa8eyhs").unwrap()
+    }
+
+    fn granter() -> AccAddress {
+        AccAddress::from_bech32("cosmos1ejrf4cur2wy6kfurg9f2jppp2h3afe5h6pkh5t").unwrap()
+    }
+
+    fn chain_id() -> Id {
+        Id::try_from("test-chain".to_string()).unwrap()
+    }
+
+    /// Returns an in-memory store initialized with the default auth params, a signer and a granter
+    /// account
+    fn setup() -> (MultiStore<MemDB, TestStoreKey>, TestKeeper) {
+        let mut store = MultiStore::new(MemDB::new()).unwrap();
+        let keeper = Keeper::new(
+            TestStoreKey::Auth,
+            gears::x::params::Keeper::new(TestStoreKey::Params),
+            TestParamsKey::Auth,
+        );
+
+        let mut ctx = InitContext::new(&mut store, 0, chain_id());
+        keeper
+            .init_genesis(
+                &mut ctx,
+                GenesisState {
+                    accounts: vec![
+                        BaseAccount {
+                            address: signer(),
+                            pub_key: None,
+                            account_number: 0,
+                            sequence: 0,
+                        },
+                        BaseAccount {
+                            address: granter(),
+                            pub_key: None,
+                            account_number: 0,
+                            sequence: 0,
+                        },
+                    ],
+                    params: crate::params::DEFAULT_PARAMS,
+                },
+            )
+            .unwrap();
+
+        (store, keeper)
+    }
+
+    /// Returns a tx with a single `MsgSend` from the signer, the signature isn't valid
+    fn tx_with_raw(memo: &str, public_key: Option<PublicKey>) -> TxWithRaw<MsgSend> {
+        let fee = Fee {
+            amount: None,
+            gas_limit: 200_000,
+            payer: None,
+            granter: String::new(),
+        };
+
+        tx_with_fee(memo, public_key, fee)
+    }
+
+    fn tx_with_fee(memo: &str, public_key: Option<PublicKey>, fee: Fee) -> TxWithRaw<MsgSend> {
+        tx_with_sign_mode(memo, public_key, fee, SignMode::Direct)
+    }
+
+    fn tx_with_sign_mode(
+        memo: &str,
+        public_key: Option<PublicKey>,
+        fee: Fee,
+        sign_mode: SignMode,
+    ) -> TxWithRaw<MsgSend> {
+        let body = TxBody {
+            messages: vec![MsgSend {
+                from_address: signer(),
+                to_address: AccAddress::from_bech32(
+                    "cosmos1ejrf4cur2wy6kfurg9f2jppp2h3afe5h6pkh5t",
+                )
+                .unwrap(),
+                amount: SendCoins::new(vec![Coin {
+                    denom: Denom::try_from("uatom".to_string()).unwrap(),
+                    amount: Uint256::from(10u32),
+                }])
+                .unwrap(),
+            }],
+            memo: memo.to_owned(),
+            timeout_height: 0,
+            extension_options: vec![],
+            non_critical_extension_options: vec![],
+        };
+
+        let auth_info = AuthInfo {
+            signer_infos: vec![SignerInfo {
+                public_key,
+                mode_info: ModeInfo::Single(sign_mode),
+                sequence: 0,
+            }],
+            fee,
+            tip: None,
+        };
+
+        let raw = RawTxRaw {
+            body_bytes: body.encode_vec(),
+            auth_info_bytes: auth_info.encode_vec(),
+            signatures: vec![vec![0; 64]],
+        };
+
+        TxWithRaw::from_bytes(raw.encode_to_vec().into()).unwrap()
+    }
+
+    /// Records the coins sent to modules and returns the same metadata for every denom
+    #[derive(Debug, Clone, Default)]
+    struct MockBankKeeper {
+        sent_to_modules: Arc<Mutex<Vec<(AccAddress, SendCoins)>>>,
+        metadata: Option<Metadata>,
+    }
+
+    impl BankKeeper<TestStoreKey> for MockBankKeeper {
+        fn send_coins_from_account_to_module<DB: Database>(
+            &self,
+            _ctx: &mut Context<'_, '_, DB, TestStoreKey>,
+            from_address: AccAddress,
+            _to_module: Module,
+            amount: SendCoins,
+        ) -> Result<(), AppError> {
+            self.sent_to_modules
+                .lock()
+                .unwrap()
+                .push((from_address, amount));
+            Ok(())
+        }
+
+        fn send_coins_between_accounts<DB: Database>(
+            &self,
+            _ctx: &mut Context<'_, '_, DB, TestStoreKey>,
+            _from_address: AccAddress,
+            _to_address: AccAddress,
+            _amount: SendCoins,
+        ) -> Result<(), AppError> {
+            Ok(())
+        }
+
+        fn get_denom_metadata<
+            DB: Database,
+            CTX: gears::types::context::read_context::ReadContext<TestStoreKey, DB>,
+        >(
+            &self,
+            _ctx: &CTX,
+            _base: &Denom,
+        ) -> Result<Option<Metadata>, AppError> {
+            Ok(self.metadata.clone())
+        }
+    }
+
+    /// Allows any granter to pay the fees of any grantee
+    #[derive(Debug, Clone)]
+    struct AllowAllFeeGrantKeeper;
+
+    impl FeeGrantKeeper<TestStoreKey> for AllowAllFeeGrantKeeper {
+        fn use_granted_fees<DB: Database, M: Message>(
+            &self,
+            _ctx: &mut Context<'_, '_, DB, TestStoreKey>,
+            _granter: &AccAddress,
+            _grantee: &AccAddress,
+            _fee: Option<&SendCoins>,
+            _msgs: &[M],
+        ) -> Result<(), AppError> {
+            Ok(())
+        }
+    }
+
+    #[derive(Debug, Clone)]
+    struct RejectDecorator;
+
+    impl<SK: StoreKey> AnteDecorator<SK> for RejectDecorator {
+        fn ante_handle<DB: Database, M: Message + ValueRenderer>(
+            &self,
+            _ctx: &mut Context<'_, '_, DB, SK>,
+            _tx: &TxWithRaw<M>,
+        ) -> Result<(), AppError> {
+            Err(AppError::TxValidation("rejected".into()))
+        }
+    }
+
+    #[test]
+    fn validate_memo_decorator_checks_max_memo_characters() {
+        let (mut store, keeper) = setup();
+        let mut ctx = InitContext::new(&mut store, 0, chain_id());
+        let decorator = ValidateMemoDecorator::new(keeper);
+
+        decorator
+            .ante_handle(&mut ctx.as_any(), &tx_with_raw(&"a".repeat(256), None))
+            .unwrap();
+
+        let err = decorator
+            .ante_handle(&mut ctx.as_any(), &tx_with_raw(&"a".repeat(257), None))
+            .unwrap_err();
+        assert_eq!(err, AppError::Memo(256));
+    }
+
+    #[test]
+    fn consume_gas_for_tx_size_decorator_charges_per_byte() {
+        let (mut store, keeper) = setup();
+        let mut ctx = InitContext::new(&mut store, 0, chain_id());
+        let tx = tx_with_raw("", None);
+
+        ConsumeGasForTxSizeDecorator::new(keeper)
+            .ante_handle(&mut ctx.as_any(), &tx)
+            .unwrap();
+
+        let tx_size = RawTxRaw::from(tx.raw).encoded_len() as u64;
+        assert_eq!(ctx.gas_meter.gas_consumed(), Gas(10 * tx_size));
+    }
+
+    #[test]
+    fn validate_sig_count_decorator_checks_tx_sig_limit() {
+        let (mut store, keeper) = setup();
+        let mut ctx = InitContext::new(&mut store, 0, chain_id());
+        let decorator = ValidateSigCountDecorator::new(keeper);
+        let public_key: PublicKey = serde_json::from_str(PUBLIC_KEY).unwrap();
+
+        decorator
+            .ante_handle(
+                &mut ctx.as_any(),
+                &tx_with_raw("", Some(public_key.clone())),
+            )
+            .unwrap();
+
+        let multisig = LegacyAminoPubKey::new(1, vec![public_key; 8]).unwrap();
+        let err = decorator
+            .ante_handle(
+                &mut ctx.as_any(),
+                &tx_with_raw("", Some(PublicKey::Multisig(multisig))),
+            )
+            .unwrap_err();
+        assert_eq!(
+            err,
+            AppError::TxValidation("too many signatures; signatures: 8, limit: 7".into())
+        );
+    }
+
+    #[test]
+    fn set_pub_key_and_increment_sequence_decorators_update_account() {
+        let (mut store, keeper) = setup();
+        let mut ctx = InitContext::new(&mut store, 0, chain_id());
+        let public_key: PublicKey = serde_json::from_str(PUBLIC_KEY).unwrap();
+
+        AnteDecoratorChain::new((
+            SetPubKeyDecorator::new(keeper.clone()),
+            IncrementSequenceDecorator::new(keeper.clone()),
+        ))
+        .run(
+            &mut ctx.as_any(),
+            &tx_with_raw("", Some(public_key.clone())),
+        )
+        .unwrap();
+
+        let account = keeper
+            .get_account(&ctx.as_any(), &signer())
+            .unwrap()
+            .unwrap();
+        assert_eq!(account.get_public_key(), &Some(public_key));
+        assert_eq!(account.get_sequence(), 1);
+    }
+
+    #[test]
+    fn sig_gas_consume_decorator_rejects_missing_public_key() {
+        let (mut store, keeper) = setup();
+        let mut ctx = InitContext::new(&mut store, 0, chain_id());
+
+        // the signer's account has no public key and the tx doesn't include it
+        let err = AnteDecoratorChain::new((
+            SetPubKeyDecorator::new(keeper.clone()),
+            SigGasConsumeDecorator::new(keeper),
+        ))
+        .run(&mut ctx.as_any(), &tx_with_raw("", None))
+        .unwrap_err();
+        assert_eq!(err, AppError::InvalidPublicKey);
+    }
+
+    #[test]
+    fn sig_verification_decorator_rejects_tx_which_cant_be_rendered() {
+        let (mut store, keeper) = setup();
+        let mut ctx = InitContext::new(&mut store, 0, chain_id());
+        let public_key: PublicKey = serde_json::from_str(PUBLIC_KEY).unwrap();
+
+        // the display unit is too large to scale the sent amount to
+        let bank_keeper = MockBankKeeper {
+            metadata: Some(Metadata {
+                description: String::new(),
+                denom_units: vec![
+                    DenomUnit {
+                        denom: Denom::try_from("uatom".to_string()).unwrap(),
+                        exponent: 0,
+                        aliases: vec![],
+                    },
+                    DenomUnit {
+                        denom: Denom::try_from("atom".to_string()).unwrap(),
+                        exponent: 100,
+                        aliases: vec![],
+                    },
+                ],
+                base: "uatom".into(),
+                display: "atom".into(),
+                name: String::new(),
+                symbol: String::new(),
+            }),
+            ..Default::default()
+        };
+        let fee = Fee {
+            amount: None,
+            gas_limit: 200_000,
+            payer: None,
+            granter: String::new(),
+        };
+
+        let err = AnteDecoratorChain::new((
+            SetPubKeyDecorator::new(keeper.clone()),
+            SigVerificationDecorator::new(bank_keeper, keeper),
+        ))
+        .run(
+            &mut ctx.as_any(),
+            &tx_with_sign_mode("", Some(public_key), fee, SignMode::Textual),
+        )
+        .unwrap_err();
+        let AppError::TxValidation(message) = err else {
+            panic!("the tx fails validation")
+        };
+        assert!(message.contains("atom denom is not supported for this signing mode"));
+    }
+
+    #[test]
+    fn decorator_chain_stops_at_first_error() {
+        let (mut store, keeper) = setup();
+        let mut ctx = InitContext::new(&mut store, 0, chain_id());
+
+        let chain = AnteDecoratorChain::new(IncrementSequenceDecorator::new(keeper.clone()))
+            .append(RejectDecorator)
+            .append(IncrementSequenceDecorator::new(keeper.clone()));
+
+        let err = chain
+            .run(&mut ctx.as_any(), &tx_with_raw("", None))
+            .unwrap_err();
+        assert_eq!(err, AppError::TxValidation("rejected".into()));
+
+        let account = keeper
+            .get_account(&ctx.as_any(), &signer())
+            .unwrap()
+            .unwrap();
+        assert_eq!(account.get_sequence(), 1);
+    }
+
+    #[test]
+    fn validate_basic_decorator_requires_fee_payer_signature() {
+        let (mut store, _) = setup();
+        let mut ctx = InitContext::new(&mut store, 0, chain_id());
+
+        let fee = Fee {
+            amount: None,
+            gas_limit: 200_000,
+            payer: Some(granter()),
+            granter: String::new(),
+        };
+
+        let err = ValidateBasicDecorator
+            .ante_handle(&mut ctx.as_any(), &tx_with_fee("", None, fee))
+            .unwrap_err();
+        assert_eq!(
+            err,
+            AppError::TxValidation("wrong number of signatures; expected 2, got 1".into())
+        );
+    }
+
+    #[test]
+    fn deduct_fee_decorator_charges_fee_granter() {
+        let (mut store, keeper) = setup();
+        let mut ctx = InitContext::new(&mut store, 0, chain_id());
+        let bank_keeper = MockBankKeeper::default();
+
+        let fee = Fee {
+            amount: Some(SendCoins::from_str("100uatom").unwrap()),
+            gas_limit: 200_000,
+            payer: None,
+            granter: granter().to_string(),
+        };
+        let tx = tx_with_fee("", None, fee);
+
+        let err = DeductFeeDecorator::new(bank_keeper.clone(), keeper.clone())
+            .ante_handle(&mut ctx.as_any(), &tx)
+            .unwrap_err();
+        assert_eq!(
+            err,
+            AppError::TxValidation(format!(
+                "{} does not allow to pay fees for {}: invalid request: fee grants are not enabled",
+                granter(),
+                signer()
+            ))
+        );
+
+        DeductFeeDecorator::new(bank_keeper.clone(), keeper)
+            .with_fee_grant_keeper(AllowAllFeeGrantKeeper)
+            .ante_handle(&mut ctx.as_any(), &tx)
+            .unwrap();
+        assert_eq!(
+            *bank_keeper.sent_to_modules.lock().unwrap(),
+            vec![(granter(), SendCoins::from_str("100uatom").unwrap())]
+        );
+    }
+}
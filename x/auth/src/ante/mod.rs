@@ -1,2 +1,4 @@
 mod ante;
+mod decorators;
 pub use ante::*;
+pub use decorators::*;
@@ -12,7 +12,8 @@ use gears::types::context::query_context::QueryContext;
 use prost::Message;
 use proto_messages::cosmos::{
     auth::v1beta1::{
-        Account, BaseAccount, ModuleAccount, QueryAccountRequest, QueryAccountResponse,
+        Account, BaseAccount, ModuleAccount, Params, QueryAccountRequest, QueryAccountResponse,
+        QueryParamsRequest, QueryParamsResponse,
     },
     ibc::protobuf::Protobuf,
 };
@@ -36,34 +37,38 @@ impl<SK: StoreKey, PSK: ParamsSubspaceKey> AuthKeeper<SK> for Keeper<SK, PSK> {
     fn get_auth_params<DB: Database>(
         &self,
         ctx: &Context<'_, '_, DB, SK>,
-    ) -> gears::x::auth::Params {
+    ) -> Result<gears::x::auth::Params, AppError> {
         self.auth_params_keeper.get(ctx)
     }
 
-    fn has_account<DB: Database>(&self, ctx: &Context<'_, '_, DB, SK>, addr: &AccAddress) -> bool {
+    fn has_account<DB: Database>(
+        &self,
+        ctx: &Context<'_, '_, DB, SK>,
+        addr: &AccAddress,
+    ) -> Result<bool, AppError> {
         let auth_store = ctx.get_kv_store(&self.store_key);
         let key = create_auth_store_key(addr.to_owned());
-        auth_store.get(&key).is_some()
+        Ok(auth_store.get(&key)?.is_some())
     }
 
     fn get_account<DB: Database>(
         &self,
         ctx: &Context<'_, '_, DB, SK>,
         addr: &AccAddress,
-    ) -> Option<Account> {
+    ) -> Result<Option<Account>, AppError> {
         let auth_store = ctx.get_kv_store(&self.store_key);
         let key = create_auth_store_key(addr.to_owned());
-        let account = auth_store.get(&key);
+        let account = auth_store.get(&key)?;
 
         if let Some(buf) = account {
             let account = Account::decode::<Bytes>(buf.to_owned().into())
                 .ok()
                 .unwrap_or_corrupt();
 
-            return Some(account);
+            return Ok(Some(account));
         }
 
-        None
+        Ok(None)
     }
 
     fn set_account<DB: Database>(&self, ctx: &mut Context<'_, '_, DB, SK>, acct: Account) {
@@ -94,28 +99,28 @@ impl<SK: StoreKey, PSK: ParamsSubspaceKey> Keeper<SK, PSK> {
         &self,
         ctx: &mut InitContext<'_, DB, SK>,
         genesis: GenesisState,
-    ) {
+    ) -> Result<(), AppError> {
         //TODO: sdk sanitizes accounts
         self.auth_params_keeper
             .set(&mut ctx.as_any(), genesis.params);
 
         for mut acct in genesis.accounts {
-            acct.account_number = self.get_next_account_number(&mut ctx.as_any());
+            acct.account_number = self.get_next_account_number(&mut ctx.as_any())?;
             self.set_account(&mut ctx.as_any(), Account::Base(acct));
         }
 
         // Create the fee collector account
-        self.check_create_new_module_account(&mut ctx.as_any(), &Module::FeeCollector);
+        self.check_create_new_module_account(&mut ctx.as_any(), &Module::FeeCollector)
     }
 
     pub fn query_account<DB: Database>(
         &self,
-        ctx: &QueryContext<'_, DB, SK>,
+        ctx: &QueryContext<DB, SK>,
         req: QueryAccountRequest,
     ) -> Result<QueryAccountResponse, AppError> {
         let auth_store = ctx.get_kv_store(&self.store_key);
         let key = create_auth_store_key(req.address);
-        let account = auth_store.get(&key);
+        let account = auth_store.get(&key)?;
 
         if let Some(buf) = account {
             let account = Account::decode::<Bytes>(buf.to_owned().into())
@@ -128,11 +133,32 @@ impl<SK: StoreKey, PSK: ParamsSubspaceKey> Keeper<SK, PSK> {
         Err(AppError::AccountNotFound)
     }
 
-    fn get_next_account_number<DB: Database>(&self, ctx: &mut Context<'_, '_, DB, SK>) -> u64 {
+    pub fn query_params<DB: Database>(
+        &self,
+        ctx: &QueryContext<DB, SK>,
+        _req: QueryParamsRequest,
+    ) -> Result<QueryParamsResponse, AppError> {
+        let params = self.auth_params_keeper.get(ctx)?;
+
+        Ok(QueryParamsResponse {
+            params: Params {
+                max_memo_characters: params.max_memo_characters,
+                tx_sig_limit: params.tx_sig_limit,
+                tx_size_cost_per_byte: params.tx_size_cost_per_byte,
+                sig_verify_cost_ed25519: params.sig_verify_cost_ed25519,
+                sig_verify_cost_secp256k1: params.sig_verify_cost_secp256k1,
+            },
+        })
+    }
+
+    fn get_next_account_number<DB: Database>(
+        &self,
+        ctx: &mut Context<'_, '_, DB, SK>,
+    ) -> Result<u64, AppError> {
         let auth_store = ctx.get_mutable_kv_store(&self.store_key);
 
         // NOTE: The next available account number is what's stored in the KV store
-        let acct_num = auth_store.get(&GLOBAL_ACCOUNT_NUMBER_KEY);
+        let acct_num = auth_store.get(&GLOBAL_ACCOUNT_NUMBER_KEY)?;
 
         let acct_num: u64 = match acct_num {
             None => 0, //initialize account numbers
@@ -144,7 +170,7 @@ impl<SK: StoreKey, PSK: ParamsSubspaceKey> Keeper<SK, PSK> {
         let next_acct_num = acct_num + 1;
         auth_store.set(GLOBAL_ACCOUNT_NUMBER_KEY, next_acct_num.encode_to_vec());
 
-        acct_num
+        Ok(acct_num)
     }
 
     pub fn set_account<DB: Database>(&self, ctx: &mut Context<'_, '_, DB, SK>, acct: Account) {
@@ -159,15 +185,17 @@ impl<SK: StoreKey, PSK: ParamsSubspaceKey> Keeper<SK, PSK> {
         &self,
         ctx: &mut Context<'_, '_, DB, SK>,
         addr: &AccAddress,
-    ) {
+    ) -> Result<(), AppError> {
         let acct = BaseAccount {
             address: addr.clone(),
             pub_key: None,
-            account_number: self.get_next_account_number(ctx),
+            account_number: self.get_next_account_number(ctx)?,
             sequence: 0,
         };
 
-        self.set_account(ctx, Account::Base(acct))
+        self.set_account(ctx, Account::Base(acct));
+
+        Ok(())
     }
 
     /// Creates a new module account if it doesn't already exist
@@ -175,16 +203,16 @@ impl<SK: StoreKey, PSK: ParamsSubspaceKey> Keeper<SK, PSK> {
         &self,
         ctx: &mut Context<'_, '_, DB, SK>,
         module: &Module,
-    ) {
+    ) -> Result<(), AppError> {
         let addr = module.get_address();
 
-        if self.has_account(ctx, &addr) {
+        if self.has_account(ctx, &addr)? {
         } else {
             let account = ModuleAccount {
                 base_account: BaseAccount {
                     address: addr.clone(),
                     pub_key: None,
-                    account_number: self.get_next_account_number(ctx),
+                    account_number: self.get_next_account_number(ctx)?,
                     sequence: 0,
                 },
                 name: module.get_name(),
@@ -193,6 +221,8 @@ impl<SK: StoreKey, PSK: ParamsSubspaceKey> Keeper<SK, PSK> {
 
             self.set_account(ctx, Account::Module(account))
         }
+
+        Ok(())
     }
 }
 
@@ -1,5 +1,6 @@
 use database::Database;
-use gears::types::context::context::Context;
+use gears::error::AppError;
+use gears::types::context::{context::Context, read_context::ReadContext};
 use gears::x::{auth::Params, params::ParamsSubspaceKey};
 //use params_module::ParamsSubspaceKey;
 // use proto_messages::utils::serialize_number_to_string;
@@ -69,40 +70,40 @@ impl<SK: StoreKey, PSK: ParamsSubspaceKey> AuthParamsKeeper<SK, PSK> {
             .expect("should be valid u64")
     }
 
-    fn get_raw_param<DB: Database>(key: &[u8], store: &ImmutablePrefixStore<'_, DB>) -> Vec<u8> {
-        store
-            .get(key)
-            .expect("key should be set in kv store")
-            .clone()
+    fn get_raw_param<DB: Database>(
+        key: &[u8],
+        store: &ImmutablePrefixStore<'_, DB>,
+    ) -> Result<Vec<u8>, AppError> {
+        Ok(store.get(key)?.expect("key should be set in kv store"))
     }
 
-    pub fn get<T: Database>(&self, ctx: &Context<'_, '_, T, SK>) -> Params {
+    pub fn get<T: Database>(&self, ctx: &impl ReadContext<SK, T>) -> Result<Params, AppError> {
         let store = self
             .params_keeper
             .get_raw_subspace(ctx, &self.params_subspace_key);
 
-        let raw = Self::get_raw_param(&KEY_MAX_MEMO_CHARACTERS, &store);
+        let raw = Self::get_raw_param(&KEY_MAX_MEMO_CHARACTERS, &store)?;
         let max_memo_characters = Self::parse_param(raw);
 
-        let raw = Self::get_raw_param(&KEY_TX_SIG_LIMIT, &store);
+        let raw = Self::get_raw_param(&KEY_TX_SIG_LIMIT, &store)?;
         let tx_sig_limit = Self::parse_param(raw);
 
-        let raw = Self::get_raw_param(&KEY_TX_SIZE_COST_PER_BYTE, &store);
+        let raw = Self::get_raw_param(&KEY_TX_SIZE_COST_PER_BYTE, &store)?;
         let tx_size_cost_per_byte = Self::parse_param(raw);
 
-        let raw = Self::get_raw_param(&KEY_SIG_VERIFY_COST_ED25519, &store);
+        let raw = Self::get_raw_param(&KEY_SIG_VERIFY_COST_ED25519, &store)?;
         let sig_verify_cost_ed25519 = Self::parse_param(raw);
 
-        let raw = Self::get_raw_param(&KEY_SIG_VERIFY_COST_SECP256K1, &store);
+        let raw = Self::get_raw_param(&KEY_SIG_VERIFY_COST_SECP256K1, &store)?;
         let sig_verify_cost_secp256k1 = Self::parse_param(raw);
 
-        Params {
+        Ok(Params {
             max_memo_characters,
             tx_sig_limit,
             tx_size_cost_per_byte,
             sig_verify_cost_ed25519,
             sig_verify_cost_secp256k1,
-        }
+        })
     }
 
     pub fn set<DB: Database>(&self, ctx: &mut Context<'_, '_, DB, SK>, params: Params) {
@@ -0,0 +1,324 @@
+//! Formatting of composite values into `Screen`s: nested messages, repeated fields and `Any`, as
+//! specified in https://docs.cosmos.network/v0.50/build/architecture/adr-050-sign-mode-textual-annex1#message
+//!
+//! The screens of a composite value are indented relative to its first screen, the message
+//! which holds the value shifts them under the field.
+
+use proto_messages::{
+    any::Any,
+    cosmos::tx::v1beta1::{
+        screen::{Content, Indent, Screen},
+        tx_metadata::Metadata,
+    },
+};
+use proto_types::Denom;
+
+use crate::signing::renderer::value_renderer::{
+    DefaultPrimitiveRenderer, Error, TryPrimitiveValueRenderer, ValueRenderer,
+};
+
+/// Screens of a message: a "`Name` object" header screen followed by the screens of its fields
+/// indented by one. Fields with default values aren't rendered so they should not be pushed.
+#[derive(Debug, Clone)]
+pub struct MessageScreens(Vec<Screen>);
+
+impl MessageScreens {
+    pub fn new(name: &str) -> Self {
+        Self(vec![untitled_screen(format!("{name} object"))])
+    }
+
+    /// Push a field which is rendered into a single screen
+    pub fn push_content(&mut self, title: &str, content: Content) {
+        self.0.push(Screen {
+            title: title.to_string(),
+            content,
+            indent: Some(Indent::one()),
+            expert: false,
+        });
+    }
+
+    /// Push a field which is rendered into several screens, the first screen is titled with
+    /// the field name
+    pub fn push_screens(&mut self, title: &str, mut screens: Vec<Screen>) -> Result<(), Error> {
+        let first = screens
+            .first_mut()
+            .ok_or_else(|| Error::Rendering(format!("empty rendering for {title}")))?;
+        first.title = title.to_string();
+
+        indent_screens(&mut screens, 1)?;
+        self.0.append(&mut screens);
+
+        Ok(())
+    }
+
+    pub fn into_screens(self) -> Vec<Screen> {
+        self.0
+    }
+
+    /// Screens of a message of a tx. The tx shows the type URL of the message in place of the
+    /// header and its fields are indented under the tx's "Message (i/n)" screen.
+    pub fn into_tx_message_screens(self) -> Result<Vec<Screen>, Error> {
+        let mut screens = self.0;
+        screens.remove(0);
+        indent_screens(&mut screens, 1)?;
+
+        Ok(screens)
+    }
+}
+
+/// Format a repeated field as a "`count` `type_name`" screen, the screens of every value, where
+/// the first is titled "`title` (i/count)" and shares the indent of the count screen, and a
+/// closing "End of `title`" screen
+pub fn format_repeated<T>(
+    title: &str,
+    type_name: &str,
+    values: &[T],
+    mut format: impl FnMut(&T) -> Result<Vec<Screen>, Error>,
+) -> Result<Vec<Screen>, Error> {
+    let count = values.len();
+    let mut screens = vec![untitled_screen(format!("{count} {type_name}"))];
+
+    for (i, value) in values.iter().enumerate() {
+        let mut value_screens = format(value)?;
+
+        let first = value_screens
+            .first_mut()
+            .ok_or_else(|| Error::Rendering(format!("empty rendering for {type_name}")))?;
+        first.title = format!("{title} ({}/{count})", i + 1);
+
+        screens.append(&mut value_screens);
+    }
+
+    screens.push(untitled_screen(format!("End of {title}")));
+
+    Ok(screens)
+}
+
+/// Format a message packed into an `Any`, which shows the type URL in place of the message
+/// header
+pub fn format_any(type_url: &str, mut message: Vec<Screen>) -> Result<Vec<Screen>, Error> {
+    let header = message
+        .first_mut()
+        .ok_or_else(|| Error::Rendering(format!("empty rendering for {type_url}")))?;
+    header.content = DefaultPrimitiveRenderer::try_format(type_url)?;
+
+    Ok(message)
+}
+
+/// Generic `Any` whose message type is unknown, so the message can't be decoded and it's
+/// rendered as its encoded bytes
+impl ValueRenderer for Any {
+    fn format<F: Fn(&Denom) -> Option<Metadata>>(
+        &self,
+        _get_metadata: &F,
+    ) -> Result<Vec<Screen>, Error> {
+        let mut message = MessageScreens::new("Any");
+        if let Ok(value) = DefaultPrimitiveRenderer::try_format(self.value.as_slice()) {
+            message.push_content("Value", value);
+        }
+
+        format_any(&self.type_url, message.into_screens())
+    }
+}
+
+/// Increase the indent of all screens
+pub fn indent_screens(screens: &mut [Screen], by: u8) -> Result<(), Error> {
+    for screen in screens {
+        let indent = screen.indent.map(Indent::into_inner).unwrap_or(0) + by;
+        screen.indent = Some(
+            Indent::new(indent)
+                .map_err(|_| Error::Rendering(format!("value is nested too deeply: {indent}")))?,
+        );
+    }
+
+    Ok(())
+}
+
+/// Screens of a value which is rendered into a single screen, e.g. an element of a repeated
+/// field of a primitive type
+pub fn content_screens(content: Content) -> Vec<Screen> {
+    vec![Screen {
+        title: String::new(),
+        content,
+        indent: None,
+        expert: false,
+    }]
+}
+
+fn untitled_screen(content: String) -> Screen {
+    Screen {
+        title: String::new(),
+        content: Content::new(content).expect("content is never empty"),
+        indent: None,
+        expert: false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Deserializer};
+
+    use crate::signing::renderer::{
+        test_functions::{get_metadata, test_vectors, ScreensTestCase},
+        value_renderer::PrimitiveValueRenderer,
+    };
+
+    use super::*;
+
+    /// Mirrors the `Foo`/`Bar` messages of the Cosmos SDK textual test protos, deserialized from
+    /// their JSON form
+    #[derive(Default, Deserialize)]
+    #[serde(default, rename_all = "camelCase")]
+    struct Bar {
+        bar_id: String,
+        #[serde(deserialize_with = "deserialize_base64")]
+        data: Vec<u8>,
+    }
+
+    #[derive(Default, Deserialize)]
+    #[serde(default, rename_all = "camelCase")]
+    struct Foo {
+        full_name: String,
+        nested: Option<Bar>,
+        bars: Vec<Bar>,
+        numbers: Vec<u64>,
+    }
+
+    /// `Foo` packed into an `Any`
+    #[derive(Deserialize)]
+    struct AnyFoo {
+        #[serde(rename = "@type")]
+        type_url: String,
+        #[serde(flatten)]
+        foo: Foo,
+    }
+
+    fn deserialize_base64<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        let encoded = String::deserialize(deserializer)?;
+
+        data_encoding::BASE64
+            .decode(encoded.as_bytes())
+            .map_err(serde::de::Error::custom)
+    }
+
+    fn format_bar(bar: &Bar) -> Result<Vec<Screen>, Error> {
+        let mut message = MessageScreens::new("Bar");
+        if let Ok(bar_id) = DefaultPrimitiveRenderer::try_format(bar.bar_id.as_str()) {
+            message.push_content("Bar id", bar_id);
+        }
+        if let Ok(data) = DefaultPrimitiveRenderer::try_format(bar.data.as_slice()) {
+            message.push_content("Data", data);
+        }
+
+        Ok(message.into_screens())
+    }
+
+    fn format_foo(foo: &Foo) -> Result<MessageScreens, Error> {
+        let mut message = MessageScreens::new("Foo");
+        if let Ok(full_name) = DefaultPrimitiveRenderer::try_format(foo.full_name.as_str()) {
+            message.push_content("Full name", full_name);
+        }
+        if let Some(nested) = &foo.nested {
+            message.push_screens("Nested", format_bar(nested)?)?;
+        }
+        if !foo.bars.is_empty() {
+            message.push_screens(
+                "Bars",
+                format_repeated("Bars", "Bar", &foo.bars, format_bar)?,
+            )?;
+        }
+        if !foo.numbers.is_empty() {
+            message.push_screens(
+                "Numbers",
+                format_repeated("Numbers", "uint64", &foo.numbers, |number| {
+                    Ok(content_screens(DefaultPrimitiveRenderer::format(*number)))
+                })?,
+            )?;
+        }
+
+        Ok(message)
+    }
+
+    #[test]
+    fn message_format() {
+        let test_data: Vec<ScreensTestCase<Foo>> = test_vectors("message.json");
+
+        for case in test_data {
+            let actual_screens = format_foo(&case.proto).expect("message is valid");
+
+            assert_eq!(case.screens, actual_screens.into_screens());
+        }
+    }
+
+    #[test]
+    fn repeated_format() {
+        let test_data: Vec<ScreensTestCase<Foo>> = test_vectors("repeated.json");
+
+        for case in test_data {
+            let actual_screens = format_foo(&case.proto).expect("message is valid");
+
+            assert_eq!(case.screens, actual_screens.into_screens());
+        }
+    }
+
+    #[test]
+    fn any_format() {
+        let test_data: Vec<ScreensTestCase<AnyFoo>> = test_vectors("any.json");
+
+        for case in test_data {
+            let actual_screens = format_foo(&case.proto.foo)
+                .and_then(|message| format_any(&case.proto.type_url, message.into_screens()))
+                .expect("message is valid");
+
+            assert_eq!(case.screens, actual_screens);
+        }
+    }
+
+    #[test]
+    fn generic_any_format() -> anyhow::Result<()> {
+        const SCREENS: &str = r#"[
+            { "content": "/ibc.lightclients.tendermint.v1.Header" },
+            { "title": "Value", "content": "0A02 0801", "indent": 1 }
+        ]"#;
+
+        let expected_screens: Vec<Screen> = serde_json::from_str(SCREENS)?;
+
+        let any = Any {
+            type_url: "/ibc.lightclients.tendermint.v1.Header".to_string(),
+            value: vec![0x0a, 0x02, 0x08, 0x01],
+        };
+        let actual_screens = ValueRenderer::format(&any, &get_metadata)
+            .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        assert_eq!(expected_screens, actual_screens);
+
+        Ok(())
+    }
+
+    #[test]
+    fn tx_message_format() -> anyhow::Result<()> {
+        const SCREENS: &str = r#"[
+            { "title": "Full name", "content": "testing", "indent": 2 },
+            { "title": "Nested", "content": "Bar object", "indent": 2 },
+            { "title": "Bar id", "content": "nested", "indent": 3 }
+        ]"#;
+
+        let expected_screens: Vec<Screen> = serde_json::from_str(SCREENS)?;
+
+        let foo = Foo {
+            full_name: "testing".to_string(),
+            nested: Some(Bar {
+                bar_id: "nested".to_string(),
+                data: vec![],
+            }),
+            ..Default::default()
+        };
+        let actual_screens = format_foo(&foo)
+            .and_then(MessageScreens::into_tx_message_screens)
+            .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        assert_eq!(expected_screens, actual_screens);
+
+        Ok(())
+    }
+}
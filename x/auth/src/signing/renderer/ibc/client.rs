@@ -0,0 +1,293 @@
+//! Formatting of IBC client types and the Tendermint light client states
+
+use proto_messages::{
+    cosmos::tx::v1beta1::{screen::Screen, tx_metadata::Metadata},
+    ibc_proto::ibc::{
+        core::{client::v1::Height, commitment::v1::MerkleRoot},
+        lightclients::tendermint::v1::{ClientState, ConsensusState, Fraction},
+    },
+};
+use proto_types::Denom;
+
+use crate::signing::renderer::{
+    composite::{content_screens, format_repeated, MessageScreens},
+    value_renderer::{
+        DefaultPrimitiveRenderer, Error, PrimitiveValueRenderer, TryPrimitiveValueRenderer,
+        ValueRenderer,
+    },
+};
+
+pub const TENDERMINT_CLIENT_STATE_TYPE_URL: &str = "/ibc.lightclients.tendermint.v1.ClientState";
+pub const TENDERMINT_CONSENSUS_STATE_TYPE_URL: &str =
+    "/ibc.lightclients.tendermint.v1.ConsensusState";
+
+impl ValueRenderer for Height {
+    fn format<F: Fn(&Denom) -> Option<Metadata>>(
+        &self,
+        _get_metadata: &F,
+    ) -> Result<Vec<Screen>, Error> {
+        let mut message = MessageScreens::new("Height");
+
+        if self.revision_number != 0 {
+            message.push_content(
+                "Revision number",
+                DefaultPrimitiveRenderer::format(self.revision_number),
+            );
+        }
+        if self.revision_height != 0 {
+            message.push_content(
+                "Revision height",
+                DefaultPrimitiveRenderer::format(self.revision_height),
+            );
+        }
+
+        Ok(message.into_screens())
+    }
+}
+
+impl ValueRenderer for Fraction {
+    fn format<F: Fn(&Denom) -> Option<Metadata>>(
+        &self,
+        _get_metadata: &F,
+    ) -> Result<Vec<Screen>, Error> {
+        let mut message = MessageScreens::new("Fraction");
+
+        if self.numerator != 0 {
+            message.push_content(
+                "Numerator",
+                DefaultPrimitiveRenderer::format(self.numerator),
+            );
+        }
+        if self.denominator != 0 {
+            message.push_content(
+                "Denominator",
+                DefaultPrimitiveRenderer::format(self.denominator),
+            );
+        }
+
+        Ok(message.into_screens())
+    }
+}
+
+impl ValueRenderer for MerkleRoot {
+    fn format<F: Fn(&Denom) -> Option<Metadata>>(
+        &self,
+        _get_metadata: &F,
+    ) -> Result<Vec<Screen>, Error> {
+        let mut message = MessageScreens::new("MerkleRoot");
+
+        if let Ok(hash) = DefaultPrimitiveRenderer::try_format(self.hash.as_slice()) {
+            message.push_content("Hash", hash);
+        }
+
+        Ok(message.into_screens())
+    }
+}
+
+impl ValueRenderer for ClientState {
+    // the allow update fields are deprecated but they're still part of the signed message
+    #[allow(deprecated)]
+    fn format<F: Fn(&Denom) -> Option<Metadata>>(
+        &self,
+        get_metadata: &F,
+    ) -> Result<Vec<Screen>, Error> {
+        let mut message = MessageScreens::new("ClientState");
+
+        if let Ok(chain_id) = DefaultPrimitiveRenderer::try_format(self.chain_id.as_str()) {
+            message.push_content("Chain id", chain_id);
+        }
+        if let Some(trust_level) = &self.trust_level {
+            message.push_screens(
+                "Trust level",
+                ValueRenderer::format(trust_level, get_metadata)?,
+            )?;
+        }
+        for (title, duration) in [
+            ("Trusting period", &self.trusting_period),
+            ("Unbonding period", &self.unbonding_period),
+            ("Max clock drift", &self.max_clock_drift),
+        ] {
+            if let Some(duration) = duration {
+                message.push_content(title, DefaultPrimitiveRenderer::try_format(duration)?);
+            }
+        }
+        for (title, height) in [
+            ("Frozen height", &self.frozen_height),
+            ("Latest height", &self.latest_height),
+        ] {
+            if let Some(height) = height {
+                message.push_screens(title, ValueRenderer::format(height, get_metadata)?)?;
+            }
+        }
+        if !self.proof_specs.is_empty() {
+            message.push_screens(
+                "Proof specs",
+                format_repeated("Proof specs", "ProofSpec", &self.proof_specs, |spec| {
+                    ValueRenderer::format(spec, get_metadata)
+                })?,
+            )?;
+        }
+        if !self.upgrade_path.is_empty() {
+            message.push_screens(
+                "Upgrade path",
+                format_repeated("Upgrade path", "string", &self.upgrade_path, |path| {
+                    Ok(content_screens(DefaultPrimitiveRenderer::try_format(
+                        path.as_str(),
+                    )?))
+                })?,
+            )?;
+        }
+        if self.allow_update_after_expiry {
+            message.push_content(
+                "Allow update after expiry",
+                DefaultPrimitiveRenderer::format(true),
+            );
+        }
+        if self.allow_update_after_misbehaviour {
+            message.push_content(
+                "Allow update after misbehaviour",
+                DefaultPrimitiveRenderer::format(true),
+            );
+        }
+
+        Ok(message.into_screens())
+    }
+}
+
+impl ValueRenderer for ConsensusState {
+    fn format<F: Fn(&Denom) -> Option<Metadata>>(
+        &self,
+        get_metadata: &F,
+    ) -> Result<Vec<Screen>, Error> {
+        let mut message = MessageScreens::new("ConsensusState");
+
+        if let Some(timestamp) = &self.timestamp {
+            message.push_content(
+                "Timestamp",
+                DefaultPrimitiveRenderer::try_format(timestamp)?,
+            );
+        }
+        if let Some(root) = &self.root {
+            message.push_screens("Root", ValueRenderer::format(root, get_metadata)?)?;
+        }
+        if let Ok(hash) = DefaultPrimitiveRenderer::try_format(self.next_validators_hash.as_slice())
+        {
+            message.push_content("Next validators hash", hash);
+        }
+
+        Ok(message.into_screens())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proto_messages::ibc_proto::{
+        google::protobuf::{Duration, Timestamp},
+        ics23::{HashOp, InnerSpec, ProofSpec},
+    };
+
+    use crate::signing::renderer::test_functions::get_metadata;
+
+    use super::*;
+
+    #[test]
+    fn client_state_format() -> anyhow::Result<()> {
+        let client_state = ClientState {
+            chain_id: "testchain-1".to_string(),
+            trust_level: Some(Fraction {
+                numerator: 1,
+                denominator: 3,
+            }),
+            trusting_period: Some(Duration {
+                seconds: 1_209_600,
+                nanos: 0,
+            }),
+            unbonding_period: Some(Duration {
+                seconds: 1_814_400,
+                nanos: 0,
+            }),
+            max_clock_drift: Some(Duration {
+                seconds: 10,
+                nanos: 0,
+            }),
+            frozen_height: Some(Height::default()),
+            latest_height: Some(Height {
+                revision_number: 1,
+                revision_height: 10_000,
+            }),
+            proof_specs: vec![ProofSpec {
+                inner_spec: Some(InnerSpec {
+                    hash: HashOp::Sha256 as i32,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }],
+            upgrade_path: vec!["upgrade".to_string(), "upgradedIBCState".to_string()],
+            ..Default::default()
+        };
+
+        const SCREENS: &str = r#"[
+            { "content": "ClientState object" },
+            { "title": "Chain id", "content": "testchain-1", "indent": 1 },
+            { "title": "Trust level", "content": "Fraction object", "indent": 1 },
+            { "title": "Numerator", "content": "1", "indent": 2 },
+            { "title": "Denominator", "content": "3", "indent": 2 },
+            { "title": "Trusting period", "content": "14 days", "indent": 1 },
+            { "title": "Unbonding period", "content": "21 days", "indent": 1 },
+            { "title": "Max clock drift", "content": "10 seconds", "indent": 1 },
+            { "title": "Frozen height", "content": "Height object", "indent": 1 },
+            { "title": "Latest height", "content": "Height object", "indent": 1 },
+            { "title": "Revision number", "content": "1", "indent": 2 },
+            { "title": "Revision height", "content": "10'000", "indent": 2 },
+            { "title": "Proof specs", "content": "1 ProofSpec", "indent": 1 },
+            { "title": "Proof specs (1/1)", "content": "ProofSpec object", "indent": 1 },
+            { "title": "Inner spec", "content": "InnerSpec object", "indent": 2 },
+            { "title": "Hash", "content": "SHA256", "indent": 3 },
+            { "content": "End of Proof specs", "indent": 1 },
+            { "title": "Upgrade path", "content": "2 string", "indent": 1 },
+            { "title": "Upgrade path (1/2)", "content": "upgrade", "indent": 1 },
+            { "title": "Upgrade path (2/2)", "content": "upgradedIBCState", "indent": 1 },
+            { "content": "End of Upgrade path", "indent": 1 }
+        ]"#;
+
+        let expected_screens: Vec<Screen> = serde_json::from_str(SCREENS)?;
+
+        let actual_screens = ValueRenderer::format(&client_state, &get_metadata)
+            .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        assert_eq!(expected_screens, actual_screens);
+
+        Ok(())
+    }
+
+    #[test]
+    fn consensus_state_format() -> anyhow::Result<()> {
+        let consensus_state = ConsensusState {
+            timestamp: Some(Timestamp {
+                seconds: 1_658_238_602,
+                nanos: 123_000_000,
+            }),
+            root: Some(MerkleRoot {
+                hash: vec![0xab, 0xcd],
+            }),
+            next_validators_hash: vec![0x01, 0x02, 0x03],
+        };
+
+        const SCREENS: &str = r#"[
+            { "content": "ConsensusState object" },
+            { "title": "Timestamp", "content": "2022-07-19T13:50:02.123Z", "indent": 1 },
+            { "title": "Root", "content": "MerkleRoot object", "indent": 1 },
+            { "title": "Hash", "content": "ABCD", "indent": 2 },
+            { "title": "Next validators hash", "content": "0102 03", "indent": 1 }
+        ]"#;
+
+        let expected_screens: Vec<Screen> = serde_json::from_str(SCREENS)?;
+
+        let actual_screens = ValueRenderer::format(&consensus_state, &get_metadata)
+            .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        assert_eq!(expected_screens, actual_screens);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,209 @@
+//! Formatting of the ICS 23 proof specs of IBC client states
+
+use proto_messages::{
+    cosmos::tx::v1beta1::{screen::Screen, tx_metadata::Metadata},
+    ibc_proto::ics23::{HashOp, InnerSpec, LeafOp, LengthOp, ProofSpec},
+};
+use proto_types::Denom;
+
+use crate::signing::renderer::{
+    composite::{content_screens, format_repeated, MessageScreens},
+    primitives::enumeration::{try_format_enum, ProtoEnum},
+    value_renderer::{
+        DefaultPrimitiveRenderer, Error, PrimitiveValueRenderer, TryPrimitiveValueRenderer,
+        ValueRenderer,
+    },
+};
+
+impl ProtoEnum for HashOp {
+    const NAME: &'static str = "cosmos.ics23.v1.HashOp";
+
+    fn value_name(&self) -> &'static str {
+        self.as_str_name()
+    }
+}
+
+impl ProtoEnum for LengthOp {
+    const NAME: &'static str = "cosmos.ics23.v1.LengthOp";
+
+    fn value_name(&self) -> &'static str {
+        self.as_str_name()
+    }
+}
+
+impl ValueRenderer for ProofSpec {
+    fn format<F: Fn(&Denom) -> Option<Metadata>>(
+        &self,
+        get_metadata: &F,
+    ) -> Result<Vec<Screen>, Error> {
+        let mut message = MessageScreens::new("ProofSpec");
+
+        if let Some(leaf_spec) = &self.leaf_spec {
+            message.push_screens("Leaf spec", ValueRenderer::format(leaf_spec, get_metadata)?)?;
+        }
+        if let Some(inner_spec) = &self.inner_spec {
+            message.push_screens(
+                "Inner spec",
+                ValueRenderer::format(inner_spec, get_metadata)?,
+            )?;
+        }
+        if self.max_depth != 0 {
+            message.push_content(
+                "Max depth",
+                DefaultPrimitiveRenderer::format(i64::from(self.max_depth)),
+            );
+        }
+        if self.min_depth != 0 {
+            message.push_content(
+                "Min depth",
+                DefaultPrimitiveRenderer::format(i64::from(self.min_depth)),
+            );
+        }
+        if self.prehash_key_before_comparison {
+            message.push_content(
+                "Prehash key before comparison",
+                DefaultPrimitiveRenderer::format(true),
+            );
+        }
+
+        Ok(message.into_screens())
+    }
+}
+
+impl ValueRenderer for LeafOp {
+    fn format<F: Fn(&Denom) -> Option<Metadata>>(
+        &self,
+        _get_metadata: &F,
+    ) -> Result<Vec<Screen>, Error> {
+        let mut message = MessageScreens::new("LeafOp");
+
+        for (title, hash_op) in [
+            ("Hash", self.hash),
+            ("Prehash key", self.prehash_key),
+            ("Prehash value", self.prehash_value),
+        ] {
+            if hash_op != 0 {
+                message.push_content(title, try_format_enum::<HashOp>(hash_op)?);
+            }
+        }
+        if self.length != 0 {
+            message.push_content("Length", try_format_enum::<LengthOp>(self.length)?);
+        }
+        if let Ok(prefix) = DefaultPrimitiveRenderer::try_format(self.prefix.as_slice()) {
+            message.push_content("Prefix", prefix);
+        }
+
+        Ok(message.into_screens())
+    }
+}
+
+impl ValueRenderer for InnerSpec {
+    fn format<F: Fn(&Denom) -> Option<Metadata>>(
+        &self,
+        _get_metadata: &F,
+    ) -> Result<Vec<Screen>, Error> {
+        let mut message = MessageScreens::new("InnerSpec");
+
+        if !self.child_order.is_empty() {
+            message.push_screens(
+                "Child order",
+                format_repeated("Child order", "int32", &self.child_order, |child| {
+                    Ok(content_screens(DefaultPrimitiveRenderer::format(
+                        i64::from(*child),
+                    )))
+                })?,
+            )?;
+        }
+        for (title, value) in [
+            ("Child size", self.child_size),
+            ("Min prefix length", self.min_prefix_length),
+            ("Max prefix length", self.max_prefix_length),
+        ] {
+            if value != 0 {
+                message.push_content(title, DefaultPrimitiveRenderer::format(i64::from(value)));
+            }
+        }
+        if let Ok(empty_child) = DefaultPrimitiveRenderer::try_format(self.empty_child.as_slice()) {
+            message.push_content("Empty child", empty_child);
+        }
+        if self.hash != 0 {
+            message.push_content("Hash", try_format_enum::<HashOp>(self.hash)?);
+        }
+
+        Ok(message.into_screens())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::signing::renderer::test_functions::get_metadata;
+
+    use super::*;
+
+    /// The proof spec of IAVL trees
+    fn iavl_spec() -> ProofSpec {
+        ProofSpec {
+            leaf_spec: Some(LeafOp {
+                hash: HashOp::Sha256 as i32,
+                prehash_key: HashOp::NoHash as i32,
+                prehash_value: HashOp::Sha256 as i32,
+                length: LengthOp::VarProto as i32,
+                prefix: vec![0],
+            }),
+            inner_spec: Some(InnerSpec {
+                child_order: vec![0, 1],
+                child_size: 33,
+                min_prefix_length: 4,
+                max_prefix_length: 12,
+                empty_child: vec![],
+                hash: HashOp::Sha256 as i32,
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn proof_spec_format() -> anyhow::Result<()> {
+        const SCREENS: &str = r#"[
+            { "content": "ProofSpec object" },
+            { "title": "Leaf spec", "content": "LeafOp object", "indent": 1 },
+            { "title": "Hash", "content": "SHA256", "indent": 2 },
+            { "title": "Prehash value", "content": "SHA256", "indent": 2 },
+            { "title": "Length", "content": "VAR_PROTO", "indent": 2 },
+            { "title": "Prefix", "content": "00", "indent": 2 },
+            { "title": "Inner spec", "content": "InnerSpec object", "indent": 1 },
+            { "title": "Child order", "content": "2 int32", "indent": 2 },
+            { "title": "Child order (1/2)", "content": "0", "indent": 2 },
+            { "title": "Child order (2/2)", "content": "1", "indent": 2 },
+            { "content": "End of Child order", "indent": 2 },
+            { "title": "Child size", "content": "33", "indent": 2 },
+            { "title": "Min prefix length", "content": "4", "indent": 2 },
+            { "title": "Max prefix length", "content": "12", "indent": 2 },
+            { "title": "Hash", "content": "SHA256", "indent": 2 }
+        ]"#;
+
+        let expected_screens: Vec<Screen> = serde_json::from_str(SCREENS)?;
+
+        let actual_screens = ValueRenderer::format(&iavl_spec(), &get_metadata)
+            .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        assert_eq!(expected_screens, actual_screens);
+
+        Ok(())
+    }
+
+    #[test]
+    fn leaf_op_format_unknown_hash_op() {
+        let leaf_op = LeafOp {
+            hash: 100,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            ValueRenderer::format(&leaf_op, &get_metadata),
+            Err(Error::Rendering(
+                "cannot get enum cosmos.ics23.v1.HashOp variant of number 100".to_string()
+            ))
+        );
+    }
+}
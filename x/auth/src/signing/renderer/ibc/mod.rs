@@ -0,0 +1,4 @@
+//! Implementation of formatting IBC types, which are nested in the IBC messages, into `Screen`
+
+pub mod client;
+pub mod ics23;
@@ -0,0 +1,223 @@
+use proto_messages::{
+    cosmos::{
+        ibc::{
+            tx::{MsgCreateClient, MsgRecoverClient, MsgUpdateClient, MsgUpgradeClient},
+            types::primitives::Signer,
+        },
+        tx::v1beta1::{screen::Screen, tx_metadata::Metadata},
+    },
+    ibc_proto::ibc::lightclients::tendermint::v1::{ClientState, ConsensusState},
+};
+use proto_types::Denom;
+
+use crate::signing::renderer::{
+    composite::{format_any, MessageScreens},
+    ibc::client::{TENDERMINT_CLIENT_STATE_TYPE_URL, TENDERMINT_CONSENSUS_STATE_TYPE_URL},
+    value_renderer::{DefaultPrimitiveRenderer, Error, TryPrimitiveValueRenderer, ValueRenderer},
+};
+
+impl ValueRenderer for MsgCreateClient {
+    /// Format `MsgCreateClient`
+    /// Note: This implementation doesn't include `Screen` with information about beginning of message and name
+    fn format<F: Fn(&Denom) -> Option<Metadata>>(
+        &self,
+        get_metadata: &F,
+    ) -> Result<Vec<Screen>, Error> {
+        let mut message = MessageScreens::new("MsgCreateClient");
+
+        let client_state: ClientState = self.client_state.clone().into();
+        message.push_screens(
+            "Client state",
+            format_any(
+                TENDERMINT_CLIENT_STATE_TYPE_URL,
+                ValueRenderer::format(&client_state, get_metadata)?,
+            )?,
+        )?;
+
+        let consensus_state: ConsensusState = self.consensus_state.clone().into();
+        message.push_screens(
+            "Consensus state",
+            format_any(
+                TENDERMINT_CONSENSUS_STATE_TYPE_URL,
+                ValueRenderer::format(&consensus_state, get_metadata)?,
+            )?,
+        )?;
+
+        push_signer(&mut message, &self.signer);
+
+        message.into_tx_message_screens()
+    }
+}
+
+impl ValueRenderer for MsgUpdateClient {
+    /// Format `MsgUpdateClient`
+    /// Note: This implementation doesn't include `Screen` with information about beginning of message and name
+    fn format<F: Fn(&Denom) -> Option<Metadata>>(
+        &self,
+        get_metadata: &F,
+    ) -> Result<Vec<Screen>, Error> {
+        let mut message = MessageScreens::new("MsgUpdateClient");
+
+        message.push_content(
+            "Client id",
+            DefaultPrimitiveRenderer::try_format(self.client_id.as_str())?,
+        );
+        message.push_screens(
+            "Client message",
+            ValueRenderer::format(&self.client_message, get_metadata)?,
+        )?;
+        push_signer(&mut message, &self.signer);
+
+        message.into_tx_message_screens()
+    }
+}
+
+impl ValueRenderer for MsgUpgradeClient {
+    /// Format `MsgUpgradeClient`
+    /// Note: This implementation doesn't include `Screen` with information about beginning of message and name
+    fn format<F: Fn(&Denom) -> Option<Metadata>>(
+        &self,
+        get_metadata: &F,
+    ) -> Result<Vec<Screen>, Error> {
+        let mut message = MessageScreens::new("MsgUpgradeClient");
+
+        message.push_content(
+            "Client id",
+            DefaultPrimitiveRenderer::try_format(self.client_id.as_str())?,
+        );
+
+        let client_state: ClientState = self.upgraded_client_state.clone().into();
+        message.push_screens(
+            "Client state",
+            format_any(
+                TENDERMINT_CLIENT_STATE_TYPE_URL,
+                ValueRenderer::format(&client_state, get_metadata)?,
+            )?,
+        )?;
+
+        let consensus_state: ConsensusState = self.upgraded_consensus_state.clone().into();
+        message.push_screens(
+            "Consensus state",
+            format_any(
+                TENDERMINT_CONSENSUS_STATE_TYPE_URL,
+                ValueRenderer::format(&consensus_state, get_metadata)?,
+            )?,
+        )?;
+
+        for (title, proof) in [
+            ("Proof upgrade client", &self.proof_upgrade_client),
+            (
+                "Proof upgrade consensus state",
+                &self.proof_upgrade_consensus_state,
+            ),
+        ] {
+            let proof: Vec<u8> = proof.clone().into();
+            message.push_content(
+                title,
+                DefaultPrimitiveRenderer::try_format(proof.as_slice())?,
+            );
+        }
+
+        push_signer(&mut message, &self.signer);
+
+        message.into_tx_message_screens()
+    }
+}
+
+impl ValueRenderer for MsgRecoverClient {
+    /// Format `MsgRecoverClient`
+    /// Note: This implementation doesn't include `Screen` with information about beginning of message and name
+    fn format<F: Fn(&Denom) -> Option<Metadata>>(
+        &self,
+        _get_metadata: &F,
+    ) -> Result<Vec<Screen>, Error> {
+        let mut message = MessageScreens::new("MsgRecoverClient");
+
+        message.push_content(
+            "Subject client id",
+            DefaultPrimitiveRenderer::try_format(self.subject_client_id.as_str())?,
+        );
+        message.push_content(
+            "Substitute client id",
+            DefaultPrimitiveRenderer::try_format(self.substitute_client_id.as_str())?,
+        );
+        push_signer(&mut message, &self.signer);
+
+        message.into_tx_message_screens()
+    }
+}
+
+/// The signer isn't validated so it's only rendered when it's set
+fn push_signer(message: &mut MessageScreens, signer: &Signer) {
+    let signer: &str = signer.as_ref();
+
+    if let Ok(signer) = DefaultPrimitiveRenderer::try_format(signer) {
+        message.push_content("Signer", signer);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use proto_messages::{
+        any::Any,
+        cosmos::ibc::{tx::RawProtoMsgRecoverClient, types::core::host::identifiers::ClientId},
+    };
+
+    use crate::signing::renderer::test_functions::get_metadata;
+
+    use super::*;
+
+    #[test]
+    fn msg_recover_client_works() -> anyhow::Result<()> {
+        let msg = MsgRecoverClient::try_from(RawProtoMsgRecoverClient {
+            subject_client_id: "07-tendermint-0".to_string(),
+            substitute_client_id: "07-tendermint-1".to_string(),
+            signer: "cosmos1ulav3hsenupswqfkw2y3sup5kgtqwnvqa8eyhs".to_string(),
+        })?;
+
+        const SCREENS: &str = r#"[
+            { "title": "Subject client id", "content": "07-tendermint-0", "indent": 2 },
+            { "title": "Substitute client id", "content": "07-tendermint-1", "indent": 2 },
+            { "title": "Signer", "content": "cosmos1ulav3hsenupswqfkw2y3sup5kgtqwnvqa8eyhs", "indent": 2 }
+        ]"#;
+
+        let expected_screens: Vec<Screen> = serde_json::from_str(SCREENS)?;
+
+        let actual_screens = ValueRenderer::format(&msg, &get_metadata)
+            .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        assert_eq!(expected_screens, actual_screens);
+
+        Ok(())
+    }
+
+    #[test]
+    fn msg_update_client_works() -> anyhow::Result<()> {
+        let msg = MsgUpdateClient {
+            client_id: ClientId::from_str("07-tendermint-0")?,
+            client_message: Any {
+                type_url: "/ibc.lightclients.tendermint.v1.Header".to_string(),
+                value: vec![0x0a, 0x02, 0x08, 0x01],
+            },
+            signer: Signer::from("cosmos1ulav3hsenupswqfkw2y3sup5kgtqwnvqa8eyhs".to_string()),
+        };
+
+        const SCREENS: &str = r#"[
+            { "title": "Client id", "content": "07-tendermint-0", "indent": 2 },
+            { "title": "Client message", "content": "/ibc.lightclients.tendermint.v1.Header", "indent": 2 },
+            { "title": "Value", "content": "0A02 0801", "indent": 3 },
+            { "title": "Signer", "content": "cosmos1ulav3hsenupswqfkw2y3sup5kgtqwnvqa8eyhs", "indent": 2 }
+        ]"#;
+
+        let expected_screens: Vec<Screen> = serde_json::from_str(SCREENS)?;
+
+        let actual_screens = ValueRenderer::format(&msg, &get_metadata)
+            .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        assert_eq!(expected_screens, actual_screens);
+
+        Ok(())
+    }
+}
@@ -1,3 +1,4 @@
 //! Implementation of formatting messages into `Screen`
 
+pub mod ibc_client;
 pub mod msg_send;
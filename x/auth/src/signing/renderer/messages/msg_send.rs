@@ -1,60 +1,53 @@
-use proto_messages::cosmos::{
-    bank::v1beta1::MsgSend,
-    tx::v1beta1::{
-        screen::{Indent, Screen},
-        tx_metadata::Metadata,
-    },
-};
-use proto_types::Denom;
-
-use crate::signing::renderer::value_renderer::{
-    DefaultPrimitiveRenderer, Error, PrimitiveValueRenderer, TryPrimitiveValueRendererWithMetadata,
-    ValueRenderer,
-};
-
-impl ValueRenderer for MsgSend {
-    /// Format `MsgSend`
-    /// Note: This implementation doesn't include `Screen` with information about beginning of message and name
-    fn format<F: Fn(&Denom) -> Option<Metadata>>(
-        &self,
-        get_metadata: &F,
-    ) -> Result<Vec<Screen>, Error> {
-        let mut screens_vec = Vec::new();
-
-        screens_vec.push(Screen {
-            title: "From address".to_string(),
-            content: DefaultPrimitiveRenderer::format(self.from_address.clone()),
-            indent: Some(Indent::two()),
-            expert: false,
-        });
-
-        screens_vec.push(Screen {
-            title: "To address".to_string(),
-            content: DefaultPrimitiveRenderer::format(self.to_address.clone()),
-            indent: Some(Indent::two()),
-            expert: false,
-        });
-
-        screens_vec.push(Screen {
-            title: "Amount".to_string(),
-            content: DefaultPrimitiveRenderer::try_format_with_metadata(
-                self.amount.to_owned(),
-                get_metadata,
-            )?,
-            indent: Some(Indent::two()),
-            expert: false,
-        });
-
-        Ok(screens_vec)
-    }
+use gears_derive::ValueRenderer;
+use proto_messages::cosmos::{bank::v1beta1::MsgSend, base::v1beta1::SendCoins};
+use proto_types::AccAddress;
+
+/// Renders `MsgSend` under the tx's "Message (i/n)" screen, so there's no screen with the
+/// beginning of the message and its name
+#[derive(ValueRenderer)]
+#[renderer(crate = "crate", remote = "MsgSend")]
+#[allow(dead_code)]
+struct MsgSendRenderer {
+    from_address: AccAddress,
+    to_address: AccAddress,
+    #[renderer(with_metadata)]
+    amount: SendCoins,
 }
 
 #[cfg(test)]
 mod tests {
-    use proto_messages::cosmos::{bank::v1beta1::MsgSend, tx::v1beta1::screen::Screen};
+    use gears_derive::ValueRenderer;
+    use proto_messages::cosmos::{
+        bank::v1beta1::MsgSend, base::v1beta1::SendCoins, tx::v1beta1::screen::Screen,
+    };
+    use proto_types::AccAddress;
 
     use crate::signing::renderer::{test_functions::get_metadata, value_renderer::ValueRenderer};
 
+    #[derive(ValueRenderer)]
+    #[renderer(crate = "crate", type_url = "/cosmos.bank.v1beta1.MsgSend", indent = 1)]
+    struct AnyMsgSend {
+        from_address: AccAddress,
+        #[renderer(with_metadata)]
+        amount: SendCoins,
+    }
+
+    #[derive(ValueRenderer)]
+    #[renderer(crate = "crate")]
+    struct AttributesMessage {
+        #[renderer(title = "Sender")]
+        from_address: AccAddress,
+        #[renderer(skip)]
+        #[allow(dead_code)]
+        to_address: AccAddress,
+        #[renderer(try_format)]
+        memo: String,
+        #[renderer(expert)]
+        sequence: u64,
+        #[renderer(message)]
+        message: AnyMsgSend,
+    }
+
     #[test]
     fn msg_send_multiple_coins() -> anyhow::Result<()> {
         const MESSAGE: &str = r#"{
@@ -106,4 +99,76 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn derived_renderer_attributes() -> anyhow::Result<()> {
+        let from_address =
+            AccAddress::from_bech32("cosmos1ulav3hsenupswqfkw2y3sup5kgtqwnvqa8eyhs")?;
+        let amount: SendCoins =
+            serde_json::from_str(r#"[{ "denom": "uatom", "amount": "10000000" }]"#)?;
+
+        let msg = AttributesMessage {
+            from_address: from_address.clone(),
+            to_address: AccAddress::from_bech32("cosmos1ejrf4cur2wy6kfurg9f2jppp2h3afe5h6pkh5t")?,
+            memo: "hello".to_string(),
+            sequence: 5,
+            message: AnyMsgSend {
+                from_address,
+                amount,
+            },
+        };
+
+        const SCREENS: &str = r#"[
+            { "title": "Sender", "content": "cosmos1ulav3hsenupswqfkw2y3sup5kgtqwnvqa8eyhs", "indent": 2 },
+            { "title": "Memo", "content": "hello", "indent": 2 },
+            { "title": "Sequence", "content": "5", "indent": 2, "expert": true },
+            { "title": "Message", "content": "/cosmos.bank.v1beta1.MsgSend", "indent": 2 },
+            { "title": "From address", "content": "cosmos1ulav3hsenupswqfkw2y3sup5kgtqwnvqa8eyhs", "indent": 3 },
+            { "title": "Amount", "content": "10 ATOM", "indent": 3 }
+        ]"#;
+
+        let expected_screens: Vec<Screen> = serde_json::from_str(SCREENS)?;
+
+        let actual_screens = ValueRenderer::format(&msg, &get_metadata)
+            .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        assert_eq!(expected_screens, actual_screens);
+
+        Ok(())
+    }
+
+    #[test]
+    fn derived_renderer_skips_default_values() -> anyhow::Result<()> {
+        let from_address =
+            AccAddress::from_bech32("cosmos1ulav3hsenupswqfkw2y3sup5kgtqwnvqa8eyhs")?;
+        let amount: SendCoins =
+            serde_json::from_str(r#"[{ "denom": "uatom", "amount": "10000000" }]"#)?;
+
+        let msg = AttributesMessage {
+            from_address: from_address.clone(),
+            to_address: AccAddress::from_bech32("cosmos1ejrf4cur2wy6kfurg9f2jppp2h3afe5h6pkh5t")?,
+            memo: String::new(),
+            sequence: 0,
+            message: AnyMsgSend {
+                from_address,
+                amount,
+            },
+        };
+
+        const SCREENS: &str = r#"[
+            { "title": "Sender", "content": "cosmos1ulav3hsenupswqfkw2y3sup5kgtqwnvqa8eyhs", "indent": 2 },
+            { "title": "Message", "content": "/cosmos.bank.v1beta1.MsgSend", "indent": 2 },
+            { "title": "From address", "content": "cosmos1ulav3hsenupswqfkw2y3sup5kgtqwnvqa8eyhs", "indent": 3 },
+            { "title": "Amount", "content": "10 ATOM", "indent": 3 }
+        ]"#;
+
+        let expected_screens: Vec<Screen> = serde_json::from_str(SCREENS)?;
+
+        let actual_screens = ValueRenderer::format(&msg, &get_metadata)
+            .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        assert_eq!(expected_screens, actual_screens);
+
+        Ok(())
+    }
 }
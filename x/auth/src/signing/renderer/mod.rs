@@ -1,3 +1,5 @@
+pub mod composite;
+pub mod ibc;
 pub mod messages;
 pub mod primitives;
 pub mod tx;
@@ -5,8 +7,147 @@ pub mod value_renderer;
 
 #[cfg(test)]
 pub(super) mod test_functions {
-    use proto_messages::cosmos::tx::v1beta1::tx_metadata::{DenomUnit, Metadata};
+    use proto_messages::{
+        cosmos::tx::v1beta1::{
+            screen::Screen,
+            tx_metadata::{DenomUnit, Metadata},
+        },
+        ibc_proto::google::protobuf::{Duration, Timestamp},
+    };
     use proto_types::Denom;
+    use serde::{de::DeserializeOwned, Deserialize, Deserializer};
+
+    /// Test case of a value which is rendered into a single screen content, `text` is `None`
+    /// when rendering fails
+    #[derive(Deserialize)]
+    pub struct PrimitiveTestCase<P> {
+        pub proto: P,
+        #[serde(default)]
+        pub text: Option<String>,
+        #[serde(default)]
+        pub error: bool,
+    }
+
+    /// Test case of a value which is rendered into screens
+    #[derive(Deserialize)]
+    pub struct ScreensTestCase<P> {
+        pub proto: P,
+        pub screens: Vec<Screen>,
+    }
+
+    /// A well known type in its protojson form, which is how the SDK's test vectors give
+    /// timestamps and durations
+    pub struct ProtoJson<T>(pub T);
+
+    impl<'de> Deserialize<'de> for ProtoJson<Timestamp> {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let value = String::deserialize(deserializer)?;
+            parse_timestamp(&value)
+                .map(ProtoJson)
+                .ok_or_else(|| serde::de::Error::custom(format!("invalid timestamp: {value}")))
+        }
+    }
+
+    impl<'de> Deserialize<'de> for ProtoJson<Duration> {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let value = String::deserialize(deserializer)?;
+            parse_duration(&value)
+                .map(ProtoJson)
+                .ok_or_else(|| serde::de::Error::custom(format!("invalid duration: {value}")))
+        }
+    }
+
+    /// Parses an RFC 3339 date, e.g. "1970-01-01T00:00:00.000000001Z"
+    fn parse_timestamp(value: &str) -> Option<Timestamp> {
+        let (date, time) = value.split_once('T')?;
+
+        let mut date = date.splitn(3, '-').map(str::parse::<i64>);
+        let (year, month, day) = (date.next()?.ok()?, date.next()?.ok()?, date.next()?.ok()?);
+
+        let (time, offset) = match time.strip_suffix('Z') {
+            Some(time) => (time, 0),
+            None => {
+                let (time, offset) = time.split_at(time.len().checked_sub(6)?);
+                let (sign, offset) = offset.split_at(1);
+                let (hours, minutes) = offset.split_once(':')?;
+                let offset = hours.parse::<i64>().ok()? * 3600 + minutes.parse::<i64>().ok()? * 60;
+                match sign {
+                    "+" => (time, offset),
+                    "-" => (time, -offset),
+                    _ => return None,
+                }
+            }
+        };
+
+        let (time, nanos) = split_fraction(time)?;
+        let mut time = time.splitn(3, ':').map(str::parse::<i64>);
+        let (hours, minutes, seconds) =
+            (time.next()?.ok()?, time.next()?.ok()?, time.next()?.ok()?);
+
+        Some(Timestamp {
+            seconds: days_from_civil(year, month, day) * 86_400
+                + hours * 3600
+                + minutes * 60
+                + seconds
+                - offset,
+            nanos,
+        })
+    }
+
+    /// Parses seconds with an "s" suffix, e.g. "-1.500s"
+    fn parse_duration(value: &str) -> Option<Duration> {
+        let value = value.strip_suffix('s')?;
+        let (negative, value) = match value.strip_prefix('-') {
+            Some(value) => (true, value),
+            None => (false, value),
+        };
+
+        let (seconds, nanos) = split_fraction(value)?;
+        let seconds = seconds.parse::<i64>().ok()?;
+
+        Some(match negative {
+            true => Duration {
+                seconds: -seconds,
+                nanos: -nanos,
+            },
+            false => Duration { seconds, nanos },
+        })
+    }
+
+    /// Splits off up to 9 fractional digits as nanos
+    fn split_fraction(value: &str) -> Option<(&str, i32)> {
+        match value.split_once('.') {
+            Some((whole, fraction))
+                if fraction.len() <= 9 && fraction.bytes().all(|b| b.is_ascii_digit()) =>
+            {
+                Some((whole, format!("{fraction:0<9}").parse().ok()?))
+            }
+            Some(_) => None,
+            None => Some((value, 0)),
+        }
+    }
+
+    /// Converts a date of the proleptic Gregorian calendar into the number of days since
+    /// 1970-01-01, see http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+    fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+        let year = if month <= 2 { year - 1 } else { year };
+        let era = year.div_euclid(400);
+        let year_of_era = year.rem_euclid(400);
+        let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+        era * 146_097 + day_of_era - 719_468
+    }
+
+    /// Load the test vectors of `testdata/textual/{name}`
+    pub fn test_vectors<T: DeserializeOwned>(name: &str) -> Vec<T> {
+        let path = format!("{}/testdata/textual/{name}", env!("CARGO_MANIFEST_DIR"));
+        let json = std::fs::read_to_string(&path)
+            .unwrap_or_else(|e| panic!("failed to read test vectors {path}: {e}"));
+
+        serde_json::from_str(&json)
+            .unwrap_or_else(|e| panic!("failed to parse test vectors {path}: {e}"))
+    }
 
     pub fn get_metadata(denom: &Denom) -> Option<Metadata> {
         match denom.to_string().as_str() {
@@ -3,10 +3,19 @@
 use proto_messages::cosmos::tx::v1beta1::screen::Content;
 use proto_types::AccAddress;
 
-use crate::signing::renderer::value_renderer::{DefaultPrimitiveRenderer, PrimitiveValueRenderer};
+use crate::signing::renderer::value_renderer::{
+    DefaultPrimitiveRenderer, IsDefault, PrimitiveValueRenderer,
+};
 
 impl PrimitiveValueRenderer<AccAddress> for DefaultPrimitiveRenderer {
     fn format(value: AccAddress) -> Content {
         Content::new(value).expect("addresses cannot be empty")
     }
 }
+
+/// An address is never empty
+impl IsDefault for AccAddress {
+    fn is_default(&self) -> bool {
+        false
+    }
+}
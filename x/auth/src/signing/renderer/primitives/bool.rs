@@ -2,7 +2,9 @@
 
 use proto_messages::cosmos::tx::v1beta1::screen::Content;
 
-use crate::signing::renderer::value_renderer::{DefaultPrimitiveRenderer, PrimitiveValueRenderer};
+use crate::signing::renderer::value_renderer::{
+    DefaultPrimitiveRenderer, IsDefault, PrimitiveValueRenderer,
+};
 
 impl PrimitiveValueRenderer<bool> for DefaultPrimitiveRenderer {
     fn format(value: bool) -> Content {
@@ -13,3 +15,9 @@ impl PrimitiveValueRenderer<bool> for DefaultPrimitiveRenderer {
         }
     }
 }
+
+impl IsDefault for bool {
+    fn is_default(&self) -> bool {
+        !*self
+    }
+}
@@ -1,5 +1,6 @@
 use crate::signing::renderer::value_renderer::{
-    DefaultPrimitiveRenderer, Error, PrimitiveValueRenderer, TryPrimitiveValueRendererWithMetadata,
+    DefaultPrimitiveRenderer, Error, IsDefault, PrimitiveValueRenderer,
+    TryPrimitiveValueRendererWithMetadata,
 };
 use proto_messages::cosmos::{
     base::v1beta1::Coin,
@@ -106,6 +107,13 @@ impl TryPrimitiveValueRendererWithMetadata<Coin> for DefaultPrimitiveRenderer {
     }
 }
 
+/// A coin always has a denom
+impl IsDefault for Coin {
+    fn is_default(&self) -> bool {
+        false
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::signing::renderer::{
@@ -1,4 +1,6 @@
-use crate::signing::renderer::value_renderer::{DefaultPrimitiveRenderer, PrimitiveValueRenderer};
+use crate::signing::renderer::value_renderer::{
+    DefaultPrimitiveRenderer, IsDefault, PrimitiveValueRenderer,
+};
 use proto_messages::cosmos::tx::v1beta1::screen::Content;
 use proto_types::Decimal256;
 
@@ -20,6 +22,12 @@ impl PrimitiveValueRenderer<Decimal256> for DefaultPrimitiveRenderer {
     }
 }
 
+impl IsDefault for Decimal256 {
+    fn is_default(&self) -> bool {
+        self.is_zero()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::str::FromStr;
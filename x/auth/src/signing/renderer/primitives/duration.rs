@@ -0,0 +1,122 @@
+//! Default formatting implementation for `google.protobuf.Duration`
+
+use proto_messages::{cosmos::tx::v1beta1::screen::Content, ibc_proto::google::protobuf::Duration};
+
+use crate::signing::renderer::value_renderer::{
+    DefaultPrimitiveRenderer, Error, IsDefault, TryPrimitiveValueRenderer,
+};
+
+/// Durations are limited to +-10'000 years
+const MAX_SECONDS: i64 = 315_576_000_000;
+
+const SECONDS_PER_MINUTE: i64 = 60;
+const SECONDS_PER_HOUR: i64 = 60 * SECONDS_PER_MINUTE;
+const SECONDS_PER_DAY: i64 = 24 * SECONDS_PER_HOUR;
+
+impl TryPrimitiveValueRenderer<&Duration> for DefaultPrimitiveRenderer {
+    /// Format a duration as days, hours, minutes and (fractional) seconds, omitting zero units
+    /// e.g. "1 day, 2 hours, 3.5 seconds"
+    fn try_format(value: &Duration) -> Result<Content, Error> {
+        let &Duration { seconds, nanos } = value;
+
+        if seconds.abs() > MAX_SECONDS
+            || nanos.abs() >= 1_000_000_000
+            || (seconds < 0 && nanos > 0)
+            || (seconds > 0 && nanos < 0)
+        {
+            return Err(Error::Rendering(format!(
+                "invalid duration: {seconds} seconds and {nanos} nanos"
+            )));
+        }
+
+        let negative = seconds < 0 || nanos < 0;
+        let (mut seconds, nanos) = (seconds.abs(), nanos.abs());
+
+        let days = seconds / SECONDS_PER_DAY;
+        seconds -= days * SECONDS_PER_DAY;
+        let hours = seconds / SECONDS_PER_HOUR;
+        seconds -= hours * SECONDS_PER_HOUR;
+        let minutes = seconds / SECONDS_PER_MINUTE;
+        seconds -= minutes * SECONDS_PER_MINUTE;
+
+        let mut components = Vec::new();
+        for (amount, unit) in [(days, "day"), (hours, "hour"), (minutes, "minute")] {
+            if amount != 0 {
+                components.push(format!("{amount} {}", pluralize(unit, amount != 1)));
+            }
+        }
+        if seconds != 0 || nanos != 0 {
+            let formatted_seconds = if nanos == 0 {
+                seconds.to_string()
+            } else {
+                format!("{seconds}.{}", format!("{nanos:09}").trim_end_matches('0'))
+            };
+
+            components.push(format!(
+                "{formatted_seconds} {}",
+                pluralize("second", seconds != 1 || nanos != 0)
+            ));
+        }
+
+        let mut formatted = if components.is_empty() {
+            "0 seconds".to_string()
+        } else {
+            components.join(", ")
+        };
+        if negative {
+            formatted.insert(0, '-');
+        }
+
+        Ok(Content::new(formatted).expect("formatted duration is not empty"))
+    }
+}
+
+fn pluralize(unit: &str, plural: bool) -> String {
+    if plural {
+        format!("{unit}s")
+    } else {
+        unit.to_string()
+    }
+}
+
+impl IsDefault for Duration {
+    fn is_default(&self) -> bool {
+        self.seconds == 0 && self.nanos == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::signing::renderer::test_functions::{test_vectors, PrimitiveTestCase, ProtoJson};
+
+    use super::*;
+
+    #[test]
+    fn duration_try_format() {
+        let test_data: Vec<PrimitiveTestCase<ProtoJson<Duration>>> = test_vectors("duration.json");
+
+        for case in test_data {
+            let actual = DefaultPrimitiveRenderer::try_format(&case.proto.0);
+
+            match case.text {
+                Some(expected) if !case.error => {
+                    assert_eq!(expected, actual.expect("duration is valid").into_inner())
+                }
+                _ => assert!(actual.is_err()),
+            }
+        }
+    }
+
+    /// Mixed signs and out of range values have no protojson form, hence no test vectors
+    #[test]
+    fn invalid_duration_try_format() {
+        for (seconds, nanos) in [(1, -1), (-1, 1), (0, 1000000000), (315576000001, 0)] {
+            assert_eq!(
+                DefaultPrimitiveRenderer::try_format(&Duration { seconds, nanos }),
+                Err(Error::Rendering(format!(
+                    "invalid duration: {seconds} seconds and {nanos} nanos"
+                )))
+            );
+        }
+    }
+}
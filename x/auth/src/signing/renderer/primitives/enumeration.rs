@@ -0,0 +1,101 @@
+//! Default formatting implementation for protobuf enums
+
+use proto_messages::cosmos::tx::v1beta1::screen::Content;
+
+use crate::signing::renderer::value_renderer::{
+    DefaultPrimitiveRenderer, Error, PrimitiveValueRenderer,
+};
+
+/// Protobuf enum which is rendered as the name of its value
+pub trait ProtoEnum: TryFrom<i32> {
+    /// Full name of the enum, used in errors
+    const NAME: &'static str;
+
+    /// Name of the value as it's defined in the proto file
+    fn value_name(&self) -> &'static str;
+}
+
+impl<E: ProtoEnum> PrimitiveValueRenderer<&E> for DefaultPrimitiveRenderer {
+    fn format(value: &E) -> Content {
+        Content::new(value.value_name()).expect("enum value names are not empty")
+    }
+}
+
+/// Format the raw value of an enum field, which fails for unknown values
+pub fn try_format_enum<E: ProtoEnum>(value: i32) -> Result<Content, Error> {
+    let value = E::try_from(value).map_err(|_| {
+        Error::Rendering(format!(
+            "cannot get enum {} variant of number {value}",
+            E::NAME
+        ))
+    })?;
+
+    Ok(DefaultPrimitiveRenderer::format(&value))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::signing::renderer::test_functions::{test_vectors, PrimitiveTestCase};
+
+    use super::*;
+
+    /// Mirrors the `Enumeration` enum of the Cosmos SDK textual test protos
+    #[derive(Debug)]
+    enum Enumeration {
+        One,
+        Two,
+        East,
+    }
+
+    impl TryFrom<i32> for Enumeration {
+        type Error = ();
+
+        fn try_from(value: i32) -> Result<Self, Self::Error> {
+            match value {
+                0 => Ok(Self::One),
+                1 => Ok(Self::Two),
+                2 => Ok(Self::East),
+                _ => Err(()),
+            }
+        }
+    }
+
+    impl ProtoEnum for Enumeration {
+        const NAME: &'static str = "testpb.Enumeration";
+
+        fn value_name(&self) -> &'static str {
+            match self {
+                Self::One => "One",
+                Self::Two => "Two",
+                Self::East => "East",
+            }
+        }
+    }
+
+    #[test]
+    fn enum_format() {
+        let test_data: Vec<PrimitiveTestCase<i32>> = test_vectors("enum.json");
+
+        for case in test_data {
+            let actual = try_format_enum::<Enumeration>(case.proto);
+
+            match case.text {
+                Some(expected) if !case.error => {
+                    assert_eq!(expected, actual.expect("value is known").into_inner())
+                }
+                _ => assert_eq!(
+                    actual,
+                    Err(Error::Rendering(format!(
+                        "cannot get enum testpb.Enumeration variant of number {}",
+                        case.proto
+                    )))
+                ),
+            }
+        }
+
+        assert_eq!(
+            "Two",
+            &DefaultPrimitiveRenderer::format(&Enumeration::Two).into_inner()
+        );
+    }
+}
@@ -5,7 +5,9 @@ use std::sync::OnceLock;
 use num_format::{Buffer, CustomFormat, Grouping};
 use proto_messages::cosmos::tx::v1beta1::screen::Content;
 
-use crate::signing::renderer::value_renderer::{DefaultPrimitiveRenderer, PrimitiveValueRenderer};
+use crate::signing::renderer::value_renderer::{
+    DefaultPrimitiveRenderer, IsDefault, PrimitiveValueRenderer,
+};
 
 const THOUSAND_SEPARATOR: &str = "'";
 
@@ -33,6 +35,12 @@ impl PrimitiveValueRenderer<i64> for DefaultPrimitiveRenderer {
     }
 }
 
+impl IsDefault for i64 {
+    fn is_default(&self) -> bool {
+        *self == 0
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::signing::renderer::value_renderer::{
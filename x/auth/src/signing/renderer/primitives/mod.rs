@@ -6,8 +6,11 @@ pub mod bool;
 pub mod bytes;
 pub mod coin;
 pub mod decimal256;
+pub mod duration;
+pub mod enumeration;
 pub mod i64;
 pub mod send_coins;
 pub mod string;
+pub mod timestamp;
 pub mod u64;
 pub mod uint256;
@@ -1,5 +1,5 @@
 use crate::signing::renderer::value_renderer::{
-    DefaultPrimitiveRenderer, Error, TryPrimitiveValueRendererWithMetadata,
+    DefaultPrimitiveRenderer, Error, IsDefault, TryPrimitiveValueRendererWithMetadata,
 };
 use proto_messages::cosmos::{
     base::v1beta1::SendCoins,
@@ -36,6 +36,13 @@ impl TryPrimitiveValueRendererWithMetadata<SendCoins> for DefaultPrimitiveRender
     }
 }
 
+/// `SendCoins` is never empty
+impl IsDefault for SendCoins {
+    fn is_default(&self) -> bool {
+        false
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use proto_messages::cosmos::{
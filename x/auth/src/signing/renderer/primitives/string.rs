@@ -3,7 +3,7 @@ use proto_messages::cosmos::tx::v1beta1::screen::Content;
 use crate::signing::renderer::value_renderer::Error;
 #[doc(inline)]
 use crate::signing::renderer::value_renderer::{
-    DefaultPrimitiveRenderer, TryPrimitiveValueRenderer,
+    DefaultPrimitiveRenderer, IsDefault, TryPrimitiveValueRenderer,
 };
 
 impl TryPrimitiveValueRenderer<&str> for DefaultPrimitiveRenderer {
@@ -25,3 +25,15 @@ impl TryPrimitiveValueRenderer<String> for DefaultPrimitiveRenderer {
         }
     }
 }
+
+impl IsDefault for str {
+    fn is_default(&self) -> bool {
+        self.is_empty()
+    }
+}
+
+impl IsDefault for String {
+    fn is_default(&self) -> bool {
+        self.is_empty()
+    }
+}
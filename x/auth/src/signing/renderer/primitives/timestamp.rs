@@ -0,0 +1,117 @@
+//! Default formatting implementation for `google.protobuf.Timestamp`
+
+use proto_messages::{
+    cosmos::tx::v1beta1::screen::Content, ibc_proto::google::protobuf::Timestamp,
+};
+
+use crate::signing::renderer::value_renderer::{
+    DefaultPrimitiveRenderer, Error, IsDefault, TryPrimitiveValueRenderer,
+};
+
+/// 0001-01-01T00:00:00Z, the earliest valid timestamp
+const MIN_SECONDS: i64 = -62_135_596_800;
+/// 9999-12-31T23:59:59Z, the latest valid timestamp
+const MAX_SECONDS: i64 = 253_402_300_799;
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+impl TryPrimitiveValueRenderer<&Timestamp> for DefaultPrimitiveRenderer {
+    /// Format a timestamp as an RFC 3339 UTC date with trailing zeros of the fractional
+    /// seconds removed, which matches Go's `time.RFC3339Nano` layout
+    fn try_format(value: &Timestamp) -> Result<Content, Error> {
+        let &Timestamp { seconds, nanos } = value;
+
+        if !(MIN_SECONDS..=MAX_SECONDS).contains(&seconds) || !(0..1_000_000_000).contains(&nanos) {
+            return Err(Error::Rendering(format!(
+                "invalid timestamp: {seconds} seconds and {nanos} nanos"
+            )));
+        }
+
+        let (year, month, day) = civil_from_days(seconds.div_euclid(SECONDS_PER_DAY));
+        let seconds_of_day = seconds.rem_euclid(SECONDS_PER_DAY);
+
+        let mut formatted = format!(
+            "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}",
+            seconds_of_day / 3600,
+            seconds_of_day % 3600 / 60,
+            seconds_of_day % 60
+        );
+
+        if nanos != 0 {
+            formatted.push('.');
+            formatted.push_str(format!("{nanos:09}").trim_end_matches('0'));
+        }
+        formatted.push('Z');
+
+        Ok(Content::new(formatted).expect("formatted timestamp is not empty"))
+    }
+}
+
+/// Converts a number of days since 1970-01-01 into a (year, month, day) date of the proleptic
+/// Gregorian calendar, see http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    (year, month, day)
+}
+
+impl IsDefault for Timestamp {
+    fn is_default(&self) -> bool {
+        self.seconds == 0 && self.nanos == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::signing::renderer::test_functions::{test_vectors, PrimitiveTestCase, ProtoJson};
+
+    use super::*;
+
+    #[test]
+    fn timestamp_try_format() {
+        let test_data: Vec<PrimitiveTestCase<ProtoJson<Timestamp>>> =
+            test_vectors("timestamp.json");
+
+        for case in test_data {
+            let actual = DefaultPrimitiveRenderer::try_format(&case.proto.0);
+
+            match case.text {
+                Some(expected) if !case.error => {
+                    assert_eq!(expected, actual.expect("timestamp is valid").into_inner())
+                }
+                _ => assert!(actual.is_err()),
+            }
+        }
+    }
+
+    /// Out of range timestamps and nanos aren't valid protojson, so the vectors can't hold them
+    #[test]
+    fn invalid_timestamp_try_format() {
+        for (seconds, nanos) in [
+            (0, -1),
+            (0, 1000000000),
+            (-62135596801, 0),
+            (253402300800, 0),
+        ] {
+            assert_eq!(
+                DefaultPrimitiveRenderer::try_format(&Timestamp { seconds, nanos }),
+                Err(Error::Rendering(format!(
+                    "invalid timestamp: {seconds} seconds and {nanos} nanos"
+                )))
+            );
+        }
+    }
+}
@@ -3,7 +3,9 @@
 use num_format::Buffer;
 use proto_messages::cosmos::tx::v1beta1::screen::Content;
 
-use crate::signing::renderer::value_renderer::{DefaultPrimitiveRenderer, PrimitiveValueRenderer};
+use crate::signing::renderer::value_renderer::{
+    DefaultPrimitiveRenderer, IsDefault, PrimitiveValueRenderer,
+};
 
 use super::i64::format_get;
 
@@ -16,6 +18,12 @@ impl PrimitiveValueRenderer<u64> for DefaultPrimitiveRenderer {
     }
 }
 
+impl IsDefault for u64 {
+    fn is_default(&self) -> bool {
+        *self == 0
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::signing::renderer::value_renderer::{
@@ -7,7 +7,9 @@ use num_format::WriteFormatted;
 use proto_messages::cosmos::tx::v1beta1::screen::Content;
 use proto_types::Uint256;
 
-use crate::signing::renderer::value_renderer::{DefaultPrimitiveRenderer, PrimitiveValueRenderer};
+use crate::signing::renderer::value_renderer::{
+    DefaultPrimitiveRenderer, IsDefault, PrimitiveValueRenderer,
+};
 
 use super::i64::format_get;
 
@@ -23,6 +25,12 @@ impl PrimitiveValueRenderer<Uint256> for DefaultPrimitiveRenderer {
     }
 }
 
+impl IsDefault for Uint256 {
+    fn is_default(&self) -> bool {
+        self.is_zero()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use proto_messages::cosmos::tx::v1beta1::screen::Content;
@@ -0,0 +1,82 @@
+use proto_messages::cosmos::{
+    crypto::ed25519::v1beta1::PubKey,
+    tx::v1beta1::{
+        screen::{Indent, Screen},
+        tx_metadata::Metadata,
+    },
+};
+use proto_types::Denom;
+
+use crate::signing::renderer::value_renderer::{
+    DefaultPrimitiveRenderer, Error, TryPrimitiveValueRenderer, ValueRenderer,
+};
+
+const TYPE_URL: &str = "/cosmos.crypto.ed25519.PubKey";
+
+impl ValueRenderer for PubKey {
+    fn format<F: Fn(&Denom) -> Option<Metadata>>(
+        &self,
+        _get_metadata: &F,
+    ) -> Result<Vec<Screen>, Error> {
+        Ok(vec![
+            Screen {
+                title: "Public key".to_string(),
+                content: DefaultPrimitiveRenderer::try_format(TYPE_URL)
+                    .expect("hard coded type URL is not empty"),
+                indent: None,
+                expert: true,
+            },
+            Screen {
+                title: "Key".to_string(),
+                content: DefaultPrimitiveRenderer::try_format(
+                    Vec::<u8>::from(self.clone()).as_slice(),
+                )
+                .expect("key is not empty so it will never fail to parse"),
+                indent: Some(Indent::one()),
+                expert: true,
+            },
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proto_messages::cosmos::{
+        crypto::ed25519::v1beta1::PubKey,
+        tx::v1beta1::screen::{Content, Indent, Screen},
+    };
+
+    use crate::signing::renderer::{test_functions::get_metadata, value_renderer::ValueRenderer};
+
+    #[test]
+    fn ed25519_pubkey_formating() -> anyhow::Result<()> {
+        let key: PubKey = serde_json::from_str(
+            r#"{
+            "@type": "/cosmos.crypto.ed25519.PubKey",
+            "key": "SeOJ9s9l2bYHuvnXs2z4ne2Q1uBhLQ4V6fwLQvT6qW4="
+        }"#,
+        )?;
+
+        let expected_screens = vec![
+            Screen {
+                title: "Public key".to_string(),
+                content: Content::new("/cosmos.crypto.ed25519.PubKey")?,
+                indent: None,
+                expert: true,
+            },
+            Screen {
+                title: "Key".to_string(),
+                content: Content::new("49E3 89F6 CF65 D9B6 07BA F9D7 B36C F89D ED90 D6E0 612D 0E15 E9FC 0B42 F4FA A96E")?,
+                indent: Some(Indent::one()),
+                expert: true,
+            },
+        ];
+
+        let actual_screens = ValueRenderer::format(&key, &get_metadata)
+            .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        assert_eq!(expected_screens, actual_screens);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,80 @@
+use proto_messages::cosmos::{
+    crypto::ethsecp256k1::v1::{PubKey, TYPE_URL},
+    tx::v1beta1::{
+        screen::{Indent, Screen},
+        tx_metadata::Metadata,
+    },
+};
+use proto_types::Denom;
+
+use crate::signing::renderer::value_renderer::{
+    DefaultPrimitiveRenderer, Error, TryPrimitiveValueRenderer, ValueRenderer,
+};
+
+impl ValueRenderer for PubKey {
+    fn format<F: Fn(&Denom) -> Option<Metadata>>(
+        &self,
+        _get_metadata: &F,
+    ) -> Result<Vec<Screen>, Error> {
+        Ok(vec![
+            Screen {
+                title: "Public key".to_string(),
+                content: DefaultPrimitiveRenderer::try_format(TYPE_URL)
+                    .expect("hard coded type URL is not empty"),
+                indent: None,
+                expert: true,
+            },
+            Screen {
+                title: "Key".to_string(),
+                content: DefaultPrimitiveRenderer::try_format(
+                    Vec::<u8>::from(self.clone()).as_slice(),
+                )
+                .expect("key is not empty so it will never fail to parse"),
+                indent: Some(Indent::one()),
+                expert: true,
+            },
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proto_messages::cosmos::{
+        crypto::ethsecp256k1::v1::PubKey,
+        tx::v1beta1::screen::{Content, Indent, Screen},
+    };
+
+    use crate::signing::renderer::{test_functions::get_metadata, value_renderer::ValueRenderer};
+
+    #[test]
+    fn eth_secp256k1_pubkey_formating() -> anyhow::Result<()> {
+        let key: PubKey = serde_json::from_str(
+            r#"{
+            "@type": "/ethermint.crypto.v1.ethsecp256k1.PubKey",
+            "key": "ApUOHN/LEz1gJBCf1In3NO60UCQY5TjChIHyK84nbySM"
+        }"#,
+        )?;
+
+        let expected_screens = vec![
+            Screen {
+                title: "Public key".to_string(),
+                content: Content::new("/ethermint.crypto.v1.ethsecp256k1.PubKey")?,
+                indent: None,
+                expert: true,
+            },
+            Screen {
+                title: "Key".to_string(),
+                content: Content::new("0295 0E1C DFCB 133D 6024 109F D489 F734 EEB4 5024 18E5 38C2 8481 F22B CE27 6F24 8C")?,
+                indent: Some(Indent::one()),
+                expert: true,
+            },
+        ];
+
+        let actual_screens = ValueRenderer::format(&key, &get_metadata)
+            .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        assert_eq!(expected_screens, actual_screens);
+
+        Ok(())
+    }
+}
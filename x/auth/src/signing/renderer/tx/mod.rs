@@ -1,5 +1,10 @@
+pub mod ed25519_pubkey;
+#[cfg(feature = "ethsecp256k1")]
+pub mod eth_secp256k1_pubkey;
+pub mod multisig_pubkey;
 pub mod public_key_enum;
 pub mod secp256_pubkey;
+pub mod secp256r1_pubkey;
 mod tx;
 
 pub use tx::*;
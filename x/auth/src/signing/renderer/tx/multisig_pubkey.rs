@@ -0,0 +1,147 @@
+use proto_messages::cosmos::{
+    crypto::multisig::{LegacyAminoPubKey, TYPE_URL},
+    tx::v1beta1::{
+        screen::{Indent, Screen},
+        tx_metadata::Metadata,
+    },
+};
+use proto_types::Denom;
+
+use crate::signing::renderer::value_renderer::{
+    DefaultPrimitiveRenderer, Error, PrimitiveValueRenderer, TryPrimitiveValueRenderer,
+    ValueRenderer,
+};
+
+impl ValueRenderer for LegacyAminoPubKey {
+    fn format<F: Fn(&Denom) -> Option<Metadata>>(
+        &self,
+        get_metadata: &F,
+    ) -> Result<Vec<Screen>, Error> {
+        let keys_count = self.public_keys().len();
+
+        let mut screens = vec![
+            Screen {
+                title: "Public key".to_string(),
+                content: DefaultPrimitiveRenderer::try_format(TYPE_URL)
+                    .expect("hard coded type URL is not empty"),
+                indent: None,
+                expert: true,
+            },
+            Screen {
+                title: "Threshold".to_string(),
+                content: DefaultPrimitiveRenderer::format(self.threshold() as u64),
+                indent: Some(Indent::one()),
+                expert: true,
+            },
+            Screen {
+                title: "Public keys".to_string(),
+                content: DefaultPrimitiveRenderer::try_format(format!("{keys_count} Any"))
+                    .expect("string is not empty"),
+                indent: Some(Indent::one()),
+                expert: true,
+            },
+        ];
+
+        for (i, key) in self.public_keys().iter().enumerate() {
+            for (j, mut screen) in ValueRenderer::format(key, get_metadata)?
+                .into_iter()
+                .enumerate()
+            {
+                if j == 0 {
+                    screen.title = format!("Public keys ({}/{keys_count})", i + 1);
+                }
+
+                let indent = screen.indent.map(Indent::into_inner).unwrap_or(0) + 2;
+                screen.indent = Some(Indent::new(indent).map_err(|_| {
+                    Error::Rendering(format!("multisig key is nested too deeply: {indent}"))
+                })?);
+
+                screens.push(screen);
+            }
+        }
+
+        Ok(screens)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proto_messages::cosmos::tx::v1beta1::{
+        public_key::PublicKey,
+        screen::{Content, Indent, Screen},
+    };
+
+    use crate::signing::renderer::{test_functions::get_metadata, value_renderer::ValueRenderer};
+
+    #[test]
+    fn multisig_pubkey_formating() -> anyhow::Result<()> {
+        let key: PublicKey = serde_json::from_str(
+            r#"{
+            "@type": "/cosmos.crypto.multisig.LegacyAminoPubKey",
+            "threshold": 1,
+            "public_keys": [
+                {
+                    "@type": "/cosmos.crypto.secp256k1.PubKey",
+                    "key": "Auvdf+T963bciiBe9l15DNMOijdaXCUo6zqSOvH7TXlN"
+                },
+                {
+                    "@type": "/cosmos.crypto.ed25519.PubKey",
+                    "key": "SeOJ9s9l2bYHuvnXs2z4ne2Q1uBhLQ4V6fwLQvT6qW4="
+                }
+            ]
+        }"#,
+        )?;
+
+        let expected_screens = vec![
+            Screen {
+                title: "Public key".to_string(),
+                content: Content::new("/cosmos.crypto.multisig.LegacyAminoPubKey")?,
+                indent: None,
+                expert: true,
+            },
+            Screen {
+                title: "Threshold".to_string(),
+                content: Content::new("1")?,
+                indent: Some(Indent::one()),
+                expert: true,
+            },
+            Screen {
+                title: "Public keys".to_string(),
+                content: Content::new("2 Any")?,
+                indent: Some(Indent::one()),
+                expert: true,
+            },
+            Screen {
+                title: "Public keys (1/2)".to_string(),
+                content: Content::new("/cosmos.crypto.secp256k1.PubKey")?,
+                indent: Some(Indent::two()),
+                expert: true,
+            },
+            Screen {
+                title: "Key".to_string(),
+                content: Content::new("02EB DD7F E4FD EB76 DC8A 205E F65D 790C D30E 8A37 5A5C 2528 EB3A 923A F1FB 4D79 4D")?,
+                indent: Some(Indent::new(3)?),
+                expert: true,
+            },
+            Screen {
+                title: "Public keys (2/2)".to_string(),
+                content: Content::new("/cosmos.crypto.ed25519.PubKey")?,
+                indent: Some(Indent::two()),
+                expert: true,
+            },
+            Screen {
+                title: "Key".to_string(),
+                content: Content::new("49E3 89F6 CF65 D9B6 07BA F9D7 B36C F89D ED90 D6E0 612D 0E15 E9FC 0B42 F4FA A96E")?,
+                indent: Some(Indent::new(3)?),
+                expert: true,
+            },
+        ];
+
+        let actual_screens = ValueRenderer::format(&key, &get_metadata)
+            .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        assert_eq!(expected_screens, actual_screens);
+
+        Ok(())
+    }
+}
@@ -12,6 +12,15 @@ impl ValueRenderer for PublicKey {
     ) -> Result<Vec<Screen>, Error> {
         match self {
             PublicKey::Secp256k1(key) => ValueRenderer::format(key, get_metadata),
+            PublicKey::Secp256r1(key) => ValueRenderer::format(key, get_metadata),
+            PublicKey::Ed25519(key) => ValueRenderer::format(key, get_metadata),
+            PublicKey::Multisig(key) => ValueRenderer::format(key, get_metadata),
+            #[cfg(feature = "ethsecp256k1")]
+            PublicKey::EthSecp256k1(key) => ValueRenderer::format(key, get_metadata),
+            // reached when another crate enables the proto-messages ethsecp256k1 feature but
+            // this crate's feature isn't enabled
+            #[allow(unreachable_patterns)]
+            _ => Err(Error::NotImplemented),
         }
     }
 }
@@ -26,6 +26,24 @@ pub trait TryPrimitiveValueRendererWithMetadata<V> {
     ) -> Result<Content, Error>;
 }
 
+/// Values of proto fields which aren't rendered when they hold the default value e.g. an empty
+/// string or zero
+pub trait IsDefault {
+    fn is_default(&self) -> bool;
+}
+
+impl<T> IsDefault for Vec<T> {
+    fn is_default(&self) -> bool {
+        self.is_empty()
+    }
+}
+
+impl<T> IsDefault for Option<T> {
+    fn is_default(&self) -> bool {
+        self.is_none()
+    }
+}
+
 #[cfg_attr(test, derive(PartialEq, Eq))]
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
@@ -6,7 +6,7 @@ use gears::types::context::tx_context::TxContext;
 use gears::{error::AppError, x::params::ParamsSubspaceKey};
 use proto_messages::cosmos::bank::v1beta1::{
     QueryAllBalancesRequest, QueryBalanceRequest, QueryDenomMetadataRequest,
-    QueryDenomMetadataResponse, QueryTotalSupplyResponse,
+    QueryDenomMetadataResponse, QueryTotalSupplyRequest, QueryTotalSupplyResponse,
 };
 use proto_messages::cosmos::ibc::protobuf::Protobuf;
 use store::StoreKey;
@@ -18,7 +18,7 @@ pub struct ABCIHandler<SK: StoreKey, PSK: ParamsSubspaceKey> {
     keeper: Keeper<SK, PSK>,
 }
 
-impl<'a, SK: StoreKey, PSK: ParamsSubspaceKey> ABCIHandler<SK, PSK> {
+impl<SK: StoreKey, PSK: ParamsSubspaceKey> ABCIHandler<SK, PSK> {
     pub fn new(keeper: Keeper<SK, PSK>) -> Self {
         ABCIHandler { keeper }
     }
@@ -37,7 +37,7 @@ impl<'a, SK: StoreKey, PSK: ParamsSubspaceKey> ABCIHandler<SK, PSK> {
 
     pub fn query<DB: Database>(
         &self,
-        ctx: &QueryContext<'a, DB, SK>,
+        ctx: &QueryContext<DB, SK>,
         query: tendermint::proto::abci::RequestQuery,
     ) -> std::result::Result<bytes::Bytes, AppError> {
         match query.path.as_str() {
@@ -45,27 +45,40 @@ impl<'a, SK: StoreKey, PSK: ParamsSubspaceKey> ABCIHandler<SK, PSK> {
                 let req = QueryAllBalancesRequest::decode(query.data)
                     .map_err(|e| proto_messages::Error::DecodeProtobuf(e.to_string()))?;
 
-                Ok(self.keeper.query_all_balances(ctx, req).encode_vec().into())
+                Ok(self
+                    .keeper
+                    .query_all_balances(ctx, req)?
+                    .encode_vec()
+                    .into())
             }
-            "/cosmos.bank.v1beta1.Query/TotalSupply" => Ok(QueryTotalSupplyResponse {
-                supply: self.keeper.get_paginated_total_supply(ctx),
-                pagination: None,
+            "/cosmos.bank.v1beta1.Query/TotalSupply" => {
+                let req = QueryTotalSupplyRequest::decode(query.data)
+                    .map_err(|e| proto_messages::Error::DecodeProtobuf(e.to_string()))?;
+
+                let (supply, pagination) = self
+                    .keeper
+                    .get_paginated_total_supply(ctx, req.pagination)?;
+
+                Ok(QueryTotalSupplyResponse {
+                    supply,
+                    pagination: Some(pagination),
+                }
+                .encode_vec()
+                .into())
             }
-            .encode_vec()
-            .into()),
             "/cosmos.bank.v1beta1.Query/Balance" => {
                 let req = QueryBalanceRequest::decode(query.data)
                     .map_err(|e| proto_messages::Error::DecodeProtobuf(e.to_string()))?;
 
-                Ok(self.keeper.query_balance(ctx, req).encode_vec().into())
+                Ok(self.keeper.query_balance(ctx, req)?.encode_vec().into())
             }
             "/cosmos.bank.v1beta1.Query/DenomsMetadata" => {
-                Ok(self.keeper.query_denoms_metadata(ctx).encode_vec().into())
+                Ok(self.keeper.query_denoms_metadata(ctx)?.encode_vec().into())
             }
             "/cosmos.bank.v1beta1.Query/DenomMetadata" => {
                 let req = QueryDenomMetadataRequest::decode(query.data)
                     .map_err(|e| proto_messages::Error::DecodeProtobuf(e.to_string()))?;
-                let metadata = self.keeper.get_denom_metadata(ctx, &req.denom);
+                let metadata = self.keeper.get_denom_metadata(ctx, &req.denom)?;
                 Ok(QueryDenomMetadataResponse { metadata }.encode_vec().into())
             }
             _ => Err(AppError::InvalidRequest("query path not found".into())),
@@ -1,3 +1,4 @@
+use database::Database;
 use proto_types::AccAddress;
 use tendermint::abci::Application;
 
@@ -26,6 +27,7 @@ use tendermint::proto::abci::RequestQuery;
 
 /// Gets the total supply of every denom
 pub async fn supply<
+    DB: Database,
     SK: StoreKey,
     PSK: ParamsSubspaceKey,
     M: Message,
@@ -33,7 +35,7 @@ pub async fn supply<
     G: Genesis,
     AI: ApplicationInfo,
 >(
-    State(app): State<BaseApp<SK, PSK, M, H, G, AI>>,
+    State(app): State<BaseApp<DB, SK, PSK, M, H, G, AI>>,
 ) -> Result<Json<QueryTotalSupplyResponse>, Error> {
     let request = RequestQuery {
         data: Default::default(),
@@ -52,6 +54,7 @@ pub async fn supply<
 
 /// Get all balances for a given address
 pub async fn get_balances<
+    DB: Database,
     SK: StoreKey,
     PSK: ParamsSubspaceKey,
     M: Message,
@@ -61,7 +64,7 @@ pub async fn get_balances<
 >(
     Path(address): Path<AccAddress>,
     _pagination: Query<Pagination>,
-    State(app): State<BaseApp<SK, PSK, M, H, G, AI>>,
+    State(app): State<BaseApp<DB, SK, PSK, M, H, G, AI>>,
 ) -> Result<Json<QueryAllBalancesResponse>, Error> {
     let req = QueryAllBalancesRequest {
         address,
@@ -92,6 +95,7 @@ pub struct RawDenom {
 /// Get balance for a given address and denom
 //#[get("/cosmos/bank/v1beta1/balances/<addr>/by_denom?<denom>")]
 pub async fn get_balances_by_denom<
+    DB: Database,
     SK: StoreKey,
     PSK: ParamsSubspaceKey,
     M: Message,
@@ -101,7 +105,7 @@ pub async fn get_balances_by_denom<
 >(
     Path(address): Path<AccAddress>,
     denom: Query<RawDenom>,
-    State(app): State<BaseApp<SK, PSK, M, H, G, AI>>,
+    State(app): State<BaseApp<DB, SK, PSK, M, H, G, AI>>,
 ) -> Result<Json<QueryBalanceResponse>, Error> {
     let req = QueryBalanceRequest {
         address,
@@ -128,13 +132,14 @@ pub async fn get_balances_by_denom<
 }
 
 pub fn get_router<
+    DB: Database,
     SK: StoreKey,
     PSK: ParamsSubspaceKey,
     M: Message,
     H: ABCIHandler<M, SK, G>,
     G: Genesis,
     AI: ApplicationInfo,
->() -> Router<RestState<SK, PSK, M, H, G, AI>> {
+>() -> Router<RestState<DB, SK, PSK, M, H, G, AI>> {
     Router::new()
         .route("/v1beta1/supply", get(supply))
         .route("/v1beta1/balances/:address", get(get_balances))
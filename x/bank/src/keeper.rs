@@ -9,6 +9,7 @@ use gears::types::context::context::Context;
 use gears::types::context::init_context::InitContext;
 use gears::types::context::query_context::QueryContext;
 use gears::types::context::read_context::ReadContext;
+use gears::types::pagination::paginate;
 use gears::{
     error::AppError,
     x::{auth::Module, params::ParamsSubspaceKey},
@@ -18,8 +19,8 @@ use proto_messages::cosmos::ibc::protobuf::Protobuf;
 use proto_messages::cosmos::tx::v1beta1::tx_metadata::Metadata;
 use proto_messages::cosmos::{
     bank::v1beta1::{
-        MsgSend, QueryAllBalancesRequest, QueryAllBalancesResponse, QueryBalanceRequest,
-        QueryBalanceResponse,
+        MsgSend, PageRequest, PageResponse, QueryAllBalancesRequest, QueryAllBalancesResponse,
+        QueryBalanceRequest, QueryBalanceResponse,
     },
     base::v1beta1::{Coin, SendCoins},
 };
@@ -49,7 +50,7 @@ impl<SK: StoreKey, PSK: ParamsSubspaceKey> BankKeeper<SK> for Keeper<SK, PSK> {
         amount: SendCoins,
     ) -> Result<(), AppError> {
         self.auth_keeper
-            .check_create_new_module_account::<DB>(ctx, &to_module);
+            .check_create_new_module_account::<DB>(ctx, &to_module)?;
 
         let msg = MsgSend {
             from_address,
@@ -60,22 +61,38 @@ impl<SK: StoreKey, PSK: ParamsSubspaceKey> BankKeeper<SK> for Keeper<SK, PSK> {
         self.send_coins(ctx, msg)
     }
 
+    fn send_coins_between_accounts<DB: Database>(
+        &self,
+        ctx: &mut Context<'_, '_, DB, SK>,
+        from_address: AccAddress,
+        to_address: AccAddress,
+        amount: SendCoins,
+    ) -> Result<(), AppError> {
+        let msg = MsgSend {
+            from_address,
+            to_address,
+            amount,
+        };
+
+        self.send_coins_from_account_to_account(ctx, &msg)
+    }
+
     fn get_denom_metadata<DB: Database, CTX: ReadContext<SK, DB>>(
         &self,
         ctx: &CTX,
         base: &Denom,
-    ) -> Option<Metadata> {
+    ) -> Result<Option<Metadata>, AppError> {
         let bank_store = ctx.get_kv_store(&self.store_key);
         let denom_metadata_store =
             bank_store.get_immutable_prefix_store(denom_metadata_key(base.to_string()));
 
-        denom_metadata_store
-            .get(&base.to_string().into_bytes())
+        Ok(denom_metadata_store
+            .get(&base.to_string().into_bytes())?
             .map(|metadata| {
                 Metadata::decode::<&[u8]>(&metadata)
                     .ok()
                     .unwrap_or_corrupt()
-            })
+            }))
     }
 }
 
@@ -145,64 +162,64 @@ impl<SK: StoreKey, PSK: ParamsSubspaceKey> Keeper<SK, PSK> {
 
     pub fn query_balance<DB: Database>(
         &self,
-        ctx: &QueryContext<'_, DB, SK>,
+        ctx: &QueryContext<DB, SK>,
         req: QueryBalanceRequest,
-    ) -> QueryBalanceResponse {
+    ) -> Result<QueryBalanceResponse, AppError> {
         let bank_store = ctx.get_kv_store(&self.store_key);
         let prefix = create_denom_balance_prefix(req.address);
 
         let account_store = bank_store.get_immutable_prefix_store(prefix);
-        let bal = account_store.get(req.denom.to_string().as_bytes());
+        let bal = account_store.get(req.denom.to_string().as_bytes())?;
 
         match bal {
-            Some(amount) => QueryBalanceResponse {
+            Some(amount) => Ok(QueryBalanceResponse {
                 balance: Some(
                     Coin::decode::<Bytes>(amount.to_owned().into())
                         .ok()
                         .unwrap_or_corrupt(),
                 ),
-            },
-            None => QueryBalanceResponse { balance: None },
+            }),
+            None => Ok(QueryBalanceResponse { balance: None }),
         }
     }
 
     pub fn query_all_balances<DB: Database>(
         &self,
-        ctx: &QueryContext<'_, DB, SK>,
+        ctx: &QueryContext<DB, SK>,
         req: QueryAllBalancesRequest,
-    ) -> QueryAllBalancesResponse {
+    ) -> Result<QueryAllBalancesResponse, AppError> {
         let bank_store = ctx.get_kv_store(&self.store_key);
         let prefix = create_denom_balance_prefix(req.address);
         let account_store = bank_store.get_immutable_prefix_store(prefix);
 
-        let mut balances = vec![];
+        let (raw_coins, pagination) = paginate(&account_store, req.pagination)?;
 
-        for (_, coin) in account_store.range(..) {
-            let coin: Coin = Coin::decode::<Bytes>(coin.to_owned().into())
-                .ok()
-                .unwrap_or_corrupt();
-            balances.push(coin);
-        }
+        let balances = raw_coins
+            .into_iter()
+            .map(|(_, coin)| Coin::decode::<Bytes>(coin.into()).ok().unwrap_or_corrupt())
+            .collect();
 
-        QueryAllBalancesResponse {
+        Ok(QueryAllBalancesResponse {
             balances,
-            pagination: None,
-        }
+            pagination: Some(pagination),
+        })
     }
 
-    /// Gets the total supply of every denom
-    // TODO: should be paginated
+    /// Gets a page of the total supply of every denom
     // TODO: should ignore coins with zero balance
-    // TODO: does this method guarantee that coins are sorted?
     pub fn get_paginated_total_supply<DB: Database>(
         &self,
-        ctx: &QueryContext<'_, DB, SK>,
-    ) -> Vec<Coin> {
+        ctx: &QueryContext<DB, SK>,
+        pagination: Option<PageRequest>,
+    ) -> Result<(Vec<Coin>, PageResponse), AppError> {
         let bank_store = ctx.get_kv_store(&self.store_key);
         let supply_store = bank_store.get_immutable_prefix_store(SUPPLY_KEY);
 
-        supply_store
-            .range(..)
+        let (raw_coins, pagination) = paginate(&supply_store, pagination)?;
+
+        // coins are sorted by denom since the store is ordered by key
+        let supply = raw_coins
+            .into_iter()
             .map(|raw_coin| {
                 let denom = Denom::from_str(&String::from_utf8_lossy(&raw_coin.0))
                     .ok()
@@ -212,7 +229,9 @@ impl<SK: StoreKey, PSK: ParamsSubspaceKey> Keeper<SK, PSK> {
                     .unwrap_or_corrupt();
                 Coin { denom, amount }
             })
-            .collect()
+            .collect();
+
+        Ok((supply, pagination))
     }
 
     pub fn send_coins_from_account_to_account<DB: Database>(
@@ -224,9 +243,9 @@ impl<SK: StoreKey, PSK: ParamsSubspaceKey> Keeper<SK, PSK> {
 
         // Create account if recipient does not exist
 
-        if !self.auth_keeper.has_account(ctx, &msg.to_address) {
+        if !self.auth_keeper.has_account(ctx, &msg.to_address)? {
             self.auth_keeper
-                .create_new_base_account(ctx, &msg.to_address);
+                .create_new_base_account(ctx, &msg.to_address)?;
         };
 
         Ok(())
@@ -249,7 +268,7 @@ impl<SK: StoreKey, PSK: ParamsSubspaceKey> Keeper<SK, PSK> {
             let mut from_account_store =
                 Self::get_address_balances_store(bank_store, &from_address);
             let from_balance = from_account_store
-                .get(send_coin.denom.to_string().as_bytes())
+                .get(send_coin.denom.to_string().as_bytes())?
                 .ok_or(AppError::Send("Insufficient funds".into()))?;
 
             let mut from_balance: Coin = Coin::decode::<Bytes>(from_balance.to_owned().into())
@@ -270,7 +289,7 @@ impl<SK: StoreKey, PSK: ParamsSubspaceKey> Keeper<SK, PSK> {
             //TODO: if balance == 0 then denom should be removed from store
 
             let mut to_account_store = Self::get_address_balances_store(bank_store, &to_address);
-            let to_balance = to_account_store.get(send_coin.denom.to_string().as_bytes());
+            let to_balance = to_account_store.get(send_coin.denom.to_string().as_bytes())?;
 
             let mut to_balance: Coin = match to_balance {
                 Some(to_balance) => Coin::decode::<Bytes>(to_balance.to_owned().into())
@@ -341,25 +360,26 @@ impl<SK: StoreKey, PSK: ParamsSubspaceKey> Keeper<SK, PSK> {
 
     pub fn query_denoms_metadata<DB: Database>(
         &self,
-        ctx: &QueryContext<'_, DB, SK>,
-    ) -> QueryDenomsMetadataResponse {
+        ctx: &QueryContext<DB, SK>,
+    ) -> Result<QueryDenomsMetadataResponse, AppError> {
         let bank_store = ctx.get_kv_store(&self.store_key);
         let mut denoms_metadata = vec![];
 
-        for (_, metadata) in bank_store
+        for pair in bank_store
             .get_immutable_prefix_store(DENOM_METADATA_PREFIX.into_iter())
             .range(..)
         {
+            let (_, metadata) = pair?;
             let metadata: Metadata = Metadata::decode::<Bytes>(metadata.to_owned().into())
                 .ok()
                 .unwrap_or_corrupt();
             denoms_metadata.push(metadata);
         }
 
-        QueryDenomsMetadataResponse {
+        Ok(QueryDenomsMetadataResponse {
             metadatas: denoms_metadata,
             pagination: None,
-        }
+        })
     }
 }
 
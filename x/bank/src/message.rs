@@ -1,14 +1,15 @@
 use auth::signing::renderer::value_renderer::{Error, ValueRenderer};
 use bytes::Bytes;
 use proto_messages::cosmos::bank::v1beta1::MsgSend;
+use proto_messages::cosmos::tx::v1beta1::message::Message as _;
 use proto_messages::cosmos::tx::v1beta1::screen::Screen;
 use proto_messages::cosmos::tx::v1beta1::tx_metadata::Metadata;
 use proto_messages::{any::Any, cosmos::ibc::protobuf::Protobuf};
 use proto_types::AccAddress;
 use proto_types::Denom;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "@type")]
 pub enum Message {
     #[serde(rename = "/cosmos.bank.v1beta1.MsgSend")]
@@ -44,6 +45,18 @@ impl proto_messages::cosmos::tx::v1beta1::message::Message for Message {
             Message::Send(_) => "/cosmos.bank.v1beta1.MsgSend",
         }
     }
+
+    fn amino_url(&self) -> Option<&'static str> {
+        match self {
+            Message::Send(msg) => msg.amino_url(),
+        }
+    }
+
+    fn to_amino_json(&self) -> Result<serde_json::Value, proto_messages::Error> {
+        match self {
+            Message::Send(msg) => msg.to_amino_json(),
+        }
+    }
 }
 
 impl From<Message> for Any {
@@ -1,4 +1,5 @@
 use database::Database;
+use gears::error::AppError;
 use gears::types::context::context::Context;
 use gears::x::params::ParamsSubspaceKey;
 use serde::{Deserialize, Serialize};
@@ -27,24 +28,23 @@ pub struct BankParamsKeeper<SK: StoreKey, PSK: ParamsSubspaceKey> {
 
 // TODO: add a macro to create this?
 impl<SK: StoreKey, PSK: ParamsSubspaceKey> BankParamsKeeper<SK, PSK> {
-    pub fn get<DB: Database>(&self, ctx: &Context<'_, '_, DB, SK>) -> Params {
+    pub fn get<DB: Database>(&self, ctx: &Context<'_, '_, DB, SK>) -> Result<Params, AppError> {
         let store = self
             .params_keeper
             .get_raw_subspace(ctx, &self.params_subspace_key);
 
         let default_send_enabled: bool = String::from_utf8(
             store
-                .get(&KEY_DEFAULT_SEND_ENABLED)
-                .expect("key should be set in kv store")
-                .clone(),
+                .get(&KEY_DEFAULT_SEND_ENABLED)?
+                .expect("key should be set in kv store"),
         )
         .expect("should be valid utf-8")
         .parse()
         .expect("should be valid bool");
 
-        Params {
+        Ok(Params {
             default_send_enabled,
-        }
+        })
     }
 
     pub fn set<DB: Database>(&self, ctx: &mut Context<'_, '_, DB, SK>, params: Params) {
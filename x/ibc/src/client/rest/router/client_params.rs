@@ -1,3 +1,4 @@
+use database::Database;
 use std::marker::PhantomData;
 
 use axum::{extract::State, routing::get, Json, Router};
@@ -25,6 +26,7 @@ use crate::client::cli::query::client_params::PARAMS_URL;
 
 #[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Default)]
 pub struct Route<
+    DB: Database,
     SK: StoreKey,
     PSK: ParamsSubspaceKey,
     M: Message,
@@ -32,6 +34,7 @@ pub struct Route<
     G: Genesis,
     AI: ApplicationInfo,
 > {
+    _db: PhantomData<DB>,
     _sk: PhantomData<SK>,
     _psk: PhantomData<PSK>,
     _m: PhantomData<M>,
@@ -41,16 +44,17 @@ pub struct Route<
 }
 
 impl<
+        DB: Database,
         SK: StoreKey,
         PSK: ParamsSubspaceKey,
         M: Message,
         H: ABCIHandler<M, SK, G>,
         G: Genesis,
         AI: ApplicationInfo,
-    > Route<SK, PSK, M, H, G, AI>
+    > Route<DB, SK, PSK, M, H, G, AI>
 {
     async fn handle(
-        State(app): State<BaseApp<SK, PSK, M, H, G, AI>>,
+        State(app): State<BaseApp<DB, SK, PSK, M, H, G, AI>>,
     ) -> Result<Json<QueryClientParamsResponse>, Error> {
         let query = QueryClientParamsRequest {};
 
@@ -70,7 +74,7 @@ impl<
         ))
     }
 
-    pub fn router() -> Router<RestState<SK, PSK, M, H, G, AI>> {
+    pub fn router() -> Router<RestState<DB, SK, PSK, M, H, G, AI>> {
         Router::new().route(PARAMS_URL, get(Self::handle))
     }
 }
@@ -1,3 +1,4 @@
+use database::Database;
 use std::marker::PhantomData;
 
 use axum::{
@@ -30,6 +31,7 @@ use crate::client::cli::query::client_state::STATE_URL;
 
 #[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Default)]
 pub struct Route<
+    DB: Database,
     SK: StoreKey,
     PSK: ParamsSubspaceKey,
     M: Message,
@@ -37,6 +39,7 @@ pub struct Route<
     G: Genesis,
     AI: ApplicationInfo,
 > {
+    _db: PhantomData<DB>,
     _sk: PhantomData<SK>,
     _psk: PhantomData<PSK>,
     _m: PhantomData<M>,
@@ -46,17 +49,18 @@ pub struct Route<
 }
 
 impl<
+        DB: Database,
         SK: StoreKey,
         PSK: ParamsSubspaceKey,
         M: Message,
         H: ABCIHandler<M, SK, G>,
         G: Genesis,
         AI: ApplicationInfo,
-    > Route<SK, PSK, M, H, G, AI>
+    > Route<DB, SK, PSK, M, H, G, AI>
 {
     async fn handle(
         Path(client_id): Path<ClientId>,
-        State(app): State<BaseApp<SK, PSK, M, H, G, AI>>,
+        State(app): State<BaseApp<DB, SK, PSK, M, H, G, AI>>,
     ) -> Result<Json<QueryClientStateResponse>, Error> {
         let query = QueryClientStateRequest {
             client_id: client_id.to_string(),
@@ -78,7 +82,7 @@ impl<
         ))
     }
 
-    pub fn router() -> Router<RestState<SK, PSK, M, H, G, AI>> {
+    pub fn router() -> Router<RestState<DB, SK, PSK, M, H, G, AI>> {
         Router::new().route(
             constcat::concat!(STATE_URL, "/:client_id"),
             get(Self::handle),
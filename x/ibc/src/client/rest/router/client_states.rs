@@ -1,3 +1,4 @@
+use database::Database;
 use std::marker::PhantomData;
 
 use axum::{
@@ -28,6 +29,7 @@ use crate::client::cli::query::client_states::STATES_URL;
 
 #[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Default)]
 pub struct Route<
+    DB: Database,
     SK: StoreKey,
     PSK: ParamsSubspaceKey,
     M: Message,
@@ -35,6 +37,7 @@ pub struct Route<
     G: Genesis,
     AI: ApplicationInfo,
 > {
+    _db: PhantomData<DB>,
     _sk: PhantomData<SK>,
     _psk: PhantomData<PSK>,
     _m: PhantomData<M>,
@@ -44,17 +47,18 @@ pub struct Route<
 }
 
 impl<
+        DB: Database,
         SK: StoreKey,
         PSK: ParamsSubspaceKey,
         M: Message,
         H: ABCIHandler<M, SK, G>,
         G: Genesis,
         AI: ApplicationInfo,
-    > Route<SK, PSK, M, H, G, AI>
+    > Route<DB, SK, PSK, M, H, G, AI>
 {
     async fn handle(
         Query(_pagination): Query<Option<PageRequest>>,
-        State(app): State<BaseApp<SK, PSK, M, H, G, AI>>,
+        State(app): State<BaseApp<DB, SK, PSK, M, H, G, AI>>,
     ) -> Result<Json<QueryClientStateResponse>, Error> {
         let query = QueryClientStatesRequest { pagination: None };
 
@@ -74,7 +78,7 @@ impl<
         ))
     }
 
-    pub fn router() -> Router<RestState<SK, PSK, M, H, G, AI>> {
+    pub fn router() -> Router<RestState<DB, SK, PSK, M, H, G, AI>> {
         Router::new().route(STATES_URL, get(Self::handle))
     }
 }
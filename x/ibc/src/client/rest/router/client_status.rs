@@ -1,3 +1,4 @@
+use database::Database;
 use std::marker::PhantomData;
 
 use axum::{
@@ -31,6 +32,7 @@ use crate::client::cli::query::client_status::STATUS_URL;
 
 #[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Default)]
 pub struct Route<
+    DB: Database,
     SK: StoreKey,
     PSK: ParamsSubspaceKey,
     M: Message,
@@ -38,6 +40,7 @@ pub struct Route<
     G: Genesis,
     AI: ApplicationInfo,
 > {
+    _db: PhantomData<DB>,
     _sk: PhantomData<SK>,
     _psk: PhantomData<PSK>,
     _m: PhantomData<M>,
@@ -47,17 +50,18 @@ pub struct Route<
 }
 
 impl<
+        DB: Database,
         SK: StoreKey,
         PSK: ParamsSubspaceKey,
         M: Message,
         H: ABCIHandler<M, SK, G>,
         G: Genesis,
         AI: ApplicationInfo,
-    > Route<SK, PSK, M, H, G, AI>
+    > Route<DB, SK, PSK, M, H, G, AI>
 {
     async fn handle(
         Path(client_id): Path<ClientId>,
-        State(app): State<BaseApp<SK, PSK, M, H, G, AI>>,
+        State(app): State<BaseApp<DB, SK, PSK, M, H, G, AI>>,
     ) -> Result<Json<QueryClientStatusResponse>, Error> {
         let query = QueryClientStatusRequest {
             client_id: client_id.to_string(),
@@ -81,7 +85,7 @@ impl<
         ))
     }
 
-    pub fn router() -> Router<RestState<SK, PSK, M, H, G, AI>> {
+    pub fn router() -> Router<RestState<DB, SK, PSK, M, H, G, AI>> {
         Router::new().route(
             constcat::concat!(STATUS_URL, "/:client_id"),
             get(Self::handle),
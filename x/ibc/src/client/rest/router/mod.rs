@@ -7,6 +7,7 @@ mod consensus_state;
 mod consensus_states;
 
 use axum::Router;
+use database::Database;
 use gears::{
     application::ApplicationInfo,
     baseapp::{ABCIHandler, Genesis},
@@ -17,13 +18,14 @@ use proto_messages::cosmos::tx::v1beta1::message::Message;
 use store::StoreKey;
 
 pub fn get_router<
+    DB: Database,
     SK: StoreKey,
     PSK: ParamsSubspaceKey,
     M: Message,
     H: ABCIHandler<M, SK, G>,
     G: Genesis,
     AI: ApplicationInfo,
->() -> Router<RestState<SK, PSK, M, H, G, AI>> {
+>() -> Router<RestState<DB, SK, PSK, M, H, G, AI>> {
     Router::new()
         .merge(client_params::Route::router())
         .merge(client_state::Route::router())
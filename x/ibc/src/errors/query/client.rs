@@ -68,6 +68,8 @@ pub enum StatusError {
 
 #[derive(Debug, thiserror::Error)]
 pub enum ConsensusStateHeightError {
+    #[error("{0}")]
+    SearchError(#[from] SearchError),
     #[error("Invalid client_id: {0}")]
     IdentifierError(#[from] IdentifierError),
     #[error("Decode: {0}")]
@@ -86,6 +88,8 @@ pub enum ConsensusStateError {
 
 #[derive(Debug, thiserror::Error)]
 pub enum ConsensusStatesError {
+    #[error("{0}")]
+    SearchError(#[from] SearchError),
     #[error("Invalid client_id: {0}")]
     IdentifierError(#[from] IdentifierError),
     #[error("Decode: {0}")]
@@ -98,7 +98,7 @@ impl<SK: StoreKey, PSK: ParamsSubspaceKey> Handler<SK, PSK> {
 
     pub fn query<DB: Database + Send + Sync>(
         &self,
-        ctx: &QueryContext<'_, DB, SK>,
+        ctx: &QueryContext<DB, SK>,
         query: tendermint::proto::abci::RequestQuery,
     ) -> Result<bytes::Bytes, errors::query::client::ClientErrors> {
         match query.path.as_str() {
@@ -37,7 +37,10 @@ fn params_get<DB: Database, SK: StoreKey, PSK: ParamsSubspaceKey>(
 ) -> Result<Params, SearchError> {
     let bytes = keeper
         .get(ctx, &params::CLIENT_PARAMS_KEY)
-        .map_err(|_| SearchError::NotFound)?;
+        .map_err(|e| match e {
+            params::ParamsError::NotFound => SearchError::NotFound,
+            params::ParamsError::Store(e) => SearchError::Store(e),
+        })?;
 
     Ok(serde_json::from_slice::<RawParams>(&bytes)
         .map_err(|e| SearchError::DecodeError(e.to_string()))?
@@ -54,7 +57,7 @@ pub fn client_state_get<DB: Database, SK: StoreKey>(
         .get_immutable_prefix_store(format!("{KEY_CLIENT_STORE_PREFIX}/{client_id}").into_bytes());
 
     let bytes = store
-        .get(params::CLIENT_STATE_KEY.as_bytes())
+        .get(params::CLIENT_STATE_KEY.as_bytes())?
         .ok_or(SearchError::NotFound)?;
 
     let state = <WrappedTendermintClientState as Protobuf<PrimitiveAny>>::decode_vec(&bytes)
@@ -74,7 +77,7 @@ pub fn client_consensus_state<DB: Database, SK: StoreKey>(
         .get_immutable_prefix_store(format!("{KEY_CLIENT_STORE_PREFIX}/{client_id}").into_bytes());
 
     let bytes = store
-        .get(format!("{KEY_CONSENSUS_STATE_PREFIX}/{height}").as_bytes())
+        .get(format!("{KEY_CONSENSUS_STATE_PREFIX}/{height}").as_bytes())?
         .ok_or(SearchError::NotFound)?;
 
     let state = <WrappedConsensusState as Protobuf<PrimitiveAny>>::decode_vec(&bytes)
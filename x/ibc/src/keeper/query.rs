@@ -2,6 +2,7 @@ use std::str::FromStr;
 
 use bytes::Bytes;
 use database::Database;
+use gears::error::SearchError;
 use gears::types::context::query_context::QueryContext;
 use gears::x::params::ParamsSubspaceKey;
 use prost::Message;
@@ -67,7 +68,7 @@ impl<SK: StoreKey, PSK: ParamsSubspaceKey> QueryKeeper<SK, PSK> {
 
     pub fn client_params<DB: Database + Send + Sync>(
         &self,
-        ctx: &QueryContext<'_, DB, SK>,
+        ctx: &QueryContext<DB, SK>,
     ) -> Result<QueryClientParamsResponse, ParamsError> {
         let params = params_get(&self.params_keeper, ctx)?;
 
@@ -78,7 +79,7 @@ impl<SK: StoreKey, PSK: ParamsSubspaceKey> QueryKeeper<SK, PSK> {
 
     pub fn client_state<DB: Database>(
         &self,
-        ctx: &QueryContext<'_, DB, SK>,
+        ctx: &QueryContext<DB, SK>,
         QueryClientStateRequest { client_id }: QueryClientStateRequest,
     ) -> Result<QueryClientStateResponse, StateError> {
         let client_id = ClientId::from_str(&client_id)?;
@@ -100,7 +101,7 @@ impl<SK: StoreKey, PSK: ParamsSubspaceKey> QueryKeeper<SK, PSK> {
 
     pub fn client_states<DB: Database>(
         &self,
-        ctx: &QueryContext<'_, DB, SK>,
+        ctx: &QueryContext<DB, SK>,
         QueryClientStatesRequest { pagination: _ }: QueryClientStatesRequest,
     ) -> Result<QueryClientStatesResponse, StatesError> {
         let any_store = ctx.get_kv_store(&self.store_key);
@@ -108,7 +109,8 @@ impl<SK: StoreKey, PSK: ParamsSubspaceKey> QueryKeeper<SK, PSK> {
             any_store.get_immutable_prefix_store(KEY_CLIENT_STORE_PREFIX.to_owned().into_bytes());
 
         let mut states = Vec::<IdentifiedClientState>::new();
-        for (_key, value) in store.range(..) {
+        for pair in store.range(..) {
+            let (_key, value) = pair.map_err(SearchError::from)?;
             states.push(
                 RawIdentifiedClientState::decode::<Bytes>(value.into())?
                     .try_into()
@@ -126,7 +128,7 @@ impl<SK: StoreKey, PSK: ParamsSubspaceKey> QueryKeeper<SK, PSK> {
 
     pub fn client_status<DB: Database + Send + Sync>(
         &self,
-        ctx: &QueryContext<'_, DB, SK>,
+        ctx: &QueryContext<DB, SK>,
         QueryClientStatusRequest { client_id }: QueryClientStatusRequest,
     ) -> Result<QueryClientStatusResponse, StatusError> {
         let client_id = ClientId::from_str(&client_id)?;
@@ -152,7 +154,7 @@ impl<SK: StoreKey, PSK: ParamsSubspaceKey> QueryKeeper<SK, PSK> {
 
     pub fn consensus_state_heights<DB: Database>(
         &self,
-        ctx: &QueryContext<'_, DB, SK>,
+        ctx: &QueryContext<DB, SK>,
         QueryConsensusStateHeightsRequest {
             client_id,
             pagination: _,
@@ -167,7 +169,8 @@ impl<SK: StoreKey, PSK: ParamsSubspaceKey> QueryKeeper<SK, PSK> {
             );
 
         let mut heights = Vec::<Height>::new();
-        for (_key, value) in store.range(..) {
+        for pair in store.range(..) {
+            let (_key, value) = pair.map_err(SearchError::from)?;
             heights.push(
                 Height::decode_vec(&value)
                     .map_err(|e| ConsensusStateHeightError::Decode(e.to_string()))?,
@@ -184,7 +187,7 @@ impl<SK: StoreKey, PSK: ParamsSubspaceKey> QueryKeeper<SK, PSK> {
 
     pub fn consensus_state<DB: Database>(
         &self,
-        ctx: &QueryContext<'_, DB, SK>,
+        ctx: &QueryContext<DB, SK>,
         QueryConsensusStateRequest {
             client_id,
             revision_number,
@@ -217,7 +220,7 @@ impl<SK: StoreKey, PSK: ParamsSubspaceKey> QueryKeeper<SK, PSK> {
 
     pub fn consensus_states<DB: Database>(
         &self,
-        ctx: &QueryContext<'_, DB, SK>,
+        ctx: &QueryContext<DB, SK>,
         QueryConsensusStatesRequest {
             client_id,
             pagination: _,
@@ -232,7 +235,8 @@ impl<SK: StoreKey, PSK: ParamsSubspaceKey> QueryKeeper<SK, PSK> {
             );
 
             let mut states = Vec::<ConsensusStateWithHeight>::new();
-            for (_key, value) in store.range(..) {
+            for pair in store.range(..) {
+                let (_key, value) = pair.map_err(SearchError::from)?;
                 states.push(RawConsensusStateWithHeight::decode::<Bytes>(value.into())?.try_into()?)
             }
 
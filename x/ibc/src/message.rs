@@ -1,17 +1,44 @@
+use auth::signing::renderer::value_renderer::{Error, ValueRenderer};
 use proto_messages::{
     any::Any,
-    cosmos::ibc::tx::{MsgCreateClient, MsgRecoverClient, MsgUpdateClient, MsgUpgradeClient},
+    cosmos::{
+        ibc::tx::{
+            MsgCreateClient, MsgRecoverClient, MsgUpdateClient, MsgUpgradeClient,
+            CREATE_CLIENT_TYPE_URL, RECOVER_CLIENT_TYPE_URL, UPDATE_CLIENT_TYPE_URL,
+            UPGRADE_CLIENT_TYPE_URL,
+        },
+        tx::v1beta1::{screen::Screen, tx_metadata::Metadata},
+    },
 };
-use proto_types::AccAddress;
+use proto_types::{AccAddress, Denom};
 
-#[derive(Debug, Clone, serde::Serialize)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "@type")]
 pub enum Message {
+    #[serde(rename = "/ibc.core.client.v1.MsgCreateClient")]
     ClientCreate(MsgCreateClient),
+    #[serde(rename = "/ibc.core.client.v1.MsgUpdateClient")]
     ClientUpdate(MsgUpdateClient),
+    #[serde(rename = "/ibc.core.client.v1.MsgUpgradeClient")]
     ClientUpgrade(MsgUpgradeClient),
+    #[serde(rename = "/ibc.core.client.v1.MsgRecoverClient")]
     RecoverClient(MsgRecoverClient),
 }
 
+impl ValueRenderer for Message {
+    fn format<F: Fn(&Denom) -> Option<Metadata>>(
+        &self,
+        get_metadata: &F,
+    ) -> Result<Vec<Screen>, Error> {
+        match self {
+            Message::ClientCreate(msg) => msg.format(get_metadata),
+            Message::ClientUpdate(msg) => msg.format(get_metadata),
+            Message::ClientUpgrade(msg) => msg.format(get_metadata),
+            Message::RecoverClient(msg) => msg.format(get_metadata),
+        }
+    }
+}
+
 impl proto_messages::cosmos::tx::v1beta1::message::Message for Message {
     fn get_signers(&self) -> Vec<&AccAddress> {
         unimplemented!()
@@ -22,7 +49,12 @@ impl proto_messages::cosmos::tx::v1beta1::message::Message for Message {
     }
 
     fn type_url(&self) -> &'static str {
-        unimplemented!()
+        match self {
+            Message::ClientCreate(_) => CREATE_CLIENT_TYPE_URL,
+            Message::ClientUpdate(_) => UPDATE_CLIENT_TYPE_URL,
+            Message::ClientUpgrade(_) => UPGRADE_CLIENT_TYPE_URL,
+            Message::RecoverClient(_) => RECOVER_CLIENT_TYPE_URL,
+        }
     }
 }
 
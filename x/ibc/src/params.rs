@@ -15,9 +15,13 @@ pub struct AbciParamsKeeper<SK: StoreKey, PSK: ParamsSubspaceKey> {
     pub params_subspace_key: PSK,
 }
 
-#[derive(Debug, Clone, thiserror::Error)]
-#[error("key should be set in kv store")]
-pub struct ParamsError;
+#[derive(Debug, thiserror::Error)]
+pub enum ParamsError {
+    #[error("key should be set in kv store")]
+    NotFound,
+    #[error("{0}")]
+    Store(#[from] store::error::Error),
+}
 
 impl<SK: StoreKey, PSK: ParamsSubspaceKey> AbciParamsKeeper<SK, PSK> {
     pub fn get<DB: Database>(
@@ -28,8 +32,8 @@ impl<SK: StoreKey, PSK: ParamsSubspaceKey> AbciParamsKeeper<SK, PSK> {
         let value = self
             .params_keeper
             .get_raw_subspace(ctx, &self.params_subspace_key)
-            .get(key.as_ref())
-            .ok_or(ParamsError)?;
+            .get(key.as_ref())?
+            .ok_or(ParamsError::NotFound)?;
 
         Ok(value)
     }
@@ -6,6 +6,7 @@ use crate::{
     params::CLIENT_STATE_KEY,
 };
 use database::Database;
+use gears::error::SearchError;
 use gears::types::context::{query_context::QueryContext, tx_context::TxContext};
 use proto_messages::{
     any::PrimitiveAny,
@@ -69,7 +70,7 @@ impl From<&str> for Signer {
 }
 
 pub enum IbcContext<'a, 'b, DB, SK> {
-    Query(&'a QueryContext<'b, DB, SK>),
+    Query(&'a QueryContext<DB, SK>),
     Tx(&'a mut TxContext<'b, DB, SK>),
 }
 
@@ -84,8 +85,8 @@ impl<'a, 'b, DB, SK: StoreKey> ContextShim<'a, 'b, DB, SK> {
     }
 }
 
-impl<'a, 'b, DB, SK> From<&'a QueryContext<'b, DB, SK>> for IbcContext<'a, 'b, DB, SK> {
-    fn from(value: &'a QueryContext<'b, DB, SK>) -> Self {
+impl<'a, 'b, DB, SK> From<&'a QueryContext<DB, SK>> for IbcContext<'a, 'b, DB, SK> {
+    fn from(value: &'a QueryContext<DB, SK>) -> Self {
         Self::Query(value)
     }
 }
@@ -108,6 +109,13 @@ impl From<InfallibleError> for ClientError {
     }
 }
 
+/// Store errors are reported as `ClientError::Other` since ibc-rs has no store error
+fn store_error(e: impl std::fmt::Display) -> ClientError {
+    ClientError::Other {
+        description: format!("store error: {e}"),
+    }
+}
+
 pub struct ConsensusState(pub WrappedConsensusState);
 
 impl From<WrappedConsensusState> for ConsensusState {
@@ -153,6 +161,7 @@ impl<'a, 'b, DB: Database + Send + Sync, SK: StoreKey> CommonContext
                 )
                 .as_bytes(),
             )
+            .map_err(store_error)?
             .ok_or(ClientError::MissingRawConsensusState)?;
 
         let state =
@@ -195,8 +204,11 @@ impl<'a, 'b, DB: Database + Send + Sync, SK: StoreKey>
         &self,
         client_id: &proto_messages::cosmos::ibc::types::core::host::identifiers::ClientId,
     ) -> Result<Self::AnyClientState, ContextError> {
-        let state = client_state_get(&self.store_key, self.ctx, client_id)
-            .map_err(|_| ClientError::MissingRawClientState)?;
+        let state =
+            client_state_get(&self.store_key, self.ctx, client_id).map_err(|e| match e {
+                SearchError::Store(e) => store_error(e),
+                _ => ClientError::MissingRawClientState,
+            })?;
 
         Ok(state)
     }
@@ -224,7 +236,10 @@ impl<'a, 'b, DB: Database + Send + Sync, SK: StoreKey>
             client_id,
             &Height::new(*revision_number, *revision_height).expect("msg"),
         )
-        .map_err(|_| ClientError::MissingRawConsensusState)?;
+        .map_err(|e| match e {
+            SearchError::Store(e) => store_error(e),
+            _ => ClientError::MissingRawConsensusState,
+        })?;
 
         Ok(state.0)
     }
@@ -247,6 +262,7 @@ impl<'a, 'b, DB: Database + Send + Sync, SK: StoreKey>
             .ctx
             .get_kv_store(&self.store_key)
             .get(format!("{KEY_CONSENSUS_STATE_PREFIX}/{}", height.revision_height()).as_bytes())
+            .map_err(store_error)?
             .ok_or(ClientError::MissingRawConsensusState)?;
 
         let state =
@@ -525,7 +541,8 @@ impl<'a, 'b, DB: Database + Send + Sync, SK: StoreKey> ClientExecutionContext
                     consensus_state_path.revision_height
                 )
                 .as_bytes(),
-            );
+            )
+            .map_err(store_error)?;
 
         Ok(())
     }
@@ -579,10 +596,10 @@ impl<'a, 'b, DB: Database + Send + Sync, SK: StoreKey> ClientExecutionContext
     ) -> Result<(), ContextError> {
         let path = format!( "{KEY_CLIENT_STORE_PREFIX}/{client_id}/{KEY_CONSENSUS_STATE_PREFIX}/{}-{}/{PROCESSED_TIME}", height.revision_number(), height.revision_height() );
 
-        let _ = self
-            .ctx
+        self.ctx
             .get_mutable_kv_store(&self.store_key)
-            .delete(path.as_bytes());
+            .delete(path.as_bytes())
+            .map_err(store_error)?;
 
         Ok(())
     }
@@ -594,10 +611,10 @@ impl<'a, 'b, DB: Database + Send + Sync, SK: StoreKey> ClientExecutionContext
     ) -> Result<(), ContextError> {
         let path = format!( "{KEY_CLIENT_STORE_PREFIX}/{client_id}/{KEY_CONSENSUS_STATE_PREFIX}/{}-{}/{PROCESSED_HEIGHT}", height.revision_number(), height.revision_height() );
 
-        let _ = self
-            .ctx
+        self.ctx
             .get_mutable_kv_store(&self.store_key)
-            .delete(path.as_bytes());
+            .delete(path.as_bytes())
+            .map_err(store_error)?;
 
         Ok(())
     }
@@ -648,7 +665,7 @@ impl<'a, 'b, DB: Database + Send + Sync, SK: StoreKey>
     }
 }
 
-pub struct QueryShim<'a, 'b, DB, SK>(pub &'a mut QueryContext<'b, DB, SK>);
+pub struct QueryShim<'a, DB, SK>(pub &'a mut QueryContext<DB, SK>);
 
 // impl<DB : Database, SK : StoreKey> tonic::client::GrpcService<tonic::body::BoxBody> for QueryShim<'_, '_, DB, SK>
 // {